@@ -0,0 +1,221 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use geometria_serializer::{
+    jt::archive::Archive as JtArchive,
+    model::Document,
+    rhino::{
+        archive::Archive as RhinoArchive, document::DocumentReader, preview_image::bitmap_file,
+    },
+    step::file::StepFile,
+};
+
+#[derive(Parser)]
+#[command(name = "geometria", about = "Inspect 3dm, jt and step files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a summary of the file
+    Info { file: PathBuf },
+    /// Print the chunk or segment tree with offsets
+    Tree { file: PathBuf },
+    /// Print the document properties
+    Props { file: PathBuf },
+    /// Write the embedded preview image of a 3dm file as a bmp
+    ExtractThumbnail { file: PathBuf, output: PathBuf },
+}
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Rhino,
+    Jt,
+    Step,
+}
+
+fn detect(path: &Path) -> Result<Format, String> {
+    let mut buffer = [0u8; 24];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut buffer))
+        .map_err(|e| e.to_string())?;
+    if buffer.starts_with(b"3D Geometry File Format ") {
+        Ok(Format::Rhino)
+    } else if buffer.starts_with(b"Version ") {
+        Ok(Format::Jt)
+    } else if buffer.starts_with(b"ISO-10303-21;") {
+        Ok(Format::Step)
+    } else {
+        Err("unknown file format".to_string())
+    }
+}
+
+fn open(path: &Path) -> Result<BufReader<File>, String> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| e.to_string())
+}
+
+fn rhino_document(path: &Path) -> Result<DocumentReader<BufReader<File>>, String> {
+    DocumentReader::open(open(path)?).map_err(|e| e.to_string())
+}
+
+fn rhino_archive(path: &Path) -> Result<RhinoArchive, String> {
+    RhinoArchive::from_stream(open(path)?).map_err(|e| e.to_string())
+}
+
+fn jt_archive(path: &Path) -> Result<JtArchive, String> {
+    JtArchive::read(open(path)?)
+}
+
+fn step_file(path: &Path) -> Result<StepFile, String> {
+    StepFile::read(open(path)?).map_err(|e| e.to_string())
+}
+
+fn info(path: &Path) -> Result<(), String> {
+    match detect(path)? {
+        Format::Rhino => {
+            let archive = rhino_archive(path)?;
+            let history = archive.properties.revision_history();
+            println!("format: 3dm");
+            println!("version: {}", Into::<u8>::into(archive.version));
+            println!("comment: {}", archive.comment.as_str().trim_end());
+            println!("created by: {}", history.created_by());
+            println!("last edited by: {}", history.last_edited_by());
+            println!("revisions: {}", history.revision_count());
+        }
+        Format::Jt => {
+            let archive = jt_archive(path)?;
+            let statistics = archive.statistics();
+            println!("format: jt");
+            if let Ok(version) = archive.header.version() {
+                println!("version: {}", version);
+            }
+            println!("segments: {}", archive.segments().len());
+            println!("nodes: {}", statistics.node_count);
+            println!("parts: {}", statistics.part_count);
+            println!("instances: {}", statistics.instance_count);
+            println!("shapes: {}", statistics.shape_count);
+            println!("bytes: {}", statistics.total_bytes());
+        }
+        Format::Step => {
+            let file = step_file(path)?;
+            println!("format: step");
+            println!("schema: {}", file.header.schemas.join(", "));
+            println!("originating system: {}", file.header.originating_system);
+            println!("entities: {}", file.len());
+        }
+    }
+    Ok(())
+}
+
+fn print_node(document: &Document, index: usize, depth: usize) {
+    if let Some(node) = document.node(index) {
+        println!(
+            "{:indent$}{}",
+            "",
+            node.name.as_deref().unwrap_or("<unnamed>"),
+            indent = 2 * depth
+        );
+        node.children
+            .iter()
+            .for_each(|child| print_node(document, *child, depth + 1));
+    }
+}
+
+fn tree(path: &Path) -> Result<(), String> {
+    match detect(path)? {
+        Format::Rhino => {
+            for entry in rhino_document(path)?.chunks().map_err(|e| e.to_string())? {
+                println!(
+                    "{:indent$}{} @ 0x{:08x} ({} bytes)",
+                    "",
+                    entry.typecode,
+                    entry.offset,
+                    entry.length,
+                    indent = 2 * entry.depth
+                );
+            }
+        }
+        Format::Jt => {
+            let archive = jt_archive(path)?;
+            for entry in archive.segments() {
+                let segment_type = entry
+                    .segment_type()
+                    .map_or("unknown".to_string(), |t| format!("{:?}", t));
+                println!(
+                    "{} {} @ 0x{:08x} ({} bytes)",
+                    entry.segment_id, segment_type, entry.segment_offset, entry.segment_length
+                );
+            }
+            let document = Document::from(&archive);
+            document
+                .roots
+                .iter()
+                .for_each(|root| print_node(&document, *root, 0));
+        }
+        Format::Step => {
+            let document = Document::from(&step_file(path)?);
+            document
+                .roots
+                .iter()
+                .for_each(|root| print_node(&document, *root, 0));
+        }
+    }
+    Ok(())
+}
+
+fn props(path: &Path) -> Result<(), String> {
+    let document = match detect(path)? {
+        Format::Rhino => Document::from(&rhino_archive(path)?),
+        Format::Jt => Document::from(&jt_archive(path)?),
+        Format::Step => Document::from(&step_file(path)?),
+    };
+    for (key, value) in document.metadata.iter() {
+        println!("{}: {}", key, value);
+    }
+    for node in document.nodes.iter().filter(|n| !n.metadata.is_empty()) {
+        println!("[{}]", node.name.as_deref().unwrap_or("<unnamed>"));
+        for (key, value) in node.metadata.iter() {
+            println!("  {}: {}", key, value);
+        }
+    }
+    Ok(())
+}
+
+fn extract_thumbnail(path: &Path, output: &Path) -> Result<(), String> {
+    if Format::Rhino != detect(path)? {
+        return Err("thumbnails are only supported for 3dm files".to_string());
+    }
+    let archive = rhino_archive(path)?;
+    let dib = archive.properties.preview_dib();
+    if dib.is_empty() {
+        return Err("no preview image found".to_string());
+    }
+    let data = bitmap_file(dib).map_err(|e| e.to_string())?;
+    std::fs::write(output, data).map_err(|e| e.to_string())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Info { file } => info(file),
+        Command::Tree { file } => tree(file),
+        Command::Props { file } => props(file),
+        Command::ExtractThumbnail { file, output } => extract_thumbnail(file, output),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
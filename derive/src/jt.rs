@@ -1,34 +1,169 @@
 use quote::quote;
 use syn::Fields;
 
+fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) if segment.ident == "Option" => {
+            match arguments.args.first() {
+                Some(syn::GenericArgument::Type(inner_ty)) => Some(inner_ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn field_deserialize(field: &syn::Field, name: String) -> syn::Result<proc_macro2::TokenStream> {
+    let is_gated = field.attrs.iter().any(|a| a.path.is_ident("file_version"));
+    let (ty, is_optional) = match option_inner_type(&field.ty) {
+        Some(inner_ty) if is_gated => (inner_ty, true),
+        _ => (&field.ty, false),
+    };
+    let field_ty = match ty {
+        syn::Type::Array(value) => {
+            quote!(<#value>)
+        }
+        syn::Type::Path(value) => {
+            quote!(<#value>)
+        }
+        ty => return Err(syn::Error::new_spanned(ty, "unsupported field type")),
+    };
+    let default_value = match field
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("default_value"))
+    {
+        Some(attr) => {
+            let default = attr.parse_args::<syn::Expr>()?;
+            quote!((#default))
+        }
+        None => {
+            let ty = &field.ty;
+            quote!(<#ty>::default())
+        }
+    };
+    if field.attrs.iter().any(|a| a.path.is_ident("skip")) {
+        return Ok(default_value);
+    }
+    let value_deserialize = match field
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("deserialize_with"))
+    {
+        Some(attr) => match attr.parse_meta()? {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => {
+                let path = lit.parse::<syn::Path>()?;
+                quote!(#path(deserializer)?)
+            }
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected `deserialize_with = \"path\"`",
+                ))
+            }
+        },
+        None => match field.attrs.iter().find(|a| a.path.is_ident("length")) {
+            Some(attr) => {
+                let length = attr.parse_args::<syn::Type>()?;
+                quote!(deserialize_vec_with_length::<#length, _, _>(deserializer)?)
+            }
+            None => quote!(#field_ty::deserialize(deserializer)?),
+        },
+    };
+    let value_deserialize = match field.attrs.iter().find(|a| a.path.is_ident("expected")) {
+        Some(attr) => {
+            let expected = attr.parse_args::<syn::Expr>()?;
+            let message = format!("invalid {}", name);
+            quote!({
+                let value: #ty = #value_deserialize;
+                if value != (#expected) {
+                    return Err(#message.to_string().into());
+                }
+                value
+            })
+        }
+        None => value_deserialize,
+    };
+    let value_deserialize = match field.attrs.iter().find(|a| a.path.is_ident("align")) {
+        Some(attr) => {
+            let alignment = attr.parse_args::<syn::LitInt>()?;
+            if 0 == alignment.base10_parse::<u64>()? {
+                return Err(syn::Error::new_spanned(
+                    alignment,
+                    "expected non-zero alignment",
+                ));
+            }
+            quote!({
+                let position = match std::io::Seek::stream_position(deserializer) {
+                    Ok(position) => position,
+                    Err(e) => return Err(e.to_string().into()),
+                };
+                let padding = (#alignment - position % #alignment) % #alignment;
+                if let Err(e) =
+                    std::io::Seek::seek(deserializer, std::io::SeekFrom::Current(padding as i64))
+                {
+                    return Err(e.to_string().into());
+                }
+                #value_deserialize
+            })
+        }
+        None => value_deserialize,
+    };
+    match field.attrs.iter().find(|a| a.path.is_ident("file_version")) {
+        Some(attr) => {
+            let range = attr.parse_args::<syn::ExprRange>()?;
+            let value_deserialize = if is_optional {
+                quote!(Some(#value_deserialize))
+            } else {
+                value_deserialize
+            };
+            Ok(quote!(
+                if (#range).contains(&deserializer.version()) {
+                    #value_deserialize
+                } else {
+                    #default_value
+                }
+            ))
+        }
+        None => Ok(value_deserialize),
+    }
+}
+
 pub fn process_data_struct(
     data: &syn::DataStruct,
     ident: &syn::Ident,
-    _attrs: &Vec<syn::Attribute>,
-) -> proc_macro2::TokenStream {
+    attrs: &[syn::Attribute],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let error_type = match attrs.iter().find(|a| a.path.is_ident("error_type")) {
+        Some(attr) => {
+            let error_type = attr.parse_args::<syn::Type>()?;
+            quote!(#error_type)
+        }
+        None => quote!(String),
+    };
     match &data.fields {
         Fields::Named(fields) => {
             let fields_iter = fields.named.iter().map(|named_field| {
                 let field_ident = named_field.ident.as_ref().unwrap();
-                let field_ty = match &named_field.ty {
-                    syn::Type::Array(value) => {
-                        quote!(<#value>)
-                    }
-                    syn::Type::Path(value) => {
-                        quote!(#value)
-                    }
-                    _ => panic!(),
-                };
-                quote!(
+                let field_deserialize = field_deserialize(named_field, field_ident.to_string())?;
+                Ok(quote!(
                     #field_ident: {
-                        #field_ty::deserialize(deserializer)?
+                        #field_deserialize
                     }
-                )
+                ))
             });
-            quote! {
+            let fields_iter = fields_iter.collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
                 impl Deserialize for #ident
                 {
-                    type Error = String;
+                    type Error = #error_type;
 
                     fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
                     where D: Deserializer
@@ -36,25 +171,19 @@ pub fn process_data_struct(
                         Ok(Self {#(#fields_iter), *})
                     }
                 }
-            }
+            })
         }
         Fields::Unnamed(fields) => {
-            let fields_iter = fields.unnamed.iter().map(|field| {
-                let field_ty = match &field.ty {
-                    syn::Type::Array(value) => {
-                        quote!(<#value>)
-                    }
-                    syn::Type::Path(value) => {
-                        quote!(#value)
-                    }
-                    _ => panic!(),
-                };
-                quote!(#field_ty::deserialize(deserializer)?)
-            });
-            quote! {
+            let fields_iter = fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(index, field)| field_deserialize(field, index.to_string()))
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
                 impl Deserialize for #ident
                 {
-                    type Error = String;
+                    type Error = #error_type;
 
                     fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
                     where D: Deserializer
@@ -62,10 +191,8 @@ pub fn process_data_struct(
                         Ok(Self (#(#fields_iter), *))
                     }
                 }
-            }
-        }
-        _ => {
-            quote!()
+            })
         }
+        _ => Ok(quote!()),
     }
 }
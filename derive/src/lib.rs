@@ -13,15 +13,35 @@ mod rhino;
         padding,
         table,
         table_field,
-        normal_chunk
+        normal_chunk,
+        skip,
+        expected,
+        deserialize_with,
+        file_version,
+        length,
+        crc,
+        default_value,
+        raw_bytes,
+        since,
+        until,
+        in_chunk,
+        error_type,
+        range
     )
 )]
 pub fn rhino_deserialize_derive(input: TokenStream) -> TokenStream {
     let DeriveInput {
-        ident, data, attrs, ..
+        ident,
+        data,
+        attrs,
+        generics,
+        ..
     }: DeriveInput = parse_macro_input!(input as DeriveInput);
     match data {
-        Data::Struct(data_struct) => rhino::process_data_struct(&data_struct, &ident, &attrs),
+        Data::Struct(data_struct) => {
+            rhino::process_data_struct(&data_struct, &ident, &attrs, &generics)
+                .unwrap_or_else(|e| e.to_compile_error())
+        }
         _ => {
             quote!()
         }
@@ -29,13 +49,26 @@ pub fn rhino_deserialize_derive(input: TokenStream) -> TokenStream {
     .into()
 }
 
-#[proc_macro_derive(JtDeserialize, attributes())]
+#[proc_macro_derive(
+    JtDeserialize,
+    attributes(
+        skip,
+        expected,
+        deserialize_with,
+        length,
+        default_value,
+        file_version,
+        align,
+        error_type
+    )
+)]
 pub fn jt_deserialize_derive(input: TokenStream) -> TokenStream {
     let DeriveInput {
         ident, data, attrs, ..
     }: DeriveInput = parse_macro_input!(input as DeriveInput);
     match data {
-        Data::Struct(data_struct) => jt::process_data_struct(&data_struct, &ident, &attrs),
+        Data::Struct(data_struct) => jt::process_data_struct(&data_struct, &ident, &attrs)
+            .unwrap_or_else(|e| e.to_compile_error()),
         _ => {
             quote!()
         }
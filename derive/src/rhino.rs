@@ -1,108 +1,260 @@
-use quote::quote;
-use syn::{self, Fields};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{self, parse::ParseStream, parse_quote, punctuated::Punctuated, Fields, Token};
 
 enum BigChunkVersion {
-    Gt(u8),
-    Lt(u8),
-    Eq(u8),
-    Ne(u8),
+    Condition(proc_macro2::TokenStream),
     Any,
 }
 
 impl BigChunkVersion {
-    fn quote_operator(&self) -> proc_macro2::TokenStream {
-        match self {
-            Self::Gt(_) => quote!(>).into(),
-            Self::Lt(_) => quote!(<).into(),
-            Self::Eq(_) => quote!(==).into(),
-            Self::Ne(_) => quote!(!=).into(),
-            Self::Any => quote!().into(),
+    fn parse(version_kind: &'static str, attrs: &[syn::Attribute]) -> syn::Result<Option<Self>> {
+        let attr = match attrs.iter().find(|a| a.path.is_ident("big_chunk_version")) {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
+        if attr.tokens.is_empty() {
+            return Ok(Some(BigChunkVersion::Any));
         }
+        let version = syn::Ident::new(version_kind, proc_macro2::Span::call_site());
+        let accessor = quote!(i64::from(chunk_version.#version()));
+        attr.parse_args_with(|input: ParseStream| {
+            if input.peek(syn::Ident) && input.peek2(Token![in]) {
+                let ident = input.parse::<syn::Ident>()?;
+                if ident != version_kind {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("expected `{}`", version_kind),
+                    ));
+                }
+                input.parse::<Token![in]>()?;
+                let range = input.parse::<syn::ExprRange>()?;
+                let from = range.from.as_deref().map(Self::parse_value).transpose()?;
+                let to = range.to.as_deref().map(Self::parse_value).transpose()?;
+                let limits = range.limits;
+                Ok(BigChunkVersion::Condition(
+                    quote!((#from #limits #to).contains(&#accessor)),
+                ))
+            } else {
+                let expr = input.parse::<syn::Expr>()?;
+                Ok(BigChunkVersion::Condition(Self::parse_condition(
+                    &expr,
+                    version_kind,
+                    &accessor,
+                )?))
+            }
+        })
+        .map(Some)
     }
 
-    fn parse(version_kind: &'static str, attrs: &Vec<syn::Attribute>) -> Option<Self> {
-        match attrs.iter().find(|a| a.path.is_ident("big_chunk_version")) {
-            Some(attr) => {
-                if attr.tokens.is_empty() {
-                    Some(BigChunkVersion::Any)
-                } else {
-                    match attr.parse_args::<syn::ExprBinary>() {
-                        Ok(expr) => match *expr.left {
-                            syn::Expr::Path(path) => {
-                                if !path.path.is_ident(version_kind) {
-                                    panic!()
-                                }
-                                match *expr.right {
-                                    syn::Expr::Lit(lit) => match lit.lit {
-                                        syn::Lit::Int(int) => match expr.op {
-                                            syn::BinOp::Gt(_) => Some(BigChunkVersion::Gt(
-                                                int.base10_parse::<u8>().unwrap(),
-                                            )),
-                                            syn::BinOp::Lt(_) => Some(BigChunkVersion::Lt(
-                                                int.base10_parse::<u8>().unwrap(),
-                                            )),
-                                            syn::BinOp::Eq(_) => Some(BigChunkVersion::Eq(
-                                                int.base10_parse::<u8>().unwrap(),
-                                            )),
-                                            syn::BinOp::Ne(_) => Some(BigChunkVersion::Ne(
-                                                int.base10_parse::<u8>().unwrap(),
-                                            )),
-                                            _ => panic!(),
-                                        },
-                                        _ => panic!(),
-                                    },
-                                    _ => panic!(),
-                                }
-                            }
-                            _ => panic!(),
-                        },
-                        _ => panic!(),
+    fn parse_since_until(attrs: &[syn::Attribute]) -> syn::Result<Option<Self>> {
+        let accessor = quote!(i64::from(chunk_version.minor()));
+        let since = parse_args::<syn::LitInt>("since", attrs)?
+            .map(|lit| Self::parse_value(&parse_quote!(#lit)))
+            .transpose()?
+            .map(|value| quote!(#accessor >= #value));
+        let until = parse_args::<syn::LitInt>("until", attrs)?
+            .map(|lit| Self::parse_value(&parse_quote!(#lit)))
+            .transpose()?
+            .map(|value| quote!(#accessor <= #value));
+        Ok(match (since, until) {
+            (Some(since), Some(until)) => Some(Self::Condition(quote!((#since) && (#until)))),
+            (Some(condition), None) | (None, Some(condition)) => Some(Self::Condition(condition)),
+            (None, None) => None,
+        })
+    }
+
+    fn parse_condition(
+        expr: &syn::Expr,
+        version_kind: &'static str,
+        accessor: &proc_macro2::TokenStream,
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        match expr {
+            syn::Expr::Paren(paren) => {
+                let inner = Self::parse_condition(&paren.expr, version_kind, accessor)?;
+                Ok(quote!((#inner)))
+            }
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Not(_),
+                expr,
+                ..
+            }) => {
+                let inner = Self::parse_condition(expr, version_kind, accessor)?;
+                Ok(quote!(!(#inner)))
+            }
+            syn::Expr::Binary(binary) => match binary.op {
+                syn::BinOp::And(_) | syn::BinOp::Or(_) => {
+                    let left = Self::parse_condition(&binary.left, version_kind, accessor)?;
+                    let right = Self::parse_condition(&binary.right, version_kind, accessor)?;
+                    let op = binary.op;
+                    Ok(quote!((#left) #op (#right)))
+                }
+                syn::BinOp::Gt(_)
+                | syn::BinOp::Lt(_)
+                | syn::BinOp::Ge(_)
+                | syn::BinOp::Le(_)
+                | syn::BinOp::Eq(_)
+                | syn::BinOp::Ne(_) => {
+                    match &*binary.left {
+                        syn::Expr::Path(path) if path.path.is_ident(version_kind) => {}
+                        left => {
+                            return Err(syn::Error::new_spanned(
+                                left,
+                                format!("expected `{}`", version_kind),
+                            ))
+                        }
                     }
+                    let value = Self::parse_value(&binary.right)?;
+                    let op = binary.op;
+                    Ok(quote!(#accessor #op #value))
+                }
+                op => Err(syn::Error::new_spanned(
+                    op,
+                    "expected a comparison, `&&` or `||`",
+                )),
+            },
+            expr => Err(syn::Error::new_spanned(
+                expr,
+                format!("expected a condition on `{}`", version_kind),
+            )),
+        }
+    }
+
+    fn parse_value(expr: &syn::Expr) -> syn::Result<proc_macro2::Literal> {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => Ok(proc_macro2::Literal::i64_unsuffixed(
+                int.base10_parse::<u8>()? as i64,
+            )),
+            expr => Err(syn::Error::new_spanned(expr, "expected integer literal")),
+        }
+    }
+}
+
+fn parse_args<T>(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Option<T>>
+where
+    T: syn::parse::Parse,
+{
+    attrs
+        .iter()
+        .find(|a| a.path.is_ident(name))
+        .map(|attr| attr.parse_args::<T>())
+        .transpose()
+}
+
+fn field_member(field: &syn::Field, index: usize) -> syn::Member {
+    match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(syn::Index::from(index)),
+    }
+}
+
+fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Path(path)
+            if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Option") =>
+        {
+            first_type_argument(ty).ok()
+        }
+        _ => None,
+    }
+}
+
+fn first_type_argument(ty: &syn::Type) -> syn::Result<&syn::Type> {
+    if let syn::Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if let Some(syn::GenericArgument::Type(item_ty)) = arguments.args.first() {
+                    return Ok(item_ty);
                 }
             }
-            None => None,
         }
     }
+    Err(syn::Error::new_spanned(ty, "expected `Vec<T>`"))
+}
+
+struct ExpectedAttr {
+    value: syn::Expr,
+    error: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for ExpectedAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value = input.parse()?;
+        let error = match input.parse::<Option<Token![,]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        Ok(Self { value, error })
+    }
 }
 
 struct TableAttr {
     typecode: Option<syn::Type>,
+    end: Option<syn::Path>,
 }
 
 struct StructAttrs {
     big_chunk_major_version: Option<BigChunkVersion>,
     table: Option<TableAttr>,
     normal_chunk: bool,
+    file_version: Option<syn::ExprRange>,
+    crc: bool,
+    error_type: Option<syn::Type>,
 }
 
 impl StructAttrs {
-    fn new(attrs: &Vec<syn::Attribute>) -> Self {
-        Self {
-            big_chunk_major_version: BigChunkVersion::parse("major", attrs),
-            table: Self::parse_table(attrs),
-            normal_chunk: Self::parse_normal_chunk(attrs),
-        }
+    fn new(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        Ok(Self {
+            big_chunk_major_version: BigChunkVersion::parse("major", attrs)?,
+            table: Self::parse_table(attrs)?,
+            normal_chunk: attrs.iter().any(|a| a.path.is_ident("normal_chunk")),
+            file_version: parse_args("file_version", attrs)?,
+            crc: attrs.iter().any(|a| a.path.is_ident("crc")),
+            error_type: parse_args("error_type", attrs)?,
+        })
     }
 
-    fn parse_table(attrs: &Vec<syn::Attribute>) -> Option<TableAttr> {
+    fn parse_table(attrs: &[syn::Attribute]) -> syn::Result<Option<TableAttr>> {
         match attrs.iter().find(|a| a.path.is_ident("table")) {
             Some(attr) => {
                 if attr.tokens.is_empty() {
-                    Some(TableAttr { typecode: None })
+                    Ok(Some(TableAttr {
+                        typecode: None,
+                        end: None,
+                    }))
                 } else {
-                    Some(TableAttr {
-                        typecode: Some(attr.parse_args::<syn::Type>().unwrap()),
+                    attr.parse_args_with(|input: ParseStream| {
+                        let typecode = if input.peek(syn::Ident) && input.peek2(Token![=]) {
+                            None
+                        } else {
+                            let typecode = input.parse::<syn::Type>()?;
+                            if !input.is_empty() {
+                                input.parse::<Token![,]>()?;
+                            }
+                            Some(typecode)
+                        };
+                        let end = if input.is_empty() {
+                            None
+                        } else {
+                            let ident = input.parse::<syn::Ident>()?;
+                            if ident != "end" {
+                                return Err(syn::Error::new_spanned(ident, "expected `end`"));
+                            }
+                            input.parse::<Token![=]>()?;
+                            Some(input.parse::<syn::Path>()?)
+                        };
+                        Ok(Some(TableAttr { typecode, end }))
                     })
                 }
             }
-            None => None,
-        }
-    }
-
-    fn parse_normal_chunk(attrs: &Vec<syn::Attribute>) -> bool {
-        match attrs.iter().find(|a| a.path.is_ident("normal_chunk")) {
-            Some(_) => true,
-            None => false,
+            None => Ok(None),
         }
     }
 }
@@ -110,255 +262,518 @@ impl StructAttrs {
 struct FieldAttrs {
     underlying_type: Option<syn::Type>,
     padding: Option<syn::Type>,
-    typecode: Option<syn::Type>,
+    typecodes: Option<Vec<syn::Path>>,
     big_chunk_minor_version: Option<BigChunkVersion>,
+    skip: bool,
+    expected: Option<ExpectedAttr>,
+    deserialize_with: Option<syn::Path>,
+    file_version: Option<syn::ExprRange>,
+    length: Option<syn::Type>,
+    default: Option<syn::Expr>,
+    raw_bytes: bool,
+    in_chunk: Option<syn::Path>,
+    range: Option<syn::ExprRange>,
 }
 
 impl FieldAttrs {
-    fn new(field: &syn::Field) -> Self {
-        Self {
-            underlying_type: Self::parse_underlying_type(&field.attrs),
-            padding: Self::parse_padding(&field.attrs),
-            typecode: Self::parse_typecode(&field.attrs),
-            big_chunk_minor_version: BigChunkVersion::parse("minor", &field.attrs),
-        }
+    fn new(field: &syn::Field) -> syn::Result<Self> {
+        Ok(Self {
+            underlying_type: parse_args("underlying_type", &field.attrs)?,
+            padding: parse_args("padding", &field.attrs)?,
+            typecodes: Self::parse_typecodes(&field.attrs)?,
+            big_chunk_minor_version: match BigChunkVersion::parse("minor", &field.attrs)? {
+                Some(version) => Some(version),
+                None => BigChunkVersion::parse_since_until(&field.attrs)?,
+            },
+            skip: field.attrs.iter().any(|a| a.path.is_ident("skip")),
+            expected: parse_args("expected", &field.attrs)?,
+            deserialize_with: Self::parse_deserialize_with(&field.attrs)?,
+            file_version: parse_args("file_version", &field.attrs)?,
+            length: parse_args("length", &field.attrs)?,
+            default: parse_args("default_value", &field.attrs)?,
+            raw_bytes: field.attrs.iter().any(|a| a.path.is_ident("raw_bytes")),
+            in_chunk: parse_args("in_chunk", &field.attrs)?,
+            range: parse_args("range", &field.attrs)?,
+        })
     }
 
-    fn parse_underlying_type(attrs: &Vec<syn::Attribute>) -> Option<syn::Type> {
-        match attrs.iter().find(|a| a.path.is_ident("underlying_type")) {
-            Some(attr) => Some(attr.parse_args::<syn::Type>().unwrap()),
-            None => None,
-        }
-    }
-
-    fn parse_padding(attrs: &Vec<syn::Attribute>) -> Option<syn::Type> {
-        match attrs.iter().find(|a| a.path.is_ident("padding")) {
-            Some(attr) => Some(attr.parse_args::<syn::Type>().unwrap()),
-            None => None,
-        }
+    fn parse_typecodes(attrs: &[syn::Attribute]) -> syn::Result<Option<Vec<syn::Path>>> {
+        attrs
+            .iter()
+            .find(|a| a.path.is_ident("table_field"))
+            .map(|attr| {
+                attr.parse_args_with(Punctuated::<syn::Path, Token![|]>::parse_separated_nonempty)
+                    .map(|typecodes| typecodes.into_iter().collect())
+            })
+            .transpose()
     }
 
-    fn parse_typecode(attrs: &Vec<syn::Attribute>) -> Option<syn::Type> {
-        match attrs.iter().find(|a| a.path.is_ident("table_field")) {
-            Some(attr) => Some(attr.parse_args::<syn::Type>().unwrap()),
-            None => None,
-        }
+    fn parse_deserialize_with(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Path>> {
+        attrs
+            .iter()
+            .find(|a| a.path.is_ident("deserialize_with"))
+            .map(|attr| match attr.parse_meta()? {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) => lit.parse::<syn::Path>(),
+                meta => Err(syn::Error::new_spanned(
+                    meta,
+                    "expected `deserialize_with = \"path\"`",
+                )),
+            })
+            .transpose()
     }
 }
 
 pub fn process_data_struct(
     data: &syn::DataStruct,
     ident: &syn::Ident,
-    attrs: &Vec<syn::Attribute>,
-) -> proc_macro2::TokenStream {
-    let struct_attrs = StructAttrs::new(&attrs);
-    match &data.fields {
-        Fields::Named(fields) => {
-            let fields_iter = fields.named.iter().map(|named_field| {
-                let field_attrs = FieldAttrs::new(named_field);
-                let field_ident = named_field.ident.as_ref().unwrap();
-                let field_ty = match &named_field.ty {
-                    syn::Type::Array(value) => {
-                        quote!(<#value>)
-                    }
-                    syn::Type::Path(value) => {
-                        quote!(#value)
+    attrs: &[syn::Attribute],
+    generics: &syn::Generics,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_attrs = StructAttrs::new(attrs)?;
+    let fields: Vec<&syn::Field> = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => return Ok(quote!()),
+    };
+    let is_table = struct_attrs.table.is_some();
+    let fields_iter = fields.iter().enumerate().map(|(index, field)| {
+        let field_attrs = FieldAttrs::new(field)?;
+        let member = field_member(field, index);
+        let field_prefix = match &field.ident {
+            Some(ident) => quote!(#ident:),
+            None => quote!(),
+        };
+        let field_ty = match &field.ty {
+            syn::Type::Array(value) => {
+                quote!(<#value>)
+            }
+            syn::Type::Path(value) => {
+                quote!(<#value>)
+            }
+            ty => return Err(syn::Error::new_spanned(ty, "unsupported field type")),
+        };
+        let default_value = match &field_attrs.default {
+            Some(default) => quote!((#default)),
+            None => quote!(#field_ty::default()),
+        };
+        let value_check = match &field_attrs.expected {
+            Some(ExpectedAttr { value, error }) => {
+                let error = match error {
+                    Some(error) => quote!(#error),
+                    None => {
+                        let message = format!("invalid {}", quote!(#member));
+                        quote!(#message.to_string())
                     }
-                    _ => panic!(),
                 };
-                let field_deserialize = if field_attrs.underlying_type.is_some() {
-                    let underlying_ty = &field_attrs.underlying_type.as_ref().unwrap();
-                    quote!(#field_ty::from(#underlying_ty::deserialize(deserializer)?))
-                } else {
-                    quote!(#field_ty::deserialize(deserializer)?)
-                };
-                let padding_deserialize = if field_attrs.padding.is_some() {
-                    let padding = &field_attrs.padding.as_ref().unwrap();
-                    quote!(#padding::deserialize(deserializer)?;)
-                } else {
-                    quote!()
-                };
-                if field_attrs.typecode.is_some() {
-                    let typecode = &field_attrs.typecode.as_ref().unwrap();
-                    match field_attrs.big_chunk_minor_version {
-                        Some(version) => match version {
-                            BigChunkVersion::Any => {
-                                quote!(
-                                    typecode::#typecode => {
-                                        #padding_deserialize
-                                        table.#field_ident = #field_deserialize;
-                                    }
-                                )
-                            }
-                            BigChunkVersion::Eq(value)
-                            | BigChunkVersion::Gt(value)
-                            | BigChunkVersion::Lt(value)
-                            | BigChunkVersion::Ne(value) => {
-                                let quote_operator = version.quote_operator();
-                                quote!(
-                                    typecode::#typecode => {
-                                        if chunk_version.minor() #quote_operator #value {
-                                            #padding_deserialize
-                                            table.#field_ident = #field_deserialize;
-                                        }
-                                    }
-                                )
-                            }
-                        },
-                        None => {
-                            quote!(
-                                typecode::#typecode => {
-                                    #padding_deserialize
-                                    table.#field_ident = #field_deserialize;
-                                }
-                            )
-                        }
+                quote!(
+                    if value != (#value) {
+                        return Err((#error).into());
                     }
-                } else {
-                    match field_attrs.big_chunk_minor_version {
-                        Some(version) => match version {
-                            BigChunkVersion::Any => {
-                                quote!(
-                                    #field_ident: {
-                                        #padding_deserialize
-                                        #field_deserialize
-                                    }
-                                )
-                            }
-                            BigChunkVersion::Eq(value)
-                            | BigChunkVersion::Gt(value)
-                            | BigChunkVersion::Lt(value)
-                            | BigChunkVersion::Ne(value) => {
-                                let quote_operator = version.quote_operator();
-                                quote!(
-                                    #field_ident: {
-                                        if chunk_version.minor() #quote_operator #value.into() {
-                                            #padding_deserialize
-                                            #field_deserialize
-                                        } else {
-                                            #field_ty::default()
-                                        }
-                                    }
-                                )
+                )
+            }
+            None => quote!(),
+        };
+        let value_check = match &field_attrs.range {
+            Some(range) => {
+                let name = quote!(#member).to_string();
+                quote!(
+                    #value_check
+                    if !(#range).contains(&value) {
+                        deserializer.report(Error::OutOfRange(#name))?;
+                    }
+                )
+            }
+            None => value_check,
+        };
+        let is_gated = field_attrs.file_version.is_some()
+            || matches!(
+                field_attrs.big_chunk_minor_version,
+                Some(BigChunkVersion::Condition(_))
+            );
+        let (ty, value) = match option_inner_type(&field.ty) {
+            Some(inner_ty) if is_gated => (inner_ty, quote!(Some(value))),
+            _ => (&field.ty, quote!(value)),
+        };
+        let field_deserialize = if field_attrs.raw_bytes {
+            quote!({
+                let mut value = Vec::new();
+                if let Err(e) = std::io::Read::read_to_end(deserializer, &mut value) {
+                    return Err(e.to_string().into());
+                }
+                #value
+            })
+        } else if let Some(path) = &field_attrs.deserialize_with {
+            quote!({
+                let value: #ty = #path(deserializer)?;
+                #value_check
+                #value
+            })
+        } else if let Some(length) = &field_attrs.length {
+            let item_ty = first_type_argument(ty)?;
+            quote!({
+                let value = <#ty>::from(<Sequence<#item_ty, #length>>::deserialize(deserializer)?);
+                #value_check
+                #value
+            })
+        } else if let Some(underlying_ty) = &field_attrs.underlying_type {
+            quote!({
+                let value = <#ty>::from(<#underlying_ty>::deserialize(deserializer)?);
+                #value_check
+                #value
+            })
+        } else {
+            quote!({
+                let value = <#ty>::deserialize(deserializer)?;
+                #value_check
+                #value
+            })
+        };
+        if field_attrs.skip {
+            return Ok(if is_table {
+                quote!()
+            } else {
+                quote!(#field_prefix #default_value)
+            });
+        }
+        let mut padding_deserialize = if let Some(padding) = &field_attrs.padding {
+            quote!(<#padding>::deserialize(deserializer)?;)
+        } else {
+            quote!()
+        };
+        let field_deserialize = match &field_attrs.in_chunk {
+            Some(typecode) => {
+                let message = format!("invalid {} chunk", quote!(#member));
+                let field_deserialize = quote!({
+                    let value = chunk::Chunk::with_child(
+                        deserializer,
+                        |deserializer| -> Result<_, Self::Error> {
+                            if typecode::#typecode != deserializer.chunk_begin().typecode {
+                                return Err(#message.to_string().into());
                             }
+                            #padding_deserialize
+                            Ok(#field_deserialize)
                         },
-                        None => {
-                            quote!(
-                                #field_ident: {
-                                    #padding_deserialize
-                                    #field_deserialize
-                                }
-                            )
-                        }
+                    )?;
+                    value
+                });
+                padding_deserialize = quote!();
+                field_deserialize
+            }
+            None => field_deserialize,
+        };
+        let field_deserialize = match &field_attrs.file_version {
+            Some(range) => {
+                let field_deserialize = quote!(
+                    if (#range).contains(&deserializer.version()) {
+                        #padding_deserialize
+                        #field_deserialize
+                    } else {
+                        #default_value
                     }
+                );
+                padding_deserialize = quote!();
+                field_deserialize
+            }
+            None => field_deserialize,
+        };
+        let field_deserialize = if struct_attrs.error_type.is_none() {
+            let field_deserialize = quote!({
+                let result = (|| -> Result<_, Error> {
+                    #padding_deserialize
+                    Ok(#field_deserialize)
+                })();
+                match result {
+                    Ok(value) => value,
+                    Err(e) => return Err(e.with_context(deserializer)),
                 }
             });
-
-            let struct_deserialize = if struct_attrs.table.is_some() {
-                if struct_attrs.table.as_ref().unwrap().typecode.is_some() {
-                    let typecode = struct_attrs.table.unwrap().typecode.unwrap();
-                    quote!(
-                        let mut table = Self::default();
-                        let mut properties_chunk = Chunk::deserialize(deserializer)?;
-                        if typecode::#typecode == properties_chunk.chunk_begin().typecode {
-                            loop {
-                                let mut chunk = Chunk::deserialize(&mut properties_chunk)?;
-                                let deserializer = &mut chunk;
-                                match deserializer.chunk_begin().typecode {
-                                    #(#fields_iter)*
-                                    typecode::ENDOFTABLE => {
-                                        break;
-                                    }
-                                    _ => {
-                                    }
-                                }
-                                chunk.seek(SeekFrom::End(1)).unwrap();
+            padding_deserialize = quote!();
+            field_deserialize
+        } else {
+            field_deserialize
+        };
+        Ok(if let Some(typecodes) = &field_attrs.typecodes {
+            let typecode = quote!(#(typecode::#typecodes)|*);
+            match field_attrs.big_chunk_minor_version {
+                Some(version) => match version {
+                    BigChunkVersion::Any => {
+                        quote!(
+                            #typecode => {
+                                #padding_deserialize
+                                table.#member = #field_deserialize;
                             }
-                        }
-                        properties_chunk.seek(SeekFrom::End(1)).unwrap();
-                        Ok(table)
-                    )
-                } else {
-                    quote!(
-                        let mut table = Self::default();
-                        loop {
-                            let mut chunk = Chunk::deserialize(deserializer)?;
-                            let deserializer = &mut chunk;
-                            match deserializer.chunk_begin().typecode {
-                                #(#fields_iter)*
-                                _ => {
-                                    break;
+                        )
+                    }
+                    BigChunkVersion::Condition(condition) => {
+                        quote!(
+                            #typecode => {
+                                if #condition {
+                                    #padding_deserialize
+                                    table.#member = #field_deserialize;
                                 }
                             }
-                            chunk.seek(SeekFrom::End(1)).unwrap();
+                        )
+                    }
+                },
+                None => {
+                    quote!(
+                        #typecode => {
+                            #padding_deserialize
+                            table.#member = #field_deserialize;
                         }
-                        Ok(table)
                     )
                 }
-            } else {
-                quote!(Ok(Self {#(#fields_iter),*}))
-            };
-
-            let chunk_deserialize = if struct_attrs.normal_chunk {
-                quote!(
-                    let mut chunk = chunk::Chunk::deserialize(deserializer)?;
-                    let deserializer = &mut chunk;
-                )
-            } else {
-                quote!()
-            };
-
-            let chunk_version_type = if struct_attrs.normal_chunk {
-                quote!(NormalVersion)
-            } else {
-                quote!(BigVersion)
-            };
-
-            let deserialize_body = match struct_attrs.big_chunk_major_version {
-                Some(major_version) => match major_version {
+            }
+        } else {
+            match field_attrs.big_chunk_minor_version {
+                Some(version) => match version {
                     BigChunkVersion::Any => {
                         quote!(
-                            #chunk_deserialize
-                            let _chunk_version = chunk::#chunk_version_type::deserialize(deserializer)?;
-                            #struct_deserialize
+                            #field_prefix {
+                                #padding_deserialize
+                                #field_deserialize
+                            }
                         )
                     }
-                    BigChunkVersion::Eq(value)
-                    | BigChunkVersion::Gt(value)
-                    | BigChunkVersion::Lt(value)
-                    | BigChunkVersion::Ne(value) => {
-                        let quote_operator = major_version.quote_operator();
+                    BigChunkVersion::Condition(condition) => {
                         quote!(
-                            #chunk_deserialize
-                            let chunk_version = chunk::#chunk_version_type::deserialize(deserializer)?;
-                            if chunk_version.major() #quote_operator #value.into() {
-                                #struct_deserialize
-                            } else {
-                                Ok(Self::default())
+                            #field_prefix {
+                                if #condition {
+                                    #padding_deserialize
+                                    #field_deserialize
+                                } else {
+                                    #default_value
+                                }
                             }
                         )
                     }
                 },
                 None => {
                     quote!(
-                        #chunk_deserialize
-                        #struct_deserialize
+                        #field_prefix {
+                            #padding_deserialize
+                            #field_deserialize
+                        }
                     )
                 }
-            };
-            quote! {
-                impl<'de, D> Deserialize<'de, D> for #ident where D: Deserializer,
-                {
-                    type Error = String;
+            }
+        })
+    });
+    let fields_iter = fields_iter.collect::<syn::Result<Vec<_>>>()?;
 
-                    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
-                        #deserialize_body
-                    }
+    let quote_crc_check = |chunk: proc_macro2::TokenStream| {
+        if struct_attrs.crc {
+            quote!(
+                if let Err(e) = #chunk.verify_crc() {
+                    #chunk.report(e)?;
                 }
+            )
+        } else {
+            quote!()
+        }
+    };
+    let crc_check = quote_crc_check(quote!(chunk));
+    let properties_crc_check = quote_crc_check(quote!(properties_chunk));
+
+    let mut table_defaults = vec![];
+    let mut typecodes: Vec<proc_macro2::TokenStream> = vec![];
+    let mut table_field_typecodes: Vec<String> = vec![];
+    for (index, field) in fields.iter().enumerate() {
+        let field_attrs = FieldAttrs::new(field)?;
+        if let Some(default) = field_attrs.default {
+            let member = field_member(field, index);
+            table_defaults.push(quote!(table.#member = #default;));
+        }
+        for typecode in field_attrs.typecodes.iter().flatten() {
+            let name = quote!(#typecode).to_string();
+            if table_field_typecodes.contains(&name) {
+                return Err(syn::Error::new_spanned(
+                    typecode,
+                    format!("duplicate table_field typecode `{}`", name),
+                ));
             }
+            table_field_typecodes.push(name);
+            typecodes.push(quote!(#typecode));
         }
-        _ => {
-            quote!()
+        if let Some(typecode) = field_attrs.in_chunk {
+            typecodes.push(quote!(#typecode));
         }
     }
+    if let Some(table) = &struct_attrs.table {
+        if let Some(typecode) = &table.typecode {
+            typecodes.push(quote!(#typecode));
+        }
+        if let Some(typecode) = &table.end {
+            typecodes.push(quote!(#typecode));
+        }
+    }
+    let typecode_checks = typecodes.iter().map(|typecode| {
+        quote_spanned!(typecode.span()=>
+            const _: typecode::Typecode = typecode::#typecode;
+        )
+    });
+
+    let struct_deserialize = if let Some(table) = struct_attrs.table {
+        let end = match &table.end {
+            Some(end) => quote!(typecode::#end),
+            None => quote!(typecode::ENDOFTABLE),
+        };
+        if let Some(typecode) = table.typecode {
+            quote!(
+                let mut table = Self::default();
+                #(#table_defaults)*
+                let mut properties_chunk = Chunk::deserialize(deserializer)?;
+                #properties_crc_check
+                if typecode::#typecode == properties_chunk.chunk_begin().typecode {
+                    loop {
+                        let mut chunk = Chunk::deserialize(&mut properties_chunk)?;
+                        #crc_check
+                        let deserializer = &mut chunk;
+                        match deserializer.chunk_begin().typecode {
+                            #(#fields_iter)*
+                            #end => {
+                                break;
+                            }
+                            other => {
+                                if !other.is_known() {
+                                    deserializer.report(Error::InvalidTypecode(other))?;
+                                }
+                            }
+                        }
+                        chunk.seek(SeekFrom::End(1)).map_err(Error::from)?;
+                    }
+                }
+                properties_chunk.seek(SeekFrom::End(1)).map_err(Error::from)?;
+                Ok(table)
+            )
+        } else {
+            let unknown_typecode = match &table.end {
+                Some(_) => quote!(
+                    #end => {
+                        break;
+                    }
+                    other => {
+                        if !other.is_known() {
+                            deserializer.report(Error::InvalidTypecode(other))?;
+                        }
+                    }
+                ),
+                None => quote!(
+                    _ => {
+                        break;
+                    }
+                ),
+            };
+            quote!(
+                let mut table = Self::default();
+                #(#table_defaults)*
+                loop {
+                    let mut chunk = Chunk::deserialize(deserializer)?;
+                    #crc_check
+                    let deserializer = &mut chunk;
+                    match deserializer.chunk_begin().typecode {
+                        #(#fields_iter)*
+                        #unknown_typecode
+                    }
+                    chunk.seek(SeekFrom::End(1)).map_err(Error::from)?;
+                }
+                Ok(table)
+            )
+        }
+    } else {
+        match &data.fields {
+            Fields::Named(_) => quote!(Ok(Self {#(#fields_iter),*})),
+            _ => quote!(Ok(Self(#(#fields_iter),*))),
+        }
+    };
+
+    let chunk_deserialize = if struct_attrs.normal_chunk {
+        quote!(
+            let mut chunk = chunk::Chunk::deserialize(deserializer)?;
+            #crc_check
+            let deserializer = &mut chunk;
+        )
+    } else {
+        quote!()
+    };
+
+    let chunk_version_type = if struct_attrs.normal_chunk {
+        quote!(NormalVersion)
+    } else {
+        quote!(BigVersion)
+    };
+
+    let file_version_check = match &struct_attrs.file_version {
+        Some(range) => quote!(
+            if !(#range).contains(&deserializer.version()) {
+                return Ok(Self::default());
+            }
+        ),
+        None => quote!(),
+    };
+
+    let deserialize_body = match struct_attrs.big_chunk_major_version {
+        Some(major_version) => match major_version {
+            BigChunkVersion::Any => {
+                quote!(
+                    #chunk_deserialize
+                    #[allow(unused_variables)]
+                    let chunk_version = chunk::#chunk_version_type::deserialize(deserializer)?;
+                    #struct_deserialize
+                )
+            }
+            BigChunkVersion::Condition(condition) => {
+                quote!(
+                    #chunk_deserialize
+                    let chunk_version = chunk::#chunk_version_type::deserialize(deserializer)?;
+                    if #condition {
+                        #struct_deserialize
+                    } else {
+                        Ok(Self::default())
+                    }
+                )
+            }
+        },
+        None => {
+            quote!(
+                #chunk_deserialize
+                #struct_deserialize
+            )
+        }
+    };
+    let error_type = match &struct_attrs.error_type {
+        Some(error_type) => quote!(#error_type),
+        None => quote!(Error),
+    };
+    let mut impl_generics = generics.clone();
+    impl_generics.params.insert(0, parse_quote!('de));
+    impl_generics.params.push(parse_quote!(D));
+    let where_clause = impl_generics.make_where_clause();
+    where_clause.predicates.push(parse_quote!(D: Deserializer));
+    for param in generics.type_params() {
+        let param_ident = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#param_ident: for<'a> Deserialize<'a, D>));
+        where_clause.predicates.push(parse_quote!(
+            #error_type: for<'a> From<<#param_ident as Deserialize<'a, D>>::Error>
+        ));
+    }
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics Deserialize<'de, D> for #ident #ty_generics #where_clause
+        {
+            type Error = #error_type;
+
+            fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+                #(#typecode_checks)*
+                #file_version_check
+                #deserialize_body
+            }
+        }
+    })
 }
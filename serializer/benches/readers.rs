@@ -0,0 +1,172 @@
+use std::io::{Cursor, Write};
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use flate2::{write::ZlibEncoder, Compression};
+use geometria_serializer::{
+    jt::{
+        common::GUID, deserialize::Deserialize as _, object_type, reader::Reader as JtReader,
+        shape_lod::ShapeLodSegment,
+    },
+    rhino::{
+        deserialize::Deserialize as _,
+        document::DocumentReader,
+        reader::Reader as RhinoReader,
+        string::{StringWithLength, WStringWithLength},
+    },
+};
+
+const COMMENTBLOCK: u32 = 0x00000001;
+const ENDOFTABLE: u32 = 0xFFFFFFFF;
+const MATERIAL_TABLE: u32 = 0x10000010;
+const MATERIAL_RECORD: u32 = 0x20008081;
+
+fn rhino_archive(tables: usize, records: usize) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![];
+    record.extend(MATERIAL_RECORD.to_le_bytes());
+    record.extend(16u32.to_le_bytes());
+    record.extend([0u8; 16]);
+
+    let mut table: Vec<u8> = vec![];
+    table.extend(MATERIAL_TABLE.to_le_bytes());
+    table.extend(((records * record.len()) as u32 + 8).to_le_bytes());
+    (0..records).for_each(|_| table.extend(&record));
+    table.extend(ENDOFTABLE.to_le_bytes());
+    table.extend(0u32.to_le_bytes());
+
+    let mut data: Vec<u8> = vec![];
+    data.extend("3D Geometry File Format        2".as_bytes());
+    data.extend(COMMENTBLOCK.to_le_bytes());
+    data.extend(3u32.to_le_bytes());
+    data.extend("abc".as_bytes());
+    (0..tables).for_each(|_| data.extend(&table));
+    data
+}
+
+fn string_data(length: usize) -> Vec<u8> {
+    let mut data: Vec<u8> = vec![];
+    data.extend((length as u32 + 1).to_le_bytes());
+    data.extend((0..length).map(|i| b'a' + (i % 26) as u8));
+    data.push(0u8);
+    data
+}
+
+fn wstring_data(length: usize) -> Vec<u8> {
+    let mut data: Vec<u8> = vec![];
+    data.extend((length as u32 + 1).to_le_bytes());
+    (0..length).for_each(|i| data.extend((0x3B1u16 + (i % 24) as u16).to_le_bytes()));
+    data.extend(0u16.to_le_bytes());
+    data
+}
+
+fn extend_guid(data: &mut Vec<u8>, guid: &GUID) {
+    data.extend(guid.0.to_le_bytes());
+    guid.1.iter().for_each(|v| data.extend(v.to_le_bytes()));
+    data.extend(guid.2);
+}
+
+fn shape_lod_segment(vertex_count: usize, compress: bool) -> Vec<u8> {
+    let raw: Vec<u8> = (0..vertex_count)
+        .flat_map(|i| [(i % 2) as f32, (i / 2) as f32, 0f32])
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+
+    let mut lod: Vec<u8> = vec![];
+    lod.extend(1i16.to_le_bytes());
+    lod.extend(1i16.to_le_bytes());
+    lod.extend(0i32.to_le_bytes());
+    lod.extend([0u8; 4]);
+    lod.extend(1i16.to_le_bytes());
+    lod.extend(1i16.to_le_bytes());
+    lod.extend([0u8; 3]);
+    lod.extend([0u8; 4]);
+    lod.push(0u8);
+    lod.extend(64i32.to_le_bytes());
+    lod.extend(2i32.to_le_bytes());
+    lod.extend(2i32.to_le_bytes());
+    lod.extend(0i32.to_le_bytes());
+    lod.extend((vertex_count as i32).to_le_bytes());
+    lod.extend((raw.len() as i32).to_le_bytes());
+    if compress {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&raw).unwrap();
+        let compressed = encoder.finish().unwrap();
+        lod.extend((compressed.len() as i32).to_le_bytes());
+        lod.extend(compressed);
+    } else {
+        lod.extend((-(raw.len() as i32)).to_le_bytes());
+        lod.extend(raw);
+    }
+
+    let mut data: Vec<u8> = vec![];
+    extend_guid(&mut data, &GUID::default());
+    data.extend(7i32.to_le_bytes());
+    data.extend((lod.len() as i32 + 4 + 17 + 24).to_le_bytes());
+    data.extend((lod.len() as i32 + 17).to_le_bytes());
+    extend_guid(&mut data, &object_type::TRI_STRIP_SET_SHAPE_LOD);
+    data.push(0u8);
+    data.extend(lod);
+    data
+}
+
+fn chunk_traversal(c: &mut Criterion) {
+    let data = rhino_archive(16, 1024);
+    let mut group = c.benchmark_group("rhino");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("chunk_traversal", |b| {
+        b.iter(|| {
+            let mut document = DocumentReader::open(Cursor::new(&data)).unwrap();
+            black_box(document.chunks().unwrap().len())
+        })
+    });
+    group.finish();
+}
+
+fn string_decoding(c: &mut Criterion) {
+    let string = string_data(64 * 1024);
+    let wstring = wstring_data(64 * 1024);
+    let mut group = c.benchmark_group("rhino");
+    group.throughput(Throughput::Bytes(string.len() as u64));
+    group.bench_function("string_decoding", |b| {
+        b.iter(|| {
+            let mut reader = RhinoReader::new(Cursor::new(&string));
+            black_box(StringWithLength::deserialize(&mut reader).unwrap())
+        })
+    });
+    group.throughput(Throughput::Bytes(wstring.len() as u64));
+    group.bench_function("wstring_decoding", |b| {
+        b.iter(|| {
+            let mut reader = RhinoReader::new(Cursor::new(&wstring));
+            black_box(WStringWithLength::deserialize(&mut reader).unwrap())
+        })
+    });
+    group.finish();
+}
+
+fn mesh_deserialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("jt");
+    for (name, compress) in [
+        ("mesh_deserialization", false),
+        ("mesh_decompression", true),
+    ] {
+        let data = shape_lod_segment(64 * 1024, compress);
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || Cursor::new(data.clone()),
+                |cursor| {
+                    black_box(ShapeLodSegment::deserialize(&mut JtReader::new(cursor)).unwrap())
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    chunk_traversal,
+    string_decoding,
+    mesh_deserialization
+);
+criterion_main!(benches);
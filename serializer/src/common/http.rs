@@ -0,0 +1,72 @@
+use ureq::Agent;
+
+use super::range::{RangeReader, RangeSource};
+
+pub struct HttpSource {
+    agent: Agent,
+    url: String,
+}
+
+impl HttpSource {
+    pub fn new<U>(url: U) -> Self
+    where
+        U: Into<String>,
+    {
+        Self {
+            agent: Agent::new_with_defaults(),
+            url: url.into(),
+        }
+    }
+
+    pub fn with_agent<U>(agent: Agent, url: U) -> Self
+    where
+        U: Into<String>,
+    {
+        Self {
+            agent,
+            url: url.into(),
+        }
+    }
+}
+
+impl RangeSource for HttpSource {
+    fn length(&mut self) -> std::io::Result<u64> {
+        let response = self.agent.head(&self.url).call().map_err(|e| e.into_io())?;
+        response
+            .headers()
+            .get("content-length")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| std::io::Error::other("missing content length"))
+    }
+
+    fn fetch(&mut self, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+        if 0 == length {
+            return Ok(vec![]);
+        }
+        let mut response = self
+            .agent
+            .get(&self.url)
+            .header("Range", format!("bytes={}-{}", offset, offset + length - 1))
+            .call()
+            .map_err(|e| e.into_io())?;
+        if 206 != response.status().as_u16() {
+            return Err(std::io::Error::other("range requests are not supported"));
+        }
+        response
+            .body_mut()
+            .with_config()
+            .limit(length)
+            .read_to_vec()
+            .map_err(|e| e.into_io())
+    }
+}
+
+impl RangeReader<HttpSource> {
+    pub fn http<U>(url: U) -> std::io::Result<Self>
+    where
+        U: Into<String>,
+    {
+        Self::new(HttpSource::new(url))
+    }
+}
@@ -1 +1,7 @@
+#[cfg(feature = "http")]
+pub mod http;
+pub mod progress;
+pub mod range;
 pub mod reader;
+mod sync;
+pub mod units;
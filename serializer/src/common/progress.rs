@@ -0,0 +1,172 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+type ProgressCallback = Box<dyn FnMut(u64, u64) + Send>;
+
+pub struct ProgressReader<T>
+where
+    T: Read + Seek,
+{
+    stream: T,
+    position: u64,
+    length: u64,
+    interval: u64,
+    reported: u64,
+    on_progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl<T> ProgressReader<T>
+where
+    T: Read + Seek,
+{
+    pub fn new(mut stream: T) -> std::io::Result<Self> {
+        let position = stream.stream_position()?;
+        let length = stream.seek(SeekFrom::End(0))?;
+        stream.seek(SeekFrom::Start(position))?;
+        Ok(Self {
+            stream,
+            position,
+            length,
+            interval: 64 * 1024,
+            reported: position,
+            on_progress: None,
+            cancellation: None,
+        })
+    }
+
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: FnMut(u64, u64) + Send + 'static,
+    {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    pub fn cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn interval(mut self, interval: u64) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn into_inner(self) -> T {
+        self.stream
+    }
+
+    fn report(&mut self) {
+        if let Some(on_progress) = &mut self.on_progress {
+            let is_due = self.position >= self.reported.saturating_add(self.interval)
+                || (self.position == self.length && self.reported != self.length);
+            if is_due {
+                self.reported = self.position;
+                on_progress(self.position, self.length);
+            }
+        }
+    }
+}
+
+impl<T> Read for ProgressReader<T>
+where
+    T: Read + Seek,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(std::io::Error::other("operation cancelled"));
+        }
+        let size = self.stream.read(buf)?;
+        self.position += size as u64;
+        self.report();
+        Ok(size)
+    }
+}
+
+impl<T> Seek for ProgressReader<T>
+where
+    T: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = self.stream.seek(pos)?;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn report_progress() {
+        let reports = Arc::new(Mutex::new(vec![]));
+        let sink = reports.clone();
+        let mut reader = ProgressReader::new(Cursor::new([0u8; 10]))
+            .unwrap()
+            .interval(4)
+            .on_progress(move |position, length| sink.lock().unwrap().push((position, length)));
+        let mut buf = [0u8; 3];
+        for _ in 0..3 {
+            reader.read_exact(&mut buf).unwrap();
+        }
+        reader.read_exact(&mut buf[..1]).unwrap();
+        assert_eq!(vec![(6, 10), (10, 10)], *reports.lock().unwrap());
+    }
+
+    #[test]
+    fn cancel() {
+        let cancellation = CancellationToken::new();
+        let mut reader = ProgressReader::new(Cursor::new([0u8; 4]))
+            .unwrap()
+            .cancellation(cancellation.clone());
+        let mut buf = [0u8; 2];
+        assert!(reader.read_exact(&mut buf).is_ok());
+        cancellation.cancel();
+        assert!(cancellation.is_cancelled());
+        assert_eq!(
+            "operation cancelled",
+            reader.read_exact(&mut buf).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn seek() {
+        let mut reader = ProgressReader::new(Cursor::new([1u8, 2, 3, 4])).unwrap();
+        assert_eq!(4, reader.length());
+        reader.seek(SeekFrom::Start(3)).unwrap();
+        let mut buf = [0u8; 1];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!([4], buf);
+        assert_eq!(4, reader.stream_position().unwrap());
+    }
+}
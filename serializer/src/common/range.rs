@@ -0,0 +1,239 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Seek, SeekFrom};
+
+pub trait RangeSource {
+    fn length(&mut self) -> std::io::Result<u64>;
+    fn fetch(&mut self, offset: u64, length: u64) -> std::io::Result<Vec<u8>>;
+}
+
+pub struct RangeReader<S>
+where
+    S: RangeSource,
+{
+    source: S,
+    position: u64,
+    length: u64,
+    block_size: u64,
+    prefetch: u64,
+    capacity: usize,
+    blocks: HashMap<u64, Vec<u8>>,
+    order: VecDeque<u64>,
+    request_count: usize,
+}
+
+impl<S> RangeReader<S>
+where
+    S: RangeSource,
+{
+    pub fn new(mut source: S) -> std::io::Result<Self> {
+        let length = source.length()?;
+        Ok(Self {
+            source,
+            position: 0,
+            length,
+            block_size: 64 * 1024,
+            prefetch: 4,
+            capacity: 256,
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+            request_count: 0,
+        })
+    }
+
+    pub fn block_size(mut self, block_size: u64) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    pub fn prefetch(mut self, prefetch: u64) -> Self {
+        self.prefetch = prefetch.max(1);
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn request_count(&self) -> usize {
+        self.request_count
+    }
+
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    fn load(&mut self, block: u64) -> std::io::Result<&[u8]> {
+        if !self.blocks.contains_key(&block) {
+            self.fetch(block)?;
+        }
+        self.blocks
+            .get(&block)
+            .map(Vec::as_slice)
+            .ok_or_else(|| std::io::ErrorKind::UnexpectedEof.into())
+    }
+
+    fn fetch(&mut self, block: u64) -> std::io::Result<()> {
+        let prefetch = self.prefetch.min(self.capacity as u64);
+        let begin = block * self.block_size;
+        let end = ((block + prefetch) * self.block_size).min(self.length);
+        let data = self.source.fetch(begin, end - begin)?;
+        self.request_count += 1;
+        if (data.len() as u64) < end - begin {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        for (i, chunk) in data.chunks(self.block_size as usize).enumerate() {
+            let index = block + i as u64;
+            if self.blocks.insert(index, chunk.to_vec()).is_none() {
+                self.order.push_back(index);
+            }
+        }
+        while self.capacity < self.order.len() {
+            if let Some(index) = self.order.pop_front() {
+                self.blocks.remove(&index);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S> Read for RangeReader<S>
+where
+    S: RangeSource,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.length <= self.position || buf.is_empty() {
+            return Ok(0);
+        }
+        let block = self.position / self.block_size;
+        let offset = (self.position - block * self.block_size) as usize;
+        let data = self.load(block)?;
+        let count = buf.len().min(data.len() - offset);
+        buf[..count].copy_from_slice(&data[offset..offset + count]);
+        self.position += count as u64;
+        Ok(count)
+    }
+}
+
+impl<S> Seek for RangeReader<S>
+where
+    S: RangeSource,
+{
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        match position {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            }
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::jt::archive::Archive;
+
+    use super::*;
+
+    struct MemorySource {
+        data: Vec<u8>,
+        requests: Vec<(u64, u64)>,
+    }
+
+    impl RangeSource for MemorySource {
+        fn length(&mut self) -> std::io::Result<u64> {
+            Ok(self.data.len() as u64)
+        }
+
+        fn fetch(&mut self, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+            self.requests.push((offset, length));
+            let begin = offset as usize;
+            Ok(self.data[begin..begin + length as usize].to_vec())
+        }
+    }
+
+    fn reader(length: u8) -> RangeReader<MemorySource> {
+        RangeReader::new(MemorySource {
+            data: (0..length).collect(),
+            requests: vec![],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn read_with_prefetch() {
+        let mut reader = reader(100).block_size(10).prefetch(3);
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer).unwrap();
+        assert_eq!((0..100).collect::<Vec<u8>>(), buffer);
+        assert_eq!(
+            vec![(0, 30), (30, 30), (60, 30), (90, 10)],
+            reader.into_inner().requests
+        );
+    }
+
+    #[test]
+    fn seek_and_cache() {
+        let mut reader = reader(100).block_size(10).prefetch(1).capacity(2);
+        let mut buffer = [0u8; 4];
+        assert_eq!(95, reader.seek(SeekFrom::End(-5)).unwrap());
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!([95, 96, 97, 98], buffer);
+        reader.seek(SeekFrom::Start(8)).unwrap();
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!([8, 9, 10, 11], buffer);
+        reader.seek(SeekFrom::Current(-12)).unwrap();
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!([0, 1, 2, 3], buffer);
+        assert_eq!(3, reader.request_count());
+        reader.seek(SeekFrom::Start(96)).unwrap();
+        reader.read_exact(&mut buffer[..1]).unwrap();
+        assert_eq!(4, reader.request_count());
+        assert!(reader.seek(SeekFrom::Current(-200)).is_err());
+
+        assert_eq!(110, reader.seek(SeekFrom::End(10)).unwrap());
+        assert_eq!(0, reader.read(&mut buffer).unwrap());
+    }
+
+    #[test]
+    fn prefetch_beyond_capacity() {
+        let mut reader = reader(100).block_size(10).prefetch(4).capacity(1);
+        let mut buffer = [0u8; 4];
+        reader.seek(SeekFrom::Start(25)).unwrap();
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!([25, 26, 27, 28], buffer);
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!([29, 30, 31, 32], buffer);
+        assert_eq!(vec![(20, 10), (30, 10)], reader.into_inner().requests);
+    }
+
+    #[test]
+    fn jt_archive_from_range_reader() {
+        let mut data = vec![0u8; 80];
+        data[..11].copy_from_slice(b"Version 8.1");
+        let mut reader = RangeReader::new(MemorySource {
+            data: data.clone(),
+            requests: vec![],
+        })
+        .unwrap();
+        assert_eq!(
+            Archive::read(Cursor::new(data)).err(),
+            Archive::read(&mut reader).err()
+        );
+    }
+}
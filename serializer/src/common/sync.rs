@@ -0,0 +1,13 @@
+use crate::{jt, model, rhino};
+
+const fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+const _: () = {
+    assert_send_sync::<rhino::archive::Archive>();
+    assert_send_sync::<rhino::document::SharedDocument>();
+    assert_send_sync::<rhino::object_index::ObjectIndex>();
+    assert_send_sync::<rhino::error::Error>();
+    assert_send_sync::<jt::archive::Archive>();
+    assert_send_sync::<jt::lsg::Lsg>();
+    assert_send_sync::<model::Document>();
+};
@@ -0,0 +1,196 @@
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum UnitSystem {
+    #[default]
+    None,
+    Angstroms,
+    Nanometers,
+    Microns,
+    Millimeters,
+    Centimeters,
+    Decimeters,
+    Meters,
+    Dekameters,
+    Hectometers,
+    Kilometers,
+    Megameters,
+    Gigameters,
+    Microinches,
+    Mils,
+    Inches,
+    Feet,
+    Yards,
+    Miles,
+    PrinterPoints,
+    PrinterPicas,
+    NauticalMiles,
+    AstronomicalUnits,
+    LightYears,
+    Parsecs,
+    CustomUnits,
+    Unset,
+}
+
+impl UnitSystem {
+    pub fn meters_per_unit(&self) -> Option<f64> {
+        match self {
+            Self::Angstroms => Some(1e-10),
+            Self::Nanometers => Some(1e-9),
+            Self::Microns => Some(1e-6),
+            Self::Millimeters => Some(1e-3),
+            Self::Centimeters => Some(1e-2),
+            Self::Decimeters => Some(1e-1),
+            Self::Meters => Some(1.),
+            Self::Dekameters => Some(1e1),
+            Self::Hectometers => Some(1e2),
+            Self::Kilometers => Some(1e3),
+            Self::Megameters => Some(1e6),
+            Self::Gigameters => Some(1e9),
+            Self::Microinches => Some(2.54e-8),
+            Self::Mils => Some(2.54e-5),
+            Self::Inches => Some(0.0254),
+            Self::Feet => Some(0.3048),
+            Self::Yards => Some(0.9144),
+            Self::Miles => Some(1609.344),
+            Self::PrinterPoints => Some(0.0254 / 72.),
+            Self::PrinterPicas => Some(0.0254 / 6.),
+            Self::NauticalMiles => Some(1852.),
+            Self::AstronomicalUnits => Some(1.495978707e11),
+            Self::LightYears => Some(9.4607304725808e15),
+            Self::Parsecs => Some(3.08567758149137e16),
+            Self::None | Self::CustomUnits | Self::Unset => None,
+        }
+    }
+
+    pub fn scale_to(&self, target: &Self) -> Option<f64> {
+        Some(self.meters_per_unit()? / target.meters_per_unit()?)
+    }
+
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Angstroms => "A",
+            Self::Nanometers => "nm",
+            Self::Microns => "um",
+            Self::Millimeters => "mm",
+            Self::Centimeters => "cm",
+            Self::Decimeters => "dm",
+            Self::Meters => "m",
+            Self::Dekameters => "dam",
+            Self::Hectometers => "hm",
+            Self::Kilometers => "km",
+            Self::Megameters => "Mm",
+            Self::Gigameters => "Gm",
+            Self::Microinches => "uin",
+            Self::Mils => "mil",
+            Self::Inches => "in",
+            Self::Feet => "ft",
+            Self::Yards => "yd",
+            Self::Miles => "mi",
+            Self::PrinterPoints => "pt",
+            Self::PrinterPicas => "pc",
+            Self::NauticalMiles => "nmi",
+            Self::AstronomicalUnits => "au",
+            Self::LightYears => "ly",
+            Self::Parsecs => "pc",
+            Self::None | Self::CustomUnits | Self::Unset => "",
+        }
+    }
+}
+
+impl From<i32> for UnitSystem {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Microns,
+            2 => Self::Millimeters,
+            3 => Self::Centimeters,
+            4 => Self::Meters,
+            5 => Self::Kilometers,
+            6 => Self::Microinches,
+            7 => Self::Mils,
+            8 => Self::Inches,
+            9 => Self::Feet,
+            10 => Self::Miles,
+            11 => Self::CustomUnits,
+            12 => Self::Angstroms,
+            13 => Self::Nanometers,
+            14 => Self::Decimeters,
+            15 => Self::Dekameters,
+            16 => Self::Hectometers,
+            17 => Self::Megameters,
+            18 => Self::Gigameters,
+            19 => Self::Yards,
+            20 => Self::PrinterPoints,
+            21 => Self::PrinterPicas,
+            22 => Self::NauticalMiles,
+            23 => Self::AstronomicalUnits,
+            24 => Self::LightYears,
+            25 => Self::Parsecs,
+            _ => Self::Unset,
+        }
+    }
+}
+
+impl From<UnitSystem> for i32 {
+    fn from(unit_system: UnitSystem) -> Self {
+        match unit_system {
+            UnitSystem::None => 0,
+            UnitSystem::Microns => 1,
+            UnitSystem::Millimeters => 2,
+            UnitSystem::Centimeters => 3,
+            UnitSystem::Meters => 4,
+            UnitSystem::Kilometers => 5,
+            UnitSystem::Microinches => 6,
+            UnitSystem::Mils => 7,
+            UnitSystem::Inches => 8,
+            UnitSystem::Feet => 9,
+            UnitSystem::Miles => 10,
+            UnitSystem::CustomUnits => 11,
+            UnitSystem::Angstroms => 12,
+            UnitSystem::Nanometers => 13,
+            UnitSystem::Decimeters => 14,
+            UnitSystem::Dekameters => 15,
+            UnitSystem::Hectometers => 16,
+            UnitSystem::Megameters => 17,
+            UnitSystem::Gigameters => 18,
+            UnitSystem::Yards => 19,
+            UnitSystem::PrinterPoints => 20,
+            UnitSystem::PrinterPicas => 21,
+            UnitSystem::NauticalMiles => 22,
+            UnitSystem::AstronomicalUnits => 23,
+            UnitSystem::LightYears => 24,
+            UnitSystem::Parsecs => 25,
+            UnitSystem::Unset => 255,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale() {
+        assert_eq!(
+            Some(25.4),
+            UnitSystem::Inches.scale_to(&UnitSystem::Millimeters)
+        );
+        assert_eq!(
+            Some(1e-3),
+            UnitSystem::Millimeters.scale_to(&UnitSystem::Meters)
+        );
+        assert_eq!(Some(1.), UnitSystem::Feet.scale_to(&UnitSystem::Feet));
+        assert_eq!(None, UnitSystem::None.scale_to(&UnitSystem::Meters));
+        assert_eq!(None, UnitSystem::Meters.scale_to(&UnitSystem::CustomUnits));
+    }
+
+    #[test]
+    fn from_i32() {
+        (0..=25).chain([255]).for_each(|value| {
+            assert_eq!(value, i32::from(UnitSystem::from(value)));
+        });
+        assert_eq!(UnitSystem::Unset, UnitSystem::from(-1));
+        assert_eq!(UnitSystem::Millimeters, UnitSystem::from(2));
+        assert_eq!("mm", UnitSystem::Millimeters.abbreviation());
+    }
+}
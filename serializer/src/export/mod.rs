@@ -0,0 +1,42 @@
+use std::io;
+
+use crate::{
+    common::units::UnitSystem,
+    math::{Transformable, Xform},
+    model::{Document, Mesh},
+};
+
+pub mod ply;
+pub mod stl;
+pub mod threemf;
+mod zip;
+
+pub use ply::{write_ply, PlyFormat, PlyOptions};
+pub use stl::{write_stl, StlFormat, StlOptions};
+pub use threemf::{write_3mf, ThreeMfOptions};
+
+pub(crate) fn scale_factor(
+    document: &Document,
+    units: Option<UnitSystem>,
+    scale: f64,
+) -> io::Result<f64> {
+    let factor = match units {
+        Some(target) => document.units.scale_to(&target).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "document units cannot be converted",
+            )
+        })?,
+        None => 1.,
+    };
+    Ok(factor * scale)
+}
+
+pub(crate) fn world_meshes(document: &Document, factor: f64) -> Vec<Mesh> {
+    let scale = Xform::uniform_scale(factor);
+    document
+        .flatten()
+        .iter()
+        .map(|placed| placed.world_mesh().transformed(&scale))
+        .collect()
+}
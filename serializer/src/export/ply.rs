@@ -0,0 +1,271 @@
+use std::io::{self, Write};
+
+use crate::{
+    common::units::UnitSystem,
+    model::{Document, Mesh},
+};
+
+use super::{scale_factor, world_meshes};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum PlyFormat {
+    #[default]
+    BinaryLittleEndian,
+    Ascii,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlyOptions {
+    pub format: PlyFormat,
+    pub units: Option<UnitSystem>,
+    pub scale: f64,
+}
+
+impl Default for PlyOptions {
+    fn default() -> Self {
+        Self {
+            format: PlyFormat::BinaryLittleEndian,
+            units: None,
+            scale: 1.,
+        }
+    }
+}
+
+impl PlyOptions {
+    pub fn with_format(mut self, format: PlyFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_units(mut self, units: UnitSystem) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+const WHITE: [f32; 4] = [1.; 4];
+
+fn color_bytes(color: &[f32; 4]) -> [u8; 4] {
+    color.map(|c| (c.clamp(0., 1.) * 255.).round() as u8)
+}
+
+fn merge(meshes: &[Mesh]) -> (Mesh, bool, bool) {
+    let with_normals = !meshes.is_empty() && meshes.iter().all(Mesh::has_normals);
+    let with_colors = meshes.iter().any(Mesh::has_colors);
+    let mut merged = Mesh::default();
+    for mesh in meshes {
+        let offset = merged.positions.len() as u32;
+        merged.positions.extend_from_slice(&mesh.positions);
+        if with_normals {
+            merged.normals.extend_from_slice(&mesh.normals);
+        }
+        if with_colors {
+            if mesh.has_colors() {
+                merged.colors.extend_from_slice(&mesh.colors);
+            } else {
+                merged.colors.extend(mesh.positions.iter().map(|_| WHITE));
+            }
+        }
+        merged.triangles.extend(
+            mesh.triangles
+                .iter()
+                .filter(|t| t.iter().all(|i| (*i as usize) < mesh.positions.len()))
+                .map(|t| t.map(|i| i + offset)),
+        );
+    }
+    (merged, with_normals, with_colors)
+}
+
+fn write_header<W: Write>(
+    mesh: &Mesh,
+    format: PlyFormat,
+    with_normals: bool,
+    with_colors: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "ply")?;
+    match format {
+        PlyFormat::BinaryLittleEndian => writeln!(writer, "format binary_little_endian 1.0")?,
+        PlyFormat::Ascii => writeln!(writer, "format ascii 1.0")?,
+    }
+    writeln!(writer, "comment generated by geometria")?;
+    writeln!(writer, "element vertex {}", mesh.positions.len())?;
+    for name in ["x", "y", "z"] {
+        writeln!(writer, "property float {}", name)?;
+    }
+    if with_normals {
+        for name in ["nx", "ny", "nz"] {
+            writeln!(writer, "property float {}", name)?;
+        }
+    }
+    if with_colors {
+        for name in ["red", "green", "blue", "alpha"] {
+            writeln!(writer, "property uchar {}", name)?;
+        }
+    }
+    writeln!(writer, "element face {}", mesh.triangles.len())?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "end_header")
+}
+
+fn write_binary<W: Write>(
+    mesh: &Mesh,
+    with_normals: bool,
+    with_colors: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    for (i, position) in mesh.positions.iter().enumerate() {
+        for value in position {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        if with_normals {
+            for value in mesh.normals[i] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        if with_colors {
+            writer.write_all(&color_bytes(&mesh.colors[i]))?;
+        }
+    }
+    for triangle in mesh.triangles.iter() {
+        writer.write_all(&[3])?;
+        for index in triangle {
+            writer.write_all(&index.to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+fn write_ascii<W: Write>(
+    mesh: &Mesh,
+    with_normals: bool,
+    with_colors: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    for (i, [x, y, z]) in mesh.positions.iter().enumerate() {
+        write!(writer, "{} {} {}", x, y, z)?;
+        if with_normals {
+            let [x, y, z] = mesh.normals[i];
+            write!(writer, " {} {} {}", x, y, z)?;
+        }
+        if with_colors {
+            let [r, g, b, a] = color_bytes(&mesh.colors[i]);
+            write!(writer, " {} {} {} {}", r, g, b, a)?;
+        }
+        writeln!(writer)?;
+    }
+    for [a, b, c] in mesh.triangles.iter() {
+        writeln!(writer, "3 {} {} {}", a, b, c)?;
+    }
+    Ok(())
+}
+
+pub fn write_ply<W: Write>(
+    document: &Document,
+    options: &PlyOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    let factor = scale_factor(document, options.units, options.scale)?;
+    let (mesh, with_normals, with_colors) = merge(&world_meshes(document, factor));
+    if u32::try_from(mesh.positions.len()).is_err() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "too many vertices",
+        ));
+    }
+    write_header(
+        &mesh,
+        options.format,
+        with_normals,
+        with_colors,
+        &mut writer,
+    )?;
+    match options.format {
+        PlyFormat::BinaryLittleEndian => {
+            write_binary(&mesh, with_normals, with_colors, &mut writer)
+        }
+        PlyFormat::Ascii => write_ascii(&mesh, with_normals, with_colors, &mut writer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::Node;
+
+    use super::*;
+
+    fn triangle(colors: Vec<[f32; 4]>) -> Mesh {
+        Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            normals: vec![],
+            colors,
+            triangles: vec![[0, 1, 2]],
+        }
+    }
+
+    fn document() -> Document {
+        Document {
+            nodes: vec![Node {
+                meshes: vec![0, 1],
+                ..Default::default()
+            }],
+            roots: vec![0],
+            meshes: vec![
+                triangle(vec![[1., 0., 0., 1.], [0., 1., 0., 1.], [0., 0., 1., 0.5]]),
+                triangle(vec![]),
+            ],
+            units: UnitSystem::Meters,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ascii() {
+        let mut data: Vec<u8> = vec![];
+        let options = PlyOptions::default()
+            .with_format(PlyFormat::Ascii)
+            .with_units(UnitSystem::Centimeters);
+        write_ply(&document(), &options, &mut data).unwrap();
+        let text = String::from_utf8(data).unwrap();
+        let (header, body) = text.split_once("end_header\n").unwrap();
+        assert!(header.starts_with("ply\nformat ascii 1.0\n"));
+        assert!(header.contains("element vertex 6\n"));
+        assert!(header.contains("property uchar alpha\n"));
+        assert!(!header.contains("property float nx\n"));
+        assert!(header.contains("element face 2\n"));
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(8, lines.len());
+        assert_eq!("100 0 0 0 255 0 255", lines[1]);
+        assert_eq!("0 100 0 0 0 255 128", lines[2]);
+        assert_eq!("0 0 0 255 255 255 255", lines[3]);
+        assert_eq!("3 3 4 5", lines[7]);
+    }
+
+    #[test]
+    fn binary() {
+        let mut document = document();
+        document.meshes.iter_mut().for_each(|mesh| {
+            mesh.colors.clear();
+            mesh.compute_vertex_normals();
+        });
+        let mut data: Vec<u8> = vec![];
+        write_ply(&document, &PlyOptions::default().with_scale(2.), &mut data).unwrap();
+        let end = b"end_header\n";
+        let start = data.windows(end.len()).position(|w| w == end).unwrap() + end.len();
+        let header = std::str::from_utf8(&data[..start]).unwrap();
+        assert!(header.contains("format binary_little_endian 1.0\n"));
+        assert!(header.contains("property float nz\n"));
+        assert!(!header.contains("property uchar red\n"));
+        let body = &data[start..];
+        assert_eq!(6 * 24 + 2 * 13, body.len());
+        assert_eq!(2f32.to_le_bytes(), body[24..28]);
+        assert_eq!(1f32.to_le_bytes(), body[44..48]);
+        assert_eq!(3, body[6 * 24]);
+        assert_eq!(5u32.to_le_bytes(), body[6 * 24 + 22..6 * 24 + 26]);
+    }
+}
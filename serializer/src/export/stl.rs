@@ -0,0 +1,194 @@
+use std::io::{self, Write};
+
+use crate::{
+    common::units::UnitSystem,
+    math::Vector3d,
+    model::{Document, Mesh},
+};
+
+use super::{scale_factor, world_meshes};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum StlFormat {
+    #[default]
+    Binary,
+    Ascii,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StlOptions {
+    pub format: StlFormat,
+    pub name: String,
+    pub units: Option<UnitSystem>,
+    pub scale: f64,
+}
+
+impl Default for StlOptions {
+    fn default() -> Self {
+        Self {
+            format: StlFormat::Binary,
+            name: "geometria".to_string(),
+            units: None,
+            scale: 1.,
+        }
+    }
+}
+
+impl StlOptions {
+    pub fn with_format(mut self, format: StlFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_units(mut self, units: UnitSystem) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+fn facets(mesh: &Mesh) -> impl Iterator<Item = ([f32; 3], [[f32; 3]; 3])> + '_ {
+    (0..mesh.triangles.len()).filter_map(|t| {
+        let normal = mesh.triangle_normal(t).unwrap_or(Vector3d::ZERO);
+        let vertices = mesh.triangles[t].map(|i| mesh.positions.get(i as usize).copied());
+        match vertices {
+            [Some(a), Some(b), Some(c)] => Some((normal.into(), [a, b, c])),
+            _ => None,
+        }
+    })
+}
+
+fn write_binary<W: Write>(meshes: &[Mesh], name: &str, writer: &mut W) -> io::Result<()> {
+    let mut header = [0u8; 80];
+    name.bytes()
+        .take(header.len())
+        .enumerate()
+        .for_each(|(i, byte)| header[i] = byte);
+    writer.write_all(&header)?;
+    let count = meshes.iter().flat_map(facets).count();
+    let count = u32::try_from(count)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many triangles"))?;
+    writer.write_all(&count.to_le_bytes())?;
+    for (normal, vertices) in meshes.iter().flat_map(facets) {
+        for value in [normal].iter().chain(vertices.iter()).flatten() {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_ascii<W: Write>(meshes: &[Mesh], name: &str, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "solid {}", name)?;
+    for (normal, vertices) in meshes.iter().flat_map(facets) {
+        writeln!(
+            writer,
+            "  facet normal {:e} {:e} {:e}",
+            normal[0], normal[1], normal[2]
+        )?;
+        writeln!(writer, "    outer loop")?;
+        for vertex in vertices {
+            writeln!(
+                writer,
+                "      vertex {:e} {:e} {:e}",
+                vertex[0], vertex[1], vertex[2]
+            )?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+    writeln!(writer, "endsolid {}", name)
+}
+
+pub fn write_stl<W: Write>(
+    document: &Document,
+    options: &StlOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    let factor = scale_factor(document, options.units, options.scale)?;
+    let meshes = world_meshes(document, factor);
+    match options.format {
+        StlFormat::Binary => write_binary(&meshes, &options.name, &mut writer),
+        StlFormat::Ascii => write_ascii(&meshes, &options.name, &mut writer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Node, Transform};
+
+    use super::*;
+
+    fn document() -> Document {
+        let mut transform = Transform::IDENTITY;
+        transform.0[12] = 2.;
+        Document {
+            nodes: vec![
+                Node {
+                    meshes: vec![0],
+                    ..Default::default()
+                },
+                Node {
+                    transform,
+                    meshes: vec![0],
+                    ..Default::default()
+                },
+            ],
+            roots: vec![0, 1],
+            meshes: vec![Mesh {
+                positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+                normals: vec![],
+                colors: vec![],
+                triangles: vec![[0, 1, 2]],
+            }],
+            units: UnitSystem::Meters,
+            ..Default::default()
+        }
+    }
+
+    fn read_f32(data: &[u8], offset: usize) -> f32 {
+        f32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn binary() {
+        let mut data: Vec<u8> = vec![];
+        let options = StlOptions::default().with_name("part");
+        write_stl(&document(), &options, &mut data).unwrap();
+        assert_eq!(84 + 2 * 50, data.len());
+        assert!(data.starts_with(b"part\0"));
+        assert_eq!(2, u32::from_le_bytes(data[80..84].try_into().unwrap()));
+        assert_eq!(1., read_f32(&data, 84 + 8));
+        assert_eq!(2., read_f32(&data, 134 + 12));
+        assert_eq!(3., read_f32(&data, 134 + 24));
+    }
+
+    #[test]
+    fn ascii() {
+        let mut data: Vec<u8> = vec![];
+        let options = StlOptions::default()
+            .with_format(StlFormat::Ascii)
+            .with_units(UnitSystem::Millimeters)
+            .with_scale(0.5);
+        write_stl(&document(), &options, &mut data).unwrap();
+        let text = String::from_utf8(data).unwrap();
+        assert!(text.starts_with("solid geometria\n"));
+        assert!(text.ends_with("endsolid geometria\n"));
+        assert_eq!(2, text.matches("facet normal 0e0 0e0 1e0").count());
+        assert_eq!(6, text.matches("vertex").count());
+        assert!(text.contains("vertex 1.5e3 0e0 0e0"));
+
+        let mut document = document();
+        document.units = UnitSystem::None;
+        assert!(write_stl(&document, &options, std::io::sink()).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    common::units::UnitSystem,
+    model::{Document, Mesh, Transform},
+};
+
+use super::{scale_factor, zip::ZipWriter};
+
+const CONTENT_TYPES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>"#,
+    r#"</Types>"#,
+);
+
+const RELATIONSHIPS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>"#,
+    r#"</Relationships>"#,
+);
+
+const CORE_NAMESPACE: &str = "http://schemas.microsoft.com/3dmanufacturing/core/2015/02";
+
+const MATERIALS_ID: usize = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreeMfOptions {
+    pub units: Option<UnitSystem>,
+    pub scale: f64,
+}
+
+impl Default for ThreeMfOptions {
+    fn default() -> Self {
+        Self {
+            units: None,
+            scale: 1.,
+        }
+    }
+}
+
+impl ThreeMfOptions {
+    pub fn with_units(mut self, units: UnitSystem) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+fn unit_name(units: UnitSystem) -> Option<&'static str> {
+    match units {
+        UnitSystem::Microns => Some("micron"),
+        UnitSystem::Millimeters => Some("millimeter"),
+        UnitSystem::Centimeters => Some("centimeter"),
+        UnitSystem::Inches => Some("inch"),
+        UnitSystem::Feet => Some("foot"),
+        UnitSystem::Meters => Some("meter"),
+        _ => None,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+fn display_color(diffuse: Option<[f32; 4]>) -> String {
+    diffuse
+        .unwrap_or([1.; 4])
+        .iter()
+        .map(|c| format!("{:02X}", (c.clamp(0., 1.) * 255.).round() as u8))
+        .fold("#".to_string(), |color, component| color + &component)
+}
+
+fn item_transform(transform: &Transform, factor: f64) -> io::Result<String> {
+    let m = &transform.0;
+    if [0., 0., 0., 1.] != [m[3], m[7], m[11], m[15]] {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "3mf build items require affine transforms",
+        ));
+    }
+    let elements: Vec<String> = [0, 1, 2, 4, 5, 6, 8, 9, 10]
+        .iter()
+        .map(|i| m[*i])
+        .chain(m[12..15].iter().map(|v| v * factor))
+        .map(|v| v.to_string())
+        .collect();
+    Ok(elements.join(" "))
+}
+
+fn write_object(model: &mut String, id: usize, mesh: &Mesh, material: Option<usize>, factor: f64) {
+    model.push_str(&format!(r#"<object id="{}" type="model""#, id));
+    if let Some(material) = material {
+        model.push_str(&format!(r#" pid="{}" pindex="{}""#, MATERIALS_ID, material));
+    }
+    model.push_str("><mesh><vertices>");
+    for position in mesh.positions.iter() {
+        let [x, y, z] = position.map(|v| (v as f64 * factor) as f32);
+        model.push_str(&format!(r#"<vertex x="{}" y="{}" z="{}"/>"#, x, y, z));
+    }
+    model.push_str("</vertices><triangles>");
+    for [a, b, c] in mesh
+        .triangles
+        .iter()
+        .filter(|t| t.iter().all(|i| (*i as usize) < mesh.positions.len()))
+    {
+        model.push_str(&format!(r#"<triangle v1="{}" v2="{}" v3="{}"/>"#, a, b, c));
+    }
+    model.push_str("</triangles></mesh></object>");
+}
+
+fn model(document: &Document, options: &ThreeMfOptions) -> io::Result<String> {
+    let target = options.units.unwrap_or(document.units);
+    let (units, unit) = match unit_name(target) {
+        Some(unit) => (Some(target), unit),
+        None if options.units.is_some() => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unit system is not supported by 3mf",
+            ))
+        }
+        None if document.units.meters_per_unit().is_some() => {
+            (Some(UnitSystem::Millimeters), "millimeter")
+        }
+        None => (None, "millimeter"),
+    };
+    let factor = scale_factor(document, units, options.scale)?;
+
+    let unique = document.unique_meshes();
+    let mut objects: HashMap<(usize, Option<usize>), usize> = HashMap::new();
+    let mut order: Vec<(usize, Option<usize>)> = vec![];
+    let mut items: Vec<(usize, String)> = vec![];
+    for (node, transform) in document.nodes.iter().zip(document.world_transforms()) {
+        let material = node.material.filter(|m| *m < document.materials.len());
+        for mesh in node.meshes.iter().filter(|mesh| **mesh < unique.len()) {
+            let key = (unique[*mesh], material);
+            let id = *objects.entry(key).or_insert_with(|| {
+                order.push(key);
+                MATERIALS_ID + order.len()
+            });
+            items.push((id, item_transform(&transform, factor)?));
+        }
+    }
+
+    let mut model = String::new();
+    model.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    model.push_str(&format!(
+        r#"<model unit="{}" xml:lang="en-US" xmlns="{}">"#,
+        unit, CORE_NAMESPACE
+    ));
+    model.push_str("<resources>");
+    if !document.materials.is_empty() {
+        model.push_str(&format!(r#"<basematerials id="{}">"#, MATERIALS_ID));
+        for (i, material) in document.materials.iter().enumerate() {
+            let name = material
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("material {}", i));
+            model.push_str(&format!(
+                r#"<base name="{}" displaycolor="{}"/>"#,
+                escape(&name),
+                display_color(material.diffuse)
+            ));
+        }
+        model.push_str("</basematerials>");
+    }
+    for (i, (mesh, material)) in order.iter().enumerate() {
+        write_object(
+            &mut model,
+            MATERIALS_ID + 1 + i,
+            &document.meshes[*mesh],
+            *material,
+            factor,
+        );
+    }
+    model.push_str("</resources><build>");
+    for (id, transform) in items.iter() {
+        model.push_str(&format!(
+            r#"<item objectid="{}" transform="{}"/>"#,
+            id, transform
+        ));
+    }
+    model.push_str("</build></model>");
+    Ok(model)
+}
+
+pub fn write_3mf<W: Write>(
+    document: &Document,
+    options: &ThreeMfOptions,
+    writer: W,
+) -> io::Result<()> {
+    let model = model(document, options)?;
+    let mut zip = ZipWriter::new(writer);
+    zip.add("[Content_Types].xml", CONTENT_TYPES.as_bytes())?;
+    zip.add("_rels/.rels", RELATIONSHIPS.as_bytes())?;
+    zip.add("3D/3dmodel.model", model.as_bytes())?;
+    zip.finish()?.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Material, Node};
+
+    use super::{super::zip::tests::entries, *};
+
+    fn document() -> Document {
+        let mut transform = Transform::IDENTITY;
+        transform.0[12] = 5.;
+        let triangle = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2]],
+        };
+        Document {
+            nodes: vec![
+                Node {
+                    children: vec![1],
+                    meshes: vec![0],
+                    material: Some(0),
+                    ..Default::default()
+                },
+                Node {
+                    transform,
+                    meshes: vec![1, 0],
+                    material: Some(0),
+                    ..Default::default()
+                },
+            ],
+            roots: vec![0],
+            meshes: vec![triangle.clone(), triangle],
+            materials: vec![Material {
+                name: Some("red & shiny".to_string()),
+                diffuse: Some([1., 0., 0., 1.]),
+            }],
+            units: UnitSystem::Meters,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn package() {
+        let mut data: Vec<u8> = vec![];
+        let options = ThreeMfOptions::default().with_units(UnitSystem::Millimeters);
+        write_3mf(&document(), &options, &mut data).unwrap();
+        let entries = entries(&data);
+        assert_eq!(
+            vec!["[Content_Types].xml", "_rels/.rels", "3D/3dmodel.model"],
+            entries
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+        );
+        let model = String::from_utf8(entries[2].1.clone()).unwrap();
+        assert!(model.contains(r#"<model unit="millimeter""#));
+        assert!(model.contains(
+            r##"<basematerials id="1"><base name="red &amp; shiny" displaycolor="#FF0000FF"/></basematerials>"##
+        ));
+        assert_eq!(1, model.matches("<object ").count());
+        assert!(model.contains(r#"<object id="2" type="model" pid="1" pindex="0">"#));
+        assert!(model.contains(r#"<vertex x="1000" y="0" z="0"/>"#));
+        assert!(model.contains(r#"<triangle v1="0" v2="1" v3="2"/>"#));
+        assert_eq!(3, model.matches(r#"<item objectid="2""#).count());
+        assert!(model.contains(r#"transform="1 0 0 0 1 0 0 0 1 5000 0 0""#));
+    }
+
+    #[test]
+    fn units() {
+        let mut document = document();
+        let xml = model(&document, &ThreeMfOptions::default()).unwrap();
+        assert!(xml.contains(r#"unit="meter""#));
+        assert!(xml.contains(r#"<vertex x="1" y="0" z="0"/>"#));
+
+        document.units = UnitSystem::Kilometers;
+        let xml = model(&document, &ThreeMfOptions::default()).unwrap();
+        assert!(xml.contains(r#"unit="millimeter""#));
+        assert!(xml.contains(r#"<vertex x="1000000" y="0" z="0"/>"#));
+
+        let options = ThreeMfOptions::default().with_units(UnitSystem::Miles);
+        assert!(write_3mf(&document, &options, io::sink()).is_err());
+
+        document.nodes[1].transform.0[3] = 0.5;
+        assert!(model(&document, &ThreeMfOptions::default()).is_err());
+    }
+}
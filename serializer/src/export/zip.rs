@@ -0,0 +1,183 @@
+use std::io::{self, Write};
+
+use flate2::{write::DeflateEncoder, Compression, Crc};
+
+const LOCAL_HEADER: u32 = 0x04034b50;
+const CENTRAL_HEADER: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x06054b50;
+const VERSION: u16 = 20;
+const DEFLATE: u16 = 8;
+const DOS_DATE: u16 = 0x21;
+
+struct Entry {
+    name: String,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    offset: u32,
+}
+
+pub(crate) struct ZipWriter<W: Write> {
+    writer: W,
+    offset: u64,
+    entries: Vec<Entry>,
+}
+
+fn too_large() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "archive too large")
+}
+
+fn to_u32(value: impl TryInto<u32>) -> io::Result<u32> {
+    value.try_into().map_err(|_| too_large())
+}
+
+fn to_u16(value: impl TryInto<u16>) -> io::Result<u16> {
+    value.try_into().map_err(|_| too_large())
+}
+
+impl<W: Write> ZipWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            offset: 0,
+            entries: vec![],
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.writer.write_all(data)?;
+        self.offset += data.len() as u64;
+        Ok(())
+    }
+
+    fn write_u16(&mut self, value: u16) -> io::Result<()> {
+        self.write(&value.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, value: u32) -> io::Result<()> {
+        self.write(&value.to_le_bytes())
+    }
+
+    fn write_entry_fields(&mut self, entry: &Entry) -> io::Result<()> {
+        self.write_u16(VERSION)?;
+        self.write_u16(0)?;
+        self.write_u16(DEFLATE)?;
+        self.write_u16(0)?;
+        self.write_u16(DOS_DATE)?;
+        self.write_u32(entry.crc)?;
+        self.write_u32(entry.compressed_size)?;
+        self.write_u32(entry.size)?;
+        self.write_u16(to_u16(entry.name.len())?)?;
+        self.write_u16(0)
+    }
+
+    pub fn add(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let mut crc = Crc::new();
+        crc.update(data);
+        let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+        encoder.write_all(data)?;
+        let compressed = encoder.finish()?;
+        let entry = Entry {
+            name: name.to_string(),
+            crc: crc.sum(),
+            compressed_size: to_u32(compressed.len())?,
+            size: to_u32(data.len())?,
+            offset: to_u32(self.offset)?,
+        };
+        self.write_u32(LOCAL_HEADER)?;
+        self.write_entry_fields(&entry)?;
+        self.write(name.as_bytes())?;
+        self.write(&compressed)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        let start = to_u32(self.offset)?;
+        let entries = std::mem::take(&mut self.entries);
+        for entry in entries.iter() {
+            self.write_u32(CENTRAL_HEADER)?;
+            self.write_u16(VERSION)?;
+            self.write_entry_fields(entry)?;
+            self.write_u16(0)?;
+            self.write_u16(0)?;
+            self.write_u16(0)?;
+            self.write_u32(0)?;
+            self.write_u32(entry.offset)?;
+            self.write(entry.name.as_bytes())?;
+        }
+        let size = to_u32(self.offset - start as u64)?;
+        let count = to_u16(entries.len())?;
+        self.write_u32(END_OF_CENTRAL_DIRECTORY)?;
+        self.write_u16(0)?;
+        self.write_u16(0)?;
+        self.write_u16(count)?;
+        self.write_u16(count)?;
+        self.write_u32(size)?;
+        self.write_u32(start)?;
+        self.write_u16(0)?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Read;
+
+    use flate2::read::DeflateDecoder;
+
+    use super::*;
+
+    fn u16_at(data: &[u8], offset: usize) -> usize {
+        u16::from_le_bytes([data[offset], data[offset + 1]]) as usize
+    }
+
+    fn u32_at(data: &[u8], offset: usize) -> usize {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+    }
+
+    pub(crate) fn entries(data: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let end = data.len() - 22;
+        assert_eq!(END_OF_CENTRAL_DIRECTORY as usize, u32_at(data, end));
+        let mut offset = u32_at(data, end + 16);
+        (0..u16_at(data, end + 10))
+            .map(|_| {
+                assert_eq!(CENTRAL_HEADER as usize, u32_at(data, offset));
+                let name_length = u16_at(data, offset + 28);
+                let local = u32_at(data, offset + 42);
+                let name = &data[offset + 46..offset + 46 + name_length];
+                offset += 46 + name_length;
+
+                assert_eq!(LOCAL_HEADER as usize, u32_at(data, local));
+                let (crc, compressed_size) = (u32_at(data, local + 14), u32_at(data, local + 18));
+                let begin = local + 30 + u16_at(data, local + 26);
+                let mut content: Vec<u8> = vec![];
+                DeflateDecoder::new(&data[begin..begin + compressed_size])
+                    .read_to_end(&mut content)
+                    .unwrap();
+                assert_eq!(u32_at(data, local + 22), content.len());
+                let mut check = Crc::new();
+                check.update(&content);
+                assert_eq!(crc, check.sum() as usize);
+                (String::from_utf8(name.to_vec()).unwrap(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut writer = ZipWriter::new(vec![]);
+        writer.add("a.txt", b"hello hello hello").unwrap();
+        writer.add("dir/b.bin", &[0u8; 1000]).unwrap();
+        writer.add("empty", &[]).unwrap();
+        let data = writer.finish().unwrap();
+        assert_eq!(
+            vec![
+                ("a.txt".to_string(), b"hello hello hello".to_vec()),
+                ("dir/b.bin".to_string(), vec![0u8; 1000]),
+                ("empty".to_string(), vec![]),
+            ],
+            entries(&data)
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::io::{Cursor, Seek, SeekFrom};
+
+use crate::{
+    jt::{
+        self, deserialize::Deserialize as _, lsg::LsgSegment, reader::Reader as JtReader,
+        shape_lod::ShapeLodSegment,
+    },
+    rhino::{
+        self,
+        chunk::{Chunk, Chunks},
+        deserialize::Deserialize as _,
+        options::ParseOptions,
+        reader::{Reader as RhinoReader, ReaderBuilder},
+        version::Version,
+    },
+};
+
+pub fn rhino_archive(data: &[u8]) {
+    let _ = rhino::archive::Archive::from_bytes(data);
+    let _ = rhino::archive::Archive::from_stream_with_options(
+        Cursor::new(data),
+        ParseOptions::strict(),
+    );
+    let _ = rhino::archive::Archive::validate(Cursor::new(data));
+}
+
+pub fn rhino_chunks(data: &[u8]) {
+    let (version, data) = match data.split_first() {
+        Some((version, data)) => (Version::try_from(*version).unwrap_or(Version::V1), data),
+        None => return,
+    };
+    let mut reader: RhinoReader<_> = ReaderBuilder::new()
+        .version(version)
+        .build(Cursor::new(data));
+    for entry in Chunks::new(&mut reader).nested(true) {
+        if entry.is_err() {
+            break;
+        }
+    }
+    if reader.seek(SeekFrom::Start(0)).is_ok() {
+        if let Ok(mut chunk) = Chunk::deserialize(&mut reader) {
+            let _ = chunk.verify_crc();
+        }
+    }
+}
+
+pub fn jt_archive(data: &[u8]) {
+    let _ = jt::archive::Archive::from_bytes(data);
+}
+
+pub fn jt_segment(data: &[u8]) {
+    let _ = LsgSegment::deserialize(&mut JtReader::new(Cursor::new(data)));
+    let _ = ShapeLodSegment::deserialize(&mut JtReader::new(Cursor::new(data)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_inputs() {
+        let mut data: Vec<u8> = vec![];
+        data.extend("3D Geometry File Format        2".as_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        data.extend("abc".as_bytes());
+        data.extend(0x10000014u32.to_le_bytes());
+        data.extend(0xFFFFFFF0u32.to_le_bytes());
+        for end in 0..data.len() {
+            rhino_archive(&data[..end]);
+            rhino_chunks(&data[..end]);
+            jt_archive(&data[..end]);
+            jt_segment(&data[..end]);
+        }
+    }
+}
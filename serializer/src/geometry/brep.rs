@@ -0,0 +1,187 @@
+use crate::{
+    math::{Transformable, Xform},
+    model::Mesh,
+};
+
+use super::{
+    nurbs_curve::NurbsCurve,
+    nurbs_surface::NurbsSurface,
+    tessellation::{tessellate_face, TessellationOptions},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum LoopKind {
+    Outer,
+    Inner,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BrepLoop {
+    pub kind: LoopKind,
+    pub trims: Vec<NurbsCurve>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BrepFace {
+    pub surface: NurbsSurface,
+    pub loops: Vec<BrepLoop>,
+    pub reversed: bool,
+    pub render_mesh: Option<Mesh>,
+}
+
+impl BrepFace {
+    pub fn from_surface(surface: NurbsSurface) -> Self {
+        Self {
+            surface,
+            loops: vec![],
+            reversed: false,
+            render_mesh: None,
+        }
+    }
+
+    pub fn with_render_mesh(mut self, render_mesh: Mesh) -> Self {
+        self.render_mesh = Some(render_mesh);
+        self
+    }
+
+    pub fn is_trimmed(&self) -> bool {
+        !self.loops.is_empty()
+    }
+
+    pub fn tessellate(&self, options: &TessellationOptions) -> Mesh {
+        tessellate_face(self, options)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Brep {
+    pub faces: Vec<BrepFace>,
+}
+
+impl Brep {
+    pub fn tessellate(&self, options: &TessellationOptions) -> Vec<Mesh> {
+        self.faces
+            .iter()
+            .map(|face| face.tessellate(options))
+            .collect()
+    }
+
+    pub fn to_mesh(&self, options: &TessellationOptions) -> Mesh {
+        let mut mesh = Mesh::default();
+        for face in self.faces.iter() {
+            let tessellated = face.tessellate(options);
+            match &face.render_mesh {
+                Some(render_mesh) if tessellated.triangles.is_empty() => mesh.append(render_mesh),
+                _ => mesh.append(&tessellated),
+            }
+        }
+        mesh.weld(options.weld_tolerance);
+        if !mesh.has_normals() {
+            mesh.compute_vertex_normals();
+        }
+        mesh
+    }
+}
+
+impl Transformable for Brep {
+    fn transform(&mut self, xform: &Xform) {
+        let mirrored = 0. > xform.determinant();
+        for face in self.faces.iter_mut() {
+            face.surface.transform(xform);
+            if let Some(render_mesh) = face.render_mesh.as_mut() {
+                render_mesh.transform(xform);
+            }
+            face.reversed ^= mirrored;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{Point3d, Vector3d};
+
+    use super::*;
+
+    fn square(origin: Point3d) -> NurbsSurface {
+        NurbsSurface::bilinear([
+            origin,
+            origin + Vector3d::X,
+            origin + Vector3d::X + Vector3d::Y,
+            origin + Vector3d::Y,
+        ])
+    }
+
+    #[test]
+    fn to_mesh() {
+        let options = TessellationOptions::default().with_max_grid_size(2);
+        let brep = Brep {
+            faces: vec![
+                BrepFace::from_surface(square(Point3d::ORIGIN)),
+                BrepFace::from_surface(square(Point3d::new(1., 0., 0.))),
+            ],
+        };
+        let separate: usize = brep
+            .tessellate(&options)
+            .iter()
+            .map(|mesh| mesh.positions.len())
+            .sum();
+        let mesh = brep.to_mesh(&options);
+        assert!(mesh.positions.len() < separate);
+        assert!(mesh.has_normals());
+        assert!(mesh.is_valid());
+        assert!((2. - mesh.area()).abs() < 1e-9);
+        assert_eq!(Point3d::new(2., 1., 0.), mesh.bounding_box().max);
+    }
+
+    #[test]
+    fn to_mesh_falls_back_to_render_mesh() {
+        let mut surface = square(Point3d::ORIGIN);
+        surface.knots[0].pop();
+        let render_mesh = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2]],
+        };
+        let brep = Brep {
+            faces: vec![
+                BrepFace::from_surface(surface.clone()).with_render_mesh(render_mesh.clone()),
+                BrepFace::from_surface(surface),
+            ],
+        };
+        let mesh = brep.to_mesh(&TessellationOptions::default());
+        assert_eq!(render_mesh.positions, mesh.positions);
+        assert_eq!(render_mesh.triangles, mesh.triangles);
+        assert!(mesh.normals.iter().all(|n| [0., 0., 1.] == *n));
+        assert_eq!(
+            Mesh::default(),
+            Brep::default().to_mesh(&TessellationOptions::default())
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let options = TessellationOptions::default().with_max_grid_size(2);
+        let face = BrepFace::from_surface(square(Point3d::ORIGIN));
+        let brep = Brep {
+            faces: vec![face.clone().with_render_mesh(face.tessellate(&options))],
+        };
+        let normal = brep.to_mesh(&options).normals[0];
+        let flipped = normal.map(|v| -v);
+
+        let mirror = Xform::scale(&Vector3d::new(1., 1., -1.)) * Xform::translation(&Vector3d::Z);
+        let mirrored = brep.transformed(&mirror);
+        assert!(mirrored.faces[0].reversed);
+        let mesh = mirrored.to_mesh(&options);
+        assert!(mesh.positions.iter().all(|p| -1. == p[2]));
+        assert!(mesh.normals.iter().all(|n| flipped == *n));
+        assert!(mesh.flipped_triangles().is_empty());
+        let render_mesh = mirrored.faces[0].render_mesh.as_ref().unwrap();
+        assert!(render_mesh.normals.iter().all(|n| flipped == *n));
+        assert!(render_mesh.flipped_triangles().is_empty());
+    }
+}
@@ -0,0 +1,283 @@
+use std::cmp::Ordering;
+
+use crate::{
+    math::{BoundingBox, Point3d, Ray},
+    model::Mesh,
+};
+
+const LEAF_SIZE: usize = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RayHit {
+    pub triangle: usize,
+    pub t: f64,
+    pub point: Point3d,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BvhNode {
+    bounding_box: BoundingBox,
+    start: usize,
+    count: usize,
+    children: Option<[usize; 2]>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Bvh {
+    triangles: Vec<[Point3d; 3]>,
+    indices: Vec<usize>,
+    nodes: Vec<BvhNode>,
+}
+
+impl Bvh {
+    pub fn new(mesh: &Mesh) -> Self {
+        let (indices, triangles): (Vec<usize>, Vec<[Point3d; 3]>) = (0..mesh.triangles.len())
+            .filter_map(|t| Some((t, mesh.triangle_points(t)?)))
+            .unzip();
+        let mut order: Vec<usize> = (0..triangles.len()).collect();
+        let mut bvh = Self::default();
+        if !order.is_empty() {
+            bvh.build(&triangles, &mut order, 0);
+        }
+        bvh.triangles = order.iter().map(|i| triangles[*i]).collect();
+        bvh.indices = order.iter().map(|i| indices[*i]).collect();
+        bvh
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.nodes
+            .first()
+            .map_or(BoundingBox::EMPTY, |node| node.bounding_box)
+    }
+
+    pub fn raycast(&self, ray: &Ray) -> Option<RayHit> {
+        let mut best: Option<(usize, f64)> = None;
+        let mut stack: Vec<usize> = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            match ray.intersect_box(&node.bounding_box) {
+                Some([near, _]) if best.is_none_or(|(_, t)| near <= t) => {}
+                _ => continue,
+            }
+            match node.children {
+                Some(children) => stack.extend(children),
+                None => {
+                    for i in node.start..node.start + node.count {
+                        if let Some(t) = ray.intersect_triangle(&self.triangles[i]) {
+                            if best.is_none_or(|(_, best)| t < best) {
+                                best = Some((i, t));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(i, t)| RayHit {
+            triangle: self.indices[i],
+            t,
+            point: ray.point_at(t),
+        })
+    }
+
+    pub fn closest_point(&self, point: &Point3d) -> Option<(usize, Point3d)> {
+        let mut best: Option<(usize, Point3d, f64)> = None;
+        let mut stack: Vec<usize> = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if best.is_some_and(|(_, _, d)| node.bounding_box.distance_to(point) > d) {
+                continue;
+            }
+            match node.children {
+                Some(children) => stack.extend(children),
+                None => {
+                    for i in node.start..node.start + node.count {
+                        let closest = closest_point_on_triangle(point, &self.triangles[i]);
+                        let distance = point.distance_to(&closest);
+                        if best.is_none_or(|(_, _, d)| distance < d) {
+                            best = Some((i, closest, distance));
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(i, closest, _)| (self.indices[i], closest))
+    }
+
+    fn build(&mut self, triangles: &[[Point3d; 3]], order: &mut [usize], start: usize) -> usize {
+        let bounding_box: BoundingBox = order.iter().flat_map(|i| triangles[*i]).collect();
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounding_box,
+            start,
+            count: order.len(),
+            children: None,
+        });
+        if order.len() <= LEAF_SIZE {
+            return index;
+        }
+        let centroid = |i: usize| {
+            let [a, b, c] = triangles[i].map(|p| p.to_array());
+            [0, 1, 2].map(|k| (a[k] + b[k] + c[k]) / 3.)
+        };
+        let extent: BoundingBox = order.iter().map(|i| Point3d::from(centroid(*i))).collect();
+        let diagonal = extent.diagonal().to_array();
+        let axis = (0..3)
+            .max_by(|a, b| diagonal[*a].total_cmp(&diagonal[*b]))
+            .unwrap_or(0);
+        let middle = order.len() / 2;
+        order.select_nth_unstable_by(middle, |a, b| {
+            centroid(*a)[axis]
+                .partial_cmp(&centroid(*b)[axis])
+                .unwrap_or(Ordering::Equal)
+        });
+        let (lower, upper) = order.split_at_mut(middle);
+        let left = self.build(triangles, lower, start);
+        let right = self.build(triangles, upper, start + middle);
+        self.nodes[index].children = Some([left, right]);
+        index
+    }
+}
+
+impl From<&Mesh> for Bvh {
+    fn from(mesh: &Mesh) -> Self {
+        Self::new(mesh)
+    }
+}
+
+pub fn closest_point_on_triangle(point: &Point3d, [a, b, c]: &[Point3d; 3]) -> Point3d {
+    let ab = *b - *a;
+    let ac = *c - *a;
+    let ap = *point - *a;
+    let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+    if 0. >= d1 && 0. >= d2 {
+        return *a;
+    }
+    let bp = *point - *b;
+    let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+    if 0. <= d3 && d4 <= d3 {
+        return *b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if 0. >= vc && 0. <= d1 && 0. >= d3 {
+        return *a + ab * (d1 / (d1 - d3));
+    }
+    let cp = *point - *c;
+    let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+    if 0. <= d6 && d5 <= d6 {
+        return *c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if 0. >= vb && 0. <= d2 && 0. >= d6 {
+        return *a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if 0. >= va && 0. <= d4 - d3 && 0. <= d5 - d6 {
+        return *b + (*c - *b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denominator = va + vb + vc;
+    if 0. == denominator {
+        return *a;
+    }
+    *a + ab * (vb / denominator) + ac * (vc / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::Vector3d;
+
+    use super::*;
+
+    fn grid(size: usize) -> Mesh {
+        let mut mesh = Mesh::default();
+        for i in 0..=size {
+            for j in 0..=size {
+                let (x, y) = (i as f32, j as f32);
+                mesh.positions
+                    .push([x, y, (0.3 * x).sin() * (0.2 * y).cos()]);
+            }
+        }
+        let index = |i: usize, j: usize| (i * (size + 1) + j) as u32;
+        for i in 0..size {
+            for j in 0..size {
+                mesh.triangles
+                    .push([index(i, j), index(i + 1, j), index(i + 1, j + 1)]);
+                mesh.triangles
+                    .push([index(i, j), index(i + 1, j + 1), index(i, j + 1)]);
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn raycast() {
+        let mesh = grid(16);
+        let bvh = Bvh::new(&mesh);
+        assert_eq!(mesh.bounding_box(), bvh.bounding_box());
+        for (x, y) in [(0.3, 0.7), (5.5, 12.25), (15.9, 0.1)] {
+            let ray = Ray::new(Point3d::new(x, y, 10.), -Vector3d::Z);
+            let expected = (0..mesh.triangles.len())
+                .filter_map(|t| Some((t, ray.intersect_triangle(&mesh.triangle_points(t)?)?)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            let hit = bvh.raycast(&ray).unwrap();
+            assert_eq!(expected, (hit.triangle, hit.t));
+            assert_eq!(ray.point_at(hit.t), hit.point);
+        }
+        let miss = Ray::new(Point3d::new(20., 0., 10.), -Vector3d::Z);
+        assert_eq!(None, bvh.raycast(&miss));
+        assert_eq!(None, Bvh::default().raycast(&miss));
+    }
+
+    #[test]
+    fn closest_point() {
+        let mesh = grid(16);
+        let bvh = Bvh::from(&mesh);
+        for query in [
+            Point3d::new(3.3, 7.2, 5.),
+            Point3d::new(-4., 20., -1.),
+            Point3d::new(8., 8., 0.),
+        ] {
+            let expected = (0..mesh.triangles.len())
+                .map(|t| {
+                    let closest =
+                        closest_point_on_triangle(&query, &mesh.triangle_points(t).unwrap());
+                    query.distance_to(&closest)
+                })
+                .min_by(|a, b| a.total_cmp(b))
+                .unwrap();
+            let (triangle, closest) = bvh.closest_point(&query).unwrap();
+            assert!((expected - query.distance_to(&closest)).abs() < 1e-12);
+            let on_triangle =
+                closest_point_on_triangle(&query, &mesh.triangle_points(triangle).unwrap());
+            assert_eq!(on_triangle, closest);
+        }
+        assert_eq!(None, Bvh::default().closest_point(&Point3d::ORIGIN));
+    }
+
+    #[test]
+    fn triangle_regions() {
+        let triangle = [
+            Point3d::ORIGIN,
+            Point3d::new(2., 0., 0.),
+            Point3d::new(0., 2., 0.),
+        ];
+        let closest =
+            |x: f64, y: f64| closest_point_on_triangle(&Point3d::new(x, y, 1.), &triangle);
+        assert_eq!(Point3d::new(0.5, 0.5, 0.), closest(0.5, 0.5));
+        assert_eq!(Point3d::ORIGIN, closest(-1., -1.));
+        assert_eq!(Point3d::new(2., 0., 0.), closest(3., -1.));
+        assert_eq!(Point3d::new(0., 2., 0.), closest(-1., 3.));
+        assert_eq!(Point3d::new(1., 0., 0.), closest(1., -1.));
+        assert_eq!(Point3d::new(0., 1., 0.), closest(-1., 1.));
+        assert_eq!(Point3d::new(1., 1., 0.), closest(2., 2.));
+    }
+}
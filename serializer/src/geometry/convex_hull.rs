@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use crate::{
+    math::{BoundingBox, Point3d, Vector3d},
+    model::Mesh,
+};
+
+struct Face {
+    vertices: [usize; 3],
+    normal: Vector3d,
+    offset: f64,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl Face {
+    fn new(points: &[Point3d], vertices: [usize; 3]) -> Option<Self> {
+        let [a, b, c] = vertices.map(|i| points[i]);
+        let normal = (b - a).cross(&(c - a)).normalized()?;
+        Some(Self {
+            vertices,
+            normal,
+            offset: normal.dot(&a.to_vector()),
+            outside: vec![],
+            alive: true,
+        })
+    }
+
+    fn distance(&self, point: &Point3d) -> f64 {
+        self.normal.dot(&point.to_vector()) - self.offset
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+fn farthest<F>(candidates: impl Iterator<Item = usize>, distance: F) -> Option<(usize, f64)>
+where
+    F: Fn(usize) -> f64,
+{
+    candidates
+        .map(|i| (i, distance(i)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+fn initial_simplex(points: &[Point3d], tolerance: f64) -> Option<[usize; 4]> {
+    let extremes: Vec<usize> = (0..3)
+        .flat_map(|axis| {
+            let coordinate = move |i: usize| points[i].to_array()[axis];
+            [
+                farthest(0..points.len(), |i| -coordinate(i)),
+                farthest(0..points.len(), coordinate),
+            ]
+        })
+        .map(|extreme| extreme.map(|(i, _)| i))
+        .collect::<Option<Vec<usize>>>()?;
+    let (a, b) = extremes
+        .iter()
+        .flat_map(|a| extremes.iter().map(move |b| (*a, *b)))
+        .max_by(|x, y| {
+            let dx = points[x.0].distance_to(&points[x.1]);
+            let dy = points[y.0].distance_to(&points[y.1]);
+            dx.total_cmp(&dy)
+        })?;
+    let direction = (points[b] - points[a]).normalized()?;
+    let (c, distance) = farthest(0..points.len(), |i| {
+        let offset = points[i] - points[a];
+        (offset - direction * offset.dot(&direction)).length()
+    })?;
+    if distance <= tolerance {
+        return None;
+    }
+    let normal = (points[b] - points[a])
+        .cross(&(points[c] - points[a]))
+        .normalized()?;
+    let (d, distance) = farthest(0..points.len(), |i| {
+        normal.dot(&(points[i] - points[a])).abs()
+    })?;
+    (distance > tolerance).then_some([a, b, c, d])
+}
+
+pub fn convex_hull(points: &[Point3d]) -> Option<Mesh> {
+    let points: Vec<Point3d> = points
+        .iter()
+        .filter(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite())
+        .copied()
+        .collect();
+    let extent = points.iter().copied().collect::<BoundingBox>().diagonal();
+    let tolerance = 1e-10 * extent.length().max(1.);
+    let [a, b, c, d] = initial_simplex(&points, tolerance)?;
+
+    let simplex = [a, b, c, d];
+    let mut faces: Vec<Face> = vec![];
+    for (vertices, opposite) in [
+        ([a, b, c], d),
+        ([a, d, b], c),
+        ([b, d, c], a),
+        ([c, d, a], b),
+    ] {
+        let face = Face::new(&points, vertices)?;
+        faces.push(if 0. < face.distance(&points[opposite]) {
+            Face::new(&points, [vertices[0], vertices[2], vertices[1]])?
+        } else {
+            face
+        });
+    }
+    assign(
+        &mut faces,
+        &points,
+        (0..points.len()).filter(|i| !simplex.contains(i)),
+        tolerance,
+    );
+
+    while let Some(index) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+        let face = &faces[index];
+        let (eye, _) = farthest(face.outside.iter().copied(), |i| face.distance(&points[i]))?;
+        let visible: Vec<usize> = (0..faces.len())
+            .filter(|f| faces[*f].alive && faces[*f].distance(&points[eye]) > tolerance)
+            .collect();
+        let edges: HashSet<(usize, usize)> =
+            visible.iter().flat_map(|f| faces[*f].edges()).collect();
+        let horizon: Vec<(usize, usize)> = edges
+            .iter()
+            .filter(|(a, b)| !edges.contains(&(*b, *a)))
+            .copied()
+            .collect();
+        let mut orphans: Vec<usize> = vec![];
+        for f in visible.iter() {
+            faces[*f].alive = false;
+            orphans.extend(std::mem::take(&mut faces[*f].outside));
+        }
+        let first = faces.len();
+        for (a, b) in horizon {
+            if let Some(face) = Face::new(&points, [a, b, eye]) {
+                faces.push(face);
+            }
+        }
+        assign(
+            &mut faces[first..],
+            &points,
+            orphans.into_iter().filter(|i| *i != eye),
+            tolerance,
+        );
+    }
+
+    let mut mesh = Mesh::default();
+    let mut remap: Vec<Option<u32>> = vec![None; points.len()];
+    for face in faces.iter().filter(|f| f.alive) {
+        let triangle = face.vertices.map(|i| {
+            *remap[i].get_or_insert_with(|| {
+                mesh.positions.push(points[i].into());
+                mesh.positions.len() as u32 - 1
+            })
+        });
+        mesh.triangles.push(triangle);
+    }
+    Some(mesh)
+}
+
+fn assign(
+    faces: &mut [Face],
+    points: &[Point3d],
+    candidates: impl Iterator<Item = usize>,
+    tolerance: f64,
+) {
+    for i in candidates {
+        if let Some(face) = faces
+            .iter_mut()
+            .find(|f| f.alive && f.distance(&points[i]) > tolerance)
+        {
+            face.outside.push(i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube() -> Vec<Point3d> {
+        (0..8)
+            .map(|i| Point3d::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64))
+            .collect()
+    }
+
+    fn random_points(count: usize) -> Vec<Point3d> {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2. - 1.
+        };
+        (0..count)
+            .map(|_| Point3d::new(next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn cube_with_interior_points() {
+        let mut points = cube();
+        points.extend([
+            Point3d::new(0.5, 0.5, 0.5),
+            Point3d::new(0.25, 0.75, 0.5),
+            Point3d::new(0.5, 0.5, 1.),
+            Point3d::new(1., 1., 1.),
+        ]);
+        let hull = convex_hull(&points).unwrap();
+        assert_eq!(8, hull.positions.len());
+        assert!(hull.is_closed());
+        assert!(hull.is_valid());
+        assert!((1. - hull.volume().unwrap()).abs() < 1e-12);
+        assert!((6. - hull.area()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn random_cloud() {
+        let points = random_points(500);
+        let hull = convex_hull(&points).unwrap();
+        assert!(hull.is_closed());
+        assert!(0. < hull.volume().unwrap());
+        assert!(hull.positions.len() < points.len());
+        for t in 0..hull.triangles.len() {
+            let [a, ..] = hull.triangle_points(t).unwrap();
+            let normal = hull.triangle_normal(t).unwrap();
+            assert!(points.iter().all(|p| normal.dot(&(*p - a)) < 1e-6));
+        }
+    }
+
+    #[test]
+    fn degenerate() {
+        assert!(convex_hull(&[]).is_none());
+        assert!(convex_hull(&[Point3d::ORIGIN; 5]).is_none());
+        let planar: Vec<Point3d> = cube().into_iter().filter(|p| 0. == p.z).collect();
+        assert!(convex_hull(&planar).is_none());
+        let mut points = cube();
+        points.push(Point3d::new(f64::NAN, 0., 0.));
+        assert_eq!(8, convex_hull(&points).unwrap().positions.len());
+    }
+}
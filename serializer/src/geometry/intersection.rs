@@ -0,0 +1,243 @@
+use std::f64::consts::TAU;
+
+use crate::{
+    math::{Arc, Circle, Line, Plane, Point3d, Ray},
+    model::Mesh,
+};
+
+use super::{bvh::RayHit, nurbs_curve::NurbsCurve};
+
+const CURVE_SAMPLES: usize = 16;
+
+pub fn line_plane(line: &Line, plane: &Plane) -> Option<f64> {
+    let normal = plane.normal();
+    let denominator = normal.dot(&line.direction());
+    if 0. == denominator {
+        None
+    } else {
+        Some(-plane.signed_distance_to(&line.from) / denominator)
+    }
+}
+
+pub fn circle_plane(circle: &Circle, plane: &Plane) -> Vec<f64> {
+    let normal = plane.normal();
+    let offset = plane.signed_distance_to(&circle.center());
+    let a = circle.radius * circle.plane.x_axis.dot(&normal);
+    let b = circle.radius * circle.plane.y_axis.dot(&normal);
+    let amplitude = a.hypot(b);
+    if 0. == amplitude || offset.abs() > amplitude {
+        return vec![];
+    }
+    let phase = b.atan2(a);
+    let spread = (-offset / amplitude).clamp(-1., 1.).acos();
+    let mut angles: Vec<f64> = [phase - spread, phase + spread]
+        .map(|angle| angle.rem_euclid(TAU))
+        .to_vec();
+    angles.sort_by(f64::total_cmp);
+    angles.dedup();
+    angles
+}
+
+pub fn arc_plane(arc: &Arc, plane: &Plane) -> Vec<f64> {
+    let mut angles: Vec<f64> = circle_plane(&arc.circle, plane)
+        .into_iter()
+        .map(|angle| arc.start_angle + (angle - arc.start_angle).rem_euclid(TAU))
+        .filter(|angle| *angle <= arc.end_angle)
+        .collect();
+    angles.sort_by(f64::total_cmp);
+    angles
+}
+
+pub fn curve_plane(curve: &NurbsCurve, plane: &Plane) -> Vec<f64> {
+    let distance = |t: f64| curve.point_at(t).map(|p| plane.signed_distance_to(&p));
+    let mut samples: Vec<f64> = vec![];
+    for [a, b] in curve.spans().unwrap_or_default() {
+        samples.extend((0..CURVE_SAMPLES).map(|i| a + (b - a) * i as f64 / CURVE_SAMPLES as f64));
+    }
+    if let Some([_, end]) = curve.domain() {
+        samples.push(end);
+    }
+    let values: Vec<Option<f64>> = samples.iter().map(|t| distance(*t)).collect();
+    let mut parameters: Vec<f64> = vec![];
+    for i in 0..samples.len() {
+        if Some(0.) == values[i] {
+            parameters.push(samples[i]);
+            continue;
+        }
+        let (mut low, mut high) = match (values[i], values.get(i + 1).copied().flatten()) {
+            (Some(a), Some(b)) if 0. > a * b => (samples[i], samples[i + 1]),
+            _ => continue,
+        };
+        let rising = 0. > values[i].unwrap_or_default();
+        while high - low > f64::EPSILON * (1. + low.abs().max(high.abs())) {
+            let middle = 0.5 * (low + high);
+            match distance(middle) {
+                Some(0.) => (low, high) = (middle, middle),
+                Some(value) if (0. > value) == rising => low = middle,
+                Some(_) => high = middle,
+                None => break,
+            }
+        }
+        parameters.push(0.5 * (low + high));
+    }
+    parameters.dedup();
+    parameters
+}
+
+pub fn segment_triangle(segment: &Line, triangle: &[Point3d; 3]) -> Option<Point3d> {
+    let ray = Ray::new(segment.from, segment.direction());
+    ray.intersect_triangle(triangle)
+        .filter(|t| *t <= 1.)
+        .map(|t| ray.point_at(t))
+}
+
+pub fn ray_mesh(ray: &Ray, mesh: &Mesh) -> Option<RayHit> {
+    (0..mesh.triangles.len())
+        .filter_map(|triangle| {
+            let t = ray.intersect_triangle(&mesh.triangle_points(triangle)?)?;
+            Some(RayHit {
+                triangle,
+                t,
+                point: ray.point_at(t),
+            })
+        })
+        .min_by(|a, b| a.t.total_cmp(&b.t))
+}
+
+pub fn mesh_plane(mesh: &Mesh, plane: &Plane) -> Vec<Line> {
+    (0..mesh.triangles.len())
+        .filter_map(|triangle| {
+            let points = mesh.triangle_points(triangle)?;
+            let distances = points.map(|p| plane.signed_distance_to(&p));
+            let mut crossings: Vec<Point3d> = vec![];
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+                if 0. == distances[i] {
+                    crossings.push(points[i]);
+                } else if 0. > distances[i] * distances[j] {
+                    let t = distances[i] / (distances[i] - distances[j]);
+                    crossings.push(points[i].lerp(&points[j], t));
+                }
+            }
+            match crossings[..] {
+                [from, to] if from != to => Some(Line::new(from, to)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, PI};
+
+    use crate::math::Vector3d;
+
+    use super::*;
+
+    fn vertical_plane(x: f64) -> Plane {
+        Plane::from_normal(Point3d::new(x, 0., 0.), &Vector3d::X).unwrap()
+    }
+
+    #[test]
+    fn line_and_circle() {
+        let line = Line::new(Point3d::new(-1., 1., 0.), Point3d::new(3., 1., 0.));
+        assert_eq!(Some(0.5), line_plane(&line, &vertical_plane(1.)));
+        assert_eq!(None, line_plane(&line, &Plane::WORLD_XY));
+
+        let circle = Circle::new(Plane::WORLD_XY, 2.);
+        let angles = circle_plane(&circle, &vertical_plane(1.));
+        assert_eq!(2, angles.len());
+        assert!((PI / 3. - angles[0]).abs() < 1e-12);
+        assert!((5. * PI / 3. - angles[1]).abs() < 1e-12);
+        assert!(circle_plane(&circle, &vertical_plane(3.)).is_empty());
+        assert!(circle_plane(&circle, &Plane::WORLD_XY).is_empty());
+
+        let arc = Arc::new(circle, 0., FRAC_PI_2).unwrap();
+        assert_eq!(1, arc_plane(&arc, &vertical_plane(1.)).len());
+        let wrapped = Arc::new(circle, -FRAC_PI_2, FRAC_PI_2).unwrap();
+        let angles = arc_plane(&wrapped, &vertical_plane(1.));
+        assert!((-PI / 3. - angles[0]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn nurbs_curve() {
+        let w = FRAC_1_SQRT_2;
+        let curve = NurbsCurve::new(
+            3,
+            vec![
+                Point3d::new(1., 0., 0.),
+                Point3d::new(1., 1., 0.),
+                Point3d::new(0., 1., 0.),
+            ],
+            vec![1., w, 1.],
+            vec![0., 0., 0., 1., 1., 1.],
+        )
+        .unwrap();
+        let plane = Plane::from_normal(Point3d::ORIGIN, &Vector3d::new(1., -1., 0.)).unwrap();
+        let parameters = curve_plane(&curve, &plane);
+        assert_eq!(1, parameters.len());
+        let point = curve.point_at(parameters[0]).unwrap();
+        assert!(point.distance_to(&Point3d::new(w, w, 0.)) < 1e-12);
+        assert_eq!(vec![0.], curve_plane(&curve, &vertical_plane(1.)));
+        assert!(curve_plane(&curve, &vertical_plane(2.)).is_empty());
+        let line = NurbsCurve::from_points(&[Point3d::ORIGIN, Point3d::new(4., 0., 0.)]).unwrap();
+        assert_eq!(vec![0.25], curve_plane(&line, &vertical_plane(1.)));
+    }
+
+    #[test]
+    fn segment_and_mesh() {
+        let triangle = [
+            Point3d::ORIGIN,
+            Point3d::new(1., 0., 0.),
+            Point3d::new(0., 1., 0.),
+        ];
+        let segment = Line::new(Point3d::new(0.25, 0.25, 1.), Point3d::new(0.25, 0.25, -1.));
+        assert_eq!(
+            Some(Point3d::new(0.25, 0.25, 0.)),
+            segment_triangle(&segment, &triangle)
+        );
+        let short = Line::new(Point3d::new(0.25, 0.25, 1.), Point3d::new(0.25, 0.25, 0.5));
+        assert_eq!(None, segment_triangle(&short, &triangle));
+
+        let mesh = Mesh {
+            positions: vec![
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 0., 1.],
+                [1., 0., 1.],
+                [1., 1., 1.],
+            ],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2], [3, 4, 5]],
+        };
+        let ray = Ray::new(Point3d::new(0.75, 0.25, 2.), -Vector3d::Z);
+        let hit = ray_mesh(&ray, &mesh).unwrap();
+        assert_eq!(1, hit.triangle);
+        assert_eq!(Point3d::new(0.75, 0.25, 1.), hit.point);
+        let up = Ray::new(Point3d::new(0.75, 0.25, 0.5), Vector3d::Z);
+        assert_eq!(Some(0.5), ray_mesh(&up, &mesh).map(|hit| hit.t));
+        assert_eq!(None, ray_mesh(&ray, &Mesh::default()));
+    }
+
+    #[test]
+    fn section() {
+        let mesh = Mesh {
+            positions: vec![[0., 0., 0.], [2., 0., 0.], [2., 2., 0.], [0., 2., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2], [0, 2, 3]],
+        };
+        let segments = mesh_plane(&mesh, &vertical_plane(1.));
+        assert_eq!(2, segments.len());
+        let length: f64 = segments.iter().map(|s| s.length()).sum();
+        assert!((2. - length).abs() < 1e-12);
+        assert!(segments.iter().all(|s| 1. == s.from.x && 1. == s.to.x));
+        let diagonal = Plane::from_normal(Point3d::ORIGIN, &Vector3d::new(1., -1., 0.)).unwrap();
+        let segments = mesh_plane(&mesh, &diagonal);
+        assert!(segments.iter().all(|s| s.from.distance_to(&s.to) > 0.));
+        assert!(mesh_plane(&mesh, &vertical_plane(5.)).is_empty());
+    }
+}
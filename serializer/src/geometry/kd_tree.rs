@@ -0,0 +1,172 @@
+use crate::{math::Point3d, model::Mesh};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KdTree {
+    points: Vec<Point3d>,
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: &[Point3d]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        Self {
+            points: points.to_vec(),
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn point(&self, index: usize) -> Option<&Point3d> {
+        self.points.get(index)
+    }
+
+    pub fn nearest(&self, query: &Point3d) -> Option<(usize, f64)> {
+        self.k_nearest(query, 1).pop()
+    }
+
+    pub fn k_nearest(&self, query: &Point3d, k: usize) -> Vec<(usize, f64)> {
+        let mut found: Vec<(usize, f64)> = vec![];
+        if 0 < k {
+            self.search(query, &self.order, 0, &mut |index, distance| {
+                let position = found.partition_point(|(_, d)| *d <= distance);
+                if position < k {
+                    found.insert(position, (index, distance));
+                    found.truncate(k);
+                }
+                if found.len() < k {
+                    f64::INFINITY
+                } else {
+                    found[k - 1].1
+                }
+            });
+        }
+        found
+    }
+
+    pub fn within_radius(&self, query: &Point3d, radius: f64) -> Vec<usize> {
+        let mut found: Vec<usize> = vec![];
+        self.search(query, &self.order, 0, &mut |index, distance| {
+            if distance <= radius {
+                found.push(index);
+            }
+            radius
+        });
+        found.sort_unstable();
+        found
+    }
+
+    fn search<F>(&self, query: &Point3d, order: &[usize], depth: usize, visit: &mut F) -> f64
+    where
+        F: FnMut(usize, f64) -> f64,
+    {
+        if order.is_empty() {
+            return f64::INFINITY;
+        }
+        let axis = depth % 3;
+        let middle = order.len() / 2;
+        let index = order[middle];
+        let mut bound = visit(index, query.distance_to(&self.points[index]));
+        let offset = query.to_array()[axis] - self.points[index].to_array()[axis];
+        let (near, far) = if 0. > offset {
+            (&order[..middle], &order[middle + 1..])
+        } else {
+            (&order[middle + 1..], &order[..middle])
+        };
+        if !near.is_empty() {
+            bound = self.search(query, near, depth + 1, visit);
+        }
+        if !far.is_empty() && offset.abs() <= bound {
+            bound = self.search(query, far, depth + 1, visit);
+        }
+        bound
+    }
+}
+
+impl From<&Mesh> for KdTree {
+    fn from(mesh: &Mesh) -> Self {
+        Self::new(&mesh.points().collect::<Vec<Point3d>>())
+    }
+}
+
+fn build(points: &[Point3d], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let middle = order.len() / 2;
+    order.select_nth_unstable_by(middle, |a, b| {
+        let (a, b) = (points[*a].to_array()[axis], points[*b].to_array()[axis]);
+        a.total_cmp(&b)
+    });
+    let (lower, upper) = order.split_at_mut(middle);
+    build(points, lower, depth + 1);
+    build(points, &mut upper[1..], depth + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<Point3d> {
+        (0..1000)
+            .map(|i| Point3d::new((i % 10) as f64, (i / 10 % 10) as f64, (i / 100) as f64))
+            .collect()
+    }
+
+    fn brute_force(points: &[Point3d], query: &Point3d) -> Vec<(usize, f64)> {
+        let mut distances: Vec<(usize, f64)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, query.distance_to(p)))
+            .collect();
+        distances.sort_by(|a, b| a.1.total_cmp(&b.1));
+        distances
+    }
+
+    #[test]
+    fn nearest() {
+        let points = grid();
+        let tree = KdTree::new(&points);
+        assert_eq!(1000, tree.len());
+        for query in [
+            Point3d::new(3.2, 4.9, 7.1),
+            Point3d::new(-5., 20., 3.),
+            Point3d::new(9.6, 0.4, 0.4),
+        ] {
+            let expected = brute_force(&points, &query);
+            assert_eq!(Some(expected[0]), tree.nearest(&query));
+            let distances: Vec<f64> = tree.k_nearest(&query, 7).iter().map(|n| n.1).collect();
+            let expected: Vec<f64> = expected[..7].iter().map(|n| n.1).collect();
+            assert_eq!(expected, distances);
+        }
+        assert_eq!(None, KdTree::default().nearest(&Point3d::ORIGIN));
+        assert!(tree.k_nearest(&Point3d::ORIGIN, 0).is_empty());
+        assert_eq!(1000, tree.k_nearest(&Point3d::ORIGIN, 2000).len());
+    }
+
+    #[test]
+    fn within_radius() {
+        let points = grid();
+        let tree = KdTree::new(&points);
+        let query = Point3d::new(5., 5., 5.);
+        let mut expected: Vec<usize> = brute_force(&points, &query)
+            .into_iter()
+            .filter(|(_, d)| *d <= 1.5)
+            .map(|(i, _)| i)
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(expected, tree.within_radius(&query, 1.5));
+        assert_eq!(19, expected.len());
+        assert!(tree
+            .within_radius(&Point3d::new(50., 0., 0.), 1.)
+            .is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+pub mod brep;
+pub mod bvh;
+pub mod convex_hull;
+pub mod intersection;
+pub mod kd_tree;
+pub mod nurbs_curve;
+pub mod nurbs_surface;
+pub mod tessellation;
+pub mod validation;
+
+pub use brep::{Brep, BrepFace, BrepLoop, LoopKind};
+pub use bvh::{Bvh, RayHit};
+pub use convex_hull::convex_hull;
+pub use kd_tree::KdTree;
+pub use nurbs_curve::NurbsCurve;
+pub use nurbs_surface::NurbsSurface;
+pub use tessellation::TessellationOptions;
+pub use validation::{GeometryIssue, GeometryIssueKind, Severity};
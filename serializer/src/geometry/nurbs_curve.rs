@@ -0,0 +1,371 @@
+use crate::math::{transformable::transform_homogeneous, Point3d, Transformable, Vector3d, Xform};
+
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0., 0.568_888_888_888_888_9),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_08),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_08),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NurbsCurve {
+    pub order: usize,
+    pub control_points: Vec<Point3d>,
+    pub weights: Vec<f64>,
+    pub knots: Vec<f64>,
+}
+
+impl NurbsCurve {
+    pub fn new(
+        order: usize,
+        control_points: Vec<Point3d>,
+        weights: Vec<f64>,
+        knots: Vec<f64>,
+    ) -> Option<Self> {
+        let curve = Self {
+            order,
+            control_points,
+            weights,
+            knots,
+        };
+        curve.is_consistent().then_some(curve)
+    }
+
+    pub fn from_points(points: &[Point3d]) -> Option<Self> {
+        let count = points.len();
+        let knots = (0..count + 2)
+            .map(|i| i.clamp(1, count) as f64 - 1.)
+            .collect();
+        Self::new(2, points.to_vec(), vec![], knots)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.order.saturating_sub(1)
+    }
+
+    pub fn is_rational(&self) -> bool {
+        !self.weights.is_empty()
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.has_consistent_counts() && is_knot_vector(&self.knots)
+    }
+
+    pub(crate) fn has_consistent_counts(&self) -> bool {
+        2 <= self.order
+            && self.order <= self.control_points.len()
+            && self.control_points.len() + self.order == self.knots.len()
+            && (self.weights.is_empty() || self.weights.len() == self.control_points.len())
+    }
+
+    pub fn domain(&self) -> Option<[f64; 2]> {
+        self.is_consistent().then(|| {
+            [
+                self.knots[self.degree()],
+                self.knots[self.control_points.len()],
+            ]
+        })
+    }
+
+    pub fn point_at(&self, t: f64) -> Option<Point3d> {
+        if !self.is_consistent() {
+            return None;
+        }
+        let points = homogeneous(&self.control_points, &self.weights);
+        dehomogenize(evaluate(self.order, &self.knots, &points, t))
+    }
+
+    pub fn derivative_at(&self, t: f64) -> Option<Vector3d> {
+        if !self.is_consistent() {
+            return None;
+        }
+        let degree = self.degree();
+        let points = homogeneous(&self.control_points, &self.weights);
+        let derivatives: Vec<[f64; 4]> = points
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let span = self.knots[i + degree + 1] - self.knots[i + 1];
+                let factor = if 0. == span { 0. } else { degree as f64 / span };
+                [0, 1, 2, 3].map(|k| factor * (pair[1][k] - pair[0][k]))
+            })
+            .collect();
+        let [x, y, z, w] = evaluate(self.order, &self.knots, &points, t);
+        if 0. == w {
+            return None;
+        }
+        let [dx, dy, dz, dw] = evaluate(
+            self.order - 1,
+            &self.knots[1..self.knots.len() - 1],
+            &derivatives,
+            t,
+        );
+        Some(Vector3d::new(dx * w - x * dw, dy * w - y * dw, dz * w - z * dw) / (w * w))
+    }
+
+    pub fn length(&self) -> Option<f64> {
+        const SUBDIVISIONS: usize = 8;
+        let mut length = 0.;
+        for [a, b] in self.spans()? {
+            let step = (b - a) / SUBDIVISIONS as f64;
+            for i in 0..SUBDIVISIONS {
+                let center = a + (i as f64 + 0.5) * step;
+                for (x, weight) in GAUSS_LEGENDRE {
+                    length +=
+                        0.5 * step * weight * self.derivative_at(center + 0.5 * step * x)?.length();
+                }
+            }
+        }
+        Some(length)
+    }
+
+    pub fn closest_parameter(&self, point: &Point3d) -> Option<f64> {
+        const SAMPLES: usize = 16;
+        let distance = |t: f64| self.point_at(t).map(|p| p.distance_to(point));
+        let mut samples: Vec<f64> = vec![];
+        for [a, b] in self.spans()? {
+            samples.extend((0..SAMPLES).map(|i| a + (b - a) * i as f64 / SAMPLES as f64));
+        }
+        samples.push(self.domain()?[1]);
+        let distances = samples
+            .iter()
+            .map(|t| distance(*t))
+            .collect::<Option<Vec<f64>>>()?;
+        let best = (0..samples.len()).fold(0, |best, i| {
+            if distances[i] < distances[best] {
+                i
+            } else {
+                best
+            }
+        });
+        let mut low = samples[best.saturating_sub(1)];
+        let mut high = samples[(best + 1).min(samples.len() - 1)];
+        let ratio = 0.5 * (5f64.sqrt() - 1.);
+        for _ in 0..100 {
+            if high - low <= f64::EPSILON * (1. + low.abs().max(high.abs())) {
+                break;
+            }
+            let left = high - ratio * (high - low);
+            let right = low + ratio * (high - low);
+            if distance(left)? <= distance(right)? {
+                high = right;
+            } else {
+                low = left;
+            }
+        }
+        let t = 0.5 * (low + high);
+        Some(if distances[best] < distance(t)? {
+            samples[best]
+        } else {
+            t
+        })
+    }
+
+    pub fn closest_point(&self, point: &Point3d) -> Option<Point3d> {
+        self.point_at(self.closest_parameter(point)?)
+    }
+
+    pub(crate) fn spans(&self) -> Option<Vec<[f64; 2]>> {
+        self.is_consistent().then(|| {
+            self.knots[self.degree()..=self.control_points.len()]
+                .windows(2)
+                .filter(|pair| pair[0] < pair[1])
+                .map(|pair| [pair[0], pair[1]])
+                .collect()
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        match self.domain() {
+            Some([start, end]) => match (self.point_at(start), self.point_at(end)) {
+                (Some(start), Some(end)) => start.distance_to(&end) <= f64::EPSILON,
+                _ => false,
+            },
+            None => false,
+        }
+    }
+}
+
+pub(crate) fn is_knot_vector(knots: &[f64]) -> bool {
+    knots.iter().all(|knot| knot.is_finite()) && knots.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+pub(crate) fn homogeneous(points: &[Point3d], weights: &[f64]) -> Vec<[f64; 4]> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let w = weights.get(i).copied().unwrap_or(1.);
+            [p.x * w, p.y * w, p.z * w, w]
+        })
+        .collect()
+}
+
+pub(crate) fn dehomogenize(point: [f64; 4]) -> Option<Point3d> {
+    let [x, y, z, w] = point;
+    (0. != w).then(|| Point3d::new(x / w, y / w, z / w))
+}
+
+pub(crate) fn evaluate(order: usize, knots: &[f64], points: &[[f64; 4]], t: f64) -> [f64; 4] {
+    let degree = order - 1;
+    let count = points.len();
+    let t = t.clamp(knots[degree], knots[count]);
+    let mut span = degree;
+    while span + 1 < count && knots[span + 1] <= t {
+        span += 1;
+    }
+    let mut d: Vec<[f64; 4]> = points[span - degree..=span].to_vec();
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let i = j + span - degree;
+            let denominator = knots[i + degree + 1 - r] - knots[i];
+            let alpha = if 0. == denominator {
+                0.
+            } else {
+                (t - knots[i]) / denominator
+            };
+            let previous = d[j - 1];
+            for (value, previous) in d[j].iter_mut().zip(previous) {
+                *value = (1. - alpha) * previous + alpha * *value;
+            }
+        }
+    }
+    d[degree]
+}
+
+impl Transformable for NurbsCurve {
+    fn transform(&mut self, xform: &Xform) {
+        transform_homogeneous(xform, &mut self.control_points, &mut self.weights);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_bezier() {
+        let curve = NurbsCurve::new(
+            3,
+            vec![
+                Point3d::new(0., 0., 0.),
+                Point3d::new(1., 2., 0.),
+                Point3d::new(2., 0., 0.),
+            ],
+            vec![],
+            vec![0., 0., 0., 1., 1., 1.],
+        )
+        .unwrap();
+        assert_eq!(2, curve.degree());
+        assert_eq!(Some([0., 1.]), curve.domain());
+        assert_eq!(Some(Point3d::new(0., 0., 0.)), curve.point_at(0.));
+        assert_eq!(Some(Point3d::new(1., 1., 0.)), curve.point_at(0.5));
+        assert_eq!(Some(Point3d::new(2., 0., 0.)), curve.point_at(1.));
+        assert_eq!(Some(Point3d::new(2., 0., 0.)), curve.point_at(3.));
+        assert!(!curve.is_closed());
+        assert_eq!(Some(Vector3d::new(2., 4., 0.)), curve.derivative_at(0.));
+        assert_eq!(Some(Vector3d::new(2., 0., 0.)), curve.derivative_at(0.5));
+    }
+
+    #[test]
+    fn evaluate_rational() {
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let curve = NurbsCurve::new(
+            3,
+            vec![
+                Point3d::new(1., 0., 0.),
+                Point3d::new(1., 1., 0.),
+                Point3d::new(0., 1., 0.),
+            ],
+            vec![1., w, 1.],
+            vec![0., 0., 0., 1., 1., 1.],
+        )
+        .unwrap();
+        assert!(curve.is_rational());
+        for i in 0..=10 {
+            let point = curve.point_at(i as f64 / 10.).unwrap();
+            assert!((1. - point.to_vector().length()).abs() < 1e-12);
+        }
+        assert!((std::f64::consts::FRAC_PI_2 - curve.length().unwrap()).abs() < 1e-9);
+        let tangent = curve.derivative_at(0.5).unwrap();
+        assert!(tangent.dot(&Vector3d::new(1., 1., 0.)).abs() < 1e-12);
+
+        let closest = curve.closest_point(&Point3d::new(2., 2., 1.)).unwrap();
+        assert!(closest.distance_to(&Point3d::new(w, w, 0.)) < 1e-7);
+        let closest = curve.closest_point(&Point3d::new(3., -1., 0.)).unwrap();
+        assert!(closest.distance_to(&Point3d::new(1., 0., 0.)) < 1e-12);
+    }
+
+    #[test]
+    fn polyline() {
+        let curve = NurbsCurve::from_points(&[
+            Point3d::new(0., 0., 0.),
+            Point3d::new(1., 0., 0.),
+            Point3d::new(1., 1., 0.),
+            Point3d::new(0., 0., 0.),
+        ])
+        .unwrap();
+        assert_eq!(vec![0., 0., 1., 2., 3., 3.], curve.knots);
+        assert_eq!(Some([0., 3.]), curve.domain());
+        assert_eq!(Some(Point3d::new(1., 0.5, 0.)), curve.point_at(1.5));
+        assert!(curve.is_closed());
+        assert!((2. + 2f64.sqrt() - curve.length().unwrap()).abs() < 1e-12);
+        assert_eq!(
+            Some(1.),
+            curve.closest_parameter(&Point3d::new(2., -1., 0.))
+        );
+        let closest = curve.closest_point(&Point3d::new(2., 0.25, 0.)).unwrap();
+        assert!(closest.distance_to(&Point3d::new(1., 0.25, 0.)) < 1e-7);
+        assert!(NurbsCurve::from_points(&[Point3d::ORIGIN]).is_none());
+    }
+
+    #[test]
+    fn inconsistent() {
+        assert!(NurbsCurve::new(2, vec![Point3d::ORIGIN; 2], vec![], vec![0.; 3]).is_none());
+        assert!(NurbsCurve::new(2, vec![Point3d::ORIGIN; 2], vec![1.], vec![0.; 4]).is_none());
+        let mut curve = NurbsCurve::from_points(&[Point3d::ORIGIN; 2]).unwrap();
+        curve.knots.pop();
+        assert_eq!(None, curve.point_at(0.));
+        assert_eq!(None, curve.domain());
+        assert_eq!(None, curve.length());
+        assert_eq!(None, curve.closest_point(&Point3d::ORIGIN));
+
+        let points = vec![Point3d::ORIGIN, Point3d::new(1., 0., 0.)];
+        assert!(NurbsCurve::new(2, points.clone(), vec![], vec![1., 1., 0., 0.]).is_none());
+        assert!(NurbsCurve::new(2, points.clone(), vec![], vec![0., 0., f64::NAN, 1.]).is_none());
+        let mut curve = NurbsCurve::new(2, points, vec![], vec![0., 0., 1., 1.]).unwrap();
+        curve.knots[3] = f64::INFINITY;
+        assert_eq!(None, curve.point_at(0.5));
+    }
+
+    #[test]
+    fn transform() {
+        let curve = NurbsCurve::new(
+            3,
+            vec![
+                Point3d::new(1., 0., 0.),
+                Point3d::new(1., 1., 0.),
+                Point3d::new(0., 1., 2.),
+            ],
+            vec![],
+            vec![0., 0., 0., 1., 1., 1.],
+        )
+        .unwrap();
+        let translation = Xform::translation(&Vector3d::new(1., 2., 3.));
+        let translated = curve.transformed(&translation);
+        assert!(!translated.is_rational());
+        assert_eq!(Some(Point3d::new(1., 3., 5.)), translated.point_at(1.));
+
+        let mut projective = Xform::IDENTITY;
+        projective.0[3] = [0.2, 0.1, 0., 1.];
+        let projected = curve.transformed(&projective);
+        assert!(projected.is_rational());
+        for i in 0..=10 {
+            let t = i as f64 / 10.;
+            let expected = projective.transform_point(&curve.point_at(t).unwrap());
+            assert!(expected.distance_to(&projected.point_at(t).unwrap()) < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::math::{transformable::transform_homogeneous, Point3d, Transformable, Vector3d, Xform};
+
+use super::nurbs_curve::{dehomogenize, evaluate, homogeneous, is_knot_vector, NurbsCurve};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NurbsSurface {
+    pub order: [usize; 2],
+    pub count: [usize; 2],
+    pub control_points: Vec<Point3d>,
+    pub weights: Vec<f64>,
+    pub knots: [Vec<f64>; 2],
+}
+
+impl NurbsSurface {
+    pub fn new(
+        order: [usize; 2],
+        count: [usize; 2],
+        control_points: Vec<Point3d>,
+        weights: Vec<f64>,
+        knots: [Vec<f64>; 2],
+    ) -> Option<Self> {
+        let surface = Self {
+            order,
+            count,
+            control_points,
+            weights,
+            knots,
+        };
+        surface.is_consistent().then_some(surface)
+    }
+
+    pub fn bilinear(corners: [Point3d; 4]) -> Self {
+        Self {
+            order: [2, 2],
+            count: [2, 2],
+            control_points: vec![corners[0], corners[3], corners[1], corners[2]],
+            weights: vec![],
+            knots: [vec![0., 0., 1., 1.], vec![0., 0., 1., 1.]],
+        }
+    }
+
+    pub fn control_point(&self, i: usize, j: usize) -> Option<&Point3d> {
+        (i < self.count[0] && j < self.count[1])
+            .then(|| self.control_points.get(i * self.count[1] + j))
+            .flatten()
+    }
+
+    pub fn is_rational(&self) -> bool {
+        !self.weights.is_empty()
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.has_consistent_counts() && self.knots.iter().all(|knots| is_knot_vector(knots))
+    }
+
+    pub(crate) fn has_consistent_counts(&self) -> bool {
+        let control_count = self.count[0] * self.count[1];
+        (0..2).all(|d| {
+            2 <= self.order[d]
+                && self.order[d] <= self.count[d]
+                && self.count[d] + self.order[d] == self.knots[d].len()
+        }) && control_count == self.control_points.len()
+            && (self.weights.is_empty() || self.weights.len() == control_count)
+    }
+
+    pub fn domain(&self, direction: usize) -> Option<[f64; 2]> {
+        (direction < 2 && self.is_consistent()).then(|| {
+            let knots = &self.knots[direction];
+            [
+                knots[self.order[direction] - 1],
+                knots[self.count[direction]],
+            ]
+        })
+    }
+
+    pub fn point_at(&self, u: f64, v: f64) -> Option<Point3d> {
+        if !self.is_consistent() {
+            return None;
+        }
+        let points = homogeneous(&self.control_points, &self.weights);
+        let column: Vec<[f64; 4]> = points
+            .chunks_exact(self.count[1])
+            .map(|row| evaluate(self.order[1], &self.knots[1], row, v))
+            .collect();
+        dehomogenize(evaluate(self.order[0], &self.knots[0], &column, u))
+    }
+
+    pub fn derivatives_at(&self, u: f64, v: f64) -> Option<[Vector3d; 2]> {
+        let domains = [self.domain(0)?, self.domain(1)?];
+        let parameters = [u, v];
+        let mut derivatives = [Vector3d::ZERO; 2];
+        for (d, derivative) in derivatives.iter_mut().enumerate() {
+            let [start, end] = domains[d];
+            let step = 1e-6 * (end - start).abs().max(f64::EPSILON);
+            let t = parameters[d].clamp(start, end);
+            let (t0, t1) = ((t - step).max(start), (t + step).min(end));
+            let at = |t: f64| {
+                if 0 == d {
+                    self.point_at(t, v)
+                } else {
+                    self.point_at(u, t)
+                }
+            };
+            *derivative = (at(t1)? - at(t0)?) / (t1 - t0);
+        }
+        Some(derivatives)
+    }
+
+    pub fn normal_at(&self, u: f64, v: f64) -> Option<Vector3d> {
+        let [du, dv] = self.derivatives_at(u, v)?;
+        du.cross(&dv).normalized()
+    }
+
+    pub fn iso_curve(&self, direction: usize, parameter: f64) -> Option<NurbsCurve> {
+        if 1 < direction || !self.is_consistent() {
+            return None;
+        }
+        let points = homogeneous(&self.control_points, &self.weights);
+        let fixed = 1 - direction;
+        let homogeneous: Vec<[f64; 4]> = (0..self.count[direction])
+            .map(|i| {
+                let line: Vec<[f64; 4]> = (0..self.count[fixed])
+                    .map(|j| {
+                        if 0 == direction {
+                            points[i * self.count[1] + j]
+                        } else {
+                            points[j * self.count[1] + i]
+                        }
+                    })
+                    .collect();
+                evaluate(self.order[fixed], &self.knots[fixed], &line, parameter)
+            })
+            .collect();
+        let control_points = homogeneous
+            .iter()
+            .map(|point| dehomogenize(*point))
+            .collect::<Option<Vec<Point3d>>>()?;
+        let weights = if self.is_rational() {
+            homogeneous.iter().map(|point| point[3]).collect()
+        } else {
+            vec![]
+        };
+        NurbsCurve::new(
+            self.order[direction],
+            control_points,
+            weights,
+            self.knots[direction].clone(),
+        )
+    }
+}
+
+impl Transformable for NurbsSurface {
+    fn transform(&mut self, xform: &Xform) {
+        transform_homogeneous(xform, &mut self.control_points, &mut self.weights);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bilinear() {
+        let surface = NurbsSurface::bilinear([
+            Point3d::new(0., 0., 0.),
+            Point3d::new(2., 0., 0.),
+            Point3d::new(2., 1., 0.),
+            Point3d::new(0., 1., 0.),
+        ]);
+        assert!(surface.is_consistent());
+        assert_eq!(Some([0., 1.]), surface.domain(0));
+        assert_eq!(None, surface.domain(2));
+        assert_eq!(
+            Some(Point3d::new(1., 0.25, 0.)),
+            surface.point_at(0.5, 0.25)
+        );
+        assert_eq!(Some(Point3d::new(2., 1., 0.)), surface.point_at(1., 1.));
+        assert_eq!(Some(&Point3d::new(2., 0., 0.)), surface.control_point(1, 0));
+        assert_eq!(None, surface.control_point(2, 0));
+        let normal = surface.normal_at(0.5, 0.5).unwrap();
+        assert!((normal - Vector3d::Z).length() < 1e-9);
+        let [du, dv] = surface.derivatives_at(1., 0.).unwrap();
+        assert!((du - Vector3d::new(2., 0., 0.)).length() < 1e-6);
+        assert!((dv - Vector3d::new(0., 1., 0.)).length() < 1e-6);
+    }
+
+    #[test]
+    fn rational_cylinder() {
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let control_points = [[1., 0.], [1., 1.], [0., 1.]]
+            .iter()
+            .flat_map(|[x, y]| [Point3d::new(*x, *y, 0.), Point3d::new(*x, *y, 3.)])
+            .collect();
+        let surface = NurbsSurface::new(
+            [3, 2],
+            [3, 2],
+            control_points,
+            vec![1., 1., w, w, 1., 1.],
+            [vec![0., 0., 0., 1., 1., 1.], vec![0., 0., 3., 3.]],
+        )
+        .unwrap();
+        for i in 0..=4 {
+            let point = surface.point_at(i as f64 / 4., 1.5).unwrap();
+            assert!((1. - Vector3d::new(point.x, point.y, 0.).length()).abs() < 1e-12);
+            assert!((1.5 - point.z).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn iso_curve() {
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let control_points = [[1., 0.], [1., 1.], [0., 1.]]
+            .iter()
+            .flat_map(|[x, y]| [Point3d::new(*x, *y, 0.), Point3d::new(*x, *y, 3.)])
+            .collect();
+        let surface = NurbsSurface::new(
+            [3, 2],
+            [3, 2],
+            control_points,
+            vec![1., 1., w, w, 1., 1.],
+            [vec![0., 0., 0., 1., 1., 1.], vec![0., 0., 3., 3.]],
+        )
+        .unwrap();
+        let arc = surface.iso_curve(0, 1.5).unwrap();
+        assert_eq!(3, arc.order);
+        assert_eq!(vec![1., w, 1.], arc.weights);
+        assert!((std::f64::consts::FRAC_PI_2 - arc.length().unwrap()).abs() < 1e-9);
+        let line = surface.iso_curve(1, 0.5).unwrap();
+        assert_eq!(Some([0., 3.]), line.domain());
+        for i in 0..=4 {
+            let t = i as f64 / 4.;
+            let expected = surface.point_at(t, 1.5).unwrap();
+            assert!(expected.distance_to(&arc.point_at(t).unwrap()) < 1e-12);
+            let expected = surface.point_at(0.5, 3. * t).unwrap();
+            assert!(expected.distance_to(&line.point_at(3. * t).unwrap()) < 1e-12);
+        }
+        assert!(surface.iso_curve(2, 0.).is_none());
+    }
+
+    #[test]
+    fn inconsistent() {
+        let mut surface = NurbsSurface::bilinear([Point3d::ORIGIN; 4]);
+        surface.control_points.pop();
+        assert!(!surface.is_consistent());
+        assert_eq!(None, surface.point_at(0., 0.));
+        assert_eq!(None, surface.normal_at(0., 0.));
+        assert_eq!(None, surface.iso_curve(0, 0.));
+        assert!(NurbsSurface::bilinear([Point3d::ORIGIN; 4])
+            .normal_at(0.5, 0.5)
+            .is_none());
+
+        let mut surface = NurbsSurface::bilinear([Point3d::ORIGIN; 4]);
+        surface.knots[1] = vec![1., 1., 0., 0.];
+        assert!(!surface.is_consistent());
+        assert_eq!(None, surface.point_at(0.5, 0.5));
+        surface.knots[1] = vec![0., 0., f64::NAN, 1.];
+        assert_eq!(None, surface.point_at(0.5, 0.5));
+    }
+}
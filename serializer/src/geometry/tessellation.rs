@@ -0,0 +1,595 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    math::{Point3d, Vector3d},
+    model::Mesh,
+};
+
+use super::{brep::BrepFace, nurbs_curve::NurbsCurve, nurbs_surface::NurbsSurface};
+
+type Uv = [f64; 2];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TessellationOptions {
+    pub tolerance: f64,
+    pub max_grid_size: usize,
+    pub max_trim_depth: usize,
+    pub weld_tolerance: f64,
+}
+
+impl Default for TessellationOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.01,
+            max_grid_size: 64,
+            max_trim_depth: 8,
+            weld_tolerance: 1e-6,
+        }
+    }
+}
+
+impl TessellationOptions {
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_grid_size(mut self, max_grid_size: usize) -> Self {
+        self.max_grid_size = max_grid_size;
+        self
+    }
+
+    pub fn with_weld_tolerance(mut self, weld_tolerance: f64) -> Self {
+        self.weld_tolerance = weld_tolerance;
+        self
+    }
+
+    fn tolerance(&self) -> f64 {
+        self.tolerance.abs().max(1e-9)
+    }
+}
+
+pub fn tessellate_face(face: &BrepFace, options: &TessellationOptions) -> Mesh {
+    let surface = &face.surface;
+    let domains = match (surface.domain(0), surface.domain(1)) {
+        (Some(u), Some(v)) if u[0] < u[1] && v[0] < v[1] => [u, v],
+        _ => return Mesh::default(),
+    };
+    let grid = [
+        grid_parameters(surface, 0, &domains, options),
+        grid_parameters(surface, 1, &domains, options),
+    ];
+    let loops: Vec<Vec<Uv>> = if face.loops.is_empty() {
+        vec![rectangle(&grid)]
+    } else {
+        face.loops
+            .iter()
+            .map(|l| trim_polygon(surface, &l.trims, options))
+            .filter(|polygon| 3 <= polygon.len())
+            .collect()
+    };
+    if loops.is_empty() {
+        return Mesh::default();
+    }
+
+    let scale = parameter_scale(surface, &domains);
+    let to_plane = |[u, v]: Uv| {
+        [
+            (u - domains[0][0]) * scale[0],
+            (v - domains[1][0]) * scale[1],
+        ]
+    };
+    let plane_loops: Vec<Vec<Uv>> = loops
+        .iter()
+        .map(|polygon| polygon.iter().map(|p| to_plane(*p)).collect())
+        .collect();
+    let segments: Vec<[Uv; 2]> = plane_loops
+        .iter()
+        .flat_map(|polygon| {
+            (0..polygon.len()).map(|i| [polygon[i], polygon[(i + 1) % polygon.len()]])
+        })
+        .collect();
+    let spacing = (0..2)
+        .map(|d| (domains[d][1] - domains[d][0]) * scale[d] / (grid[d].len() - 1) as f64)
+        .fold(f64::INFINITY, f64::min);
+
+    let mut parameters: Vec<Uv> = loops.concat();
+    for u in grid[0].iter() {
+        for v in grid[1].iter() {
+            let point = to_plane([*u, *v]);
+            if is_inside(&point, &plane_loops)
+                && segments
+                    .iter()
+                    .all(|s| segment_distance(&point, s) > 0.25 * spacing)
+            {
+                parameters.push([*u, *v]);
+            }
+        }
+    }
+    let planar: Vec<Uv> = parameters.iter().map(|p| to_plane(*p)).collect();
+    let triangles: Vec<[usize; 3]> = delaunay(&planar)
+        .into_iter()
+        .filter(|t| {
+            let [a, b, c] = t.map(|i| planar[i]);
+            let centroid = [(a[0] + b[0] + c[0]) / 3., (a[1] + b[1] + c[1]) / 3.];
+            is_inside(&centroid, &plane_loops)
+                && [[a, b], [b, c], [c, a]]
+                    .iter()
+                    .all(|edge| segments.iter().all(|s| !crosses(edge, s)))
+        })
+        .collect();
+
+    let center = [
+        0.5 * (domains[0][0] + domains[0][1]),
+        0.5 * (domains[1][0] + domains[1][1]),
+    ];
+    let mut mesh = Mesh::default();
+    let mut vertices: HashMap<usize, u32> = HashMap::new();
+    for triangle in triangles {
+        let mut indices = [0u32; 3];
+        for (index, vertex) in indices.iter_mut().zip(triangle) {
+            *index = match vertices.get(&vertex) {
+                Some(index) => *index,
+                None => {
+                    let [u, v] = parameters[vertex];
+                    let position = match surface.point_at(u, v) {
+                        Some(position) => position,
+                        None => return Mesh::default(),
+                    };
+                    let normal = surface
+                        .normal_at(u, v)
+                        .or_else(|| {
+                            surface
+                                .normal_at(u + (center[0] - u) * 1e-3, v + (center[1] - v) * 1e-3)
+                        })
+                        .unwrap_or(Vector3d::ZERO);
+                    let normal = if face.reversed { -normal } else { normal };
+                    mesh.positions.push(position.into());
+                    mesh.normals
+                        .push([normal.x as f32, normal.y as f32, normal.z as f32]);
+                    vertices.insert(vertex, mesh.positions.len() as u32 - 1);
+                    mesh.positions.len() as u32 - 1
+                }
+            };
+        }
+        if face.reversed {
+            indices.swap(1, 2);
+        }
+        mesh.triangles.push(indices);
+    }
+    mesh
+}
+
+fn surface_point(surface: &NurbsSurface, direction: usize, t: f64, s: f64) -> Option<Point3d> {
+    if 0 == direction {
+        surface.point_at(t, s)
+    } else {
+        surface.point_at(s, t)
+    }
+}
+
+fn grid_parameters(
+    surface: &NurbsSurface,
+    direction: usize,
+    domains: &[Uv; 2],
+    options: &TessellationOptions,
+) -> Vec<f64> {
+    let [start, end] = domains[direction];
+    let [other_start, other_end] = domains[1 - direction];
+    let max_count = options.max_grid_size.max(1);
+    let mut count = (surface.count[direction] - 1).clamp(1, max_count);
+    let parameter = |i: usize, count: usize| start + (end - start) * i as f64 / count as f64;
+    while count < max_count {
+        let deviates = (0..=4).any(|k| {
+            let s = other_start + (other_end - other_start) * k as f64 / 4.;
+            (0..count).any(|i| {
+                let (a, b) = (parameter(i, count), parameter(i + 1, count));
+                match (
+                    surface_point(surface, direction, a, s),
+                    surface_point(surface, direction, b, s),
+                    surface_point(surface, direction, 0.5 * (a + b), s),
+                ) {
+                    (Some(a), Some(b), Some(m)) => {
+                        m.distance_to(&a.midpoint(&b)) > options.tolerance()
+                    }
+                    _ => false,
+                }
+            })
+        });
+        if !deviates {
+            break;
+        }
+        count = (2 * count).min(max_count);
+    }
+    (0..=count).map(|i| parameter(i, count)).collect()
+}
+
+fn parameter_scale(surface: &NurbsSurface, domains: &[Uv; 2]) -> [f64; 2] {
+    [0, 1].map(|direction| {
+        let [start, end] = domains[direction];
+        let s = 0.5 * (domains[1 - direction][0] + domains[1 - direction][1]);
+        let points: Vec<Point3d> = (0..=16)
+            .filter_map(|i| {
+                surface_point(
+                    surface,
+                    direction,
+                    start + (end - start) * i as f64 / 16.,
+                    s,
+                )
+            })
+            .collect();
+        let length: f64 = points.windows(2).map(|w| w[0].distance_to(&w[1])).sum();
+        if length.is_normal() {
+            length / (end - start)
+        } else {
+            1. / (end - start)
+        }
+    })
+}
+
+fn rectangle(grid: &[Vec<f64>; 2]) -> Vec<Uv> {
+    let (u, v) = (&grid[0], &grid[1]);
+    let (u0, u1, v0, v1) = (u[0], u[u.len() - 1], v[0], v[v.len() - 1]);
+    let mut polygon: Vec<Uv> = vec![];
+    polygon.extend(u[..u.len() - 1].iter().map(|u| [*u, v0]));
+    polygon.extend(v[..v.len() - 1].iter().map(|v| [u1, *v]));
+    polygon.extend(u[1..].iter().rev().map(|u| [*u, v1]));
+    polygon.extend(v[1..].iter().rev().map(|v| [u0, *v]));
+    polygon
+}
+
+fn trim_point(trim: &NurbsCurve, t: f64) -> Option<Uv> {
+    trim.point_at(t).map(|p| [p.x, p.y])
+}
+
+fn deviation(surface: &NurbsSurface, a: &Uv, b: &Uv, m: &Uv) -> f64 {
+    match (
+        surface.point_at(a[0], a[1]),
+        surface.point_at(b[0], b[1]),
+        surface.point_at(m[0], m[1]),
+    ) {
+        (Some(a), Some(b), Some(m)) => m.distance_to(&a.midpoint(&b)),
+        _ => 0.,
+    }
+}
+
+fn refine_trim(
+    surface: &NurbsSurface,
+    trim: &NurbsCurve,
+    (a, b): (f64, f64),
+    (pa, pb): (Uv, Uv),
+    depth: usize,
+    options: &TessellationOptions,
+    polygon: &mut Vec<Uv>,
+) {
+    let m = 0.5 * (a + b);
+    match trim_point(trim, m) {
+        Some(pm) if 0 < depth && deviation(surface, &pa, &pb, &pm) > options.tolerance() => {
+            refine_trim(surface, trim, (a, m), (pa, pm), depth - 1, options, polygon);
+            refine_trim(surface, trim, (m, b), (pm, pb), depth - 1, options, polygon);
+        }
+        _ => polygon.push(pb),
+    }
+}
+
+fn trim_polygon(
+    surface: &NurbsSurface,
+    trims: &[NurbsCurve],
+    options: &TessellationOptions,
+) -> Vec<Uv> {
+    let mut polygon: Vec<Uv> = vec![];
+    for trim in trims {
+        let [start, end] = match trim.domain() {
+            Some(domain) => domain,
+            None => continue,
+        };
+        let count = (trim.control_points.len() - 1).max(1);
+        let parameters: Vec<f64> = (0..=count)
+            .map(|i| start + (end - start) * i as f64 / count as f64)
+            .collect();
+        let mut previous = match trim_point(trim, start) {
+            Some(point) => point,
+            None => continue,
+        };
+        polygon.push(previous);
+        for window in parameters.windows(2) {
+            if let Some(next) = trim_point(trim, window[1]) {
+                refine_trim(
+                    surface,
+                    trim,
+                    (window[0], window[1]),
+                    (previous, next),
+                    options.max_trim_depth,
+                    options,
+                    &mut polygon,
+                );
+                previous = next;
+            }
+        }
+    }
+    polygon.dedup_by(|a, b| (a[0] - b[0]).abs() <= 1e-12 && (a[1] - b[1]).abs() <= 1e-12);
+    while 1 < polygon.len() {
+        let (first, last) = (polygon[0], polygon[polygon.len() - 1]);
+        if (first[0] - last[0]).abs() <= 1e-12 && (first[1] - last[1]).abs() <= 1e-12 {
+            polygon.pop();
+        } else {
+            break;
+        }
+    }
+    polygon
+}
+
+fn orient(a: &Uv, b: &Uv, c: &Uv) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn in_circle(a: &Uv, b: &Uv, c: &Uv, p: &Uv) -> bool {
+    let [ax, ay] = [a[0] - p[0], a[1] - p[1]];
+    let [bx, by] = [b[0] - p[0], b[1] - p[1]];
+    let [cx, cy] = [c[0] - p[0], c[1] - p[1]];
+    let determinant = (ax * ax + ay * ay) * (bx * cy - cx * by)
+        - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay);
+    0. < determinant
+}
+
+fn is_inside(point: &Uv, loops: &[Vec<Uv>]) -> bool {
+    let mut inside = false;
+    for polygon in loops {
+        for i in 0..polygon.len() {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            if (a[1] > point[1]) != (b[1] > point[1])
+                && point[0] < a[0] + (point[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1])
+            {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn segment_distance(point: &Uv, [a, b]: &[Uv; 2]) -> f64 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length_squared = dx * dx + dy * dy;
+    let t = if 0. < length_squared {
+        (((point[0] - a[0]) * dx + (point[1] - a[1]) * dy) / length_squared).clamp(0., 1.)
+    } else {
+        0.
+    };
+    let (x, y) = (a[0] + t * dx - point[0], a[1] + t * dy - point[1]);
+    (x * x + y * y).sqrt()
+}
+
+fn crosses([a, b]: &[Uv; 2], [c, d]: &[Uv; 2]) -> bool {
+    let (o1, o2) = (orient(a, b, c), orient(a, b, d));
+    let (o3, o4) = (orient(c, d, a), orient(c, d, b));
+    o1 * o2 < 0. && o3 * o4 < 0.
+}
+
+fn delaunay(points: &[Uv]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return vec![];
+    }
+    let (mut min, mut max) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+    for point in points {
+        for d in 0..2 {
+            min[d] = min[d].min(point[d]);
+            max[d] = max[d].max(point[d]);
+        }
+    }
+    let size = (max[0] - min[0]).max(max[1] - min[1]).max(f64::EPSILON);
+    let center = [0.5 * (min[0] + max[0]), 0.5 * (min[1] + max[1])];
+    let mut vertices = points.to_vec();
+    vertices.push([center[0] - 20. * size, center[1] - size]);
+    vertices.push([center[0] + 20. * size, center[1] - size]);
+    vertices.push([center[0], center[1] + 20. * size]);
+    let n = points.len();
+    let mut triangles: Vec<[usize; 3]> = vec![[n, n + 1, n + 2]];
+
+    for (i, p) in points.iter().enumerate() {
+        let mut bad: Vec<usize> = (0..triangles.len())
+            .filter(|t| {
+                let [a, b, c] = triangles[*t].map(|v| vertices[v]);
+                in_circle(&a, &b, &c, p)
+            })
+            .collect();
+        let boundary = loop {
+            let edges: HashSet<(usize, usize)> = bad
+                .iter()
+                .flat_map(|t| {
+                    let [a, b, c] = triangles[*t];
+                    [(a, b), (b, c), (c, a)]
+                })
+                .collect();
+            let boundary: Vec<(usize, (usize, usize))> = bad
+                .iter()
+                .flat_map(|t| {
+                    let [a, b, c] = triangles[*t];
+                    [(*t, (a, b)), (*t, (b, c)), (*t, (c, a))]
+                })
+                .filter(|(_, (a, b))| !edges.contains(&(*b, *a)))
+                .collect();
+            match boundary
+                .iter()
+                .find(|(_, (a, b))| 0. >= orient(&vertices[*a], &vertices[*b], p))
+            {
+                Some((t, _)) => {
+                    let t = *t;
+                    bad.retain(|b| *b != t);
+                }
+                None => break boundary,
+            }
+        };
+        if bad.is_empty() {
+            continue;
+        }
+        bad.sort_unstable();
+        bad.iter().rev().for_each(|t| {
+            triangles.swap_remove(*t);
+        });
+        triangles.extend(boundary.iter().map(|(_, (a, b))| [*a, *b, i]));
+    }
+    triangles.retain(|t| t.iter().all(|v| *v < n));
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    use crate::geometry::brep::{BrepLoop, LoopKind};
+
+    use super::*;
+
+    fn area(mesh: &Mesh) -> f64 {
+        mesh.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| Point3d::from(mesh.positions[i as usize]));
+                0.5 * (b - a).cross(&(c - a)).length()
+            })
+            .sum()
+    }
+
+    fn square() -> NurbsSurface {
+        NurbsSurface::bilinear([
+            Point3d::new(0., 0., 0.),
+            Point3d::new(1., 0., 0.),
+            Point3d::new(1., 1., 0.),
+            Point3d::new(0., 1., 0.),
+        ])
+    }
+
+    fn circle(center: [f64; 2], radius: f64) -> NurbsCurve {
+        let w = FRAC_1_SQRT_2;
+        let offsets = [
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+            [-1., 1.],
+            [-1., 0.],
+            [-1., -1.],
+            [0., -1.],
+            [1., -1.],
+            [1., 0.],
+        ];
+        NurbsCurve::new(
+            3,
+            offsets
+                .iter()
+                .map(|[x, y]| Point3d::new(center[0] + radius * x, center[1] + radius * y, 0.))
+                .collect(),
+            vec![1., w, 1., w, 1., w, 1., w, 1.],
+            vec![0., 0., 0., 1., 1., 2., 2., 3., 3., 4., 4., 4.],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn delaunay_square() {
+        let triangles = delaunay(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.5]]);
+        assert_eq!(4, triangles.len());
+        assert!(triangles.iter().all(|t| {
+            let [a, b, c] = t.map(|i| [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.5]][i]);
+            0. < orient(&a, &b, &c)
+        }));
+    }
+
+    #[test]
+    fn untrimmed() {
+        let face = BrepFace::from_surface(NurbsSurface::bilinear([
+            Point3d::new(0., 0., 0.),
+            Point3d::new(2., 0., 0.),
+            Point3d::new(2., 1., 0.),
+            Point3d::new(0., 1., 0.),
+        ]));
+        let mesh = face.tessellate(&TessellationOptions::default());
+        assert!(4 <= mesh.positions.len());
+        assert_eq!(mesh.positions.len(), mesh.normals.len());
+        assert!((2. - area(&mesh)).abs() < 1e-9);
+        assert!(mesh.normals.iter().all(|n| [0., 0., 1.] == *n));
+
+        let reversed = BrepFace {
+            reversed: true,
+            ..face
+        }
+        .tessellate(&TessellationOptions::default());
+        assert!(reversed.normals.iter().all(|n| [0., 0., -1.] == *n));
+        let [a, b, c] =
+            reversed.triangles[0].map(|i| Point3d::from(reversed.positions[i as usize]));
+        assert!(0. > (b - a).cross(&(c - a)).z);
+    }
+
+    #[test]
+    fn trimmed_hole() {
+        let corners = [
+            Point3d::new(0., 0., 0.),
+            Point3d::new(1., 0., 0.),
+            Point3d::new(1., 1., 0.),
+            Point3d::new(0., 1., 0.),
+        ];
+        let outer = (0..4)
+            .map(|i| NurbsCurve::from_points(&[corners[i], corners[(i + 1) % 4]]).unwrap())
+            .collect();
+        let face = BrepFace {
+            surface: square(),
+            loops: vec![
+                BrepLoop {
+                    kind: LoopKind::Outer,
+                    trims: outer,
+                },
+                BrepLoop {
+                    kind: LoopKind::Inner,
+                    trims: vec![circle([0.5, 0.5], 0.25)],
+                },
+            ],
+            reversed: false,
+            render_mesh: None,
+        };
+        let options = TessellationOptions::default().with_tolerance(1e-4);
+        let mesh = face.tessellate(&options);
+        assert!((1. - PI / 16. - area(&mesh)).abs() < 1e-2);
+        assert!(mesh.triangles.iter().all(|t| {
+            let [a, b, c] = t.map(|i| Point3d::from(mesh.positions[i as usize]));
+            let centroid = Point3d::new((a.x + b.x + c.x) / 3., (a.y + b.y + c.y) / 3., 0.);
+            centroid.distance_to(&Point3d::new(0.5, 0.5, 0.)) > 0.25
+        }));
+    }
+
+    #[test]
+    fn curved_surface() {
+        let w = FRAC_1_SQRT_2;
+        let control_points = [[1., 0.], [1., 1.], [0., 1.]]
+            .iter()
+            .flat_map(|[x, y]| [Point3d::new(*x, *y, 0.), Point3d::new(*x, *y, 1.)])
+            .collect();
+        let surface = NurbsSurface::new(
+            [3, 2],
+            [3, 2],
+            control_points,
+            vec![1., 1., w, w, 1., 1.],
+            [vec![0., 0., 0., 1., 1., 1.], vec![0., 0., 1., 1.]],
+        )
+        .unwrap();
+        let tolerance = 1e-3;
+        let mesh = BrepFace::from_surface(surface)
+            .tessellate(&TessellationOptions::default().with_tolerance(tolerance));
+        assert!(!mesh.triangles.is_empty());
+        assert!(mesh.triangles.iter().all(|t| {
+            let [a, b, c] = t.map(|i| Point3d::from(mesh.positions[i as usize]));
+            let centroid = (a.to_vector() + b.to_vector() + c.to_vector()) / 3.;
+            1. - (centroid.x * centroid.x + centroid.y * centroid.y).sqrt() < 2. * tolerance
+        }));
+        assert!((0.5 * PI - area(&mesh)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn invalid_surface() {
+        let mut surface = square();
+        surface.knots[0].pop();
+        assert_eq!(
+            Mesh::default(),
+            BrepFace::from_surface(surface).tessellate(&TessellationOptions::default())
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use crate::math::{Arc, Circle, Line, Point3d};
+
+use super::{brep::Brep, nurbs_curve::NurbsCurve, nurbs_surface::NurbsSurface};
+
+const TRIM_SAMPLES: usize = 32;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum GeometryIssueKind {
+    InconsistentCounts,
+    NonFiniteCoordinate { index: usize },
+    NonMonotonicKnots { index: usize },
+    InvalidWeight { index: usize },
+    InvalidRadius,
+    InvalidAngle,
+    ZeroLength,
+    ZeroLengthEdge,
+    OpenTrimLoop { trim: usize },
+    SelfIntersectingTrim,
+}
+
+impl GeometryIssueKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::ZeroLength
+            | Self::ZeroLengthEdge
+            | Self::OpenTrimLoop { .. }
+            | Self::SelfIntersectingTrim => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeometryIssue {
+    pub kind: GeometryIssueKind,
+    pub severity: Severity,
+    pub face: Option<usize>,
+    pub trim: Option<[usize; 2]>,
+}
+
+impl From<GeometryIssueKind> for GeometryIssue {
+    fn from(kind: GeometryIssueKind) -> Self {
+        Self {
+            kind,
+            severity: kind.severity(),
+            face: None,
+            trim: None,
+        }
+    }
+}
+
+fn has_errors(issues: &[GeometryIssue]) -> bool {
+    issues.iter().any(|issue| Severity::Error == issue.severity)
+}
+
+fn is_finite(point: &Point3d) -> bool {
+    point.x.is_finite() && point.y.is_finite() && point.z.is_finite()
+}
+
+fn check_points(points: &[Point3d], issues: &mut Vec<GeometryIssue>) {
+    issues.extend(
+        points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| !is_finite(point))
+            .map(|(index, _)| {
+                GeometryIssue::from(GeometryIssueKind::NonFiniteCoordinate { index })
+            }),
+    );
+}
+
+fn check_weights(weights: &[f64], issues: &mut Vec<GeometryIssue>) {
+    issues.extend(
+        weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| !weight.is_finite() || 0. >= **weight)
+            .map(|(index, _)| GeometryIssue::from(GeometryIssueKind::InvalidWeight { index })),
+    );
+}
+
+fn check_knots(knots: &[f64], issues: &mut Vec<GeometryIssue>) {
+    if let Some(index) = knots.iter().position(|knot| !knot.is_finite()) {
+        issues.push(GeometryIssueKind::NonMonotonicKnots { index }.into());
+    } else if let Some(index) = knots.windows(2).position(|pair| pair[0] > pair[1]) {
+        issues.push(GeometryIssueKind::NonMonotonicKnots { index: index + 1 }.into());
+    }
+}
+
+impl NurbsCurve {
+    pub fn is_valid(&self) -> bool {
+        !has_errors(&self.validate())
+    }
+
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let mut issues: Vec<GeometryIssue> = vec![];
+        if !self.has_consistent_counts() {
+            issues.push(GeometryIssueKind::InconsistentCounts.into());
+        }
+        check_points(&self.control_points, &mut issues);
+        check_weights(&self.weights, &mut issues);
+        check_knots(&self.knots, &mut issues);
+        if issues.is_empty()
+            && self
+                .control_points
+                .iter()
+                .all(|point| *point == self.control_points[0])
+        {
+            issues.push(GeometryIssueKind::ZeroLength.into());
+        }
+        issues
+    }
+}
+
+impl NurbsSurface {
+    pub fn is_valid(&self) -> bool {
+        !has_errors(&self.validate())
+    }
+
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let mut issues: Vec<GeometryIssue> = vec![];
+        if !self.has_consistent_counts() {
+            issues.push(GeometryIssueKind::InconsistentCounts.into());
+        }
+        check_points(&self.control_points, &mut issues);
+        check_weights(&self.weights, &mut issues);
+        self.knots
+            .iter()
+            .for_each(|knots| check_knots(knots, &mut issues));
+        issues
+    }
+}
+
+fn trim_polyline(trim: &NurbsCurve) -> Vec<[f64; 2]> {
+    let [start, end] = match trim.domain() {
+        Some(domain) => domain,
+        None => return vec![],
+    };
+    (0..=TRIM_SAMPLES)
+        .filter_map(|i| trim.point_at(start + (end - start) * i as f64 / TRIM_SAMPLES as f64))
+        .map(|point| [point.x, point.y])
+        .collect()
+}
+
+fn crosses([a, b]: [[f64; 2]; 2], [c, d]: [[f64; 2]; 2]) -> bool {
+    let orient = |p: [f64; 2], q: [f64; 2], r: [f64; 2]| {
+        (q[0] - p[0]) * (r[1] - p[1]) - (q[1] - p[1]) * (r[0] - p[0])
+    };
+    let (d1, d2) = (orient(a, b, c), orient(a, b, d));
+    let (d3, d4) = (orient(c, d, a), orient(c, d, b));
+    0. > d1 * d2 && 0. > d3 * d4
+}
+
+impl Brep {
+    pub fn is_valid(&self) -> bool {
+        !has_errors(&self.validate())
+    }
+
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let mut issues: Vec<GeometryIssue> = vec![];
+        for (face_index, face) in self.faces.iter().enumerate() {
+            let in_face = |issue: GeometryIssue| GeometryIssue {
+                face: Some(face_index),
+                ..issue
+            };
+            issues.extend(face.surface.validate().into_iter().map(in_face));
+            for (loop_index, brep_loop) in face.loops.iter().enumerate() {
+                let in_trim = |trim: usize, issue: GeometryIssue| GeometryIssue {
+                    face: Some(face_index),
+                    trim: Some([loop_index, trim]),
+                    ..issue
+                };
+                let mut segments: Vec<[[f64; 2]; 2]> = vec![];
+                for (trim_index, trim) in brep_loop.trims.iter().enumerate() {
+                    let trim_issues = trim.validate();
+                    if trim_issues
+                        .iter()
+                        .any(|i| GeometryIssueKind::ZeroLength == i.kind)
+                    {
+                        issues.push(in_trim(
+                            trim_index,
+                            GeometryIssueKind::ZeroLengthEdge.into(),
+                        ));
+                    }
+                    issues.extend(
+                        trim_issues
+                            .into_iter()
+                            .filter(|i| GeometryIssueKind::ZeroLength != i.kind)
+                            .map(|issue| in_trim(trim_index, issue)),
+                    );
+                    let polyline = trim_polyline(trim);
+                    segments.extend(polyline.windows(2).map(|pair| [pair[0], pair[1]]));
+
+                    let next = &brep_loop.trims[(trim_index + 1) % brep_loop.trims.len()];
+                    let end = trim.domain().and_then(|[_, end]| trim.point_at(end));
+                    let start = next.domain().and_then(|[start, _]| next.point_at(start));
+                    if let (Some(end), Some(start)) = (end, start) {
+                        if end.distance_to(&start) > 1e-9 * (1. + end.to_vector().length()) {
+                            issues.push(in_trim(
+                                trim_index,
+                                GeometryIssueKind::OpenTrimLoop { trim: trim_index }.into(),
+                            ));
+                        }
+                    }
+                }
+                let count = segments.len();
+                let intersecting = (0..count).any(|i| {
+                    (i + 2..count)
+                        .filter(|j| !(0 == i && count - 1 == *j))
+                        .any(|j| crosses(segments[i], segments[j]))
+                });
+                if intersecting {
+                    issues.push(GeometryIssue {
+                        face: Some(face_index),
+                        trim: Some([loop_index, 0]),
+                        ..GeometryIssueKind::SelfIntersectingTrim.into()
+                    });
+                }
+            }
+        }
+        issues
+    }
+}
+
+impl Line {
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let mut issues: Vec<GeometryIssue> = vec![];
+        check_points(&[self.from, self.to], &mut issues);
+        if issues.is_empty() && self.from == self.to {
+            issues.push(GeometryIssueKind::ZeroLength.into());
+        }
+        issues
+    }
+}
+
+impl Circle {
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let mut issues: Vec<GeometryIssue> = vec![];
+        check_points(&[self.plane.origin], &mut issues);
+        if !self.radius.is_finite() || 0. >= self.radius {
+            issues.push(GeometryIssueKind::InvalidRadius.into());
+        }
+        issues
+    }
+}
+
+impl Arc {
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let mut issues = self.circle.validate();
+        let angle = self.angle();
+        if !angle.is_finite() || 0. >= angle || std::f64::consts::TAU < angle {
+            issues.push(GeometryIssueKind::InvalidAngle.into());
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{BrepFace, BrepLoop, LoopKind};
+
+    fn polyline(points: &[[f64; 2]]) -> NurbsCurve {
+        let points: Vec<Point3d> = points
+            .iter()
+            .map(|p| Point3d::new(p[0], p[1], 0.))
+            .collect();
+        NurbsCurve::from_points(&points).unwrap()
+    }
+
+    fn trimmed(trims: Vec<NurbsCurve>) -> Brep {
+        let mut face = BrepFace::from_surface(NurbsSurface::bilinear([
+            Point3d::new(0., 0., 0.),
+            Point3d::new(1., 0., 0.),
+            Point3d::new(0., 1., 0.),
+            Point3d::new(1., 1., 0.),
+        ]));
+        face.loops.push(BrepLoop {
+            kind: LoopKind::Outer,
+            trims,
+        });
+        Brep { faces: vec![face] }
+    }
+
+    fn kinds(issues: &[GeometryIssue]) -> Vec<GeometryIssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn nurbs_curve() {
+        let mut curve = polyline(&[[0., 0.], [1., 0.], [1., 1.]]);
+        assert!(curve.validate().is_empty());
+
+        curve.knots = vec![0., 0., 2., 1., 2.];
+        curve.control_points[2].y = f64::NAN;
+        curve.weights = vec![1., 0., 1.];
+        assert_eq!(
+            vec![
+                GeometryIssueKind::NonFiniteCoordinate { index: 2 },
+                GeometryIssueKind::InvalidWeight { index: 1 },
+                GeometryIssueKind::NonMonotonicKnots { index: 3 },
+            ],
+            kinds(&curve.validate())
+        );
+        assert!(!curve.is_valid());
+
+        let point = polyline(&[[1., 1.], [1., 1.]]);
+        let issues = point.validate();
+        assert_eq!(vec![GeometryIssueKind::ZeroLength], kinds(&issues));
+        assert_eq!(Severity::Warning, issues[0].severity);
+        assert!(point.is_valid());
+    }
+
+    #[test]
+    fn nurbs_surface() {
+        let mut surface = NurbsSurface::bilinear([Point3d::ORIGIN; 4]);
+        assert!(surface.validate().is_empty());
+        surface.count = [3, 2];
+        assert_eq!(
+            vec![GeometryIssueKind::InconsistentCounts],
+            kinds(&surface.validate())
+        );
+    }
+
+    #[test]
+    fn brep_trims() {
+        let square = vec![
+            polyline(&[[0., 0.], [1., 0.]]),
+            polyline(&[[1., 0.], [1., 1.]]),
+            polyline(&[[1., 1.], [0., 1.]]),
+            polyline(&[[0., 1.], [0., 0.]]),
+        ];
+        assert!(trimmed(square.clone()).validate().is_empty());
+
+        let mut open = square.clone();
+        open.pop();
+        let issues = trimmed(open).validate();
+        assert_eq!(
+            vec![GeometryIssueKind::OpenTrimLoop { trim: 2 }],
+            kinds(&issues)
+        );
+        assert_eq!(Some(0), issues[0].face);
+        assert_eq!(Some([0, 2]), issues[0].trim);
+
+        let mut degenerate = square.clone();
+        degenerate.insert(1, polyline(&[[1., 0.], [1., 0.]]));
+        assert_eq!(
+            vec![GeometryIssueKind::ZeroLengthEdge],
+            kinds(&trimmed(degenerate).validate())
+        );
+
+        let bowtie = vec![
+            polyline(&[[0., 0.], [1., 1.]]),
+            polyline(&[[1., 1.], [1., 0.]]),
+            polyline(&[[1., 0.], [0., 0.7]]),
+            polyline(&[[0., 0.7], [0., 0.]]),
+        ];
+        let brep = trimmed(bowtie);
+        assert_eq!(
+            vec![GeometryIssueKind::SelfIntersectingTrim],
+            kinds(&brep.validate())
+        );
+        assert!(brep.is_valid());
+    }
+
+    #[test]
+    fn curves() {
+        let line = Line {
+            from: Point3d::ORIGIN,
+            to: Point3d::ORIGIN,
+        };
+        assert_eq!(vec![GeometryIssueKind::ZeroLength], kinds(&line.validate()));
+
+        let mut circle = Circle::new(crate::math::Plane::default(), 1.);
+        assert!(circle.validate().is_empty());
+        circle.radius = f64::NAN;
+        assert_eq!(
+            vec![GeometryIssueKind::InvalidRadius],
+            kinds(&circle.validate())
+        );
+        let arc = Arc {
+            circle,
+            start_angle: 1.,
+            end_angle: 0.,
+        };
+        assert_eq!(
+            vec![
+                GeometryIssueKind::InvalidRadius,
+                GeometryIssueKind::InvalidAngle
+            ],
+            kinds(&arc.validate())
+        );
+    }
+}
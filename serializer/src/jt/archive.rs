@@ -0,0 +1,394 @@
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use super::{
+    common::GUID,
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    header::Header,
+    lsg::{Lsg, LsgSegment, Node},
+    property::PropertyValue,
+    reader::Reader,
+    segment::SegmentType,
+    shape_lod::{ShapeLodElement, ShapeLodSegment},
+    statistics::SceneStatistics,
+    toc::{Toc, TocEntry},
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Archive {
+    pub header: Header,
+    pub toc: Toc,
+    pub lsg_segment: LsgSegment,
+}
+
+impl Archive {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        Self::read(Cursor::new(bytes))
+    }
+
+    pub fn read<T>(source: T) -> Result<Self, String>
+    where
+        T: Read + Seek,
+    {
+        Self::deserialize(&mut Reader::new(source))
+    }
+
+    pub fn lsg(&self) -> &Lsg {
+        &self.lsg_segment.lsg
+    }
+
+    pub fn segments(&self) -> &[TocEntry] {
+        &self.toc.entries
+    }
+
+    pub fn segment(&self, segment_id: &GUID) -> Option<&TocEntry> {
+        self.toc.find(segment_id)
+    }
+
+    pub fn segments_of_type(&self, segment_type: SegmentType) -> impl Iterator<Item = &TocEntry> {
+        self.toc
+            .entries
+            .iter()
+            .filter(move |e| Ok(segment_type) == e.segment_type())
+    }
+
+    pub fn load_segment<T, D>(&self, deserializer: &mut D, segment_id: &GUID) -> Result<T, String>
+    where
+        T: Deserialize,
+        D: Deserializer,
+        String: From<<T as Deserialize>::Error>,
+    {
+        let entry = self.segment(segment_id).ok_or("segment not found")?;
+        if let Err(e) = deserializer.seek(SeekFrom::Start(entry.segment_offset)) {
+            return Err(e.to_string());
+        }
+        Ok(T::deserialize(deserializer)?)
+    }
+
+    pub fn properties(&self, node_id: i32) -> Vec<(&PropertyValue, &PropertyValue)> {
+        self.lsg().metadata.properties(node_id)
+    }
+
+    pub fn find_nodes_with_property(&self, key: &str, value: &str) -> Vec<i32> {
+        self.lsg().metadata.find_nodes_with_value(key, value)
+    }
+
+    pub fn statistics(&self) -> SceneStatistics {
+        let lsg = self.lsg();
+        let mut statistics = SceneStatistics {
+            node_count: lsg.nodes.len(),
+            attribute_count: lsg.attributes.len(),
+            segment_count: self.toc.entries.len(),
+            ..Default::default()
+        };
+        for node in lsg.nodes.values() {
+            match node {
+                Node::Part(_) => statistics.part_count += 1,
+                Node::Instance(_) => statistics.instance_count += 1,
+                Node::TriStripSetShape(_) | Node::PolylineSetShape(_) => {
+                    statistics.shape_count += 1
+                }
+                _ => {}
+            }
+        }
+        for entry in &self.toc.entries {
+            if let Ok(segment_type) = entry.segment_type() {
+                *statistics.segment_bytes.entry(segment_type).or_default() +=
+                    entry.segment_length as u64;
+            }
+        }
+        statistics
+    }
+
+    pub fn statistics_with_geometry<D>(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<SceneStatistics, String>
+    where
+        D: Deserializer,
+    {
+        let mut statistics = self.statistics();
+        for entry in &self.toc.entries {
+            let level = match entry.segment_type() {
+                Ok(SegmentType::ShapeLod(level)) => level,
+                _ => continue,
+            };
+            let segment: ShapeLodSegment = self.load_segment(deserializer, &entry.segment_id)?;
+            let triangle_count = match &segment.element {
+                ShapeLodElement::TriStripSet(lod) => lod.triangle_count()?,
+                _ => 0,
+            };
+            *statistics.lod_triangle_counts.entry(level).or_default() += triangle_count;
+        }
+        Ok(statistics)
+    }
+}
+
+impl Deserialize for Archive {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let header = Header::deserialize(deserializer)?;
+        if let Err(e) = deserializer.seek(SeekFrom::Start(header.toc_offset())) {
+            return Err(e.to_string());
+        }
+        let toc = Toc::deserialize(deserializer)?;
+        let lsg_entry = toc
+            .find(&header.lsg_segment_id())
+            .ok_or("lsg segment not found")?;
+        if let Err(e) = deserializer.seek(SeekFrom::Start(lsg_entry.segment_offset)) {
+            return Err(e.to_string());
+        }
+        let lsg_segment = LsgSegment::deserialize(deserializer)?;
+        Ok(Self {
+            header,
+            toc,
+            lsg_segment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use crate::jt::{object_type, reader::ByteOrder, test_utils::extend_guid_with_byte_order};
+
+    use super::*;
+
+    macro_rules! to_bytes {
+        ($byte_order: expr, $value: expr) => {
+            match $byte_order {
+                ByteOrder::LittleEndian => $value.to_le_bytes().to_vec(),
+                ByteOrder::BigEndian => $value.to_be_bytes().to_vec(),
+            }
+        };
+    }
+
+    fn element(
+        data: &mut Vec<u8>,
+        byte_order: ByteOrder,
+        object_type: &GUID,
+        base_type: u8,
+        body: &[u8],
+    ) {
+        data.extend(to_bytes!(byte_order, 17 + body.len() as i32));
+        extend_guid_with_byte_order(data, byte_order, object_type);
+        data.push(base_type);
+        data.extend(body);
+    }
+
+    fn end_of_elements(data: &mut Vec<u8>, byte_order: ByteOrder) {
+        element(data, byte_order, &object_type::END_OF_ELEMENTS, 0xFF, &[]);
+    }
+
+    fn i32s(byte_order: ByteOrder, values: &[i32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| to_bytes!(byte_order, v))
+            .collect()
+    }
+
+    fn string_atom(data: &mut Vec<u8>, byte_order: ByteOrder, object_id: i32, value: &str) {
+        let mut body = i32s(byte_order, &[object_id, 0, value.len() as i32]);
+        value
+            .encode_utf16()
+            .for_each(|u| body.extend(to_bytes!(byte_order, u)));
+        element(
+            data,
+            byte_order,
+            &object_type::STRING_PROPERTY_ATOM,
+            5,
+            &body,
+        );
+    }
+
+    fn lsg_data(byte_order: ByteOrder) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        let mut partition = i32s(byte_order, &[1, 0, 0, 1, 2, 0, 0]);
+        partition.extend([0u8; 6 * 4 + 4 + 3 * 2 * 4]);
+        element(
+            &mut data,
+            byte_order,
+            &object_type::PARTITION_NODE,
+            1,
+            &partition,
+        );
+        let mut part = i32s(byte_order, &[2, 0, 1, 3, 0]);
+        part.extend(to_bytes!(byte_order, 1i16));
+        part.extend(to_bytes!(byte_order, 1i16));
+        part.extend(to_bytes!(byte_order, 0i32));
+        element(&mut data, byte_order, &object_type::PART_NODE, 1, &part);
+        let mut transform = to_bytes!(byte_order, 3i32);
+        transform.push(0u8);
+        transform.extend(to_bytes!(byte_order, 0u32));
+        transform.extend(to_bytes!(byte_order, 0x0008u16));
+        transform.extend(to_bytes!(byte_order, 5.0f32));
+        element(
+            &mut data,
+            byte_order,
+            &object_type::GEOMETRIC_TRANSFORM_ATTRIBUTE,
+            3,
+            &transform,
+        );
+        element(
+            &mut data,
+            byte_order,
+            &GUID(0x1234, [0; 2], [0; 8]),
+            0xFF,
+            &[1, 2, 3],
+        );
+        end_of_elements(&mut data, byte_order);
+        string_atom(&mut data, byte_order, 10, "PART_NUMBER");
+        string_atom(&mut data, byte_order, 11, "A-100");
+        end_of_elements(&mut data, byte_order);
+        data.extend(to_bytes!(byte_order, 1i16));
+        data.extend(i32s(byte_order, &[1, 2, 10, 11, 0]));
+        data
+    }
+
+    fn jt_data(byte_order: ByteOrder) -> Vec<u8> {
+        let lsg_id = GUID(0xABCD, [1, 2], [3; 8]);
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&lsg_data(byte_order)).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut segment: Vec<u8> = vec![];
+        extend_guid_with_byte_order(&mut segment, byte_order, &lsg_id);
+        segment.extend(i32s(
+            byte_order,
+            &[
+                1,
+                24 + 9 + compressed.len() as i32,
+                2,
+                compressed.len() as i32 + 1,
+            ],
+        ));
+        segment.push(2u8);
+        segment.extend(compressed);
+
+        let header_length = 80 + 1 + 4 + 4 + 16;
+        let toc_offset = header_length + segment.len();
+        let mut data = [b' '; 80].to_vec();
+        data[..14].copy_from_slice(b"Version 8.1 JT");
+        data.push((ByteOrder::BigEndian == byte_order) as u8);
+        data.extend(i32s(byte_order, &[0, toc_offset as i32]));
+        extend_guid_with_byte_order(&mut data, byte_order, &lsg_id);
+        data.extend(segment.iter());
+        data.extend(to_bytes!(byte_order, 1i32));
+        extend_guid_with_byte_order(&mut data, byte_order, &lsg_id);
+        data.extend(i32s(
+            byte_order,
+            &[header_length as i32, segment.len() as i32],
+        ));
+        data.extend(to_bytes!(byte_order, 0x01000000u32));
+        data
+    }
+
+    fn check_archive(archive: &Archive) {
+        assert_eq!(1, archive.segments().len());
+        assert_eq!(
+            1,
+            archive
+                .segments_of_type(SegmentType::LogicalSceneGraph)
+                .count()
+        );
+        let lsg = archive.lsg();
+        assert_eq!(2, lsg.nodes.len());
+        assert_eq!(Some(1), lsg.root_node_id);
+        assert_eq!(&[2], lsg.root().unwrap().children());
+        match lsg.node(2) {
+            Some(Node::Part(part)) => assert_eq!(
+                vec![3],
+                part.meta_data_node_data
+                    .group_node_data
+                    .base_node_data
+                    .attribute_object_ids
+            ),
+            _ => panic!("expected part node"),
+        }
+        assert!(lsg.attribute(3).is_some());
+        assert_eq!(
+            vec![(
+                &PropertyValue::String("PART_NUMBER".to_string()),
+                &PropertyValue::String("A-100".to_string())
+            )],
+            archive.properties(2)
+        );
+        assert_eq!(
+            vec![2],
+            archive.find_nodes_with_property("PART_NUMBER", "A-100")
+        );
+    }
+
+    #[test]
+    fn read_archive() {
+        let archive = Archive::read(Cursor::new(jt_data(ByteOrder::LittleEndian))).unwrap();
+        assert_eq!(ByteOrder::LittleEndian, archive.header.byte_order());
+        check_archive(&archive);
+    }
+
+    #[test]
+    fn read_big_endian_archive() {
+        let archive = Archive::from_bytes(&jt_data(ByteOrder::BigEndian)).unwrap();
+        assert_eq!(ByteOrder::BigEndian, archive.header.byte_order());
+        check_archive(&archive);
+    }
+
+    #[test]
+    fn load_segment() {
+        let data = jt_data(ByteOrder::LittleEndian);
+        let archive = Archive::read(Cursor::new(data.clone())).unwrap();
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let segment_id = archive.header.lsg_segment_id();
+        let segment: LsgSegment = archive
+            .load_segment(&mut deserializer, &segment_id)
+            .unwrap();
+        assert_eq!(2, segment.lsg.nodes.len());
+        assert!(archive
+            .load_segment::<LsgSegment, _>(&mut deserializer, &GUID::default())
+            .is_err());
+    }
+
+    #[test]
+    fn archive_statistics() {
+        let data = jt_data(ByteOrder::LittleEndian);
+        let archive = Archive::read(Cursor::new(data.clone())).unwrap();
+        let statistics = archive.statistics();
+        assert_eq!(2, statistics.node_count);
+        assert_eq!(1, statistics.part_count);
+        assert_eq!(0, statistics.instance_count);
+        assert_eq!(0, statistics.shape_count);
+        assert_eq!(1, statistics.attribute_count);
+        assert_eq!(1, statistics.segment_count);
+        assert_eq!(
+            Some(&(archive.segments()[0].segment_length as u64)),
+            statistics
+                .segment_bytes
+                .get(&SegmentType::LogicalSceneGraph)
+        );
+        assert_eq!(
+            archive.segments()[0].segment_length as u64,
+            statistics.total_bytes()
+        );
+        assert_eq!(0, statistics.triangle_count());
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert_eq!(
+            Ok(statistics),
+            archive.statistics_with_geometry(&mut deserializer)
+        );
+    }
+
+    #[test]
+    fn read_truncated_archive() {
+        let mut data = jt_data(ByteOrder::LittleEndian);
+        data.truncate(120);
+        assert!(Archive::read(Cursor::new(data)).is_err());
+    }
+}
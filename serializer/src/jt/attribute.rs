@@ -0,0 +1,81 @@
+use geometria_derive::JtDeserialize;
+
+use crate::math::Xform;
+
+use super::{common::Mx4F32, deserialize::Deserialize, deserializer::Deserializer};
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BaseAttributeData {
+    pub object_id: i32,
+    pub state_flags: u8,
+    pub field_inhibit_flags: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeometricTransformAttribute {
+    pub base_attribute_data: BaseAttributeData,
+    pub stored_values_mask: u16,
+    pub transformation_matrix: Mx4F32,
+}
+
+impl GeometricTransformAttribute {
+    pub fn xform(&self) -> Xform {
+        Xform::from(&self.transformation_matrix)
+    }
+}
+
+impl Deserialize for GeometricTransformAttribute {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let base_attribute_data = BaseAttributeData::deserialize(deserializer)?;
+        let stored_values_mask = u16::deserialize(deserializer)?;
+        let mut elements = Mx4F32::IDENTITY.0;
+        for (i, element) in elements.iter_mut().enumerate() {
+            if 0 != stored_values_mask & (0x8000 >> i) {
+                *element = f32::deserialize(deserializer)?;
+            }
+        }
+        Ok(Self {
+            base_attribute_data,
+            stored_values_mask,
+            transformation_matrix: Mx4F32(elements),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{jt::reader::Reader, math::Point3d};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_geometric_transform_attribute() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(4i32.to_le_bytes());
+        data.push(0u8);
+        data.extend(0u32.to_le_bytes());
+        data.extend(0x000Eu16.to_le_bytes());
+        [2.0f32, 3.0, 4.0]
+            .iter()
+            .for_each(|v| data.extend(v.to_le_bytes()));
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let attribute = GeometricTransformAttribute::deserialize(&mut deserializer).unwrap();
+        assert_eq!(4, attribute.base_attribute_data.object_id);
+        assert_eq!(
+            [1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 2., 3., 4., 1.],
+            attribute.transformation_matrix.0
+        );
+        assert_eq!(
+            Point3d::new(3., 4., 5.),
+            attribute.xform() * Point3d::new(1., 1., 1.)
+        );
+    }
+}
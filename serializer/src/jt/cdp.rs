@@ -0,0 +1,979 @@
+use std::collections::HashMap;
+
+use super::{deserialize::Deserialize, deserializer::Deserializer};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CodecType {
+    Null,
+    Bitlength,
+    Huffman,
+    Arithmetic,
+}
+
+impl TryFrom<u8> for CodecType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Null),
+            1 => Ok(Self::Bitlength),
+            2 => Ok(Self::Huffman),
+            3 => Ok(Self::Arithmetic),
+            _ => Err("invalid codec type".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PredictorType {
+    Lag1,
+    Lag2,
+    Stride1,
+    Stride2,
+    StripIndex,
+    Ramp,
+    Xor1,
+    Xor2,
+    Null,
+}
+
+impl PredictorType {
+    const PRIMED_VALUES: usize = 4;
+
+    fn predict(self, values: &[i32], index: usize) -> i32 {
+        let v1 = values[index - 1];
+        let v2 = values[index - 2];
+        let v4 = values[index - 4];
+        match self {
+            Self::Lag1 | Self::Xor1 => v1,
+            Self::Lag2 | Self::Xor2 => v2,
+            Self::Stride1 => v1.wrapping_add(v1.wrapping_sub(v2)),
+            Self::Stride2 => v2.wrapping_add(v2.wrapping_sub(v4)),
+            Self::StripIndex => {
+                let stride = v2.wrapping_sub(v4);
+                if -8 < stride && 8 > stride {
+                    v2.wrapping_add(stride)
+                } else {
+                    v2.wrapping_add(2)
+                }
+            }
+            Self::Ramp => index as i32,
+            Self::Null => 0,
+        }
+    }
+
+    pub fn unpack(self, residuals: &[i32]) -> Vec<i32> {
+        let mut values: Vec<i32> = Vec::with_capacity(residuals.len());
+        for (index, residual) in residuals.iter().enumerate() {
+            if Self::PRIMED_VALUES > index || Self::Null == self {
+                values.push(*residual);
+            } else {
+                let predicted = self.predict(&values, index);
+                values.push(match self {
+                    Self::Xor1 | Self::Xor2 => residual ^ predicted,
+                    _ => residual.wrapping_add(predicted),
+                });
+            }
+        }
+        values
+    }
+}
+
+struct BitReader<'a> {
+    words: &'a [u32],
+    length: usize,
+    position: usize,
+    padding: usize,
+}
+
+impl<'a> BitReader<'a> {
+    const MAX_PADDING: usize = 16;
+
+    fn new(words: &'a [u32], length: usize) -> Self {
+        Self {
+            words,
+            length: length.min(words.len() * 32),
+            position: 0,
+            padding: 0,
+        }
+    }
+
+    fn read_unsigned(&mut self, bits: u8) -> Result<u32, String> {
+        if 32 < bits || self.length < self.position + bits as usize {
+            return Err("unexpected end of code text".to_string());
+        }
+        let mut value = 0u64;
+        for _ in 0..bits {
+            let word = self.words[self.position / 32];
+            let bit = (word >> (31 - self.position % 32)) & 1;
+            value = (value << 1) | bit as u64;
+            self.position += 1;
+        }
+        Ok(value as u32)
+    }
+
+    fn remaining(&self) -> usize {
+        self.length - self.position
+    }
+
+    fn read_padded_bit(&mut self) -> Result<u32, String> {
+        if self.position < self.length {
+            self.read_unsigned(1)
+        } else if Self::MAX_PADDING > self.padding {
+            self.padding += 1;
+            Ok(0)
+        } else {
+            Err("unexpected end of code text".to_string())
+        }
+    }
+
+    fn read_signed(&mut self, bits: u8) -> Result<i32, String> {
+        let value = self.read_unsigned(bits)?;
+        if 0 == bits || 32 == bits {
+            Ok(value as i32)
+        } else {
+            let shift = 32 - bits as u32;
+            Ok(((value << shift) as i32) >> shift)
+        }
+    }
+}
+
+fn bitlength_decode(
+    reader: &mut BitReader,
+    count: usize,
+    max_count: usize,
+) -> Result<Vec<i32>, String> {
+    if max_count < count {
+        return Err("too many values".to_string());
+    }
+    if 0 == reader.read_unsigned(1)? {
+        let min = reader.read_signed(32)?;
+        let max = reader.read_signed(32)?;
+        let range = max.wrapping_sub(min) as u32;
+        let bits = (32 - range.leading_zeros()) as u8;
+        if 0 == bits {
+            return Ok(vec![min; count]);
+        }
+        if count > reader.remaining() / bits as usize {
+            return Err("unexpected end of code text".to_string());
+        }
+        (0..count)
+            .map(|_| Ok(min.wrapping_add(reader.read_unsigned(bits)? as i32)))
+            .collect()
+    } else {
+        let mean = reader.read_signed(32)?;
+        let block_length_bits = reader.read_unsigned(3)? as u8;
+        if 2 > block_length_bits {
+            return Err("invalid bitlength block length".to_string());
+        }
+        if count > reader.remaining() / block_length_bits as usize {
+            return Err("unexpected end of code text".to_string());
+        }
+        let mut values: Vec<i32> = Vec::with_capacity(count);
+        let max_decrement = -(1i32 << (block_length_bits - 1));
+        let max_increment = (1i32 << (block_length_bits - 1)) - 1;
+        let mut field_width = 0i32;
+        while count > values.len() {
+            loop {
+                let adjustment = reader.read_signed(block_length_bits)?;
+                field_width += adjustment;
+                if max_decrement != adjustment && max_increment != adjustment {
+                    break;
+                }
+            }
+            if !(0..=32).contains(&field_width) {
+                return Err("invalid bitlength field width".to_string());
+            }
+            values.push(mean.wrapping_add(reader.read_signed(field_width as u8)?));
+        }
+        Ok(values)
+    }
+}
+
+struct StreamBitReader<'a, D> {
+    deserializer: &'a mut D,
+    byte: u8,
+    remaining: u8,
+}
+
+impl<'a, D> StreamBitReader<'a, D>
+where
+    D: Deserializer,
+{
+    fn new(deserializer: &'a mut D) -> Self {
+        Self {
+            deserializer,
+            byte: 0,
+            remaining: 0,
+        }
+    }
+
+    fn read_unsigned(&mut self, bits: u8) -> Result<u32, String> {
+        if 32 < bits {
+            return Err("invalid bit field width".to_string());
+        }
+        let mut value = 0u64;
+        for _ in 0..bits {
+            if 0 == self.remaining {
+                self.byte = u8::deserialize(self.deserializer)?;
+                self.remaining = 8;
+            }
+            self.remaining -= 1;
+            value = (value << 1) | ((self.byte >> self.remaining) & 1) as u64;
+        }
+        Ok(value as u32)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProbabilityContextEntry {
+    pub symbol: i32,
+    pub occurrence_count: u32,
+    pub associated_value: i32,
+    pub next_context: u32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProbabilityContextTable {
+    pub entries: Vec<ProbabilityContextEntry>,
+}
+
+impl ProbabilityContextTable {
+    fn total_count(&self) -> Option<u32> {
+        self.entries
+            .iter()
+            .try_fold(0u32, |total, e| total.checked_add(e.occurrence_count))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProbabilityContexts {
+    pub tables: Vec<ProbabilityContextTable>,
+}
+
+impl ProbabilityContexts {
+    pub const ESCAPE_SYMBOL: i32 = -2;
+
+    fn has_escape_symbol(&self) -> bool {
+        self.tables
+            .iter()
+            .flat_map(|t| t.entries.iter())
+            .any(|e| Self::ESCAPE_SYMBOL == e.symbol)
+    }
+}
+
+impl Deserialize for ProbabilityContexts {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let table_count = u8::deserialize(deserializer)?;
+        let mut reader = StreamBitReader::new(deserializer);
+        let mut contexts = Self::default();
+        let mut value_bits = 0u8;
+        let mut min_value = 0i32;
+        let mut associated_values: HashMap<i32, i32> = HashMap::new();
+        for table_index in 0..table_count {
+            let entry_count = reader.read_unsigned(32)? as usize;
+            if entry_count > Int32Cdp::DEFAULT_MAX_VALUE_COUNT {
+                return Err("too many probability context entries".to_string());
+            }
+            let symbol_bits = reader.read_unsigned(6)? as u8;
+            let occurrence_count_bits = reader.read_unsigned(6)? as u8;
+            if 0 == table_index {
+                value_bits = reader.read_unsigned(6)? as u8;
+            }
+            let next_context_bits = reader.read_unsigned(6)? as u8;
+            if 0 == table_index {
+                min_value = reader.read_unsigned(32)? as i32;
+            }
+            if 0 < entry_count && (0 == symbol_bits || 0 == occurrence_count_bits) {
+                return Err("invalid probability context bit widths".to_string());
+            }
+            let mut table = ProbabilityContextTable::default();
+            for _ in 0..entry_count {
+                let symbol = (reader.read_unsigned(symbol_bits)? as i32).wrapping_sub(2);
+                let occurrence_count = reader.read_unsigned(occurrence_count_bits)?;
+                let associated_value = if 0 == table_index {
+                    min_value.wrapping_add(reader.read_unsigned(value_bits)? as i32)
+                } else {
+                    associated_values.get(&symbol).copied().unwrap_or(0)
+                };
+                table.entries.push(ProbabilityContextEntry {
+                    symbol,
+                    occurrence_count,
+                    associated_value,
+                    next_context: reader.read_unsigned(next_context_bits)?,
+                });
+            }
+            if 0 == table_index {
+                for entry in table.entries.iter() {
+                    associated_values
+                        .entry(entry.symbol)
+                        .or_insert(entry.associated_value);
+                }
+            }
+            contexts.tables.push(table);
+        }
+        Ok(contexts)
+    }
+}
+
+fn arithmetic_decode(
+    reader: &mut BitReader,
+    contexts: &ProbabilityContexts,
+    out_of_band_values: &[i32],
+    symbol_count: usize,
+    max_count: usize,
+) -> Result<Vec<i32>, String> {
+    if max_count < symbol_count {
+        return Err("too many symbols".to_string());
+    }
+    let mut values: Vec<i32> = Vec::with_capacity(symbol_count.min(reader.remaining()));
+    let mut out_of_band_values = out_of_band_values.iter();
+    let mut low = 0u32;
+    let mut high = 0xFFFFu32;
+    let mut code = 0u32;
+    for _ in 0..16 {
+        code = (code << 1) | reader.read_padded_bit()?;
+    }
+    let mut context = 0usize;
+    for _ in 0..symbol_count {
+        let table = match contexts.tables.get(context) {
+            Some(table) => table,
+            None => return Err("invalid probability context".to_string()),
+        };
+        let overflow = || "arithmetic code overflow".to_string();
+        let total = table.total_count().ok_or_else(overflow)? as u64;
+        if 0 == total {
+            return Err("empty probability context".to_string());
+        }
+        let range = high.checked_sub(low).ok_or_else(overflow)? as u64 + 1;
+        let offset = code.checked_sub(low).ok_or_else(overflow)? as u64 + 1;
+        let rescaled = (offset * total - 1) / range;
+        let mut low_count = 0u64;
+        let entry = table.entries.iter().find(|e| {
+            if rescaled < low_count + e.occurrence_count as u64 {
+                true
+            } else {
+                low_count += e.occurrence_count as u64;
+                false
+            }
+        });
+        let entry = match entry {
+            Some(entry) => entry,
+            None => return Err("invalid arithmetic code".to_string()),
+        };
+        let high_count = low_count + entry.occurrence_count as u64;
+        high = (low as u64 + range * high_count / total)
+            .checked_sub(1)
+            .ok_or_else(overflow)? as u32;
+        low += (range * low_count / total) as u32;
+        loop {
+            if (high & 0x8000) != (low & 0x8000) {
+                if 0 != (low & 0x4000) && 0 == (high & 0x4000) {
+                    code ^= 0x4000;
+                    low &= 0x3FFF;
+                    high |= 0x4000;
+                } else {
+                    break;
+                }
+            }
+            low = (low << 1) & 0xFFFF;
+            high = ((high << 1) | 1) & 0xFFFF;
+            code = ((code << 1) | reader.read_padded_bit()?) & 0xFFFF;
+        }
+        if ProbabilityContexts::ESCAPE_SYMBOL == entry.symbol {
+            match out_of_band_values.next() {
+                Some(value) => values.push(*value),
+                None => return Err("missing out-of-band value".to_string()),
+            }
+        } else {
+            values.push(entry.associated_value);
+        }
+        context = entry.next_context as usize;
+    }
+    Ok(values)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Int32Cdp {
+    codec_type: CodecType,
+    probability_contexts: Option<ProbabilityContexts>,
+    out_of_band_values: Vec<i32>,
+    code_text_length: i32,
+    value_element_count: i32,
+    symbol_count: i32,
+    code_text: Vec<u32>,
+    max_value_count: usize,
+}
+
+impl Int32Cdp {
+    pub const DEFAULT_MAX_VALUE_COUNT: usize = 1 << 26;
+
+    pub fn with_max_value_count(mut self, max_value_count: usize) -> Self {
+        self.max_value_count = max_value_count;
+        self
+    }
+
+    pub fn codec_type(&self) -> CodecType {
+        self.codec_type
+    }
+
+    pub fn value_element_count(&self) -> usize {
+        self.value_element_count as usize
+    }
+
+    pub fn decode(&self) -> Result<Vec<i32>, String> {
+        match self.codec_type {
+            CodecType::Null => {
+                if (self.code_text.len() * 32) < self.code_text_length as usize
+                    || self.code_text.len() < self.value_element_count()
+                {
+                    Err("invalid code text length".to_string())
+                } else {
+                    Ok(self
+                        .code_text
+                        .iter()
+                        .take(self.value_element_count())
+                        .map(|word| *word as i32)
+                        .collect())
+                }
+            }
+            CodecType::Bitlength => bitlength_decode(
+                &mut BitReader::new(&self.code_text, self.code_text_length as usize),
+                self.value_element_count(),
+                self.max_value_count,
+            ),
+            CodecType::Arithmetic => match &self.probability_contexts {
+                Some(contexts) => arithmetic_decode(
+                    &mut BitReader::new(&self.code_text, self.code_text_length as usize),
+                    contexts,
+                    &self.out_of_band_values,
+                    self.symbol_count as usize,
+                    self.max_value_count,
+                ),
+                None => Err("missing probability contexts".to_string()),
+            },
+            CodecType::Huffman => Err("unsupported codec type".to_string()),
+        }
+    }
+
+    pub fn decode_with(&self, predictor: PredictorType) -> Result<Vec<i32>, String> {
+        Ok(predictor.unpack(&self.decode()?))
+    }
+}
+
+impl Int32Cdp {
+    const MAX_OUT_OF_BAND_DEPTH: usize = 1;
+
+    fn deserialize_nested<D>(deserializer: &mut D, depth: usize) -> Result<Self, String>
+    where
+        D: Deserializer,
+    {
+        let codec_type = CodecType::try_from(u8::deserialize(deserializer)?)?;
+        let probability_contexts = match codec_type {
+            CodecType::Huffman | CodecType::Arithmetic => {
+                Some(ProbabilityContexts::deserialize(deserializer)?)
+            }
+            _ => None,
+        };
+        let out_of_band_values = match &probability_contexts {
+            Some(contexts)
+                if CodecType::Arithmetic == codec_type && contexts.has_escape_symbol() =>
+            {
+                if Self::MAX_OUT_OF_BAND_DEPTH <= depth {
+                    return Err("too deeply nested out-of-band data".to_string());
+                }
+                Self::deserialize_nested(deserializer, depth + 1)?.decode()?
+            }
+            _ => vec![],
+        };
+        let code_text_length = i32::deserialize(deserializer)?;
+        let value_element_count = i32::deserialize(deserializer)?;
+        let symbol_count = match &probability_contexts {
+            Some(contexts) if 1 < contexts.tables.len() => i32::deserialize(deserializer)?,
+            _ => value_element_count,
+        };
+        if 0 > code_text_length {
+            return Err("invalid code text length".to_string());
+        }
+        if 0 > value_element_count {
+            return Err("invalid value element count".to_string());
+        }
+        if 0 > symbol_count {
+            return Err("invalid symbol count".to_string());
+        }
+        Ok(Self {
+            codec_type,
+            probability_contexts,
+            out_of_band_values,
+            code_text_length,
+            value_element_count,
+            symbol_count,
+            code_text: Vec::<u32>::deserialize(deserializer)?,
+            max_value_count: Self::DEFAULT_MAX_VALUE_COUNT,
+        })
+    }
+}
+
+impl Deserialize for Int32Cdp {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        Self::deserialize_nested(deserializer, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::jt::{reader::Reader, test_utils::null_cdp_data};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct BitWriter {
+        words: Vec<u32>,
+        length: usize,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: i32, bits: u8) {
+            for i in (0..bits).rev() {
+                if self.length.is_multiple_of(32) {
+                    self.words.push(0);
+                }
+                let bit = ((value as u32) >> i) & 1;
+                *self.words.last_mut().unwrap() |= bit << (31 - self.length % 32);
+                self.length += 1;
+            }
+        }
+
+        fn cdp(&self, count: usize) -> Int32Cdp {
+            Int32Cdp {
+                codec_type: CodecType::Bitlength,
+                probability_contexts: None,
+                out_of_band_values: vec![],
+                code_text_length: self.length as i32,
+                value_element_count: count as i32,
+                symbol_count: count as i32,
+                code_text: self.words.clone(),
+                max_value_count: Int32Cdp::DEFAULT_MAX_VALUE_COUNT,
+            }
+        }
+    }
+
+    #[test]
+    fn bit_reader_signed() {
+        let mut reader = BitReader::new(&[0b1011_0000 << 24], 8);
+        assert_eq!(Ok(-5), reader.read_signed(4));
+        assert_eq!(Ok(0), reader.read_signed(4));
+        assert!(reader.read_unsigned(1).is_err());
+    }
+
+    #[test]
+    fn decode_bitlength_fixed_width() {
+        let mut writer = BitWriter::default();
+        writer.write(0, 1);
+        writer.write(-3, 32);
+        writer.write(4, 32);
+        [0, 7, 3, 5].iter().for_each(|v| writer.write(*v, 3));
+        assert_eq!(vec![-3, 4, 0, 2], writer.cdp(4).decode().unwrap());
+    }
+
+    #[test]
+    fn decode_bitlength_constant() {
+        let mut writer = BitWriter::default();
+        writer.write(0, 1);
+        writer.write(9, 32);
+        writer.write(9, 32);
+        assert_eq!(vec![9, 9, 9], writer.cdp(3).decode().unwrap());
+    }
+
+    #[test]
+    fn decode_bitlength_variable_width() {
+        let mut writer = BitWriter::default();
+        writer.write(1, 1);
+        writer.write(10, 32);
+        writer.write(3, 3);
+        writer.write(3, 3);
+        writer.write(-1, 3);
+        writer.write(-2, 2);
+        writer.write(0, 3);
+        writer.write(1, 2);
+        writer.write(-4, 3);
+        writer.write(3, 3);
+        writer.write(0, 3);
+        writer.write(-1, 1);
+        assert_eq!(vec![8, 11, 9], writer.cdp(3).decode().unwrap());
+    }
+
+    #[test]
+    fn decode_bitlength_truncated() {
+        let mut writer = BitWriter::default();
+        writer.write(0, 1);
+        writer.write(0, 32);
+        writer.write(7, 32);
+        assert!(writer.cdp(2).decode().is_err());
+    }
+
+    #[test]
+    fn decode_bitlength_value_count_limit() {
+        let mut writer = BitWriter::default();
+        writer.write(0, 1);
+        writer.write(9, 32);
+        writer.write(9, 32);
+        let cdp = writer.cdp(i32::MAX as usize);
+        assert!(cdp.decode().is_err());
+        assert_eq!(
+            vec![9; 4],
+            writer.cdp(4).with_max_value_count(4).decode().unwrap()
+        );
+        assert!(writer.cdp(5).with_max_value_count(4).decode().is_err());
+
+        let mut writer = BitWriter::default();
+        writer.write(1, 1);
+        writer.write(0, 32);
+        writer.write(2, 3);
+        assert!(writer.cdp(i32::MAX as usize).decode().is_err());
+    }
+
+    fn arithmetic_encode(table: &ProbabilityContextTable, symbols: &[usize]) -> BitWriter {
+        let mut writer = BitWriter::default();
+        let total = table.total_count().unwrap() as u64;
+        let mut low = 0u32;
+        let mut high = 0xFFFFu32;
+        let mut pending = 0;
+        let emit = |writer: &mut BitWriter, bit: i32, pending: &mut i32| {
+            writer.write(bit, 1);
+            (0..*pending).for_each(|_| writer.write(1 - bit, 1));
+            *pending = 0;
+        };
+        for symbol in symbols {
+            let low_count: u32 = table.entries[..*symbol]
+                .iter()
+                .map(|e| e.occurrence_count)
+                .sum();
+            let high_count = low_count + table.entries[*symbol].occurrence_count;
+            let range = (high - low + 1) as u64;
+            high = low + (range * high_count as u64 / total) as u32 - 1;
+            low += (range * low_count as u64 / total) as u32;
+            loop {
+                if (high & 0x8000) == (low & 0x8000) {
+                    emit(&mut writer, (high >> 15) as i32, &mut pending);
+                } else if 0 != (low & 0x4000) && 0 == (high & 0x4000) {
+                    pending += 1;
+                    low &= 0x3FFF;
+                    high |= 0x4000;
+                } else {
+                    break;
+                }
+                low = (low << 1) & 0xFFFF;
+                high = ((high << 1) | 1) & 0xFFFF;
+            }
+        }
+        pending += 1;
+        emit(&mut writer, (low >> 14 & 1) as i32, &mut pending);
+        writer
+    }
+
+    fn single_table_contexts() -> ProbabilityContexts {
+        let entry = |symbol, occurrence_count, associated_value| ProbabilityContextEntry {
+            symbol,
+            occurrence_count,
+            associated_value,
+            next_context: 0,
+        };
+        ProbabilityContexts {
+            tables: vec![ProbabilityContextTable {
+                entries: vec![
+                    entry(0, 5, 10),
+                    entry(1, 2, 20),
+                    entry(ProbabilityContexts::ESCAPE_SYMBOL, 1, 0),
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn decode_arithmetic() {
+        let contexts = single_table_contexts();
+        let symbols = [0, 0, 1, 2, 0, 1, 1, 0, 2, 0];
+        let writer = arithmetic_encode(&contexts.tables[0], &symbols);
+        let cdp = Int32Cdp {
+            codec_type: CodecType::Arithmetic,
+            probability_contexts: Some(contexts),
+            out_of_band_values: vec![99, -7],
+            code_text_length: writer.length as i32,
+            value_element_count: symbols.len() as i32,
+            symbol_count: symbols.len() as i32,
+            code_text: writer.words.clone(),
+            max_value_count: Int32Cdp::DEFAULT_MAX_VALUE_COUNT,
+        };
+        assert_eq!(
+            vec![10, 10, 20, 99, 10, 20, 20, 10, -7, 10],
+            cdp.decode().unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_arithmetic_missing_out_of_band_value() {
+        let contexts = single_table_contexts();
+        let writer = arithmetic_encode(&contexts.tables[0], &[2]);
+        let cdp = Int32Cdp {
+            codec_type: CodecType::Arithmetic,
+            probability_contexts: Some(contexts),
+            out_of_band_values: vec![],
+            code_text_length: writer.length as i32,
+            value_element_count: 1,
+            symbol_count: 1,
+            code_text: writer.words.clone(),
+            max_value_count: Int32Cdp::DEFAULT_MAX_VALUE_COUNT,
+        };
+        assert!(cdp.decode().is_err());
+    }
+
+    #[test]
+    fn decode_arithmetic_exhausted_code_text() {
+        let mut contexts = single_table_contexts();
+        contexts.tables[0].entries[0].occurrence_count = 2;
+        let cdp = |symbol_count: i32| Int32Cdp {
+            codec_type: CodecType::Arithmetic,
+            probability_contexts: Some(contexts.clone()),
+            out_of_band_values: vec![],
+            code_text_length: 0,
+            value_element_count: symbol_count,
+            symbol_count,
+            code_text: vec![],
+            max_value_count: Int32Cdp::DEFAULT_MAX_VALUE_COUNT,
+        };
+        assert!(cdp(1000).decode().is_err());
+        assert!(cdp(i32::MAX).decode().is_err());
+        assert!(cdp(2).with_max_value_count(1).decode().is_err());
+    }
+
+    #[test]
+    fn decode_arithmetic_overflowing_counts() {
+        let mut contexts = single_table_contexts();
+        contexts.tables[0].entries[0].occurrence_count = u32::MAX;
+        contexts.tables[0].entries[1].occurrence_count = u32::MAX;
+        assert_eq!(None, contexts.tables[0].total_count());
+        let cdp = Int32Cdp {
+            codec_type: CodecType::Arithmetic,
+            probability_contexts: Some(contexts),
+            out_of_band_values: vec![],
+            code_text_length: 32,
+            value_element_count: 1,
+            symbol_count: 1,
+            code_text: vec![u32::MAX],
+            max_value_count: Int32Cdp::DEFAULT_MAX_VALUE_COUNT,
+        };
+        assert!(cdp.decode().is_err());
+    }
+
+    fn escape_contexts_data() -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        writer.write(1, 32);
+        writer.write(2, 6);
+        writer.write(1, 6);
+        writer.write(1, 6);
+        writer.write(0, 6);
+        writer.write(0, 32);
+        writer.write(0, 2);
+        writer.write(1, 1);
+        writer.write(0, 1);
+        let mut data: Vec<u8> = vec![1u8];
+        writer
+            .words
+            .iter()
+            .for_each(|w| data.extend(w.to_be_bytes()));
+        data
+    }
+
+    #[test]
+    fn deserialize_nested_out_of_band_data() {
+        let mut data: Vec<u8> = vec![];
+        for _ in 0..64 {
+            data.push(3u8);
+            data.extend(escape_contexts_data());
+        }
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert!(Int32Cdp::deserialize(&mut deserializer).is_err());
+
+        let mut data: Vec<u8> = vec![3u8];
+        data.extend(escape_contexts_data());
+        null_cdp_data(&mut data, &[5]);
+        [0i32, 1, 0]
+            .iter()
+            .for_each(|v| data.extend(v.to_le_bytes()));
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let cdp = Int32Cdp::deserialize(&mut deserializer).unwrap();
+        assert_eq!(vec![5], cdp.out_of_band_values);
+    }
+
+    #[test]
+    fn deserialize_probability_contexts() {
+        let mut writer = BitWriter::default();
+        writer.write(2, 32);
+        writer.write(3, 6);
+        writer.write(4, 6);
+        writer.write(5, 6);
+        writer.write(1, 6);
+        writer.write(-3, 32);
+        [(2, 5, 13, 0), (0, 1, 0, 1)]
+            .iter()
+            .for_each(|(symbol, count, value, next)| {
+                writer.write(*symbol, 3);
+                writer.write(*count, 4);
+                writer.write(*value, 5);
+                writer.write(*next, 1);
+            });
+        let mut data: Vec<u8> = vec![1u8];
+        writer
+            .words
+            .iter()
+            .for_each(|w| data.extend(w.to_be_bytes()));
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let contexts = ProbabilityContexts::deserialize(&mut deserializer).unwrap();
+        assert_eq!(1, contexts.tables.len());
+        assert_eq!(
+            vec![
+                ProbabilityContextEntry {
+                    symbol: 0,
+                    occurrence_count: 5,
+                    associated_value: 10,
+                    next_context: 0,
+                },
+                ProbabilityContextEntry {
+                    symbol: -2,
+                    occurrence_count: 1,
+                    associated_value: -3,
+                    next_context: 1,
+                },
+            ],
+            contexts.tables[0].entries
+        );
+        assert!(contexts.has_escape_symbol());
+    }
+
+    #[test]
+    fn deserialize_probability_contexts_limits() {
+        let contexts_data = |entry_count: u32, symbol_bits: u32, occurrence_count_bits: u32| {
+            let mut writer = BitWriter::default();
+            writer.write(entry_count as i32, 32);
+            writer.write(symbol_bits as i32, 6);
+            writer.write(occurrence_count_bits as i32, 6);
+            writer.write(0, 6);
+            writer.write(0, 6);
+            writer.write(0, 32);
+            let mut data: Vec<u8> = vec![1u8];
+            writer
+                .words
+                .iter()
+                .for_each(|w| data.extend(w.to_be_bytes()));
+            data
+        };
+        for data in [
+            contexts_data(u32::MAX, 0, 0),
+            contexts_data(1 << 25, 0, 0),
+            contexts_data(1 << 25, 1, 0),
+            contexts_data(1 << 25, 0, 1),
+        ] {
+            let mut deserializer = Reader::new(Cursor::new(data));
+            assert!(ProbabilityContexts::deserialize(&mut deserializer).is_err());
+        }
+
+        let mut deserializer = Reader::new(Cursor::new(contexts_data(1 << 25, 1, 1)));
+        assert!(ProbabilityContexts::deserialize(&mut deserializer).is_err());
+
+        let mut deserializer = Reader::new(Cursor::new(contexts_data(0, 0, 0)));
+        let contexts = ProbabilityContexts::deserialize(&mut deserializer).unwrap();
+        assert!(contexts.tables[0].entries.is_empty());
+    }
+
+    #[test]
+    fn codec_type_conversions() {
+        assert_eq!(Ok(CodecType::Null), CodecType::try_from(0));
+        assert_eq!(Ok(CodecType::Bitlength), CodecType::try_from(1));
+        assert_eq!(Ok(CodecType::Huffman), CodecType::try_from(2));
+        assert_eq!(Ok(CodecType::Arithmetic), CodecType::try_from(3));
+        assert!(CodecType::try_from(4).is_err());
+    }
+
+    #[test]
+    fn unpack_lag1() {
+        assert_eq!(
+            vec![1, 2, 3, 4, 9, 10],
+            PredictorType::Lag1.unpack(&[1, 2, 3, 4, 5, 1])
+        );
+    }
+
+    #[test]
+    fn unpack_stride1() {
+        assert_eq!(
+            vec![0, 1, 2, 3, 4, 5],
+            PredictorType::Stride1.unpack(&[0, 1, 2, 3, 0, 0])
+        );
+    }
+
+    #[test]
+    fn unpack_strip_index() {
+        assert_eq!(
+            vec![0, 1, 2, 3, 4, 5, 20, 9, 10],
+            PredictorType::StripIndex.unpack(&[0, 1, 2, 3, 0, 0, 14, 2, -12])
+        );
+    }
+
+    #[test]
+    fn unpack_xor1() {
+        assert_eq!(
+            vec![1, 2, 3, 4, 4 ^ 7, 4 ^ 7 ^ 1],
+            PredictorType::Xor1.unpack(&[1, 2, 3, 4, 7, 1])
+        );
+    }
+
+    #[test]
+    fn unpack_null() {
+        assert_eq!(
+            vec![1, 2, 3, 4, 5, 6],
+            PredictorType::Null.unpack(&[1, 2, 3, 4, 5, 6])
+        );
+    }
+
+    #[test]
+    fn deserialize_null_codec() {
+        let mut data: Vec<u8> = vec![];
+        null_cdp_data(&mut data, &[0, 1, 2, 3, 0, 0]);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let cdp = Int32Cdp::deserialize(&mut deserializer).unwrap();
+        assert_eq!(CodecType::Null, cdp.codec_type());
+        assert_eq!(6, cdp.value_element_count());
+        assert_eq!(vec![0, 1, 2, 3, 0, 0], cdp.decode().unwrap());
+        assert_eq!(
+            vec![0, 1, 2, 3, 4, 5],
+            cdp.decode_with(PredictorType::Stride1).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_value_element_count() {
+        let mut data: Vec<u8> = vec![0u8];
+        data.extend(0i32.to_le_bytes());
+        data.extend((-1i32).to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert!(Int32Cdp::deserialize(&mut deserializer).is_err());
+    }
+}
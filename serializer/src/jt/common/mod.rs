@@ -1,40 +1,584 @@
+use std::{fmt::Display, str::FromStr};
+
 use geometria_derive::JtDeserialize;
 
+use crate::math::{BoundingBox, Plane, Point3d, Vector3d, Xform};
+
 use super::{deserialize::Deserialize, deserializer::Deserializer};
 
-#[derive(Default, JtDeserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CoordF32(pub [f32; 3]);
 
-#[derive(Default, JtDeserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DirF32(pub [f32; 3]);
 
-#[derive(Default, JtDeserialize)]
+impl From<&CoordF32> for Point3d {
+    fn from(coord: &CoordF32) -> Self {
+        Self::from(coord.0)
+    }
+}
+
+impl From<&DirF32> for Vector3d {
+    fn from(direction: &DirF32) -> Self {
+        Self::from(direction.0)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BBoxF32 {
     pub min_corner: CoordF32,
     pub max_corner: CoordF32,
 }
 
-#[derive(Default, JtDeserialize)]
+impl BBoxF32 {
+    pub fn new(min_corner: [f32; 3], max_corner: [f32; 3]) -> Self {
+        Self {
+            min_corner: CoordF32(min_corner),
+            max_corner: CoordF32(max_corner),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(0..3).all(|i| self.min_corner.0[i] <= self.max_corner.0[i])
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            let mut union = *self;
+            for i in 0..3 {
+                union.min_corner.0[i] = union.min_corner.0[i].min(other.min_corner.0[i]);
+                union.max_corner.0[i] = union.max_corner.0[i].max(other.max_corner.0[i]);
+            }
+            union
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut intersection = *self;
+        for i in 0..3 {
+            intersection.min_corner.0[i] = self.min_corner.0[i].max(other.min_corner.0[i]);
+            intersection.max_corner.0[i] = self.max_corner.0[i].min(other.max_corner.0[i]);
+        }
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn contains(&self, point: &[f32; 3]) -> bool {
+        (0..3).all(|i| (self.min_corner.0[i]..=self.max_corner.0[i]).contains(&point[i]))
+    }
+
+    pub fn contains_bbox(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (self.contains(&other.min_corner.0) && self.contains(&other.max_corner.0))
+    }
+
+    pub fn center(&self) -> [f32; 3] {
+        [0, 1, 2].map(|i| 0.5 * (self.min_corner.0[i] + self.max_corner.0[i]))
+    }
+
+    pub fn extent(&self) -> [f32; 3] {
+        [0, 1, 2].map(|i| self.max_corner.0[i] - self.min_corner.0[i])
+    }
+}
+
+impl From<&BBoxF32> for BoundingBox {
+    fn from(bbox: &BBoxF32) -> Self {
+        if bbox.is_empty() {
+            Self::EMPTY
+        } else {
+            Self {
+                min: Point3d::from(&bbox.min_corner),
+                max: Point3d::from(&bbox.max_corner),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GUID(pub u32, pub [u16; 2], pub [u8; 8]);
 
+impl Display for GUID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-",
+            self.0, self.1[0], self.1[1], self.2[0], self.2[1]
+        )?;
+        self.2[2..].iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+impl FromStr for GUID {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .unwrap_or(s);
+        let groups: Vec<&str> = s.split('-').collect();
+        if [8, 4, 4, 4, 12] != groups.iter().map(|g| g.len()).collect::<Vec<usize>>()[..]
+            || !groups
+                .iter()
+                .all(|g| g.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err("invalid guid".to_string());
+        }
+        let hex = |g: &str| u32::from_str_radix(g, 16).map_err(|e| e.to_string());
+        let tail = format!("{}{}", groups[3], groups[4]);
+        let mut bytes = [0u8; 8];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = hex(&tail[2 * i..2 * i + 2])? as u8;
+        }
+        Ok(Self(
+            hex(groups[0])?,
+            [hex(groups[1])? as u16, hex(groups[2])? as u16],
+            bytes,
+        ))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MbString(pub String);
 
-//TODO implement Deserialize trait for MbString
+impl Deserialize for MbString {
+    type Error = String;
 
-#[derive(Default, JtDeserialize)]
-pub struct Mx4F32(pub [f32; 16]);
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let units = Vec::<u16>::deserialize(deserializer)?;
+        match String::from_utf16(&units) {
+            Ok(string) => Ok(Self(string)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
 
-#[derive(Default, JtDeserialize)]
-pub struct Mx4F64(pub [f64; 16]);
+macro_rules! impl_mx4 {
+    ($ident:ident, $ty:ty) => {
+        #[derive(Debug, Default, Clone, Copy, PartialEq, JtDeserialize)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+        pub struct $ident(pub [$ty; 16]);
+
+        impl $ident {
+            pub const IDENTITY: Self = Self([
+                1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
+            ]);
+
+            pub fn multiply(&self, other: &Self) -> Self {
+                let mut elements = [0.; 16];
+                for (i, element) in elements.iter_mut().enumerate() {
+                    let (row, column) = (i / 4, i % 4);
+                    *element = (0..4)
+                        .map(|k| self.0[row * 4 + k] * other.0[k * 4 + column])
+                        .sum();
+                }
+                Self(elements)
+            }
+
+            pub fn transpose(&self) -> Self {
+                let mut elements = [0.; 16];
+                for (i, element) in elements.iter_mut().enumerate() {
+                    *element = self.0[(i % 4) * 4 + i / 4];
+                }
+                Self(elements)
+            }
+
+            pub fn invert(&self) -> Option<Self> {
+                Xform::from(self).inverse().map(|xform| Self::from(&xform))
+            }
+
+            pub fn transform_point(&self, point: [$ty; 3]) -> [$ty; 3] {
+                let m = &self.0;
+                let [x, y, z] = point;
+                let w = x * m[3] + y * m[7] + z * m[11] + m[15];
+                let w = if 0. == w { 1. } else { w };
+                [
+                    (x * m[0] + y * m[4] + z * m[8] + m[12]) / w,
+                    (x * m[1] + y * m[5] + z * m[9] + m[13]) / w,
+                    (x * m[2] + y * m[6] + z * m[10] + m[14]) / w,
+                ]
+            }
+
+            pub fn transform_direction(&self, direction: [$ty; 3]) -> [$ty; 3] {
+                let m = &self.0;
+                let [x, y, z] = direction;
+                [
+                    x * m[0] + y * m[4] + z * m[8],
+                    x * m[1] + y * m[5] + z * m[9],
+                    x * m[2] + y * m[6] + z * m[10],
+                ]
+            }
+        }
+
+        impl From<&$ident> for Xform {
+            fn from(matrix: &$ident) -> Self {
+                Xform::from_row_major(&matrix.0.map(f64::from)).transpose()
+            }
+        }
+
+        impl From<&Xform> for $ident {
+            fn from(xform: &Xform) -> Self {
+                Self(xform.transpose().to_row_major().map(|v| v as $ty))
+            }
+        }
+    };
+}
+
+impl_mx4! {Mx4F32, f32}
+impl_mx4! {Mx4F64, f64}
 
 #[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PlaneF32(pub [f32; 4]);
 
-#[derive(Default, JtDeserialize)]
+impl PlaneF32 {
+    pub fn to_plane(&self) -> Option<Plane> {
+        Plane::from_equation(&self.0.map(f64::from))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Quaternion(pub [f32; 4]);
 
+impl Quaternion {
+    pub const IDENTITY: Self = Self([1., 0., 0., 0.]);
+
+    pub fn from_axis_angle(axis: [f32; 3], angle: f32) -> Option<Self> {
+        let length = axis.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if !length.is_normal() {
+            return None;
+        }
+        let (sin, cos) = (0.5 * angle).sin_cos();
+        let [x, y, z] = axis.map(|v| v / length * sin);
+        Some(Self([cos, x, y, z]))
+    }
+
+    pub fn dot(&self, other: &Self) -> f32 {
+        self.0.iter().zip(other.0).map(|(a, b)| a * b).sum()
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Option<Self> {
+        let length = self.length();
+        length.is_normal().then(|| Self(self.0.map(|v| v / length)))
+    }
+
+    pub fn conjugate(&self) -> Self {
+        let [w, x, y, z] = self.0;
+        Self([w, -x, -y, -z])
+    }
+
+    pub fn multiply(&self, other: &Self) -> Self {
+        let [w1, x1, y1, z1] = self.0;
+        let [w2, x2, y2, z2] = other.0;
+        Self([
+            w1 * w2 - x1 * x2 - y1 * y2 - z1 * z2,
+            w1 * x2 + x1 * w2 + y1 * z2 - z1 * y2,
+            w1 * y2 - x1 * z2 + y1 * w2 + z1 * x2,
+            w1 * z2 + x1 * y2 - y1 * x2 + z1 * w2,
+        ])
+    }
+
+    pub fn rotate(&self, vector: [f32; 3]) -> [f32; 3] {
+        let [x, y, z] = vector;
+        let rotated = self
+            .multiply(&Self([0., x, y, z]))
+            .multiply(&self.conjugate());
+        [rotated.0[1], rotated.0[2], rotated.0[3]]
+    }
+
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let mut cos = self.dot(other);
+        let mut other = *other;
+        if 0. > cos {
+            cos = -cos;
+            other = Self(other.0.map(|v| -v));
+        }
+        let (a, b) = if cos > 0.9995 {
+            (1. - t, t)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+            (((1. - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+        let mut elements = [0f32; 4];
+        for (i, element) in elements.iter_mut().enumerate() {
+            *element = a * self.0[i] + b * other.0[i];
+        }
+        let result = Self(elements);
+        result.normalize().unwrap_or(result)
+    }
+
+    pub fn to_matrix(&self) -> Mx4F32 {
+        let [w, x, y, z] = self.normalize().unwrap_or(Self::IDENTITY).0;
+        Mx4F32([
+            1. - 2. * (y * y + z * z),
+            2. * (x * y + w * z),
+            2. * (x * z - w * y),
+            0.,
+            2. * (x * y - w * z),
+            1. - 2. * (x * x + z * z),
+            2. * (y * z + w * x),
+            0.,
+            2. * (x * z + w * y),
+            2. * (y * z - w * x),
+            1. - 2. * (x * x + y * y),
+            0.,
+            0.,
+            0.,
+            0.,
+            1.,
+        ])
+    }
+}
+
 #[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RGB(pub [f32; 3]);
 
 #[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RGBA(pub [f32; 4]);
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+    use std::io::Cursor;
+
+    use crate::jt::reader::Reader;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_mb_string() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(3i32.to_le_bytes());
+        "Näm"
+            .encode_utf16()
+            .for_each(|u| data.extend(u.to_le_bytes()));
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert_eq!(
+            MbString("Näm".to_string()),
+            MbString::deserialize(&mut deserializer).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_mb_string() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1i32.to_le_bytes());
+        data.extend(0xD800u16.to_le_bytes());
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert!(MbString::deserialize(&mut deserializer).is_err());
+    }
+
+    #[test]
+    fn coord_to_point() {
+        assert_eq!(
+            Point3d::new(1., 2.5, -3.),
+            Point3d::from(&CoordF32([1., 2.5, -3.]))
+        );
+        assert_eq!(Vector3d::Y, Vector3d::from(&DirF32([0., 1., 0.])));
+    }
+
+    #[test]
+    fn bbox_set_operations() {
+        let a = BBoxF32::new([0., 0., 0.], [2., 2., 2.]);
+        let b = BBoxF32::new([1., -1., 1.], [3., 1., 4.]);
+        let c = BBoxF32::new([5., 5., 5.], [6., 6., 6.]);
+        let empty = BBoxF32::new([1., 1., 1.], [0., 0., 0.]);
+        assert_eq!(BBoxF32::new([0., -1., 0.], [3., 2., 4.]), a.union(&b));
+        assert_eq!(a, a.union(&empty));
+        assert_eq!(a, empty.union(&a));
+        assert_eq!(
+            Some(BBoxF32::new([1., 0., 1.], [2., 1., 2.])),
+            a.intersection(&b)
+        );
+        assert_eq!(None, a.intersection(&c));
+        assert!(a.contains(&[2., 0., 1.]));
+        assert!(!a.contains(&[2.5, 0., 1.]));
+        assert!(a.union(&b).contains_bbox(&b));
+        assert!(!a.contains_bbox(&b));
+        assert_eq!([2., 0., 2.5], b.center());
+        assert_eq!([2., 2., 3.], b.extent());
+        assert!(empty.is_empty());
+        assert!(!BBoxF32::default().is_empty());
+    }
+
+    #[test]
+    fn bbox_to_bounding_box() {
+        let bbox = BBoxF32::new([0., -1., 0.5], [2., 1., 4.]);
+        assert_eq!(
+            BoundingBox::new(Point3d::new(0., -1., 0.5), Point3d::new(2., 1., 4.)),
+            BoundingBox::from(&bbox)
+        );
+        assert!(BoundingBox::from(&BBoxF32::new([1.; 3], [0.; 3])).is_empty());
+    }
+
+    #[test]
+    fn plane_from_equation() {
+        let plane = PlaneF32([0., 0., 1., -2.]).to_plane().unwrap();
+        assert_eq!(Point3d::new(0., 0., 2.), plane.origin);
+        assert_eq!(Vector3d::Z, plane.normal());
+        assert!(PlaneF32::default().to_plane().is_none());
+    }
+
+    #[test]
+    fn display_guid() {
+        assert_eq!(
+            "10dd1035-2ac8-11d1-9b6b-0080c7bb5997",
+            GUID(
+                0x10DD1035,
+                [0x2AC8, 0x11D1],
+                [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97]
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn guid_from_str() {
+        let guid = GUID(
+            0xCE357245,
+            [0x38FB, 0x11D1],
+            [0xA5, 0x06, 0x00, 0x60, 0x97, 0xBD, 0xC6, 0xE1],
+        );
+        assert_eq!(Ok(guid), "ce357245-38fb-11d1-a506-006097bdc6e1".parse());
+        assert_eq!(Ok(guid), "{CE357245-38FB-11D1-A506-006097BDC6E1}".parse());
+        assert_eq!(Ok(guid), guid.to_string().parse());
+        assert!("ce357245-38fb-11d1-a506-006097bdc6e"
+            .parse::<GUID>()
+            .is_err());
+        assert!("ce357245-38fb-11d1-a506+006097bdc6e1"
+            .parse::<GUID>()
+            .is_err());
+        assert!("ce357245-38fb-11d1-a506-006097bdc6eg"
+            .parse::<GUID>()
+            .is_err());
+        assert!("+e357245-38fb-11d1-a506-006097bdc6e1"
+            .parse::<GUID>()
+            .is_err());
+    }
+
+    fn assert_close(expected: &[f32], actual: &[f32]) {
+        assert!(
+            expected
+                .iter()
+                .zip(actual)
+                .all(|(e, a)| (e - a).abs() < 1e-6),
+            "{:?} != {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn quaternion_rotation() {
+        let quarter = Quaternion::from_axis_angle([0., 0., 2.], FRAC_PI_2).unwrap();
+        assert_close(&[0., 1., 0.], &quarter.rotate([1., 0., 0.]));
+        let half = quarter.multiply(&quarter);
+        assert_close(&[-1., 0., 0.], &half.rotate([1., 0., 0.]));
+        assert_close(
+            &Quaternion::IDENTITY.0,
+            &quarter.multiply(&quarter.conjugate()).0,
+        );
+        assert_eq!(None, Quaternion::from_axis_angle([0.; 3], 1.));
+        assert_eq!(None, Quaternion::default().normalize());
+        assert_close(
+            &[0.5, 0.5, 0.5, 0.5],
+            &Quaternion([2., 2., 2., 2.]).normalize().unwrap().0,
+        );
+    }
+
+    #[test]
+    fn quaternion_slerp() {
+        let quarter = Quaternion::from_axis_angle([0., 0., 1.], FRAC_PI_2).unwrap();
+        let eighth = Quaternion::IDENTITY.slerp(&quarter, 0.5);
+        assert_close(
+            &Quaternion::from_axis_angle([0., 0., 1.], FRAC_PI_4)
+                .unwrap()
+                .0,
+            &eighth.0,
+        );
+        assert_close(&quarter.0, &Quaternion::IDENTITY.slerp(&quarter, 1.).0);
+        let negated = Quaternion(quarter.0.map(|v| -v));
+        assert_close(&eighth.0, &Quaternion::IDENTITY.slerp(&negated, 0.5).0);
+    }
+
+    #[test]
+    fn quaternion_to_matrix() {
+        let quarter = Quaternion::from_axis_angle([0., 0., 1.], FRAC_PI_2).unwrap();
+        let mut translation = Mx4F32::IDENTITY;
+        translation.0[12] = 5.;
+        let matrix = quarter.to_matrix().multiply(&translation);
+        let point = [1f32, 0., 0., 1.];
+        let transformed: Vec<f32> = (0..4)
+            .map(|c| (0..4).map(|r| point[r] * matrix.0[r * 4 + c]).sum())
+            .collect();
+        assert_close(&[5., 1., 0., 1.], &transformed);
+        assert_eq!(Mx4F32::IDENTITY, Quaternion::IDENTITY.to_matrix());
+    }
+
+    #[test]
+    fn invert_mx4() {
+        let mut matrix = Mx4F64::IDENTITY;
+        matrix.0[0] = 2.;
+        matrix.0[5] = 4.;
+        matrix.0[12..15].copy_from_slice(&[1., 2., 3.]);
+        let inverse = matrix.invert().unwrap();
+        assert_eq!(Mx4F64::IDENTITY, matrix.multiply(&inverse));
+        assert_eq!(
+            [0., 0., 0.],
+            inverse.transform_point(matrix.transform_point([0.; 3]))
+        );
+        assert_eq!(None, Mx4F32::default().invert());
+        let matrix = Mx4F32(matrix.0.map(|v| v as f32));
+        assert_eq!(Mx4F32::IDENTITY, matrix.multiply(&matrix.invert().unwrap()));
+    }
+
+    #[test]
+    fn transform_mx4() {
+        let mut matrix = Mx4F32::IDENTITY;
+        matrix.0[12..15].copy_from_slice(&[1., 2., 3.]);
+        matrix.0[0] = 2.;
+        assert_eq!([3., 3., 4.], matrix.transform_point([1., 1., 1.]));
+        assert_eq!([2., 1., 1.], matrix.transform_direction([1., 1., 1.]));
+        assert_eq!([1., 2., 3.], [3, 7, 11].map(|i| matrix.transpose().0[i]));
+        assert_eq!(matrix, matrix.transpose().transpose());
+        let xform = Xform::from(&matrix);
+        assert_eq!(
+            Point3d::new(3., 3., 4.),
+            xform.transform_point(&Point3d::new(1., 1., 1.))
+        );
+        assert_eq!(matrix, Mx4F32::from(&xform));
+        assert_eq!(Xform::from(&Mx4F64(matrix.0.map(f64::from))), xform);
+    }
+
+    #[test]
+    fn multiply_mx4_f32() {
+        let mut translation = Mx4F32::IDENTITY;
+        translation.0[12..15].copy_from_slice(&[1., 2., 3.]);
+        let mut scale = Mx4F32::IDENTITY;
+        scale.0[0] = 2.;
+        scale.0[5] = 2.;
+        scale.0[10] = 2.;
+        assert_eq!(translation, translation.multiply(&Mx4F32::IDENTITY));
+        assert_eq!([2., 4., 6.], translation.multiply(&scale).0[12..15]);
+        assert_eq!([1., 2., 3.], scale.multiply(&translation).0[12..15]);
+    }
+}
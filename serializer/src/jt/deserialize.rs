@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use super::deserializer::Deserializer;
+use super::{deserializer::Deserializer, error::Error};
 
 pub trait Deserialize
 where
@@ -19,16 +19,13 @@ macro_rules! impl_deserialize_for_number {
         where
             Self: Sized,
         {
-            type Error = String;
+            type Error = Error;
 
             fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
             where
                 D: Deserializer,
             {
-                match deserializer.$method() {
-                    Ok(v) => Ok(v),
-                    Err(e) => Err(e.to_string()),
-                }
+                Ok(deserializer.$method()?)
             }
         }
     };
@@ -51,7 +48,7 @@ impl Deserialize for String
 where
     Self: Sized,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
     where
@@ -59,63 +56,77 @@ where
     {
         let length = i32::deserialize(deserializer)?;
         if 0 > length {
-            Err("invalid string length".to_string())
+            Err(Error::InvalidLength("string"))
         } else {
             let mut string = String::new();
-            match deserializer.take(length as u64).read_to_string(&mut string) {
-                Ok(size) => {
-                    if size as u64 == length as u64 {
-                        Ok(string)
-                    } else {
-                        Err("invalid string length".to_string())
-                    }
-                }
-                Err(e) => Err(format!("{}", e)),
+            let size = deserializer
+                .take(length as u64)
+                .read_to_string(&mut string)?;
+            if size as u64 == length as u64 {
+                Ok(string)
+            } else {
+                Err(Error::InvalidLength("string"))
             }
         }
     }
 }
 
-impl<T> Deserialize for Vec<T>
+pub fn deserialize_vec_with_length<L, T, D>(
+    deserializer: &mut D,
+) -> Result<Vec<T>, <T as Deserialize>::Error>
 where
+    L: Deserialize,
+    usize: TryFrom<L>,
     T: Deserialize,
-    String: From<<T as Deserialize>::Error>,
+    <T as Deserialize>::Error: From<<L as Deserialize>::Error> + From<Error>,
+    D: Deserializer,
 {
-    type Error = String;
-
-    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
-    where
-        D: Deserializer,
-    {
-        let length = i32::deserialize(deserializer)?;
-        if 0 > length {
-            Err("invalid vector length".to_string())
-        } else {
+    match usize::try_from(L::deserialize(deserializer)?) {
+        Ok(length) => {
             let mut vector: Vec<T> = vec![];
             for _ in 0..length {
                 vector.push(T::deserialize(deserializer)?);
             }
             Ok(vector)
         }
+        Err(_) => Err(Error::InvalidLength("vector").into()),
+    }
+}
+
+impl<T> Deserialize for Vec<T>
+where
+    T: Deserialize,
+    <T as Deserialize>::Error: From<Error>,
+{
+    type Error = <T as Deserialize>::Error;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        deserialize_vec_with_length::<i32, T, D>(deserializer)
     }
 }
 
 impl<T, const N: usize> Deserialize for [T; N]
 where
-    T: Deserialize + Default + Copy,
-    String: From<<T as Deserialize>::Error>,
+    T: Deserialize,
+    <T as Deserialize>::Error: From<Error>,
 {
-    type Error = String;
+    type Error = <T as Deserialize>::Error;
 
     fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
     where
         D: Deserializer,
     {
-        let mut rv: Self = [T::default(); N];
-        for i in 0..N {
-            rv[i] = T::deserialize(deserializer)?;
+        let mut values: Vec<T> = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(T::deserialize(deserializer)?);
+        }
+        match values.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => Err(Error::InvalidLength("array").into()),
         }
-        Ok(rv)
     }
 }
 
@@ -123,16 +134,263 @@ where
 mod tests {
     use std::io::Cursor;
 
-    use crate::common::reader::{BigEndianNumberReader, LittleEndianNumberReader};
+    use geometria_derive::JtDeserialize;
+
+    use crate::jt::{
+        reader::{ByteOrder, Reader},
+        version::Version,
+    };
 
     use super::*;
 
+    #[derive(JtDeserialize)]
+    struct Cached {
+        value: u16,
+        #[skip]
+        cache: Vec<u16>,
+        next: u16,
+    }
+
+    #[derive(JtDeserialize)]
+    struct CachedPair(u8, #[skip] Option<u8>, u8);
+
+    #[derive(JtDeserialize)]
+    struct Versioned {
+        value: u8,
+        #[file_version(Version::V9_0..)]
+        bindings: u16,
+        #[file_version(..Version::V9_0)]
+        #[default_value(-1)]
+        legacy: i8,
+    }
+
+    #[derive(JtDeserialize)]
+    struct OptionalVersioned {
+        #[file_version(Version::V9_0..)]
+        value: Option<u8>,
+    }
+
+    #[derive(JtDeserialize)]
+    struct Aligned {
+        tag: u8,
+        #[align(4)]
+        value: u32,
+        #[align(8)]
+        next: u8,
+    }
+
+    #[derive(JtDeserialize)]
+    struct Scaled {
+        value: u8,
+        #[skip]
+        #[default_value(1.5)]
+        scale: f32,
+    }
+
+    #[derive(JtDeserialize)]
+    struct Magic {
+        #[expected(0x4A54)]
+        magic: u16,
+        value: u8,
+    }
+
+    #[derive(JtDeserialize)]
+    struct MagicPair(#[expected([1, 2])] [u8; 2], u8);
+
+    #[derive(Debug, PartialEq)]
+    struct MagicError(String);
+
+    impl From<String> for MagicError {
+        fn from(message: String) -> Self {
+            Self(message)
+        }
+    }
+
+    impl From<Error> for MagicError {
+        fn from(error: Error) -> Self {
+            Self(error.to_string())
+        }
+    }
+
+    #[derive(JtDeserialize)]
+    #[error_type(MagicError)]
+    struct TypedMagic {
+        #[expected(0x4A54)]
+        magic: u16,
+        value: u8,
+    }
+
+    #[derive(JtDeserialize)]
+    struct Flagged {
+        #[deserialize_with = "deserialize_flag"]
+        flag: bool,
+        value: u8,
+    }
+
+    #[derive(JtDeserialize)]
+    struct ShortVectors {
+        #[length(u8)]
+        bytes: Vec<u8>,
+        #[length(u16)]
+        values: Vec<u16>,
+    }
+
+    fn deserialize_flag<D>(deserializer: &mut D) -> Result<bool, String>
+    where
+        D: Deserializer,
+    {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err("invalid flag".to_string()),
+        }
+    }
+
+    #[test]
+    fn deserialize_skipped_field() {
+        let mut deserializer = Reader::new(Cursor::new([1u8, 0u8, 2u8, 0u8, 3u8, 4u8]));
+        let cached = Cached::deserialize(&mut deserializer).unwrap();
+        assert_eq!(1, cached.value);
+        assert!(cached.cache.is_empty());
+        assert_eq!(2, cached.next);
+        let pair = CachedPair::deserialize(&mut deserializer).unwrap();
+        assert_eq!((3, None, 4), (pair.0, pair.1, pair.2));
+    }
+
+    #[test]
+    fn deserialize_skipped_field_with_default() {
+        let mut deserializer = Reader::new(Cursor::new([3u8]));
+        let scaled = Scaled::deserialize(&mut deserializer).unwrap();
+        assert_eq!((3, 1.5), (scaled.value, scaled.scale));
+    }
+
+    #[test]
+    fn deserialize_file_version_gated_fields() {
+        let mut deserializer = Reader::new(Cursor::new([1u8, 2, 3, 4, 0]));
+        let versioned = Versioned::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            (1, 0, 2),
+            (versioned.value, versioned.bindings, versioned.legacy)
+        );
+        deserializer.version = Version::V10_0;
+        let versioned = Versioned::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            (3, 4, -1),
+            (versioned.value, versioned.bindings, versioned.legacy)
+        );
+    }
+
+    #[test]
+    fn deserialize_aligned_fields() {
+        let mut data = vec![1u8, 0xFF, 0xFF, 0xFF];
+        data.extend(2u32.to_le_bytes());
+        data.push(3);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let aligned = Aligned::deserialize(&mut deserializer).unwrap();
+        assert_eq!((1, 2, 3), (aligned.tag, aligned.value, aligned.next));
+    }
+
+    #[test]
+    fn deserialize_non_copy_array() {
+        let mut data: Vec<u8> = vec![];
+        for text in ["ab", "c"] {
+            data.extend((text.len() as i32).to_le_bytes());
+            data.extend(text.as_bytes());
+        }
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert_eq!(
+            ["ab".to_string(), "c".to_string()],
+            <[String; 2]>::deserialize(&mut deserializer).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_file_version_gated_option() {
+        let mut deserializer = Reader::new(Cursor::new([0u8]));
+        let versioned = OptionalVersioned::deserialize(&mut deserializer).unwrap();
+        assert_eq!(None, versioned.value);
+        deserializer.version = Version::V9_0;
+        let versioned = OptionalVersioned::deserialize(&mut deserializer).unwrap();
+        assert_eq!(Some(0), versioned.value);
+    }
+
+    #[test]
+    fn deserialize_expected_value() {
+        let mut deserializer = Reader::new(Cursor::new([0x54u8, 0x4A, 7, 1, 2, 8]));
+        let magic = Magic::deserialize(&mut deserializer).unwrap();
+        assert_eq!((0x4A54, 7), (magic.magic, magic.value));
+        let pair = MagicPair::deserialize(&mut deserializer).unwrap();
+        assert_eq!(([1, 2], 8), (pair.0, pair.1));
+    }
+
+    #[test]
+    fn deserialize_unexpected_value() {
+        let mut deserializer = Reader::new(Cursor::new([0x55u8, 0x4A, 7]));
+        assert_eq!(
+            Some("invalid magic".to_string()),
+            Magic::deserialize(&mut deserializer).err()
+        );
+        let mut deserializer = Reader::new(Cursor::new([1u8, 3, 8]));
+        assert_eq!(
+            Some("invalid 0".to_string()),
+            MagicPair::deserialize(&mut deserializer).err()
+        );
+    }
+
+    #[test]
+    fn deserialize_custom_error_type() {
+        let mut deserializer = Reader::new(Cursor::new([0x54u8, 0x4A, 7, 0x55, 0x4A, 7]));
+        let magic = TypedMagic::deserialize(&mut deserializer).unwrap();
+        assert_eq!((0x4A54, 7), (magic.magic, magic.value));
+        assert_eq!(
+            Some(MagicError("invalid magic".to_string())),
+            TypedMagic::deserialize(&mut deserializer).err()
+        );
+    }
+
+    #[test]
+    fn deserialize_structured_error() {
+        let mut deserializer = Reader::new(Cursor::new((-1i32).to_le_bytes()));
+        assert!(matches!(
+            String::deserialize(&mut deserializer),
+            Err(Error::InvalidLength("string"))
+        ));
+        let mut deserializer = Reader::new(Cursor::new([1u8]));
+        assert!(matches!(
+            u16::deserialize(&mut deserializer),
+            Err(Error::Io(_))
+        ));
+        let mut deserializer = Reader::new(Cursor::new([2u8, 7]));
+        assert!(matches!(
+            ShortVectors::deserialize(&mut deserializer),
+            Err(message) if message.contains("failed to fill whole buffer")
+        ));
+    }
+
+    #[test]
+    fn deserialize_with_function() {
+        let mut deserializer = Reader::new(Cursor::new([1u8, 5, 2, 6]));
+        let flagged = Flagged::deserialize(&mut deserializer).unwrap();
+        assert_eq!((true, 5), (flagged.flag, flagged.value));
+        assert_eq!(
+            Some("invalid flag".to_string()),
+            Flagged::deserialize(&mut deserializer).err()
+        );
+    }
+
+    #[test]
+    fn deserialize_custom_length_vectors() {
+        let mut deserializer = Reader::new(Cursor::new([2u8, 7, 8, 1, 0, 9, 0]));
+        let vectors = ShortVectors::deserialize(&mut deserializer).unwrap();
+        assert_eq!(vec![7, 8], vectors.bytes);
+        assert_eq!(vec![9], vectors.values);
+    }
+
     #[test]
     fn deserialize_u8() {
         let data = 11u8.to_le_bytes();
-        let mut reader = BigEndianNumberReader {
-            source: Cursor::new(data),
-        };
+        let mut reader = Reader::new(Cursor::new(data));
+        reader.set_byte_order(ByteOrder::BigEndian);
         assert_eq!(11u8, u8::deserialize(&mut reader).unwrap());
     }
 
@@ -141,9 +399,8 @@ mod tests {
             #[test]
             fn $test_name() {
                 let data = $value.to_be_bytes();
-                let mut deserializer = BigEndianNumberReader {
-                    source: Cursor::new(data),
-                };
+                let mut deserializer = Reader::new(Cursor::new(data));
+                deserializer.set_byte_order(ByteOrder::BigEndian);
                 assert_eq!($value, <$type>::deserialize(&mut deserializer).unwrap());
             }
         };
@@ -154,9 +411,7 @@ mod tests {
             #[test]
             fn $test_name() {
                 let data = $value.to_le_bytes();
-                let mut deserializer = LittleEndianNumberReader {
-                    source: Cursor::new(data),
-                };
+                let mut deserializer = Reader::new(Cursor::new(data));
                 assert_eq!($value, <$type>::deserialize(&mut deserializer).unwrap());
             }
         };
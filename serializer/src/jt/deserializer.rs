@@ -2,7 +2,14 @@ use std::io::{Read, Seek};
 
 use crate::common::reader::{BigEndianNumberReader, LittleEndianNumberReader, NumberReader};
 
-pub trait Deserializer: NumberReader + Read + Seek {}
+use super::{reader::ByteOrder, version::Version};
+
+pub trait Deserializer: NumberReader + Read + Seek {
+    fn version(&self) -> Version;
+    fn set_version(&mut self, version: Version);
+    fn byte_order(&self) -> ByteOrder;
+    fn set_byte_order(&mut self, byte_order: ByteOrder);
+}
 
 impl<T> Read for BigEndianNumberReader<T>
 where
@@ -22,8 +29,6 @@ where
     }
 }
 
-impl<T> Deserializer for BigEndianNumberReader<T> where T: Read + Seek {}
-
 impl<T> Read for LittleEndianNumberReader<T>
 where
     T: Read,
@@ -41,5 +46,3 @@ where
         self.source.seek(pos)
     }
 }
-
-impl<T> Deserializer for LittleEndianNumberReader<T> where T: Read + Seek {}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    InvalidLength(&'static str),
+    Other(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::InvalidLength(name) => write!(f, "invalid {} length", name),
+            Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "invalid string length",
+            Error::InvalidLength("string").to_string()
+        );
+        assert_eq!("unknown", Error::from("unknown".to_string()).to_string());
+        assert_eq!(
+            "invalid vector length",
+            String::from(Error::InvalidLength("vector"))
+        );
+    }
+
+    #[test]
+    fn io_source() {
+        let error = Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        assert!(matches!(error, Error::Io(_)));
+        assert!(error.source().is_some());
+        assert!(Error::InvalidLength("array").source().is_none());
+    }
+}
@@ -1,9 +1,152 @@
-use super::common::*;
+use super::{
+    common::*, deserialize::Deserialize, deserializer::Deserializer, reader::ByteOrder,
+    version::Version,
+};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Header {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_version"))]
     version: [u8; 80],
-    byte_order: u8,
+    byte_order: ByteOrder,
     empty_field: i32,
     toc_offset: u64,
     lsg_segment_id: GUID,
 }
+
+#[cfg(feature = "serde")]
+fn serialize_version<S>(version: &[u8; 80], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let version = String::from_utf8_lossy(version);
+    serializer.serialize_str(version.trim_end_matches(|c: char| c == '\0' || c.is_whitespace()))
+}
+
+impl Header {
+    pub fn version(&self) -> Result<Version, String> {
+        Version::try_from(&self.version[..])
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    pub fn empty_field(&self) -> i32 {
+        self.empty_field
+    }
+
+    pub fn toc_offset(&self) -> u64 {
+        self.toc_offset
+    }
+
+    pub fn lsg_segment_id(&self) -> GUID {
+        self.lsg_segment_id
+    }
+}
+
+impl Deserialize for Header {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let version = <[u8; 80]>::deserialize(deserializer)?;
+        deserializer.set_version(Version::try_from(&version[..])?);
+        let byte_order = ByteOrder::try_from(u8::deserialize(deserializer)?)?;
+        deserializer.set_byte_order(byte_order);
+        let empty_field = i32::deserialize(deserializer)?;
+        let toc_offset = if Version::V10_0 <= deserializer.version() {
+            u64::deserialize(deserializer)?
+        } else {
+            match u32::try_from(i32::deserialize(deserializer)?) {
+                Ok(offset) => offset as u64,
+                Err(_) => return Err("invalid toc offset".to_string()),
+            }
+        };
+        Ok(Self {
+            version,
+            byte_order,
+            empty_field,
+            toc_offset,
+            lsg_segment_id: GUID::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::jt::reader::Reader;
+
+    use super::*;
+
+    fn header_data(version: &str, toc_offset: &[u8]) -> Vec<u8> {
+        let mut data = [b' '; 80].to_vec();
+        data[..version.len()].copy_from_slice(version.as_bytes());
+        data.push(0u8);
+        data.extend(0i32.to_le_bytes());
+        data.extend(toc_offset);
+        data.extend(7u32.to_le_bytes());
+        data.extend([0u8; 12]);
+        data
+    }
+
+    #[test]
+    fn deserialize_v8_header() {
+        let mut deserializer = Reader::new(Cursor::new(header_data(
+            "Version 8.1 JT",
+            &1024i32.to_le_bytes(),
+        )));
+        let header = Header::deserialize(&mut deserializer).unwrap();
+        assert_eq!(Ok(Version::V8_1), header.version());
+        assert_eq!(Version::V8_1, deserializer.version());
+        assert_eq!(1024, header.toc_offset());
+        assert_eq!(7, header.lsg_segment_id().0);
+    }
+
+    #[test]
+    fn deserialize_v10_header() {
+        let mut deserializer = Reader::new(Cursor::new(header_data(
+            "Version 10.0 JT",
+            &(1u64 << 33).to_le_bytes(),
+        )));
+        let header = Header::deserialize(&mut deserializer).unwrap();
+        assert_eq!(Version::V10_0, deserializer.version());
+        assert_eq!(1u64 << 33, header.toc_offset());
+        assert_eq!(7, header.lsg_segment_id().0);
+    }
+
+    #[test]
+    fn deserialize_big_endian_header() {
+        let mut data = [b' '; 80].to_vec();
+        data[..14].copy_from_slice(b"Version 9.5 JT");
+        data.push(1u8);
+        data.extend(0i32.to_be_bytes());
+        data.extend(2048i32.to_be_bytes());
+        data.extend(7u32.to_be_bytes());
+        data.extend([0u8; 12]);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let header = Header::deserialize(&mut deserializer).unwrap();
+        assert_eq!(ByteOrder::BigEndian, header.byte_order());
+        assert_eq!(ByteOrder::BigEndian, deserializer.byte_order());
+        assert_eq!(2048, header.toc_offset());
+        assert_eq!(7, header.lsg_segment_id().0);
+    }
+
+    #[test]
+    fn deserialize_header_with_invalid_byte_order() {
+        let mut data = header_data("Version 8.1 JT", &0i32.to_le_bytes());
+        data[80] = 2;
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert!(Header::deserialize(&mut deserializer).is_err());
+    }
+
+    #[test]
+    fn deserialize_header_with_invalid_version() {
+        let mut deserializer =
+            Reader::new(Cursor::new(header_data("JT file", &0i32.to_le_bytes())));
+        assert!(Header::deserialize(&mut deserializer).is_err());
+    }
+}
@@ -0,0 +1,748 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, SeekFrom};
+
+use crate::math::Xform;
+
+use super::{
+    attribute::{BaseAttributeData, GeometricTransformAttribute},
+    common::Mx4F32,
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    metadata::Metadata,
+    node::{
+        BaseNodeData, GroupNodeData, InstanceNode, LodNodeData, MetaDataNodeData, PartNode,
+        PartitionNode, PolylineSetShapeNode, RangeLodNode, SwitchNode, TriStripSetShapeNode,
+    },
+    object_type::{self, ObjectTypeId},
+    property::{PropertyAtom, PropertyTable},
+    reader::Reader,
+    segment::{ElementHeader, ElementHeaderZlib, SegmentHeader, SegmentType},
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Node {
+    Partition(PartitionNode),
+    Group(GroupNodeData),
+    Instance(InstanceNode),
+    Part(PartNode),
+    MetaData(MetaDataNodeData),
+    Lod(LodNodeData),
+    RangeLod(RangeLodNode),
+    Switch(SwitchNode),
+    TriStripSetShape(TriStripSetShapeNode),
+    PolylineSetShape(PolylineSetShapeNode),
+    Other(ObjectTypeId, GroupNodeData),
+}
+
+impl Node {
+    pub fn base_node_data(&self) -> &BaseNodeData {
+        match self {
+            Self::Partition(node) => &node.group_node_data.base_node_data,
+            Self::Group(node) | Self::Other(_, node) => &node.base_node_data,
+            Self::Instance(node) => &node.base_node_data,
+            Self::Part(node) => &node.meta_data_node_data.group_node_data.base_node_data,
+            Self::MetaData(node) => &node.group_node_data.base_node_data,
+            Self::Lod(node) => &node.group_node_data.base_node_data,
+            Self::RangeLod(node) => &node.lod_node_data.group_node_data.base_node_data,
+            Self::Switch(node) => &node.group_node_data.base_node_data,
+            Self::TriStripSetShape(node) => {
+                &node
+                    .vertex_shape_node_data
+                    .base_shape_node_data
+                    .base_node_data
+            }
+            Self::PolylineSetShape(node) => {
+                &node
+                    .vertex_shape_node_data
+                    .base_shape_node_data
+                    .base_node_data
+            }
+        }
+    }
+
+    pub fn object_id(&self) -> i32 {
+        self.base_node_data().object_id
+    }
+
+    pub fn is_shape(&self) -> bool {
+        matches!(self, Self::TriStripSetShape(_) | Self::PolylineSetShape(_))
+    }
+
+    pub fn children(&self) -> &[i32] {
+        match self {
+            Self::Partition(node) => &node.group_node_data.child_node_object_ids,
+            Self::Group(node) | Self::Other(_, node) => &node.child_node_object_ids,
+            Self::Instance(node) => std::slice::from_ref(&node.child_node_object_id),
+            Self::Part(node) => {
+                &node
+                    .meta_data_node_data
+                    .group_node_data
+                    .child_node_object_ids
+            }
+            Self::MetaData(node) => &node.group_node_data.child_node_object_ids,
+            Self::Lod(node) => &node.group_node_data.child_node_object_ids,
+            Self::RangeLod(node) => &node.lod_node_data.group_node_data.child_node_object_ids,
+            Self::Switch(node) => &node.group_node_data.child_node_object_ids,
+            Self::TriStripSetShape(_) | Self::PolylineSetShape(_) => &[],
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Attribute {
+    GeometricTransform(GeometricTransformAttribute),
+    Other(ObjectTypeId, BaseAttributeData),
+}
+
+impl Attribute {
+    pub fn base_attribute_data(&self) -> &BaseAttributeData {
+        match self {
+            Self::GeometricTransform(attribute) => &attribute.base_attribute_data,
+            Self::Other(_, attribute) => attribute,
+        }
+    }
+
+    pub fn object_id(&self) -> i32 {
+        self.base_attribute_data().object_id
+    }
+
+    pub fn object_type_id(&self) -> ObjectTypeId {
+        match self {
+            Self::GeometricTransform(_) => object_type::GEOMETRIC_TRANSFORM_ATTRIBUTE,
+            Self::Other(object_type_id, _) => *object_type_id,
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        0 != self.base_attribute_data().state_flags & Self::ACCUMULATION_FINAL
+    }
+
+    fn is_forced(&self) -> bool {
+        0 != self.base_attribute_data().state_flags & Self::ACCUMULATION_FORCE
+    }
+
+    const ACCUMULATION_FINAL: u8 = 0x1;
+    const ACCUMULATION_FORCE: u8 = 0x2;
+}
+
+enum GraphElement {
+    Node(Node),
+    Attribute(Attribute),
+    Unknown,
+}
+
+impl GraphElement {
+    const GROUP_GRAPH_NODE: u8 = 1;
+    const BASE_ATTRIBUTE: u8 = 3;
+
+    fn deserialize_with_header<D>(
+        deserializer: &mut D,
+        header: &ElementHeader,
+    ) -> Result<Self, String>
+    where
+        D: Deserializer,
+    {
+        Ok(match header.object_type_id {
+            object_type::PARTITION_NODE => {
+                Self::Node(Node::Partition(PartitionNode::deserialize(deserializer)?))
+            }
+            object_type::GROUP_NODE => {
+                Self::Node(Node::Group(GroupNodeData::deserialize(deserializer)?))
+            }
+            object_type::INSTANCE_NODE => {
+                Self::Node(Node::Instance(InstanceNode::deserialize(deserializer)?))
+            }
+            object_type::PART_NODE => Self::Node(Node::Part(PartNode::deserialize(deserializer)?)),
+            object_type::META_DATA_NODE => {
+                Self::Node(Node::MetaData(MetaDataNodeData::deserialize(deserializer)?))
+            }
+            object_type::LOD_NODE => Self::Node(Node::Lod(LodNodeData::deserialize(deserializer)?)),
+            object_type::RANGE_LOD_NODE => {
+                Self::Node(Node::RangeLod(RangeLodNode::deserialize(deserializer)?))
+            }
+            object_type::SWITCH_NODE => {
+                Self::Node(Node::Switch(SwitchNode::deserialize(deserializer)?))
+            }
+            object_type::TRI_STRIP_SET_SHAPE_NODE => Self::Node(Node::TriStripSetShape(
+                TriStripSetShapeNode::deserialize(deserializer)?,
+            )),
+            object_type::POLYLINE_SET_SHAPE_NODE => Self::Node(Node::PolylineSetShape(
+                PolylineSetShapeNode::deserialize(deserializer)?,
+            )),
+            object_type::GEOMETRIC_TRANSFORM_ATTRIBUTE => {
+                Self::Attribute(Attribute::GeometricTransform(
+                    GeometricTransformAttribute::deserialize(deserializer)?,
+                ))
+            }
+            _ => match header.object_base_type {
+                Self::GROUP_GRAPH_NODE => Self::Node(Node::Other(
+                    header.object_type_id,
+                    GroupNodeData::deserialize(deserializer)?,
+                )),
+                Self::BASE_ATTRIBUTE => Self::Attribute(Attribute::Other(
+                    header.object_type_id,
+                    BaseAttributeData::deserialize(deserializer)?,
+                )),
+                _ => Self::Unknown,
+            },
+        })
+    }
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Lsg {
+    pub nodes: HashMap<i32, Node>,
+    pub attributes: HashMap<i32, Attribute>,
+    pub root_node_id: Option<i32>,
+    pub metadata: Metadata,
+}
+
+impl Lsg {
+    pub const MAX_VISIT_COUNT: usize = 1 << 20;
+
+    pub fn node(&self, object_id: i32) -> Option<&Node> {
+        self.nodes.get(&object_id)
+    }
+
+    pub fn attribute(&self, object_id: i32) -> Option<&Attribute> {
+        self.attributes.get(&object_id)
+    }
+
+    pub fn root(&self) -> Option<&Node> {
+        self.root_node_id.and_then(|id| self.node(id))
+    }
+
+    pub fn traverse(&self) -> Traversal<'_> {
+        Traversal {
+            lsg: self,
+            stack: self
+                .root_node_id
+                .map(|id| (id, 0, Scope::default()))
+                .into_iter()
+                .collect(),
+            path: Path::default(),
+            visit_count: 0,
+        }
+    }
+
+    pub fn flatten(&self) -> Result<Vec<PlacedShape<'_>>, String> {
+        let mut shapes: Vec<PlacedShape<'_>> = vec![];
+        let mut path = Path::default();
+        let mut visit_count = 0usize;
+        let mut stack: Vec<(i32, usize, Scope<'_>)> = self
+            .root_node_id
+            .map(|id| (id, 0, Scope::default()))
+            .into_iter()
+            .collect();
+        while let Some((object_id, depth, mut scope)) = stack.pop() {
+            path.truncate(depth);
+            let node = match self.node(object_id) {
+                Some(node) if !path.contains(object_id) => node,
+                _ => continue,
+            };
+            visit_count += 1;
+            if visit_count > Self::MAX_VISIT_COUNT {
+                return Err("too many lsg node visits".to_string());
+            }
+            node.base_node_data()
+                .attribute_object_ids
+                .iter()
+                .filter_map(|id| self.attribute(*id))
+                .for_each(|attribute| scope.apply(attribute));
+            path.push(object_id);
+            if node.is_shape() {
+                shapes.push(PlacedShape {
+                    node,
+                    path: path.ids.clone(),
+                    world_transform: scope.transform,
+                    attributes: scope.attributes,
+                });
+                continue;
+            }
+            let children = match node {
+                Node::Lod(_) | Node::RangeLod(_) => {
+                    &node.children()[..node.children().len().min(1)]
+                }
+                Node::Switch(switch) => usize::try_from(switch.selected_child)
+                    .ok()
+                    .and_then(|i| node.children().get(i))
+                    .map_or(&[][..], std::slice::from_ref),
+                _ => node.children(),
+            };
+            children
+                .iter()
+                .rev()
+                .for_each(|child| stack.push((*child, depth + 1, scope.clone())));
+        }
+        Ok(shapes)
+    }
+
+    fn position<D>(deserializer: &mut D) -> Result<u64, String>
+    where
+        D: Deserializer,
+    {
+        deserializer.stream_position().map_err(|e| e.to_string())
+    }
+
+    fn seek<D>(deserializer: &mut D, position: u64) -> Result<(), String>
+    where
+        D: Deserializer,
+    {
+        match deserializer.seek(SeekFrom::Start(position)) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+impl Deserialize for Lsg {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut lsg = Self::default();
+        loop {
+            let element_begin = Self::position(deserializer)?;
+            let header = ElementHeader::deserialize(deserializer)?;
+            if object_type::END_OF_ELEMENTS == header.object_type_id {
+                break;
+            }
+            if 0 > header.element_length {
+                return Err("invalid element length".to_string());
+            }
+            match GraphElement::deserialize_with_header(deserializer, &header)? {
+                GraphElement::Node(node) => {
+                    lsg.root_node_id.get_or_insert(node.object_id());
+                    lsg.nodes.insert(node.object_id(), node);
+                }
+                GraphElement::Attribute(attribute) => {
+                    lsg.attributes.insert(attribute.object_id(), attribute);
+                }
+                GraphElement::Unknown => {}
+            }
+            Self::seek(
+                deserializer,
+                element_begin + 4 + header.element_length as u64,
+            )?;
+        }
+        let mut atoms: Vec<PropertyAtom> = vec![];
+        loop {
+            let element_begin = Self::position(deserializer)?;
+            let header = ElementHeader::deserialize(deserializer)?;
+            if object_type::END_OF_ELEMENTS == header.object_type_id {
+                break;
+            }
+            Self::seek(deserializer, element_begin)?;
+            atoms.push(PropertyAtom::deserialize(deserializer)?);
+        }
+        lsg.metadata = Metadata::new(atoms, PropertyTable::deserialize(deserializer)?);
+        Ok(lsg)
+    }
+}
+
+#[derive(Default)]
+struct Path {
+    ids: Vec<i32>,
+    set: HashSet<i32>,
+}
+
+impl Path {
+    fn truncate(&mut self, depth: usize) {
+        while self.ids.len() > depth {
+            if let Some(id) = self.ids.pop() {
+                self.set.remove(&id);
+            }
+        }
+    }
+
+    fn contains(&self, object_id: i32) -> bool {
+        self.set.contains(&object_id)
+    }
+
+    fn push(&mut self, object_id: i32) {
+        self.ids.push(object_id);
+        self.set.insert(object_id);
+    }
+}
+
+#[derive(Clone)]
+struct Scope<'a> {
+    transform: Mx4F32,
+    attributes: Vec<&'a Attribute>,
+}
+
+impl<'a> Default for Scope<'a> {
+    fn default() -> Self {
+        Self {
+            transform: Mx4F32::IDENTITY,
+            attributes: vec![],
+        }
+    }
+}
+
+impl<'a> Scope<'a> {
+    fn apply(&mut self, attribute: &'a Attribute) {
+        if let Attribute::GeometricTransform(transform) = attribute {
+            self.transform = transform.transformation_matrix.multiply(&self.transform);
+            return;
+        }
+        match self
+            .attributes
+            .iter()
+            .position(|a| a.object_type_id() == attribute.object_type_id())
+        {
+            Some(i) if self.attributes[i].is_final() && !attribute.is_forced() => {}
+            Some(i) => self.attributes[i] = attribute,
+            None => self.attributes.push(attribute),
+        }
+    }
+}
+
+pub struct Visit<'a> {
+    pub node: &'a Node,
+    pub depth: usize,
+    pub transform: Mx4F32,
+    pub attributes: Vec<&'a Attribute>,
+}
+
+pub struct Traversal<'a> {
+    lsg: &'a Lsg,
+    stack: Vec<(i32, usize, Scope<'a>)>,
+    path: Path,
+    visit_count: usize,
+}
+
+impl<'a> Iterator for Traversal<'a> {
+    type Item = Visit<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((object_id, depth, mut scope)) = self.stack.pop() {
+            self.path.truncate(depth);
+            let node = match self.lsg.node(object_id) {
+                Some(node) if !self.path.contains(object_id) => node,
+                _ => continue,
+            };
+            if Lsg::MAX_VISIT_COUNT <= self.visit_count {
+                self.stack.clear();
+                return None;
+            }
+            self.visit_count += 1;
+            node.base_node_data()
+                .attribute_object_ids
+                .iter()
+                .filter_map(|id| self.lsg.attribute(*id))
+                .for_each(|attribute| scope.apply(attribute));
+            self.path.push(object_id);
+            node.children()
+                .iter()
+                .rev()
+                .for_each(|child| self.stack.push((*child, depth + 1, scope.clone())));
+            return Some(Visit {
+                node,
+                depth,
+                transform: scope.transform,
+                attributes: scope.attributes,
+            });
+        }
+        None
+    }
+}
+
+pub struct PlacedShape<'a> {
+    pub node: &'a Node,
+    pub path: Vec<i32>,
+    pub world_transform: Mx4F32,
+    pub attributes: Vec<&'a Attribute>,
+}
+
+impl<'a> PlacedShape<'a> {
+    pub fn xform(&self) -> Xform {
+        Xform::from(&self.world_transform)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LsgSegment {
+    pub header: SegmentHeader,
+    pub lsg: Lsg,
+}
+
+impl Deserialize for LsgSegment {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let header = SegmentHeader::deserialize(deserializer)?;
+        if SegmentType::LogicalSceneGraph != header.segment_type {
+            return Err("invalid lsg segment type".to_string());
+        }
+        let zlib_header = ElementHeaderZlib::deserialize(deserializer)?;
+        let lsg = if zlib_header.is_compressed() {
+            let mut inflated = Reader::nested(
+                Cursor::new(zlib_header.inflate(deserializer)?),
+                deserializer,
+            );
+            Lsg::deserialize(&mut inflated)?
+        } else {
+            Lsg::deserialize(deserializer)?
+        };
+        Ok(Self { header, lsg })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jt::common::GUID;
+
+    use super::*;
+
+    fn group(object_id: i32, attributes: Vec<i32>, children: Vec<i32>) -> Node {
+        Node::Group(GroupNodeData {
+            base_node_data: BaseNodeData {
+                object_id,
+                node_flags: 0,
+                attribute_object_ids: attributes,
+            },
+            child_node_object_ids: children,
+        })
+    }
+
+    fn translation(object_id: i32, offset: [f32; 3]) -> Attribute {
+        let mut transformation_matrix = Mx4F32::IDENTITY;
+        transformation_matrix.0[12..15].copy_from_slice(&offset);
+        Attribute::GeometricTransform(GeometricTransformAttribute {
+            base_attribute_data: BaseAttributeData {
+                object_id,
+                ..Default::default()
+            },
+            stored_values_mask: 0x000E,
+            transformation_matrix,
+        })
+    }
+
+    fn material(object_id: i32, state_flags: u8) -> Attribute {
+        Attribute::Other(
+            GUID(0x10DD1030, [0x2AC8, 0x11D1], [0; 8]),
+            BaseAttributeData {
+                object_id,
+                state_flags,
+                field_inhibit_flags: 0,
+            },
+        )
+    }
+
+    fn lsg() -> Lsg {
+        let mut lsg = Lsg {
+            root_node_id: Some(1),
+            ..Default::default()
+        };
+        [
+            group(1, vec![10, 20], vec![2, 3]),
+            group(2, vec![11, 21], vec![4]),
+            group(3, vec![22], vec![]),
+            group(4, vec![], vec![1, 99]),
+        ]
+        .into_iter()
+        .for_each(|node| {
+            lsg.nodes.insert(node.object_id(), node);
+        });
+        [
+            translation(10, [1., 0., 0.]),
+            translation(11, [0., 2., 0.]),
+            material(20, Attribute::ACCUMULATION_FINAL),
+            material(21, 0),
+            material(22, Attribute::ACCUMULATION_FORCE),
+        ]
+        .into_iter()
+        .for_each(|attribute| {
+            lsg.attributes.insert(attribute.object_id(), attribute);
+        });
+        lsg
+    }
+
+    #[test]
+    fn traverse_depth_first() {
+        let lsg = lsg();
+        let visits: Vec<(i32, usize)> = lsg
+            .traverse()
+            .map(|visit| (visit.node.object_id(), visit.depth))
+            .collect();
+        assert_eq!(vec![(1, 0), (2, 1), (4, 2), (3, 1)], visits);
+    }
+
+    #[test]
+    fn traverse_accumulates_transforms() {
+        let lsg = lsg();
+        let transforms: Vec<[f32; 3]> = lsg
+            .traverse()
+            .map(|visit| visit.transform.0[12..15].try_into().unwrap())
+            .collect();
+        assert_eq!(
+            vec![[1., 0., 0.], [1., 2., 0.], [1., 2., 0.], [1., 0., 0.]],
+            transforms
+        );
+    }
+
+    #[test]
+    fn traverse_inherits_attributes() {
+        let lsg = lsg();
+        let attributes: Vec<Vec<i32>> = lsg
+            .traverse()
+            .map(|visit| visit.attributes.iter().map(|a| a.object_id()).collect())
+            .collect();
+        assert_eq!(vec![vec![20], vec![20], vec![20], vec![22]], attributes);
+    }
+
+    #[test]
+    fn traverse_exponential_fan_out() {
+        let mut lsg = Lsg {
+            root_node_id: Some(0),
+            ..Default::default()
+        };
+        (0..32).for_each(|i| {
+            lsg.nodes.insert(i, group(i, vec![], vec![i + 1, i + 1]));
+        });
+        assert_eq!(Lsg::MAX_VISIT_COUNT, lsg.traverse().count());
+    }
+
+    #[test]
+    fn traverse_empty_lsg() {
+        assert_eq!(0, Lsg::default().traverse().count());
+    }
+
+    fn shape(object_id: i32, attributes: Vec<i32>) -> Node {
+        let mut node = TriStripSetShapeNode::default();
+        node.vertex_shape_node_data
+            .base_shape_node_data
+            .base_node_data = BaseNodeData {
+            object_id,
+            node_flags: 0,
+            attribute_object_ids: attributes,
+        };
+        Node::TriStripSetShape(node)
+    }
+
+    fn instance(object_id: i32, attributes: Vec<i32>, child: i32) -> Node {
+        Node::Instance(InstanceNode {
+            base_node_data: BaseNodeData {
+                object_id,
+                node_flags: 0,
+                attribute_object_ids: attributes,
+            },
+            child_node_object_id: child,
+        })
+    }
+
+    #[test]
+    fn flatten_instances() {
+        let mut lsg = Lsg {
+            root_node_id: Some(1),
+            ..Default::default()
+        };
+        [
+            group(1, vec![10], vec![2, 3]),
+            instance(2, vec![11], 4),
+            instance(3, vec![12], 4),
+            group(4, vec![], vec![5, 1]),
+            shape(5, vec![13]),
+        ]
+        .into_iter()
+        .for_each(|node| {
+            lsg.nodes.insert(node.object_id(), node);
+        });
+        [
+            translation(10, [1., 0., 0.]),
+            translation(11, [0., 2., 0.]),
+            translation(12, [0., 0., 3.]),
+            translation(13, [0., 0., 1.]),
+        ]
+        .into_iter()
+        .for_each(|attribute| {
+            lsg.attributes.insert(attribute.object_id(), attribute);
+        });
+        let shapes = lsg.flatten().unwrap();
+        assert_eq!(2, shapes.len());
+        assert_eq!(vec![1, 2, 4, 5], shapes[0].path);
+        assert_eq!(vec![1, 3, 4, 5], shapes[1].path);
+        assert_eq!([1., 2., 1.], shapes[0].world_transform.0[12..15]);
+        assert_eq!([1., 0., 4.], shapes[1].world_transform.0[12..15]);
+        assert_eq!(
+            crate::math::Point3d::new(1., 0., 4.),
+            shapes[1].xform() * crate::math::Point3d::ORIGIN
+        );
+        assert!(shapes.iter().all(|shape| shape.node.is_shape()));
+    }
+
+    #[test]
+    fn flatten_selects_lod_and_switch_children() {
+        let mut lsg = Lsg {
+            root_node_id: Some(1),
+            ..Default::default()
+        };
+        let switch = SwitchNode {
+            group_node_data: GroupNodeData {
+                base_node_data: BaseNodeData {
+                    object_id: 3,
+                    ..Default::default()
+                },
+                child_node_object_ids: vec![6, 7],
+            },
+            selected_child: 1,
+        };
+        let lod = LodNodeData {
+            group_node_data: GroupNodeData {
+                base_node_data: BaseNodeData {
+                    object_id: 2,
+                    ..Default::default()
+                },
+                child_node_object_ids: vec![4, 5],
+            },
+            ..Default::default()
+        };
+        [
+            group(1, vec![], vec![2, 3]),
+            Node::Lod(lod),
+            Node::Switch(switch),
+            shape(4, vec![]),
+            shape(5, vec![]),
+            shape(6, vec![]),
+            shape(7, vec![]),
+        ]
+        .into_iter()
+        .for_each(|node| {
+            lsg.nodes.insert(node.object_id(), node);
+        });
+        let shapes: Vec<i32> = lsg
+            .flatten()
+            .unwrap()
+            .iter()
+            .map(|shape| shape.node.object_id())
+            .collect();
+        assert_eq!(vec![4, 7], shapes);
+        assert!(Lsg::default().flatten().unwrap().is_empty());
+    }
+
+    #[test]
+    fn flatten_exponential_fan_out() {
+        let mut lsg = Lsg {
+            root_node_id: Some(0),
+            ..Default::default()
+        };
+        (0..32).for_each(|i| {
+            lsg.nodes.insert(i, group(i, vec![], vec![i + 1, i + 1]));
+        });
+        lsg.nodes.insert(32, shape(32, vec![]));
+        assert!(lsg.flatten().is_err());
+
+        (0..8).for_each(|i| {
+            lsg.nodes.insert(i, group(i, vec![], vec![i + 1, i + 1]));
+        });
+        lsg.nodes.insert(8, shape(8, vec![]));
+        assert_eq!(256, lsg.flatten().unwrap().len());
+    }
+}
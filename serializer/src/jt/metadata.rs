@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use super::property::{PropertyAtom, PropertyTable, PropertyValue};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Metadata {
+    atoms: HashMap<i32, PropertyValue>,
+    tables: HashMap<i32, Vec<(i32, i32)>>,
+}
+
+impl Metadata {
+    pub fn new(atoms: Vec<PropertyAtom>, table: PropertyTable) -> Self {
+        Self {
+            atoms: atoms
+                .into_iter()
+                .map(|atom| (atom.object_id(), atom.value))
+                .collect(),
+            tables: table
+                .element_property_tables
+                .into_iter()
+                .map(|t| (t.element_object_id, t.entries))
+                .collect(),
+        }
+    }
+
+    pub fn properties(&self, node_id: i32) -> Vec<(&PropertyValue, &PropertyValue)> {
+        self.tables.get(&node_id).map_or(vec![], |entries| {
+            entries
+                .iter()
+                .filter_map(|(key, value)| Some((self.atoms.get(key)?, self.atoms.get(value)?)))
+                .collect()
+        })
+    }
+
+    pub fn property(&self, node_id: i32, key: &str) -> Option<&PropertyValue> {
+        self.properties(node_id)
+            .into_iter()
+            .find(|(k, _)| key == k.to_string())
+            .map(|(_, value)| value)
+    }
+
+    pub fn find_nodes(&self, key: &str) -> Vec<i32> {
+        self.find_nodes_by(|k, _| key == k.to_string())
+    }
+
+    pub fn find_nodes_with_value(&self, key: &str, value: &str) -> Vec<i32> {
+        self.find_nodes_by(|k, v| key == k.to_string() && value == v.to_string())
+    }
+
+    fn find_nodes_by<F>(&self, predicate: F) -> Vec<i32>
+    where
+        F: Fn(&PropertyValue, &PropertyValue) -> bool,
+    {
+        let mut nodes: Vec<i32> = self
+            .tables
+            .keys()
+            .filter(|node_id| {
+                self.properties(**node_id)
+                    .iter()
+                    .any(|(key, value)| predicate(key, value))
+            })
+            .copied()
+            .collect();
+        nodes.sort_unstable();
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jt::property::{BasePropertyAtomData, ElementPropertyTable};
+
+    use super::*;
+
+    fn atom(object_id: i32, value: PropertyValue) -> PropertyAtom {
+        PropertyAtom {
+            base_property_atom_data: BasePropertyAtomData {
+                object_id,
+                state_flags: 0,
+            },
+            value,
+        }
+    }
+
+    fn metadata() -> Metadata {
+        let atoms = vec![
+            atom(10, PropertyValue::String("PART_NUMBER".to_string())),
+            atom(11, PropertyValue::String("A-100".to_string())),
+            atom(12, PropertyValue::String("A-200".to_string())),
+            atom(13, PropertyValue::String("MASS".to_string())),
+            atom(14, PropertyValue::Float(2.5)),
+        ];
+        let table = PropertyTable {
+            version: 1,
+            element_property_tables: vec![
+                ElementPropertyTable {
+                    element_object_id: 1,
+                    entries: vec![(10, 11), (13, 14)],
+                },
+                ElementPropertyTable {
+                    element_object_id: 2,
+                    entries: vec![(10, 12)],
+                },
+                ElementPropertyTable {
+                    element_object_id: 3,
+                    entries: vec![(10, 11)],
+                },
+            ],
+        };
+        Metadata::new(atoms, table)
+    }
+
+    #[test]
+    fn node_properties() {
+        let metadata = metadata();
+        assert_eq!(2, metadata.properties(1).len());
+        assert_eq!(
+            Some(&PropertyValue::Float(2.5)),
+            metadata.property(1, "MASS")
+        );
+        assert_eq!(None, metadata.property(2, "MASS"));
+        assert!(metadata.properties(4).is_empty());
+    }
+
+    #[test]
+    fn find_nodes() {
+        let metadata = metadata();
+        assert_eq!(vec![1, 2, 3], metadata.find_nodes("PART_NUMBER"));
+        assert_eq!(vec![1], metadata.find_nodes("MASS"));
+        assert_eq!(
+            vec![1, 3],
+            metadata.find_nodes_with_value("PART_NUMBER", "A-100")
+        );
+        assert!(metadata.find_nodes_with_value("MASS", "3").is_empty());
+    }
+}
@@ -1,4 +1,27 @@
-mod common;
-mod deserialize;
-mod deserializer;
-mod header;
+pub mod archive;
+pub mod attribute;
+mod cdp;
+pub mod common;
+pub mod deserialize;
+pub(crate) mod deserializer;
+pub mod error;
+pub mod header;
+pub mod lsg;
+pub mod metadata;
+pub mod node;
+pub mod object_type;
+pub mod property;
+pub mod quantization;
+pub mod reader;
+pub mod segment;
+pub mod shape_lod;
+pub mod statistics;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod toc;
+pub mod topo_mesh;
+pub mod ulp;
+pub mod version;
+pub mod vertex;
+pub mod wireframe;
+pub mod xt_brep;
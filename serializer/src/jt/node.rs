@@ -0,0 +1,266 @@
+use geometria_derive::JtDeserialize;
+
+use super::{
+    common::{BBoxF32, CoordF32, MbString},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    quantization::QuantizationParameters,
+    version::Version,
+};
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BaseNodeData {
+    pub object_id: i32,
+    pub node_flags: u32,
+    pub attribute_object_ids: Vec<i32>,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GroupNodeData {
+    pub base_node_data: BaseNodeData,
+    pub child_node_object_ids: Vec<i32>,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CountRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PartitionNode {
+    pub group_node_data: GroupNodeData,
+    pub partition_flags: i32,
+    pub file_name: MbString,
+    pub transformed_bbox: BBoxF32,
+    pub area: f32,
+    pub vertex_count_range: CountRange,
+    pub node_count_range: CountRange,
+    pub polygon_count_range: CountRange,
+    pub untransformed_bbox: Option<BBoxF32>,
+}
+
+impl PartitionNode {
+    const UNTRANSFORMED_BBOX: i32 = 0x1;
+}
+
+impl Deserialize for PartitionNode {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut node = Self {
+            group_node_data: GroupNodeData::deserialize(deserializer)?,
+            partition_flags: i32::deserialize(deserializer)?,
+            file_name: MbString::deserialize(deserializer)?,
+            transformed_bbox: BBoxF32::deserialize(deserializer)?,
+            area: f32::deserialize(deserializer)?,
+            vertex_count_range: CountRange::deserialize(deserializer)?,
+            node_count_range: CountRange::deserialize(deserializer)?,
+            polygon_count_range: CountRange::deserialize(deserializer)?,
+            untransformed_bbox: None,
+        };
+        if 0 != node.partition_flags & Self::UNTRANSFORMED_BBOX {
+            node.untransformed_bbox = Some(BBoxF32::deserialize(deserializer)?);
+        }
+        Ok(node)
+    }
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InstanceNode {
+    pub base_node_data: BaseNodeData,
+    pub child_node_object_id: i32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MetaDataNodeData {
+    pub group_node_data: GroupNodeData,
+    pub version: i16,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PartNode {
+    pub meta_data_node_data: MetaDataNodeData,
+    pub version: i16,
+    pub reserved_field: i32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LodNodeData {
+    pub group_node_data: GroupNodeData,
+    pub reserved_field: Vec<f32>,
+    pub reserved_field_2: i32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RangeLodNode {
+    pub lod_node_data: LodNodeData,
+    pub range_limits: Vec<f32>,
+    pub center: CoordF32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SwitchNode {
+    pub group_node_data: GroupNodeData,
+    pub selected_child: i32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BaseShapeNodeData {
+    pub base_node_data: BaseNodeData,
+    pub reserved_field: BBoxF32,
+    pub untransformed_bbox: BBoxF32,
+    pub area: f32,
+    pub vertex_count_range: CountRange,
+    pub node_count_range: CountRange,
+    pub polygon_count_range: CountRange,
+    pub size: i32,
+    pub compression_level: f32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VertexShapeNodeData {
+    pub base_shape_node_data: BaseShapeNodeData,
+    #[file_version(Version::V9_0..)]
+    pub version: i16,
+    #[file_version(Version::V9_0..)]
+    pub vertex_bindings: u64,
+    #[file_version(..Version::V9_0)]
+    pub normal_binding: i32,
+    #[file_version(..Version::V9_0)]
+    pub texture_coord_binding: i32,
+    #[file_version(..Version::V9_0)]
+    pub color_binding: i32,
+    pub quantization_parameters: QuantizationParameters,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TriStripSetShapeNode {
+    pub vertex_shape_node_data: VertexShapeNodeData,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PolylineSetShapeNode {
+    pub vertex_shape_node_data: VertexShapeNodeData,
+    pub version: i16,
+    pub area_factor: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::jt::reader::Reader;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_tri_strip_set_shape_node() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(7i32.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.extend(2i32.to_le_bytes());
+        data.extend(3i32.to_le_bytes());
+        data.extend(4i32.to_le_bytes());
+        data.extend([0u8; 2 * 6 * 4]);
+        data.extend(1.5f32.to_le_bytes());
+        data.extend([0u8; 3 * 2 * 4]);
+        data.extend(128i32.to_le_bytes());
+        data.extend(0.5f32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend([12u8, 2u8, 0u8, 0u8]);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let node = TriStripSetShapeNode::deserialize(&mut deserializer).unwrap();
+        let vertex_shape = &node.vertex_shape_node_data;
+        let base_node = &vertex_shape.base_shape_node_data.base_node_data;
+        assert_eq!(7, base_node.object_id);
+        assert_eq!(1, base_node.node_flags);
+        assert_eq!(vec![3, 4], base_node.attribute_object_ids);
+        assert_eq!(1.5, vertex_shape.base_shape_node_data.area);
+        assert_eq!(128, vertex_shape.base_shape_node_data.size);
+        assert_eq!(1, vertex_shape.normal_binding);
+        assert_eq!(12, vertex_shape.quantization_parameters.bits_per_vertex);
+    }
+
+    #[test]
+    fn deserialize_v9_vertex_shape_node_data() {
+        let mut data: Vec<u8> = vec![0u8; 4 + 4 + 4 + 2 * 6 * 4 + 4 + 3 * 2 * 4 + 4 + 4];
+        data.extend(1i16.to_le_bytes());
+        data.extend(0x9u64.to_le_bytes());
+        data.extend([16u8, 1u8, 0u8, 0u8]);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        deserializer.set_version(Version::new(9, 5));
+        let vertex_shape = VertexShapeNodeData::deserialize(&mut deserializer).unwrap();
+        assert_eq!(1, vertex_shape.version);
+        assert_eq!(0x9, vertex_shape.vertex_bindings);
+        assert_eq!(0, vertex_shape.normal_binding);
+        assert_eq!(16, vertex_shape.quantization_parameters.bits_per_vertex);
+    }
+
+    #[test]
+    fn deserialize_partition_node() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1i32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend(2i32.to_le_bytes());
+        data.extend(2i32.to_le_bytes());
+        data.extend(3i32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend(u16::from(b'a').to_le_bytes());
+        data.extend([0u8; 6 * 4 + 4 + 3 * 2 * 4]);
+        data.extend(1.0f32.to_le_bytes());
+        data.extend([0u8; 5 * 4]);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let node = PartitionNode::deserialize(&mut deserializer).unwrap();
+        assert_eq!(vec![2, 3], node.group_node_data.child_node_object_ids);
+        assert_eq!("a", node.file_name.0);
+        assert_eq!(1.0, node.untransformed_bbox.unwrap().min_corner.0[0]);
+    }
+
+    #[test]
+    fn deserialize_polyline_set_shape_node() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(9i32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend([0u8; 2 * 6 * 4 + 4 + 3 * 2 * 4 + 4 + 4 + 3 * 4 + 4]);
+        data.extend(1i16.to_le_bytes());
+        data.extend(2.5f32.to_le_bytes());
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let node = PolylineSetShapeNode::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            9,
+            node.vertex_shape_node_data
+                .base_shape_node_data
+                .base_node_data
+                .object_id
+        );
+        assert_eq!(1, node.version);
+        assert_eq!(2.5, node.area_factor);
+    }
+}
@@ -0,0 +1,224 @@
+use super::common::GUID;
+
+pub type ObjectTypeId = GUID;
+
+pub const END_OF_ELEMENTS: ObjectTypeId = GUID(0xFFFFFFFF, [0xFFFF, 0xFFFF], [0xFF; 8]);
+pub const TRI_STRIP_SET_SHAPE_NODE: ObjectTypeId = GUID(
+    0x10DD1077,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const TRI_STRIP_SET_SHAPE_LOD: ObjectTypeId = GUID(
+    0x10DD10AB,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const VERTEX_SHAPE_LOD: ObjectTypeId = GUID(
+    0x10DD10B0,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const POLYLINE_SET_SHAPE_NODE: ObjectTypeId = GUID(
+    0x10DD1046,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const POLYLINE_SET_SHAPE_LOD: ObjectTypeId = GUID(
+    0x10DD10A1,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const XT_BREP: ObjectTypeId = GUID(
+    0x873A70E0,
+    [0x2AC9, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const BASE_PROPERTY_ATOM: ObjectTypeId = GUID(
+    0x10DD104E,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const STRING_PROPERTY_ATOM: ObjectTypeId = GUID(
+    0x10DD106E,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const INTEGER_PROPERTY_ATOM: ObjectTypeId = GUID(
+    0x10DD102B,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const FLOATING_POINT_PROPERTY_ATOM: ObjectTypeId = GUID(
+    0x10DD1019,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const DATE_PROPERTY_ATOM: ObjectTypeId = GUID(
+    0xCE357246,
+    [0x38FB, 0x11D1],
+    [0xA5, 0x06, 0x00, 0x60, 0x97, 0xBD, 0xC6, 0xE1],
+);
+pub const LATE_LOADED_PROPERTY_ATOM: ObjectTypeId = GUID(
+    0xE0B05BE5,
+    [0xFBBD, 0x11D1],
+    [0xA3, 0xA7, 0x00, 0xAA, 0x00, 0xD1, 0x09, 0x54],
+);
+pub const PARTITION_NODE: ObjectTypeId = GUID(
+    0x10DD103E,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const GROUP_NODE: ObjectTypeId = GUID(
+    0x10DD101B,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const INSTANCE_NODE: ObjectTypeId = GUID(
+    0x10DD102A,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const PART_NODE: ObjectTypeId = GUID(
+    0xCE357244,
+    [0x38FB, 0x11D1],
+    [0xA5, 0x06, 0x00, 0x60, 0x97, 0xBD, 0xC6, 0xE1],
+);
+pub const META_DATA_NODE: ObjectTypeId = GUID(
+    0xCE357245,
+    [0x38FB, 0x11D1],
+    [0xA5, 0x06, 0x00, 0x60, 0x97, 0xBD, 0xC6, 0xE1],
+);
+pub const LOD_NODE: ObjectTypeId = GUID(
+    0x10DD102C,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const RANGE_LOD_NODE: ObjectTypeId = GUID(
+    0x10DD104C,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const SWITCH_NODE: ObjectTypeId = GUID(
+    0x10DD10F3,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const GEOMETRIC_TRANSFORM_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1083,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const WIREFRAME_REP: ObjectTypeId = GUID(
+    0x873A70D0,
+    [0x2AC9, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const ULP: ObjectTypeId = GUID(
+    0xF338A4AF,
+    [0xD7D2, 0x41C5],
+    [0xBC, 0xF2, 0xC5, 0x5A, 0x88, 0xB2, 0x1E, 0x73],
+);
+pub const JT_BREP: ObjectTypeId = GUID(
+    0x873A70C0,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const MATERIAL_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1030,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const TEXTURE_IMAGE_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1073,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const DRAW_STYLE_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1014,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const LIGHT_SET_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1096,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const INFINITE_LIGHT_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1028,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const POINT_LIGHT_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD1045,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const LINESTYLE_ATTRIBUTE: ObjectTypeId = GUID(
+    0x10DD10C4,
+    [0x2AC8, 0x11D1],
+    [0x9B, 0x6B, 0x00, 0x80, 0xC7, 0xBB, 0x59, 0x97],
+);
+pub const POINTSTYLE_ATTRIBUTE: ObjectTypeId = GUID(
+    0x8D57C010,
+    [0xE5CB, 0x11D4],
+    [0x84, 0x0E, 0x00, 0xA0, 0xD2, 0x18, 0x2F, 0x9D],
+);
+pub const PROPERTY_PROXY_META_DATA: ObjectTypeId = GUID(
+    0xCE357247,
+    [0x38FB, 0x11D1],
+    [0xA5, 0x06, 0x00, 0x60, 0x97, 0xBD, 0xC6, 0xE1],
+);
+pub const PMI_MANAGER_META_DATA: ObjectTypeId = GUID(
+    0xCE357249,
+    [0x38FB, 0x11D1],
+    [0xA5, 0x06, 0x00, 0x60, 0x97, 0xBD, 0xC6, 0xE1],
+);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn object_type_ids_are_unique() {
+        let ids = [
+            END_OF_ELEMENTS,
+            TRI_STRIP_SET_SHAPE_NODE,
+            TRI_STRIP_SET_SHAPE_LOD,
+            VERTEX_SHAPE_LOD,
+            POLYLINE_SET_SHAPE_NODE,
+            POLYLINE_SET_SHAPE_LOD,
+            XT_BREP,
+            BASE_PROPERTY_ATOM,
+            STRING_PROPERTY_ATOM,
+            INTEGER_PROPERTY_ATOM,
+            FLOATING_POINT_PROPERTY_ATOM,
+            DATE_PROPERTY_ATOM,
+            LATE_LOADED_PROPERTY_ATOM,
+            PARTITION_NODE,
+            GROUP_NODE,
+            INSTANCE_NODE,
+            PART_NODE,
+            META_DATA_NODE,
+            LOD_NODE,
+            RANGE_LOD_NODE,
+            SWITCH_NODE,
+            GEOMETRIC_TRANSFORM_ATTRIBUTE,
+            WIREFRAME_REP,
+            ULP,
+            JT_BREP,
+            MATERIAL_ATTRIBUTE,
+            TEXTURE_IMAGE_ATTRIBUTE,
+            DRAW_STYLE_ATTRIBUTE,
+            LIGHT_SET_ATTRIBUTE,
+            INFINITE_LIGHT_ATTRIBUTE,
+            POINT_LIGHT_ATTRIBUTE,
+            LINESTYLE_ATTRIBUTE,
+            POINTSTYLE_ATTRIBUTE,
+            PROPERTY_PROXY_META_DATA,
+            PMI_MANAGER_META_DATA,
+        ];
+        assert_eq!(ids.len(), ids.iter().collect::<HashSet<&GUID>>().len());
+    }
+}
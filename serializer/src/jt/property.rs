@@ -0,0 +1,352 @@
+use std::fmt::Display;
+use std::io::SeekFrom;
+use std::sync::OnceLock;
+
+use geometria_derive::JtDeserialize;
+
+use super::{
+    common::{MbString, GUID},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    object_type,
+    segment::ElementHeader,
+    toc::Toc,
+};
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BasePropertyAtomData {
+    pub object_id: i32,
+    pub state_flags: u32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LateLoadedPropertyAtom {
+    pub base_property_atom_data: BasePropertyAtomData,
+    pub version: i16,
+    pub segment_id: GUID,
+    pub payload_object_type: i32,
+}
+
+impl LateLoadedPropertyAtom {
+    pub fn late_loaded<T>(&self) -> LateLoaded<T> {
+        LateLoaded::new(self.segment_id)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(bound = "T: serde::Serialize"))]
+pub struct LateLoaded<T> {
+    segment_id: GUID,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_late_loaded"))]
+    value: OnceLock<T>,
+}
+
+#[cfg(feature = "serde")]
+fn serialize_late_loaded<T, S>(value: &OnceLock<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: serde::Serialize,
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&value.get(), serializer)
+}
+
+impl<T> LateLoaded<T> {
+    pub fn new(segment_id: GUID) -> Self {
+        Self {
+            segment_id,
+            value: OnceLock::new(),
+        }
+    }
+
+    pub fn segment_id(&self) -> GUID {
+        self.segment_id
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    pub fn resolve<D>(&self, deserializer: &mut D, toc: &Toc) -> Result<&T, String>
+    where
+        D: Deserializer,
+        T: Deserialize,
+        String: From<<T as Deserialize>::Error>,
+    {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+        let entry = toc
+            .find(&self.segment_id)
+            .ok_or("late loaded segment not found")?;
+        if let Err(e) = deserializer.seek(SeekFrom::Start(entry.segment_offset)) {
+            return Err(e.to_string());
+        }
+        let value = T::deserialize(deserializer)?;
+        Ok(self.value.get_or_init(|| value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PropertyValue {
+    None,
+    String(String),
+    Integer(i32),
+    Float(f32),
+    Date([i16; 6]),
+    LateLoaded(GUID),
+}
+
+impl Display for PropertyValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => Ok(()),
+            Self::String(value) => write!(f, "{}", value),
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Date([year, month, day, hour, minute, second]) => write!(
+                f,
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                year, month, day, hour, minute, second
+            ),
+            Self::LateLoaded(segment_id) => write!(f, "{:?}", segment_id),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PropertyAtom {
+    pub base_property_atom_data: BasePropertyAtomData,
+    pub value: PropertyValue,
+}
+
+impl PropertyAtom {
+    pub fn object_id(&self) -> i32 {
+        self.base_property_atom_data.object_id
+    }
+}
+
+impl Deserialize for PropertyAtom {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let element_begin = match deserializer.stream_position() {
+            Ok(position) => position,
+            Err(e) => return Err(e.to_string()),
+        };
+        let header = ElementHeader::deserialize(deserializer)?;
+        if 0 > header.element_length {
+            return Err("invalid element length".to_string());
+        }
+        let base_property_atom_data = BasePropertyAtomData::deserialize(deserializer)?;
+        let value = match header.object_type_id {
+            object_type::BASE_PROPERTY_ATOM => PropertyValue::None,
+            object_type::STRING_PROPERTY_ATOM => {
+                PropertyValue::String(MbString::deserialize(deserializer)?.0)
+            }
+            object_type::INTEGER_PROPERTY_ATOM => {
+                PropertyValue::Integer(i32::deserialize(deserializer)?)
+            }
+            object_type::FLOATING_POINT_PROPERTY_ATOM => {
+                PropertyValue::Float(f32::deserialize(deserializer)?)
+            }
+            object_type::DATE_PROPERTY_ATOM => {
+                PropertyValue::Date(<[i16; 6]>::deserialize(deserializer)?)
+            }
+            object_type::LATE_LOADED_PROPERTY_ATOM => {
+                i16::deserialize(deserializer)?;
+                PropertyValue::LateLoaded(GUID::deserialize(deserializer)?)
+            }
+            _ => return Err("unsupported property atom".to_string()),
+        };
+        let element_end = element_begin + 4 + header.element_length as u64;
+        match deserializer.seek(SeekFrom::Start(element_end)) {
+            Ok(_) => Ok(Self {
+                base_property_atom_data,
+                value,
+            }),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ElementPropertyTable {
+    pub element_object_id: i32,
+    pub entries: Vec<(i32, i32)>,
+}
+
+impl Deserialize for ElementPropertyTable {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut table = Self {
+            element_object_id: i32::deserialize(deserializer)?,
+            ..Default::default()
+        };
+        loop {
+            let key = i32::deserialize(deserializer)?;
+            if 0 == key {
+                break;
+            }
+            table.entries.push((key, i32::deserialize(deserializer)?));
+        }
+        Ok(table)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PropertyTable {
+    pub version: i16,
+    pub element_property_tables: Vec<ElementPropertyTable>,
+}
+
+impl Deserialize for PropertyTable {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        Ok(Self {
+            version: i16::deserialize(deserializer)?,
+            element_property_tables: Vec::<ElementPropertyTable>::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Seek};
+
+    use crate::jt::{
+        reader::Reader,
+        segment::{SegmentHeader, SegmentType},
+        toc::TocEntry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn resolve_late_loaded_segment() {
+        let segment_id = GUID(5, [1, 2], [3; 8]);
+        let mut data: Vec<u8> = vec![0u8; 8];
+        data.extend(segment_id.0.to_le_bytes());
+        segment_id
+            .1
+            .iter()
+            .for_each(|v| data.extend(v.to_le_bytes()));
+        data.extend(segment_id.2);
+        data.extend(4i32.to_le_bytes());
+        data.extend(28i32.to_le_bytes());
+        let toc = Toc {
+            entries: vec![TocEntry {
+                segment_id,
+                segment_offset: 8,
+                segment_length: 24,
+                segment_attributes: 0x04000000,
+            }],
+        };
+        let mut deserializer = Reader::new(Cursor::new(data));
+
+        let atom = LateLoadedPropertyAtom {
+            segment_id,
+            ..Default::default()
+        };
+        let late_loaded = atom.late_loaded::<SegmentHeader>();
+        assert!(!late_loaded.is_loaded());
+        let header = late_loaded.resolve(&mut deserializer, &toc).unwrap();
+        assert_eq!(SegmentType::MetaData, header.segment_type);
+        assert_eq!(28, header.segment_length);
+        assert!(late_loaded.is_loaded());
+
+        deserializer.seek(SeekFrom::Start(0)).unwrap();
+        assert!(late_loaded.resolve(&mut deserializer, &toc).is_ok());
+        assert_eq!(0, deserializer.stream_position().unwrap());
+    }
+
+    #[test]
+    fn deserialize_property_atoms() {
+        let mut data: Vec<u8> = vec![];
+        let mut element = |object_type: &GUID, value: &[u8]| {
+            data.extend((17 + 8 + value.len() as i32).to_le_bytes());
+            data.extend(object_type.0.to_le_bytes());
+            object_type
+                .1
+                .iter()
+                .for_each(|v| data.extend(v.to_le_bytes()));
+            data.extend(object_type.2);
+            data.push(0u8);
+            data.extend(7i32.to_le_bytes());
+            data.extend(0u32.to_le_bytes());
+            data.extend(value);
+        };
+        let mut string: Vec<u8> = 2i32.to_le_bytes().to_vec();
+        "ab".encode_utf16()
+            .for_each(|u| string.extend(u.to_le_bytes()));
+        element(&object_type::STRING_PROPERTY_ATOM, &string);
+        element(&object_type::INTEGER_PROPERTY_ATOM, &5i32.to_le_bytes());
+        element(
+            &object_type::FLOATING_POINT_PROPERTY_ATOM,
+            &0.5f32.to_le_bytes(),
+        );
+        let date: Vec<u8> = [2024i16, 1, 2, 3, 4, 5]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        element(&object_type::DATE_PROPERTY_ATOM, &date);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let values: Vec<String> = (0..4)
+            .map(|_| {
+                let atom = PropertyAtom::deserialize(&mut deserializer).unwrap();
+                assert_eq!(7, atom.object_id());
+                atom.value.to_string()
+            })
+            .collect();
+        assert_eq!(vec!["ab", "5", "0.5", "2024-01-02 03:04:05"], values);
+    }
+
+    #[test]
+    fn deserialize_property_table() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1i16.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        [3i32, 10, 11, 12, 13, 0]
+            .iter()
+            .for_each(|v| data.extend(v.to_le_bytes()));
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let table = PropertyTable::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            vec![ElementPropertyTable {
+                element_object_id: 3,
+                entries: vec![(10, 11), (12, 13)],
+            }],
+            table.element_property_tables
+        );
+    }
+
+    #[test]
+    fn resolve_missing_segment() {
+        let mut deserializer = Reader::new(Cursor::new(vec![]));
+        let late_loaded = LateLoaded::<SegmentHeader>::new(GUID::default());
+        assert!(late_loaded
+            .resolve(&mut deserializer, &Toc::default())
+            .is_err());
+        assert!(late_loaded.get().is_none());
+    }
+}
@@ -0,0 +1,133 @@
+use geometria_derive::JtDeserialize;
+
+use super::{deserialize::Deserialize, deserializer::Deserializer};
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuantizationParameters {
+    pub bits_per_vertex: u8,
+    pub normal_bits_factor: u8,
+    pub bits_per_texture_coord: u8,
+    pub bits_per_color: u8,
+}
+
+impl QuantizationParameters {
+    pub fn is_lossless(&self) -> bool {
+        0 == self.bits_per_vertex
+    }
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UniformQuantizerData {
+    pub min: f32,
+    pub max: f32,
+    pub number_of_bits: u8,
+}
+
+impl UniformQuantizerData {
+    pub fn dequantize(&self, code: i32) -> f32 {
+        if 0 == self.number_of_bits || 32 < self.number_of_bits {
+            self.min
+        } else {
+            let range = ((1u64 << self.number_of_bits) - 1) as f64;
+            (self.min as f64 + (self.max as f64 - self.min as f64) * (code as u32 as f64 / range))
+                as f32
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DeeringNormalCodec {
+    number_of_bits: u8,
+}
+
+impl DeeringNormalCodec {
+    const PSI_MAX: f64 = 0.615479709;
+
+    pub fn new(number_of_bits: u8) -> Result<Self, String> {
+        if 31 < number_of_bits {
+            Err("invalid number of normal bits".to_string())
+        } else {
+            Ok(Self { number_of_bits })
+        }
+    }
+
+    pub fn decode(&self, sextant: u32, octant: u32, theta: u32, psi: u32) -> [f32; 3] {
+        let size = (1u64 << self.number_of_bits) as f64;
+        let theta = (theta as f64).min(size);
+        let psi = (psi as f64).min(size);
+        let theta_angle = (Self::PSI_MAX * (size - theta) / size).tan().asin();
+        let psi_angle = Self::PSI_MAX * (psi / size);
+        let xx = theta_angle.cos() * psi_angle.cos();
+        let yy = psi_angle.sin();
+        let zz = theta_angle.sin() * psi_angle.cos();
+        let [x, y, z] = match sextant % 6 {
+            0 => [xx, yy, zz],
+            1 => [yy, xx, zz],
+            2 => [yy, zz, xx],
+            3 => [zz, yy, xx],
+            4 => [zz, xx, yy],
+            _ => [xx, zz, yy],
+        };
+        [
+            (if 0 != octant & 0x4 { -x } else { x }) as f32,
+            (if 0 != octant & 0x2 { -y } else { y }) as f32,
+            (if 0 != octant & 0x1 { -z } else { z }) as f32,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dequantize() {
+        let quantizer = UniformQuantizerData {
+            min: -1.0,
+            max: 1.0,
+            number_of_bits: 2,
+        };
+        assert_eq!(-1.0, quantizer.dequantize(0));
+        assert_eq!(1.0, quantizer.dequantize(3));
+        assert!((quantizer.dequantize(1) + 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn dequantize_without_bits() {
+        let quantizer = UniformQuantizerData {
+            min: 2.0,
+            max: 4.0,
+            number_of_bits: 0,
+        };
+        assert_eq!(2.0, quantizer.dequantize(7));
+    }
+
+    #[test]
+    fn deering_normal_is_unit_length() {
+        let codec = DeeringNormalCodec::new(6).unwrap();
+        for sextant in 0..6 {
+            for octant in 0..8 {
+                for (theta, psi) in [(0, 0), (13, 27), (64, 64), (32, 5)] {
+                    let [x, y, z] = codec.decode(sextant, octant, theta, psi);
+                    assert!(((x * x + y * y + z * z) - 1.0).abs() < 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deering_normal_octant_signs() {
+        let codec = DeeringNormalCodec::new(6).unwrap();
+        let [x, y, z] = codec.decode(0, 0, 13, 27);
+        assert!(0.0 < x && 0.0 < y && 0.0 < z);
+        let [x, y, z] = codec.decode(0, 7, 13, 27);
+        assert!(0.0 > x && 0.0 > y && 0.0 > z);
+    }
+
+    #[test]
+    fn deering_invalid_number_of_bits() {
+        assert!(DeeringNormalCodec::new(32).is_err());
+    }
+}
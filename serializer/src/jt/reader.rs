@@ -0,0 +1,157 @@
+use std::io::{Read, Seek};
+
+use crate::common::reader::NumberReader;
+
+use super::{deserializer::Deserializer, version::Version};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ByteOrder {
+    #[default]
+    LittleEndian,
+    BigEndian,
+}
+
+impl TryFrom<u8> for ByteOrder {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::LittleEndian),
+            1 => Ok(Self::BigEndian),
+            _ => Err("invalid byte order".to_string()),
+        }
+    }
+}
+
+pub struct Reader<T>
+where
+    T: Read + Seek,
+{
+    pub reader: T,
+    pub version: Version,
+    pub byte_order: ByteOrder,
+}
+
+impl<T> Reader<T>
+where
+    T: Read + Seek,
+{
+    pub fn new(reader: T) -> Self {
+        Self {
+            reader,
+            version: Version::default(),
+            byte_order: ByteOrder::default(),
+        }
+    }
+
+    pub fn nested<D>(reader: T, parent: &D) -> Self
+    where
+        D: Deserializer,
+    {
+        Self {
+            reader,
+            version: parent.version(),
+            byte_order: parent.byte_order(),
+        }
+    }
+}
+
+macro_rules! impl_read_number_for_reader {
+    ($primitive: ty, $method: ident) => {
+        fn $method(&mut self) -> std::io::Result<$primitive> {
+            let mut buf = [0u8; std::mem::size_of::<$primitive>()];
+            self.reader.read_exact(&mut buf)?;
+            Ok(match self.byte_order {
+                ByteOrder::LittleEndian => <$primitive>::from_le_bytes(buf),
+                ByteOrder::BigEndian => <$primitive>::from_be_bytes(buf),
+            })
+        }
+    };
+}
+
+impl<T> NumberReader for Reader<T>
+where
+    T: Read + Seek,
+{
+    impl_read_number_for_reader! {i8, read_i8}
+    impl_read_number_for_reader! {i16, read_i16}
+    impl_read_number_for_reader! {i32, read_i32}
+    impl_read_number_for_reader! {i64, read_i64}
+    impl_read_number_for_reader! {i128, read_i128}
+
+    impl_read_number_for_reader! {u8, read_u8}
+    impl_read_number_for_reader! {u16, read_u16}
+    impl_read_number_for_reader! {u32, read_u32}
+    impl_read_number_for_reader! {u64, read_u64}
+    impl_read_number_for_reader! {u128, read_u128}
+
+    impl_read_number_for_reader! {f32, read_f32}
+    impl_read_number_for_reader! {f64, read_f64}
+}
+
+impl<T> Read for Reader<T>
+where
+    T: Read + Seek,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl<T> Seek for Reader<T>
+where
+    T: Read + Seek,
+{
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.reader.seek(pos)
+    }
+}
+
+impl<T> Deserializer for Reader<T>
+where
+    T: Read + Seek,
+{
+    fn version(&self) -> Version {
+        self.version
+    }
+
+    fn set_version(&mut self, version: Version) {
+        self.version = version;
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn read_numbers_in_byte_order() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(0x0102i16.to_le_bytes());
+        data.extend(1.5f32.to_be_bytes());
+        data.extend(7u64.to_be_bytes());
+        let mut reader = Reader::new(Cursor::new(data));
+        assert_eq!(0x0102, reader.read_i16().unwrap());
+        reader.set_byte_order(ByteOrder::BigEndian);
+        assert_eq!(1.5, reader.read_f32().unwrap());
+        assert_eq!(7, reader.read_u64().unwrap());
+    }
+
+    #[test]
+    fn byte_order_from_u8() {
+        assert_eq!(Ok(ByteOrder::LittleEndian), ByteOrder::try_from(0));
+        assert_eq!(Ok(ByteOrder::BigEndian), ByteOrder::try_from(1));
+        assert!(ByteOrder::try_from(2).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+use geometria_derive::JtDeserialize;
+
+use super::{common::GUID, deserialize::Deserialize, deserializer::Deserializer};
+
+pub const MAX_DECOMPRESSED_SIZE: u64 = 1 << 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SegmentType {
+    LogicalSceneGraph,
+    JtBRep,
+    PmiData,
+    MetaData,
+    Shape,
+    ShapeLod(u8),
+    XtBRep,
+    WireframeRepresentation,
+    Ulp,
+    Lwpa,
+}
+
+impl TryFrom<i32> for SegmentType {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::LogicalSceneGraph),
+            2 => Ok(Self::JtBRep),
+            3 => Ok(Self::PmiData),
+            4 => Ok(Self::MetaData),
+            6 => Ok(Self::Shape),
+            7..=16 => Ok(Self::ShapeLod((value - 7) as u8)),
+            17 => Ok(Self::XtBRep),
+            18 => Ok(Self::WireframeRepresentation),
+            20 => Ok(Self::Ulp),
+            24 => Ok(Self::Lwpa),
+            _ => Err("invalid segment type".to_string()),
+        }
+    }
+}
+
+impl Deserialize for SegmentType {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        Self::try_from(i32::deserialize(deserializer)?)
+    }
+}
+
+#[derive(JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SegmentHeader {
+    pub segment_id: GUID,
+    pub segment_type: SegmentType,
+    pub segment_length: i32,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ElementHeader {
+    pub element_length: i32,
+    pub object_type_id: GUID,
+    pub object_base_type: u8,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ElementHeaderZlib {
+    pub compression_flag: i32,
+    pub compressed_data_length: i32,
+    pub compression_algorithm: u8,
+}
+
+impl ElementHeaderZlib {
+    const COMPRESSED: i32 = 2;
+    const ZLIB: u8 = 2;
+
+    pub fn is_compressed(&self) -> bool {
+        Self::COMPRESSED == self.compression_flag && Self::ZLIB == self.compression_algorithm
+    }
+
+    pub fn inflate<D>(&self, deserializer: &mut D) -> Result<Vec<u8>, String>
+    where
+        D: Deserializer,
+    {
+        self.inflate_with_limit(deserializer, MAX_DECOMPRESSED_SIZE)
+    }
+
+    fn inflate_with_limit<D>(&self, deserializer: &mut D, limit: u64) -> Result<Vec<u8>, String>
+    where
+        D: Deserializer,
+    {
+        if 1 > self.compressed_data_length {
+            return Err("invalid compressed data length".to_string());
+        }
+        let mut bytes: Vec<u8> = vec![];
+        let compressed = deserializer.take(self.compressed_data_length as u64 - 1);
+        match ZlibDecoder::new(compressed)
+            .take(limit + 1)
+            .read_to_end(&mut bytes)
+        {
+            Ok(_) if limit < bytes.len() as u64 => {
+                Err("decompressed data size exceeds limit".to_string())
+            }
+            Ok(_) => Ok(bytes),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use crate::jt::reader::Reader;
+
+    use super::*;
+
+    #[test]
+    fn inflate_limit() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[7u8; 1024]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let header = ElementHeaderZlib {
+            compression_flag: 2,
+            compressed_data_length: compressed.len() as i32 + 1,
+            compression_algorithm: 2,
+        };
+        let mut deserializer = Reader::new(Cursor::new(compressed.clone()));
+        assert_eq!(vec![7u8; 1024], header.inflate(&mut deserializer).unwrap());
+        let mut deserializer = Reader::new(Cursor::new(compressed.clone()));
+        assert!(header.inflate_with_limit(&mut deserializer, 1024).is_ok());
+        let mut deserializer = Reader::new(Cursor::new(compressed));
+        assert!(header.inflate_with_limit(&mut deserializer, 1023).is_err());
+    }
+
+    #[test]
+    fn segment_type_conversions() {
+        assert_eq!(Ok(SegmentType::LogicalSceneGraph), SegmentType::try_from(1));
+        assert_eq!(Ok(SegmentType::Shape), SegmentType::try_from(6));
+        assert_eq!(Ok(SegmentType::ShapeLod(0)), SegmentType::try_from(7));
+        assert_eq!(Ok(SegmentType::ShapeLod(9)), SegmentType::try_from(16));
+        assert_eq!(Ok(SegmentType::XtBRep), SegmentType::try_from(17));
+        assert_eq!(Ok(SegmentType::Lwpa), SegmentType::try_from(24));
+        assert!(SegmentType::try_from(0).is_err());
+        assert!(SegmentType::try_from(5).is_err());
+        assert!(SegmentType::try_from(25).is_err());
+    }
+}
@@ -0,0 +1,685 @@
+use std::io::{Read, SeekFrom};
+use std::ops::Range;
+
+use flate2::read::ZlibDecoder;
+use geometria_derive::JtDeserialize;
+
+use crate::model::Mesh;
+
+use super::{
+    cdp::{Int32Cdp, PredictorType},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    object_type,
+    quantization::QuantizationParameters,
+    reader::ByteOrder,
+    segment::{ElementHeader, SegmentHeader, SegmentType, MAX_DECOMPRESSED_SIZE},
+    topo_mesh::TopoMeshCompressedLodData,
+    version::Version,
+    vertex::{
+        Binding, QuantizedVertexCoordArray, QuantizedVertexNormalArray,
+        QuantizedVertexTextureCoordArray, VertexData,
+    },
+};
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BaseShapeLodData {
+    pub version: i16,
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VertexShapeLodData {
+    pub base_shape_lod_data: BaseShapeLodData,
+    pub version: i16,
+    #[file_version(..Version::V9_0)]
+    pub binding_attributes: i32,
+    #[file_version(Version::V9_0..)]
+    pub vertex_bindings: u64,
+    #[file_version(..Version::V9_0)]
+    pub quantization_parameters: QuantizationParameters,
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VertexBasedShapeCompressedRepData {
+    pub version: i16,
+    pub normal_binding: Binding,
+    pub texture_coord_binding: Binding,
+    pub color_binding: Binding,
+    pub quantization_parameters: QuantizationParameters,
+    pub primitive_list_indices: Vec<i32>,
+    pub vertex_data: VertexData,
+}
+
+impl VertexBasedShapeCompressedRepData {
+    fn deserialize_raw_vertex_data<D>(deserializer: &mut D) -> Result<Vec<f32>, String>
+    where
+        D: Deserializer,
+    {
+        let uncompressed_data_size = i32::deserialize(deserializer)?;
+        let compressed_data_size = i32::deserialize(deserializer)?;
+        if 0 > uncompressed_data_size {
+            return Err("invalid uncompressed data size".to_string());
+        }
+        let size = uncompressed_data_size as u64;
+        if MAX_DECOMPRESSED_SIZE < size {
+            return Err("uncompressed data size exceeds limit".to_string());
+        }
+        let mut bytes: Vec<u8> = vec![];
+        let result = if 0 < compressed_data_size {
+            ZlibDecoder::new(deserializer.take(compressed_data_size as u64))
+                .take(size + 1)
+                .read_to_end(&mut bytes)
+        } else {
+            deserializer.take(size).read_to_end(&mut bytes)
+        };
+        if let Err(e) = result {
+            return Err(e.to_string());
+        }
+        if bytes.len() != uncompressed_data_size as usize || !bytes.len().is_multiple_of(4) {
+            return Err("invalid uncompressed data size".to_string());
+        }
+        let byte_order = deserializer.byte_order();
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| match byte_order {
+                ByteOrder::LittleEndian => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                ByteOrder::BigEndian => f32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            })
+            .collect())
+    }
+
+    fn deserialize_quantized_vertex_data<D>(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<VertexData, String>
+    where
+        D: Deserializer,
+    {
+        let coordinates = QuantizedVertexCoordArray::deserialize(deserializer)?.decode()?;
+        let normals = match self.normal_binding {
+            Binding::None => vec![],
+            _ => QuantizedVertexNormalArray::deserialize(deserializer)?.decode()?,
+        };
+        let texture_coordinates = match self.texture_coord_binding {
+            Binding::None => vec![],
+            _ => QuantizedVertexTextureCoordArray::deserialize(deserializer)?.decode()?,
+        };
+        if Binding::None != self.color_binding {
+            return Err("lossy quantized vertex colors are not supported".to_string());
+        }
+        let indices =
+            Int32Cdp::deserialize(deserializer)?.decode_with(PredictorType::StripIndex)?;
+        let mut vertex_data = VertexData::default();
+        for index in indices {
+            let index = index as usize;
+            match coordinates
+                .chunks_exact(VertexData::COORDINATE_SIZE)
+                .nth(index)
+            {
+                Some(coordinate) => vertex_data.coordinates.extend_from_slice(coordinate),
+                None => return Err("invalid vertex data index".to_string()),
+            }
+            if Binding::PerVertex == self.normal_binding {
+                match normals.chunks_exact(VertexData::NORMAL_SIZE).nth(index) {
+                    Some(normal) => vertex_data.normals.extend_from_slice(normal),
+                    None => return Err("invalid vertex data index".to_string()),
+                }
+            }
+            if Binding::PerVertex == self.texture_coord_binding {
+                match texture_coordinates
+                    .chunks_exact(VertexData::TEXTURE_COORDINATE_SIZE)
+                    .nth(index)
+                {
+                    Some(texture_coordinate) => vertex_data
+                        .texture_coordinates
+                        .extend_from_slice(texture_coordinate),
+                    None => return Err("invalid vertex data index".to_string()),
+                }
+            }
+        }
+        Ok(vertex_data)
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_data.vertex_count()
+    }
+
+    pub fn check_primitive_list_indices(&self) -> Result<(), String> {
+        if self
+            .primitive_list_indices
+            .windows(2)
+            .any(|w| 0 > w[0] || w[0] > w[1])
+        {
+            Err("invalid primitive list indices".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn primitives(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let count = self.vertex_count();
+        self.primitive_list_indices
+            .windows(2)
+            .filter(|w| 0 <= w[0] && w[0] <= w[1])
+            .map(move |w| (w[0] as usize).min(count)..(w[1] as usize).min(count))
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = [f32; 3]> + '_ {
+        self.vertex_data
+            .coordinates
+            .chunks_exact(VertexData::COORDINATE_SIZE)
+            .map(|c| [c[0], c[1], c[2]])
+    }
+}
+
+impl Deserialize for VertexBasedShapeCompressedRepData {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut rep_data = Self {
+            version: i16::deserialize(deserializer)?,
+            normal_binding: Binding::deserialize(deserializer)?,
+            texture_coord_binding: Binding::deserialize(deserializer)?,
+            color_binding: Binding::deserialize(deserializer)?,
+            quantization_parameters: QuantizationParameters::deserialize(deserializer)?,
+            ..Default::default()
+        };
+        rep_data.primitive_list_indices =
+            Int32Cdp::deserialize(deserializer)?.decode_with(PredictorType::Stride1)?;
+        rep_data.vertex_data = if rep_data.quantization_parameters.is_lossless() {
+            VertexData::from_interleaved(
+                &Self::deserialize_raw_vertex_data(deserializer)?,
+                rep_data.normal_binding,
+                rep_data.texture_coord_binding,
+                rep_data.color_binding,
+            )?
+        } else {
+            rep_data.deserialize_quantized_vertex_data(deserializer)?
+        };
+        Ok(rep_data)
+    }
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VertexShapeLod {
+    pub vertex_shape_lod_data: VertexShapeLodData,
+    pub version: i16,
+    pub vertex_based_shape_compressed_rep_data: VertexBasedShapeCompressedRepData,
+}
+
+impl VertexShapeLod {
+    pub fn vertex_data(&self) -> &VertexData {
+        &self.vertex_based_shape_compressed_rep_data.vertex_data
+    }
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TriStripSetShapeLod {
+    pub vertex_shape_lod_data: VertexShapeLodData,
+    pub version: i16,
+    pub vertex_based_shape_compressed_rep_data: VertexBasedShapeCompressedRepData,
+    pub topo_mesh_compressed_lod_data: Option<Box<TopoMeshCompressedLodData>>,
+}
+
+impl Deserialize for TriStripSetShapeLod {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut lod = Self {
+            vertex_shape_lod_data: VertexShapeLodData::deserialize(deserializer)?,
+            version: i16::deserialize(deserializer)?,
+            ..Default::default()
+        };
+        if Version::V9_0 <= deserializer.version() {
+            lod.topo_mesh_compressed_lod_data = Some(Box::new(
+                TopoMeshCompressedLodData::deserialize(deserializer)?,
+            ));
+        } else {
+            lod.vertex_based_shape_compressed_rep_data =
+                VertexBasedShapeCompressedRepData::deserialize(deserializer)?;
+        }
+        Ok(lod)
+    }
+}
+
+pub fn strip_triangles(strip: &[u32]) -> impl Iterator<Item = [u32; 3]> + '_ {
+    strip.windows(3).enumerate().filter_map(|(i, window)| {
+        let [a, b, c] = [window[0], window[1], window[2]];
+        if a == b || b == c || c == a {
+            None
+        } else if 0 == i % 2 {
+            Some([a, b, c])
+        } else {
+            Some([b, a, c])
+        }
+    })
+}
+
+fn mesh(vertex_data: &VertexData, triangles: impl Iterator<Item = [u32; 3]>) -> Mesh {
+    let positions: Vec<[f32; 3]> = vertex_data
+        .coordinates
+        .chunks_exact(VertexData::COORDINATE_SIZE)
+        .map(|c| [c[0], c[1], c[2]])
+        .collect();
+    let normals = if vertex_data.normals.len() == vertex_data.coordinates.len() {
+        vertex_data
+            .normals
+            .chunks_exact(VertexData::NORMAL_SIZE)
+            .map(|n| [n[0], n[1], n[2]])
+            .collect()
+    } else {
+        vec![]
+    };
+    let colors = if vertex_data.colors.len() == vertex_data.coordinates.len() {
+        vertex_data
+            .colors
+            .chunks_exact(VertexData::COLOR_SIZE)
+            .map(|c| [c[0], c[1], c[2], 1.])
+            .collect()
+    } else {
+        vec![]
+    };
+    let triangles = triangles
+        .filter(|t| t.iter().all(|i| (*i as usize) < positions.len()))
+        .collect();
+    Mesh {
+        positions,
+        normals,
+        colors,
+        triangles,
+    }
+}
+
+impl TriStripSetShapeLod {
+    pub fn to_mesh(&self) -> Result<Mesh, String> {
+        match &self.topo_mesh_compressed_lod_data {
+            Some(data) => {
+                let rep_data = &data.topologically_compressed_rep_data;
+                Ok(mesh(
+                    &rep_data.vertex_records.vertex_data,
+                    rep_data.decode()?.triangles(),
+                ))
+            }
+            None => {
+                self.vertex_based_shape_compressed_rep_data
+                    .check_primitive_list_indices()?;
+                let strips: Vec<Vec<u32>> = self
+                    .strips()
+                    .map(|strip| strip.map(|i| i as u32).collect())
+                    .collect();
+                Ok(mesh(
+                    &self.vertex_based_shape_compressed_rep_data.vertex_data,
+                    strips.iter().flat_map(|strip| strip_triangles(strip)),
+                ))
+            }
+        }
+    }
+
+    pub fn strips(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.vertex_based_shape_compressed_rep_data.primitives()
+    }
+
+    pub fn triangle_count(&self) -> Result<usize, String> {
+        match &self.topo_mesh_compressed_lod_data {
+            Some(data) => Ok(data
+                .topologically_compressed_rep_data
+                .decode()?
+                .triangles()
+                .count()),
+            None => {
+                self.vertex_based_shape_compressed_rep_data
+                    .check_primitive_list_indices()?;
+                Ok(self
+                    .strips()
+                    .map(|strip| strip.len().saturating_sub(2))
+                    .sum())
+            }
+        }
+    }
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PolylineSetShapeLod {
+    pub vertex_shape_lod_data: VertexShapeLodData,
+    pub version: i16,
+    pub vertex_based_shape_compressed_rep_data: VertexBasedShapeCompressedRepData,
+}
+
+impl PolylineSetShapeLod {
+    pub fn polylines(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.vertex_based_shape_compressed_rep_data.primitives()
+    }
+
+    pub fn polyline_points(&self) -> Vec<Vec<[f32; 3]>> {
+        let vertex_data = &self.vertex_based_shape_compressed_rep_data.vertex_data;
+        self.polylines()
+            .map(|polyline| {
+                polyline
+                    .filter_map(|index| vertex_data.coordinate(index))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ShapeLodElement {
+    TriStripSet(TriStripSetShapeLod),
+    PolylineSet(PolylineSetShapeLod),
+    Vertex(VertexShapeLod),
+}
+
+impl Deserialize for ShapeLodElement {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let element_begin = match deserializer.stream_position() {
+            Ok(position) => position,
+            Err(e) => return Err(e.to_string()),
+        };
+        let header = ElementHeader::deserialize(deserializer)?;
+        if 0 > header.element_length {
+            return Err("invalid element length".to_string());
+        }
+        let element = match header.object_type_id {
+            object_type::TRI_STRIP_SET_SHAPE_LOD => {
+                Self::TriStripSet(TriStripSetShapeLod::deserialize(deserializer)?)
+            }
+            object_type::POLYLINE_SET_SHAPE_LOD => {
+                Self::PolylineSet(PolylineSetShapeLod::deserialize(deserializer)?)
+            }
+            object_type::VERTEX_SHAPE_LOD => {
+                Self::Vertex(VertexShapeLod::deserialize(deserializer)?)
+            }
+            _ => return Err("unsupported shape lod element".to_string()),
+        };
+        let element_end = element_begin + 4 + header.element_length as u64;
+        match deserializer.seek(SeekFrom::Start(element_end)) {
+            Ok(_) => Ok(element),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ShapeLodSegment {
+    pub header: SegmentHeader,
+    pub element: ShapeLodElement,
+}
+
+impl Deserialize for ShapeLodSegment {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let header = SegmentHeader::deserialize(deserializer)?;
+        match header.segment_type {
+            SegmentType::ShapeLod(_) => Ok(Self {
+                header,
+                element: ShapeLodElement::deserialize(deserializer)?,
+            }),
+            _ => Err("invalid shape lod segment type".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use crate::jt::common::GUID;
+
+    use crate::jt::reader::Reader;
+    use crate::jt::test_utils::{extend_guid, null_cdp_data};
+
+    use super::*;
+
+    fn shape_lod_data(normal_binding: u8, bits_per_vertex: u8) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1i16.to_le_bytes());
+        data.extend(1i16.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend([bits_per_vertex, 0u8, 0u8, 0u8]);
+        data.extend(1i16.to_le_bytes());
+        data.extend(1i16.to_le_bytes());
+        data.extend([normal_binding, 0u8, 0u8]);
+        data.extend([bits_per_vertex, 0u8, 0u8, 0u8]);
+        null_cdp_data(&mut data, &[0, 4]);
+        data
+    }
+
+    fn tri_strip_set_shape_lod_data(compress: bool) -> Vec<u8> {
+        let coordinates: [f32; 12] = [0., 0., 0., 1., 0., 0., 0., 1., 0., 1., 1., 0.];
+        let raw: Vec<u8> = coordinates.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let mut data = shape_lod_data(0, 0);
+        data.extend((raw.len() as i32).to_le_bytes());
+        if compress {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&raw).unwrap();
+            let compressed = encoder.finish().unwrap();
+            data.extend((compressed.len() as i32).to_le_bytes());
+            data.extend(compressed);
+        } else {
+            data.extend((-(raw.len() as i32)).to_le_bytes());
+            data.extend(raw);
+        }
+        data
+    }
+
+    #[test]
+    fn deserialize_raw_vertex_data_sizes() {
+        let deserialize = |data: Vec<u8>| {
+            let mut deserializer = Reader::new(Cursor::new(data));
+            VertexBasedShapeCompressedRepData::deserialize_raw_vertex_data(&mut deserializer)
+        };
+        let mut data: Vec<u8> = vec![];
+        data.extend(i32::MAX.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        assert!(deserialize(data).is_err());
+
+        let mut data: Vec<u8> = vec![];
+        data.extend(8i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend(1f32.to_le_bytes());
+        assert!(deserialize(data).is_err());
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[0u8; 64]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let mut data: Vec<u8> = vec![];
+        data.extend(8i32.to_le_bytes());
+        data.extend((compressed.len() as i32).to_le_bytes());
+        data.extend(compressed);
+        assert!(deserialize(data).is_err());
+    }
+
+    #[test]
+    fn deserialize_big_endian_raw_vertex_data() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(8i32.to_be_bytes());
+        data.extend((-8i32).to_be_bytes());
+        data.extend(1.5f32.to_be_bytes());
+        data.extend((-2f32).to_be_bytes());
+        let mut deserializer = Reader::new(Cursor::new(data));
+        deserializer.set_byte_order(ByteOrder::BigEndian);
+        assert_eq!(
+            Ok(vec![1.5, -2.]),
+            VertexBasedShapeCompressedRepData::deserialize_raw_vertex_data(&mut deserializer)
+        );
+    }
+
+    #[test]
+    fn strip_triangles_with_restarts() {
+        let triangles: Vec<[u32; 3]> = strip_triangles(&[0, 1, 2, 3, 3, 4, 4, 5, 6]).collect();
+        assert_eq!(vec![[0, 1, 2], [2, 1, 3], [4, 5, 6]], triangles);
+        assert_eq!(0, strip_triangles(&[0, 1]).count());
+    }
+
+    #[test]
+    fn mesh_with_colors() {
+        let vertex_data = VertexData::from_interleaved(
+            &[
+                1., 0., 0., 0., 0., 0., 0., 1., 0., 1., 0., 0., 0., 0., 1., 0., 1., 0.,
+            ],
+            Binding::None,
+            Binding::None,
+            Binding::PerVertex,
+        )
+        .unwrap();
+        assert_eq!(Some([0., 1., 0.]), vertex_data.color(1));
+        let mesh = mesh(&vertex_data, [[0, 1, 2]].into_iter());
+        assert!(mesh.has_colors());
+        assert_eq!([0., 0., 1., 1.], mesh.colors[2]);
+        assert_eq!([0., 1., 0.], mesh.positions[2]);
+    }
+
+    #[test]
+    fn deserialize_tri_strip_set_shape_lod() {
+        for compress in [false, true] {
+            let mut deserializer = Reader::new(Cursor::new(tri_strip_set_shape_lod_data(compress)));
+            let lod = TriStripSetShapeLod::deserialize(&mut deserializer).unwrap();
+            let rep_data = &lod.vertex_based_shape_compressed_rep_data;
+            assert_eq!(4, rep_data.vertex_count());
+            assert!(rep_data.vertex_data.normals.is_empty());
+            assert_eq!(vec![0..4], lod.strips().collect::<Vec<Range<usize>>>());
+            assert_eq!(Ok(2), lod.triangle_count());
+            let mesh = lod.to_mesh().unwrap();
+            assert_eq!(vec![[0, 1, 2], [2, 1, 3]], mesh.triangles);
+            assert_eq!(4, mesh.positions.len());
+            assert!(mesh.normals.is_empty());
+            assert_eq!(1., mesh.area());
+            assert_eq!(
+                vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.]],
+                rep_data.coordinates().collect::<Vec<[f32; 3]>>()
+            );
+
+            let mut lod = lod;
+            lod.vertex_based_shape_compressed_rep_data
+                .primitive_list_indices = vec![0, i32::MAX];
+            assert_eq!(vec![0..4], lod.strips().collect::<Vec<Range<usize>>>());
+            assert_eq!(2, lod.to_mesh().unwrap().triangles.len());
+            for indices in [vec![0, -1], vec![-1, 2], vec![3, 1]] {
+                lod.vertex_based_shape_compressed_rep_data
+                    .primitive_list_indices = indices;
+                assert!(lod.strips().next().is_none());
+                assert!(lod.to_mesh().is_err());
+                assert!(lod.triangle_count().is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn deserialize_vertex_shape_lod_with_normals() {
+        let vertices: [f32; 12] = [0., 0., 1., 1., 2., 3., 0., 1., 0., 4., 5., 6.];
+        let raw: Vec<u8> = vertices.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let mut data = shape_lod_data(1, 0);
+        data.extend((raw.len() as i32).to_le_bytes());
+        data.extend((-(raw.len() as i32)).to_le_bytes());
+        data.extend(raw);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let lod = VertexShapeLod::deserialize(&mut deserializer).unwrap();
+        let vertex_data = lod.vertex_data();
+        assert_eq!(2, vertex_data.vertex_count());
+        assert_eq!(vec![1., 2., 3., 4., 5., 6.], vertex_data.coordinates);
+        assert_eq!(vec![0., 0., 1., 0., 1., 0.], vertex_data.normals);
+    }
+
+    #[test]
+    fn deserialize_lossy_vertex_shape_lod() {
+        let mut data = shape_lod_data(0, 2);
+        for (min, max) in [(0f32, 3f32), (-1f32, 1f32), (2f32, 2f32)] {
+            data.extend(min.to_le_bytes());
+            data.extend(max.to_le_bytes());
+            data.push(2u8);
+        }
+        data.extend(2i32.to_le_bytes());
+        null_cdp_data(&mut data, &[0, 3]);
+        null_cdp_data(&mut data, &[3, 0]);
+        null_cdp_data(&mut data, &[1, 0]);
+        null_cdp_data(&mut data, &[0, 1, 1, 0]);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let lod = VertexShapeLod::deserialize(&mut deserializer).unwrap();
+        let vertex_data = lod.vertex_data();
+        assert_eq!(4, vertex_data.vertex_count());
+        assert_eq!(Some([0., 1., 2.]), vertex_data.coordinate(0));
+        assert_eq!(Some([3., -1., 2.]), vertex_data.coordinate(1));
+        assert_eq!(Some([3., -1., 2.]), vertex_data.coordinate(2));
+        assert_eq!(Some([0., 1., 2.]), vertex_data.coordinate(3));
+    }
+
+    #[test]
+    fn deserialize_polyline_set_shape_lod() {
+        let mut data: Vec<u8> = vec![];
+        extend_guid(&mut data, &object_type::POLYLINE_SET_SHAPE_LOD);
+        data.push(0u8);
+        data.extend(tri_strip_set_shape_lod_data(false));
+        let data = [(data.len() as i32).to_le_bytes().to_vec(), data].concat();
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        match ShapeLodElement::deserialize(&mut deserializer).unwrap() {
+            ShapeLodElement::PolylineSet(lod) => {
+                assert_eq!(vec![0..4], lod.polylines().collect::<Vec<Range<usize>>>());
+                assert_eq!(
+                    vec![vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.]]],
+                    lod.polyline_points()
+                );
+            }
+            _ => panic!("unexpected shape lod element"),
+        }
+    }
+
+    #[test]
+    fn deserialize_shape_lod_segment() {
+        let lod = tri_strip_set_shape_lod_data(true);
+        let mut data: Vec<u8> = vec![];
+        extend_guid(&mut data, &GUID::default());
+        data.extend(7i32.to_le_bytes());
+        data.extend((lod.len() as i32 + 4 + 17 + 24).to_le_bytes());
+        data.extend((lod.len() as i32 + 17).to_le_bytes());
+        extend_guid(&mut data, &object_type::TRI_STRIP_SET_SHAPE_LOD);
+        data.push(0u8);
+        data.extend(lod);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let segment = ShapeLodSegment::deserialize(&mut deserializer).unwrap();
+        assert_eq!(SegmentType::ShapeLod(0), segment.header.segment_type);
+        match segment.element {
+            ShapeLodElement::TriStripSet(lod) => {
+                assert_eq!(4, lod.vertex_based_shape_compressed_rep_data.vertex_count())
+            }
+            _ => panic!("unexpected shape lod element"),
+        }
+    }
+
+    #[test]
+    fn deserialize_unsupported_shape_lod_element() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(17i32.to_le_bytes());
+        extend_guid(&mut data, &object_type::TRI_STRIP_SET_SHAPE_NODE);
+        data.push(0u8);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert!(ShapeLodElement::deserialize(&mut deserializer).is_err());
+    }
+}
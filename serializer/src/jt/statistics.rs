@@ -0,0 +1,26 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::segment::SegmentType;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SceneStatistics {
+    pub node_count: usize,
+    pub part_count: usize,
+    pub instance_count: usize,
+    pub shape_count: usize,
+    pub attribute_count: usize,
+    pub segment_count: usize,
+    pub segment_bytes: HashMap<SegmentType, u64>,
+    pub lod_triangle_counts: BTreeMap<u8, usize>,
+}
+
+impl SceneStatistics {
+    pub fn total_bytes(&self) -> u64 {
+        self.segment_bytes.values().sum()
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.lod_triangle_counts.values().sum()
+    }
+}
@@ -0,0 +1,27 @@
+use super::{common::GUID, reader::ByteOrder};
+
+pub(crate) fn extend_guid(data: &mut Vec<u8>, guid: &GUID) {
+    extend_guid_with_byte_order(data, ByteOrder::LittleEndian, guid);
+}
+
+pub(crate) fn extend_guid_with_byte_order(data: &mut Vec<u8>, byte_order: ByteOrder, guid: &GUID) {
+    match byte_order {
+        ByteOrder::LittleEndian => {
+            data.extend(guid.0.to_le_bytes());
+            guid.1.iter().for_each(|v| data.extend(v.to_le_bytes()));
+        }
+        ByteOrder::BigEndian => {
+            data.extend(guid.0.to_be_bytes());
+            guid.1.iter().for_each(|v| data.extend(v.to_be_bytes()));
+        }
+    }
+    data.extend(guid.2);
+}
+
+pub(crate) fn null_cdp_data(data: &mut Vec<u8>, values: &[i32]) {
+    data.push(0u8);
+    data.extend((values.len() as i32 * 32).to_le_bytes());
+    data.extend((values.len() as i32).to_le_bytes());
+    data.extend((values.len() as i32).to_le_bytes());
+    values.iter().for_each(|v| data.extend(v.to_le_bytes()));
+}
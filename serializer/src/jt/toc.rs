@@ -0,0 +1,119 @@
+use super::{
+    common::GUID, deserialize::Deserialize, deserializer::Deserializer, segment::SegmentType,
+    version::Version,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TocEntry {
+    pub segment_id: GUID,
+    pub segment_offset: u64,
+    pub segment_length: u32,
+    pub segment_attributes: u32,
+}
+
+impl TocEntry {
+    pub fn segment_type(&self) -> Result<SegmentType, String> {
+        SegmentType::try_from((self.segment_attributes >> 24) as i32)
+    }
+}
+
+impl Deserialize for TocEntry {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let segment_id = GUID::deserialize(deserializer)?;
+        let (segment_offset, segment_length) = if Version::V10_0 <= deserializer.version() {
+            (
+                u64::deserialize(deserializer)?,
+                u32::deserialize(deserializer)?,
+            )
+        } else {
+            match (
+                u32::try_from(i32::deserialize(deserializer)?),
+                u32::try_from(i32::deserialize(deserializer)?),
+            ) {
+                (Ok(offset), Ok(length)) => (offset as u64, length),
+                _ => return Err("invalid toc entry".to_string()),
+            }
+        };
+        Ok(Self {
+            segment_id,
+            segment_offset,
+            segment_length,
+            segment_attributes: u32::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Toc {
+    pub entries: Vec<TocEntry>,
+}
+
+impl Toc {
+    pub fn find(&self, segment_id: &GUID) -> Option<&TocEntry> {
+        self.entries.iter().find(|e| *segment_id == e.segment_id)
+    }
+}
+
+impl Deserialize for Toc {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        Ok(Self {
+            entries: Vec::<TocEntry>::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::jt::reader::Reader;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_toc() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(2i32.to_le_bytes());
+        for (id, offset, attributes) in [(1u32, 100i32, 0x07000000u32), (2, 200, 0x01000000)] {
+            data.extend(id.to_le_bytes());
+            data.extend([0u8; 12]);
+            data.extend(offset.to_le_bytes());
+            data.extend(50i32.to_le_bytes());
+            data.extend(attributes.to_le_bytes());
+        }
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let toc = Toc::deserialize(&mut deserializer).unwrap();
+        assert_eq!(2, toc.entries.len());
+        let entry = toc.find(&GUID(2, [0; 2], [0; 8])).unwrap();
+        assert_eq!(200, entry.segment_offset);
+        assert_eq!(50, entry.segment_length);
+        assert_eq!(Ok(SegmentType::LogicalSceneGraph), entry.segment_type());
+        assert_eq!(Ok(SegmentType::ShapeLod(0)), toc.entries[0].segment_type());
+        assert_eq!(None, toc.find(&GUID::default()));
+    }
+
+    #[test]
+    fn deserialize_v10_toc_entry() {
+        let mut data: Vec<u8> = vec![0u8; 16];
+        data.extend((1u64 << 32).to_le_bytes());
+        data.extend(50u32.to_le_bytes());
+        data.extend(0x04000000u32.to_le_bytes());
+        let mut deserializer = Reader::new(Cursor::new(data));
+        deserializer.set_version(Version::V10_0);
+        let entry = TocEntry::deserialize(&mut deserializer).unwrap();
+        assert_eq!(1u64 << 32, entry.segment_offset);
+        assert_eq!(Ok(SegmentType::MetaData), entry.segment_type());
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::VecDeque;
+
+use geometria_derive::JtDeserialize;
+
+use super::{
+    cdp::Int32Cdp,
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    quantization::QuantizationParameters,
+    vertex::{QuantizedVertexCoordArray, QuantizedVertexNormalArray, VertexData},
+};
+
+const FACE_DEGREE_CONTEXTS: usize = 8;
+
+struct Stream {
+    values: Vec<i32>,
+    position: usize,
+}
+
+impl Stream {
+    fn new(cdp: &Int32Cdp) -> Result<Self, String> {
+        Ok(Self {
+            values: cdp.decode()?,
+            position: 0,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.len() <= self.position
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn next(&mut self) -> Result<i32, String> {
+        match self.values.get(self.position) {
+            Some(value) => {
+                self.position += 1;
+                Ok(*value)
+            }
+            None => Err("unexpected end of topological data".to_string()),
+        }
+    }
+
+    fn next_or(&mut self, default: i32) -> i32 {
+        self.next().unwrap_or(default)
+    }
+}
+
+struct DualVfVertex {
+    faces: Vec<Option<usize>>,
+}
+
+struct DualVfFace {
+    vertices: Vec<Option<usize>>,
+}
+
+#[derive(Default)]
+struct DualVfMesh {
+    vertices: Vec<DualVfVertex>,
+    faces: Vec<DualVfFace>,
+    max_valence: usize,
+    max_degree: usize,
+}
+
+impl DualVfMesh {
+    fn new_vertex(&mut self, valence: i32) -> Result<usize, String> {
+        if 1 > valence || self.max_valence < valence as usize {
+            return Err("invalid vertex valence".to_string());
+        }
+        self.vertices.push(DualVfVertex {
+            faces: vec![None; valence as usize],
+        });
+        Ok(self.vertices.len() - 1)
+    }
+
+    fn new_face(&mut self, degree: i32) -> Result<usize, String> {
+        if 3 > degree || self.max_degree < degree as usize {
+            return Err("invalid face degree".to_string());
+        }
+        self.faces.push(DualVfFace {
+            vertices: vec![None; degree as usize],
+        });
+        Ok(self.faces.len() - 1)
+    }
+
+    fn attach(&mut self, vertex: usize, face: usize, face_slot: usize) -> Result<(), String> {
+        match self.vertices[vertex].faces.iter_mut().find(|f| f.is_none()) {
+            Some(slot) => *slot = Some(face),
+            None => return Err("vertex valence exceeded".to_string()),
+        }
+        self.faces[face].vertices[face_slot] = Some(vertex);
+        Ok(())
+    }
+}
+
+struct TopoMeshDecoder {
+    face_degrees: Vec<Stream>,
+    vertex_valences: Stream,
+    vertex_groups: Stream,
+    vertex_flags: Stream,
+    split_face_syms: Stream,
+    split_face_positions: Stream,
+    mesh: DualVfMesh,
+    groups: Vec<i32>,
+    flags: Vec<i32>,
+}
+
+impl TopoMeshDecoder {
+    fn face_context(valence: usize) -> usize {
+        valence.clamp(2, FACE_DEGREE_CONTEXTS + 1) - 2
+    }
+
+    fn activate_vertex(&mut self, queue: &mut VecDeque<usize>) -> Result<usize, String> {
+        let vertex = self.mesh.new_vertex(self.vertex_valences.next()?)?;
+        self.groups.push(self.vertex_groups.next_or(0));
+        self.flags.push(self.vertex_flags.next_or(0));
+        queue.push_back(vertex);
+        Ok(vertex)
+    }
+
+    fn complete_vertex(
+        &mut self,
+        vertex: usize,
+        faces: &mut VecDeque<usize>,
+    ) -> Result<(), String> {
+        let valence = self.mesh.vertices[vertex].faces.len();
+        let context = Self::face_context(valence);
+        while self.mesh.vertices[vertex].faces.iter().any(|f| f.is_none()) {
+            let face = self.mesh.new_face(self.face_degrees[context].next()?)?;
+            self.mesh.attach(vertex, face, 0)?;
+            faces.push_back(face);
+        }
+        Ok(())
+    }
+
+    fn complete_face(&mut self, face: usize, vertices: &mut VecDeque<usize>) -> Result<(), String> {
+        for slot in 0..self.mesh.faces[face].vertices.len() {
+            if self.mesh.faces[face].vertices[slot].is_some() {
+                continue;
+            }
+            let sym = self.split_face_syms.next()?;
+            let vertex = if 0 > sym {
+                self.activate_vertex(vertices)?
+            } else {
+                let target = face
+                    .checked_sub(1 + sym as usize)
+                    .ok_or("invalid split face")?;
+                let position = self.split_face_positions.next()?;
+                match self.mesh.faces[target].vertices.get(position as usize) {
+                    Some(Some(vertex)) => *vertex,
+                    _ => return Err("invalid split face position".to_string()),
+                }
+            };
+            self.mesh.attach(vertex, face, slot)?;
+        }
+        Ok(())
+    }
+
+    fn run(mut self) -> Result<TopoMesh, String> {
+        while !self.vertex_valences.is_empty() {
+            let mut vertices: VecDeque<usize> = VecDeque::new();
+            let mut faces: VecDeque<usize> = VecDeque::new();
+            self.activate_vertex(&mut vertices)?;
+            while !vertices.is_empty() || !faces.is_empty() {
+                if let Some(vertex) = vertices.pop_front() {
+                    self.complete_vertex(vertex, &mut faces)?;
+                }
+                while let Some(face) = faces.pop_front() {
+                    self.complete_face(face, &mut vertices)?;
+                }
+            }
+        }
+        let mut faces: Vec<Vec<u32>> = Vec::with_capacity(self.mesh.faces.len());
+        for face in &self.mesh.faces {
+            faces.push(
+                face.vertices
+                    .iter()
+                    .map(|v| v.map(|v| v as u32).ok_or("incomplete face"))
+                    .collect::<Result<Vec<u32>, &str>>()?,
+            );
+        }
+        Ok(TopoMesh {
+            faces,
+            vertex_groups: self.groups,
+            vertex_flags: self.flags,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ContextCdps(pub Vec<Int32Cdp>);
+
+impl Deserialize for ContextCdps {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut cdps: Vec<Int32Cdp> = Vec::with_capacity(FACE_DEGREE_CONTEXTS);
+        for _ in 0..FACE_DEGREE_CONTEXTS {
+            cdps.push(Int32Cdp::deserialize(deserializer)?);
+        }
+        Ok(Self(cdps))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopoMesh {
+    pub faces: Vec<Vec<u32>>,
+    pub vertex_groups: Vec<i32>,
+    pub vertex_flags: Vec<i32>,
+}
+
+impl TopoMesh {
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_groups.len()
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        self.faces
+            .iter()
+            .flat_map(|face| (1..face.len() - 1).map(move |i| [face[0], face[i], face[i + 1]]))
+    }
+}
+
+#[derive(JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopologicallyCompressedRepData {
+    pub face_degrees: ContextCdps,
+    pub vertex_valences: Int32Cdp,
+    pub vertex_groups: Int32Cdp,
+    pub vertex_flags: Int32Cdp,
+    pub face_attribute_masks: ContextCdps,
+    pub face_attribute_mask_8_30: Int32Cdp,
+    pub face_attribute_mask_8_4: Int32Cdp,
+    pub high_degree_face_attribute_masks: Int32Cdp,
+    pub split_face_syms: Int32Cdp,
+    pub split_face_positions: Int32Cdp,
+    pub composite_hash: u32,
+    pub vertex_records: TopologicallyCompressedVertexRecords,
+}
+
+impl TopologicallyCompressedRepData {
+    pub fn decode(&self) -> Result<TopoMesh, String> {
+        let mut face_degrees: Vec<Stream> = Vec::with_capacity(FACE_DEGREE_CONTEXTS);
+        for cdp in &self.face_degrees.0 {
+            face_degrees.push(Stream::new(cdp)?);
+        }
+        let vertex_valences = Stream::new(&self.vertex_valences)?;
+        let mesh = DualVfMesh {
+            max_valence: face_degrees.iter().map(Stream::len).sum(),
+            max_degree: vertex_valences.len(),
+            ..Default::default()
+        };
+        TopoMeshDecoder {
+            face_degrees,
+            vertex_valences,
+            vertex_groups: Stream::new(&self.vertex_groups)?,
+            vertex_flags: Stream::new(&self.vertex_flags)?,
+            split_face_syms: Stream::new(&self.split_face_syms)?,
+            split_face_positions: Stream::new(&self.split_face_positions)?,
+            mesh,
+            groups: vec![],
+            flags: vec![],
+        }
+        .run()
+    }
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopologicallyCompressedVertexRecords {
+    pub vertex_bindings: u64,
+    pub quantization_parameters: QuantizationParameters,
+    pub number_of_topological_vertices: i32,
+    pub number_of_vertex_attributes: i32,
+    pub vertex_data: VertexData,
+}
+
+impl TopologicallyCompressedVertexRecords {
+    const COORDINATE_BINDING_MASK: u64 = 0x7;
+    const NORMAL_BINDING_MASK: u64 = 0x8;
+}
+
+impl Deserialize for TopologicallyCompressedVertexRecords {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let mut records = Self {
+            vertex_bindings: u64::deserialize(deserializer)?,
+            quantization_parameters: QuantizationParameters::deserialize(deserializer)?,
+            number_of_topological_vertices: i32::deserialize(deserializer)?,
+            ..Default::default()
+        };
+        if 0 < records.number_of_topological_vertices {
+            records.number_of_vertex_attributes = i32::deserialize(deserializer)?;
+            if 0 != records.vertex_bindings & Self::COORDINATE_BINDING_MASK {
+                records.vertex_data.coordinates =
+                    QuantizedVertexCoordArray::deserialize(deserializer)?.decode()?;
+            }
+            if 0 != records.vertex_bindings & Self::NORMAL_BINDING_MASK {
+                records.vertex_data.normals =
+                    QuantizedVertexNormalArray::deserialize(deserializer)?.decode()?;
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[derive(Default, JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopoMeshLodData {
+    pub version: i16,
+    pub vertex_records_object_id: i32,
+}
+
+#[derive(JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopoMeshCompressedLodData {
+    pub topo_mesh_lod_data: TopoMeshLodData,
+    pub version: i16,
+    pub topologically_compressed_rep_data: TopologicallyCompressedRepData,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::jt::{reader::Reader, test_utils::null_cdp_data};
+
+    use super::*;
+
+    fn rep_data(
+        face_degrees: &[(usize, &[i32])],
+        valences: &[i32],
+        split_face_syms: &[i32],
+        split_face_positions: &[i32],
+    ) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        for context in 0..FACE_DEGREE_CONTEXTS {
+            let degrees = face_degrees
+                .iter()
+                .find(|(c, _)| context == *c)
+                .map_or(&[][..], |(_, d)| *d);
+            null_cdp_data(&mut data, degrees);
+        }
+        null_cdp_data(&mut data, valences);
+        null_cdp_data(&mut data, &vec![0; valences.len()]);
+        null_cdp_data(&mut data, &vec![1; valences.len()]);
+        (0..FACE_DEGREE_CONTEXTS + 3).for_each(|_| null_cdp_data(&mut data, &[]));
+        null_cdp_data(&mut data, split_face_syms);
+        null_cdp_data(&mut data, split_face_positions);
+        data.extend(0u32.to_le_bytes());
+        data.extend(0u64.to_le_bytes());
+        data.extend([0u8; 4]);
+        data.extend(0i32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn decode_single_triangle() {
+        let data = rep_data(&[(0, &[3])], &[1, 1, 1], &[-1, -1], &[]);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let rep_data = TopologicallyCompressedRepData::deserialize(&mut deserializer).unwrap();
+        let mesh = rep_data.decode().unwrap();
+        assert_eq!(vec![vec![0, 1, 2]], mesh.faces);
+        assert_eq!(3, mesh.vertex_count());
+        assert_eq!(vec![1, 1, 1], mesh.vertex_flags);
+        assert_eq!(vec![[0, 1, 2]], mesh.triangles().collect::<Vec<[u32; 3]>>());
+    }
+
+    #[test]
+    fn decode_shared_edge() {
+        let data = rep_data(&[(0, &[3, 3])], &[2, 1, 2, 1], &[-1, -1, -1, 0], &[2]);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let rep_data = TopologicallyCompressedRepData::deserialize(&mut deserializer).unwrap();
+        let mesh = rep_data.decode().unwrap();
+        assert_eq!(vec![vec![0, 1, 2], vec![0, 3, 2]], mesh.faces);
+        assert_eq!(4, mesh.vertex_count());
+        assert_eq!(
+            vec![[0, 1, 2], [0, 3, 2]],
+            mesh.triangles().collect::<Vec<[u32; 3]>>()
+        );
+    }
+
+    #[test]
+    fn decode_exceeded_valence() {
+        let data = rep_data(&[(0, &[3, 3])], &[2, 1, 1, 1], &[-1, -1, -1, 0], &[1]);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let rep_data = TopologicallyCompressedRepData::deserialize(&mut deserializer).unwrap();
+        assert!(rep_data.decode().is_err());
+    }
+
+    #[test]
+    fn deserialize_vertex_records_with_normals() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(0x39u64.to_le_bytes());
+        data.extend([0u8; 4]);
+        data.extend(1i32.to_le_bytes());
+        data.extend(3i32.to_le_bytes());
+        for _ in 0..3 {
+            data.extend(0f32.to_le_bytes());
+            data.extend(1f32.to_le_bytes());
+            data.push(1u8);
+        }
+        data.extend(1i32.to_le_bytes());
+        [1, 0, 1]
+            .iter()
+            .for_each(|v| null_cdp_data(&mut data, &[*v]));
+        data.push(6u8);
+        data.extend(1i32.to_le_bytes());
+        (0..4).for_each(|_| null_cdp_data(&mut data, &[0]));
+        data.extend([0xFFu8; 8]);
+
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let records = TopologicallyCompressedVertexRecords::deserialize(&mut deserializer).unwrap();
+        assert_eq!(Some([1., 0., 1.]), records.vertex_data.coordinate(0));
+        let [x, y, z] = records.vertex_data.normal(0).unwrap();
+        assert!((1. - (x * x + y * y + z * z)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn decode_oversized_valence_and_degree() {
+        for data in [
+            rep_data(&[(0, &[3])], &[i32::MAX, 1, 1], &[-1, -1], &[]),
+            rep_data(&[(0, &[i32::MAX])], &[1, 1, 1], &[-1, -1], &[]),
+        ] {
+            let mut deserializer = Reader::new(Cursor::new(data));
+            let rep_data = TopologicallyCompressedRepData::deserialize(&mut deserializer).unwrap();
+            assert!(rep_data.decode().is_err());
+        }
+    }
+}
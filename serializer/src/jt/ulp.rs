@@ -0,0 +1,136 @@
+use std::io::{Cursor, Read};
+
+use super::{
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    object_type,
+    reader::Reader,
+    segment::{ElementHeader, ElementHeaderZlib, SegmentHeader, SegmentType},
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UlpElement {
+    pub element_header: ElementHeader,
+    pub version: i16,
+    pub ulp_data: Vec<u8>,
+}
+
+impl UlpElement {
+    const ELEMENT_HEADER_SIZE: i32 = 17;
+    const VERSION_SIZE: i32 = 2;
+}
+
+impl Deserialize for UlpElement {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let element_header = ElementHeader::deserialize(deserializer)?;
+        if object_type::ULP != element_header.object_type_id {
+            return Err("invalid ulp element".to_string());
+        }
+        let data_length = match element_header
+            .element_length
+            .checked_sub(Self::ELEMENT_HEADER_SIZE + Self::VERSION_SIZE)
+        {
+            Some(length) if 0 <= length => length as u64,
+            _ => return Err("invalid element length".to_string()),
+        };
+        let version = i16::deserialize(deserializer)?;
+        let mut ulp_data: Vec<u8> = vec![];
+        if let Err(e) = deserializer.take(data_length).read_to_end(&mut ulp_data) {
+            return Err(e.to_string());
+        }
+        if data_length != ulp_data.len() as u64 {
+            return Err("unexpected end of ulp data".to_string());
+        }
+        Ok(Self {
+            element_header,
+            version,
+            ulp_data,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UlpSegment {
+    pub header: SegmentHeader,
+    pub element: UlpElement,
+}
+
+impl UlpSegment {
+    pub fn ulp_data(&self) -> &[u8] {
+        &self.element.ulp_data
+    }
+}
+
+impl Deserialize for UlpSegment {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let header = SegmentHeader::deserialize(deserializer)?;
+        if SegmentType::Ulp != header.segment_type {
+            return Err("invalid ulp segment type".to_string());
+        }
+        let zlib_header = ElementHeaderZlib::deserialize(deserializer)?;
+        let element = if zlib_header.is_compressed() {
+            let mut inflated = Reader::nested(
+                Cursor::new(zlib_header.inflate(deserializer)?),
+                deserializer,
+            );
+            UlpElement::deserialize(&mut inflated)?
+        } else {
+            UlpElement::deserialize(deserializer)?
+        };
+        Ok(Self { header, element })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jt::{common::GUID, test_utils::extend_guid};
+
+    use super::*;
+
+    fn ulp_segment_data(element_length: i32) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        extend_guid(&mut data, &GUID(6, [0; 2], [0; 8]));
+        data.extend(20i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend(element_length.wrapping_add(5).to_le_bytes());
+        data.push(1u8);
+        data.extend(element_length.to_le_bytes());
+        extend_guid(&mut data, &object_type::ULP);
+        data.push(0u8);
+        data.extend(3i16.to_le_bytes());
+        data.extend([1u8, 2u8, 3u8, 4u8]);
+        data
+    }
+
+    #[test]
+    fn deserialize_ulp_segment() {
+        let mut deserializer = Reader::new(Cursor::new(ulp_segment_data(17 + 2 + 4)));
+        let segment = UlpSegment::deserialize(&mut deserializer).unwrap();
+        assert_eq!(SegmentType::Ulp, segment.header.segment_type);
+        assert_eq!(3, segment.element.version);
+        assert_eq!(&[1, 2, 3, 4], segment.ulp_data());
+    }
+
+    #[test]
+    fn deserialize_ulp_segment_with_invalid_length() {
+        let mut deserializer = Reader::new(Cursor::new(ulp_segment_data(10)));
+        assert!(UlpSegment::deserialize(&mut deserializer).is_err());
+        let mut deserializer = Reader::new(Cursor::new(ulp_segment_data(17 + 2 + 5)));
+        assert!(UlpSegment::deserialize(&mut deserializer).is_err());
+        for length in [i32::MIN, i32::MIN + 1, i32::MAX] {
+            let mut deserializer = Reader::new(Cursor::new(ulp_segment_data(length)));
+            assert!(UlpSegment::deserialize(&mut deserializer).is_err());
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl Version {
+    pub const V8_1: Self = Self::new(8, 1);
+    pub const V9_0: Self = Self::new(9, 0);
+    pub const V10_0: Self = Self::new(10, 0);
+
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Self::V8_1
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl TryFrom<&[u8]> for Version {
+    type Error = String;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let text: String = value
+            .iter()
+            .take_while(|c| c.is_ascii_graphic() || b' ' == **c)
+            .map(|c| *c as char)
+            .collect();
+        let mut words = text.split_whitespace();
+        if Some("Version") != words.next() {
+            return Err("invalid version".to_string());
+        }
+        let number = words.next().ok_or("invalid version")?;
+        let (major, minor) = number.split_once('.').ok_or("invalid version")?;
+        match (major.parse::<u8>(), minor.parse::<u8>()) {
+            (Ok(major), Ok(minor)) if 8 <= major => Ok(Self::new(major, minor)),
+            _ => Err("unsupported version".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_version(text: &str) -> [u8; 80] {
+        let mut data = [b' '; 80];
+        data[..text.len()].copy_from_slice(text.as_bytes());
+        data[75..].copy_from_slice(b"\n\r\n \0");
+        data
+    }
+
+    #[test]
+    fn version_from_header() {
+        assert_eq!(
+            Ok(Version::V8_1),
+            Version::try_from(&header_version("Version 8.1 JT")[..])
+        );
+        assert_eq!(
+            Ok(Version::new(9, 5)),
+            Version::try_from(&header_version("Version 9.5 JT")[..])
+        );
+        assert_eq!(
+            Ok(Version::V10_0),
+            Version::try_from(&header_version("Version 10.0 JT")[..])
+        );
+        assert!(Version::try_from(&header_version("Version 7.0 JT")[..]).is_err());
+        assert!(Version::try_from(&header_version("JT 8.1")[..]).is_err());
+    }
+
+    #[test]
+    fn version_ordering() {
+        assert!(Version::V8_1 < Version::new(9, 5));
+        assert!(Version::new(9, 5) < Version::V10_0);
+        assert_eq!("9.5", Version::new(9, 5).to_string());
+    }
+}
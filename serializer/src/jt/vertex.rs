@@ -0,0 +1,300 @@
+use geometria_derive::JtDeserialize;
+
+use crate::math::{Point3d, Vector3d};
+
+use super::{
+    cdp::{Int32Cdp, PredictorType},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    quantization::{DeeringNormalCodec, UniformQuantizerData},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Binding {
+    #[default]
+    None,
+    PerVertex,
+    PerFacet,
+    PerPrimitive,
+}
+
+impl TryFrom<u8> for Binding {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::PerVertex),
+            2 => Ok(Self::PerFacet),
+            3 => Ok(Self::PerPrimitive),
+            _ => Err("invalid binding".to_string()),
+        }
+    }
+}
+
+impl Deserialize for Binding {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        Self::try_from(u8::deserialize(deserializer)?)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VertexData {
+    pub coordinates: Vec<f32>,
+    pub normals: Vec<f32>,
+    pub texture_coordinates: Vec<f32>,
+    pub colors: Vec<f32>,
+}
+
+impl VertexData {
+    pub const COORDINATE_SIZE: usize = 3;
+    pub const NORMAL_SIZE: usize = 3;
+    pub const TEXTURE_COORDINATE_SIZE: usize = 2;
+    pub const COLOR_SIZE: usize = 3;
+
+    pub fn vertex_count(&self) -> usize {
+        self.coordinates.len() / Self::COORDINATE_SIZE
+    }
+
+    pub fn coordinate(&self, index: usize) -> Option<[f32; 3]> {
+        self.coordinates
+            .chunks_exact(Self::COORDINATE_SIZE)
+            .nth(index)
+            .map(|c| [c[0], c[1], c[2]])
+    }
+
+    pub fn normal(&self, index: usize) -> Option<[f32; 3]> {
+        self.normals
+            .chunks_exact(Self::NORMAL_SIZE)
+            .nth(index)
+            .map(|n| [n[0], n[1], n[2]])
+    }
+
+    pub fn color(&self, index: usize) -> Option<[f32; 3]> {
+        self.colors
+            .chunks_exact(Self::COLOR_SIZE)
+            .nth(index)
+            .map(|c| [c[0], c[1], c[2]])
+    }
+
+    pub fn point(&self, index: usize) -> Option<Point3d> {
+        self.coordinate(index).map(Point3d::from)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point3d> + '_ {
+        self.coordinates
+            .chunks_exact(Self::COORDINATE_SIZE)
+            .map(|c| Point3d::from([c[0], c[1], c[2]]))
+    }
+
+    pub fn normal_vector(&self, index: usize) -> Option<Vector3d> {
+        self.normal(index).map(Vector3d::from)
+    }
+
+    pub fn texture_coordinate(&self, index: usize) -> Option<[f32; 2]> {
+        self.texture_coordinates
+            .chunks_exact(Self::TEXTURE_COORDINATE_SIZE)
+            .nth(index)
+            .map(|t| [t[0], t[1]])
+    }
+
+    fn component_size(binding: Binding, size: usize) -> usize {
+        if Binding::PerVertex == binding {
+            size
+        } else {
+            0
+        }
+    }
+
+    pub fn from_interleaved(
+        raw: &[f32],
+        normal_binding: Binding,
+        texture_coord_binding: Binding,
+        color_binding: Binding,
+    ) -> Result<Self, String> {
+        let texture_coordinate_size =
+            Self::component_size(texture_coord_binding, Self::TEXTURE_COORDINATE_SIZE);
+        let color_size = Self::component_size(color_binding, Self::COLOR_SIZE);
+        let normal_size = Self::component_size(normal_binding, Self::NORMAL_SIZE);
+        let stride = texture_coordinate_size + color_size + normal_size + Self::COORDINATE_SIZE;
+        if !raw.len().is_multiple_of(stride) {
+            return Err("invalid interleaved vertex data length".to_string());
+        }
+        let mut vertex_data = Self::default();
+        for vertex in raw.chunks_exact(stride) {
+            let (texture_coordinate, vertex) = vertex.split_at(texture_coordinate_size);
+            let (color, vertex) = vertex.split_at(color_size);
+            let (normal, coordinate) = vertex.split_at(normal_size);
+            vertex_data
+                .texture_coordinates
+                .extend_from_slice(texture_coordinate);
+            vertex_data.colors.extend_from_slice(color);
+            vertex_data.normals.extend_from_slice(normal);
+            vertex_data.coordinates.extend_from_slice(coordinate);
+        }
+        Ok(vertex_data)
+    }
+}
+
+fn decode_component(
+    cdp: &Int32Cdp,
+    quantizer: &UniformQuantizerData,
+    count: usize,
+) -> Result<Vec<f32>, String> {
+    let codes = cdp.decode_with(PredictorType::Lag1)?;
+    if codes.len() != count {
+        Err("invalid quantized array length".to_string())
+    } else {
+        Ok(codes
+            .iter()
+            .map(|code| quantizer.dequantize(*code))
+            .collect())
+    }
+}
+
+fn interleave(components: &[Vec<f32>]) -> Vec<f32> {
+    let count = components.first().map_or(0, |c| c.len());
+    (0..count)
+        .flat_map(|i| components.iter().map(move |c| c[i]))
+        .collect()
+}
+
+#[derive(JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuantizedVertexCoordArray {
+    pub x_quantizer: UniformQuantizerData,
+    pub y_quantizer: UniformQuantizerData,
+    pub z_quantizer: UniformQuantizerData,
+    pub vertex_count: i32,
+    pub x_codes: Int32Cdp,
+    pub y_codes: Int32Cdp,
+    pub z_codes: Int32Cdp,
+}
+
+impl QuantizedVertexCoordArray {
+    pub fn decode(&self) -> Result<Vec<f32>, String> {
+        let count = self.vertex_count.max(0) as usize;
+        Ok(interleave(&[
+            decode_component(&self.x_codes, &self.x_quantizer, count)?,
+            decode_component(&self.y_codes, &self.y_quantizer, count)?,
+            decode_component(&self.z_codes, &self.z_quantizer, count)?,
+        ]))
+    }
+}
+
+#[derive(JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuantizedVertexNormalArray {
+    pub number_of_bits: u8,
+    pub normal_count: i32,
+    pub sextant_codes: Int32Cdp,
+    pub octant_codes: Int32Cdp,
+    pub theta_codes: Int32Cdp,
+    pub psi_codes: Int32Cdp,
+}
+
+impl QuantizedVertexNormalArray {
+    pub fn decode(&self) -> Result<Vec<f32>, String> {
+        let codec = DeeringNormalCodec::new(self.number_of_bits)?;
+        let count = self.normal_count.max(0) as usize;
+        let sextants = self.sextant_codes.decode_with(PredictorType::Lag1)?;
+        let octants = self.octant_codes.decode_with(PredictorType::Lag1)?;
+        let thetas = self.theta_codes.decode_with(PredictorType::Lag1)?;
+        let psis = self.psi_codes.decode_with(PredictorType::Lag1)?;
+        if [&sextants, &octants, &thetas, &psis]
+            .iter()
+            .any(|codes| codes.len() != count)
+        {
+            return Err("invalid quantized array length".to_string());
+        }
+        Ok((0..count)
+            .flat_map(|i| {
+                codec.decode(
+                    sextants[i] as u32,
+                    octants[i] as u32,
+                    thetas[i] as u32,
+                    psis[i] as u32,
+                )
+            })
+            .collect())
+    }
+}
+
+#[derive(JtDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuantizedVertexTextureCoordArray {
+    pub u_quantizer: UniformQuantizerData,
+    pub v_quantizer: UniformQuantizerData,
+    pub texture_coord_count: i32,
+    pub u_codes: Int32Cdp,
+    pub v_codes: Int32Cdp,
+}
+
+impl QuantizedVertexTextureCoordArray {
+    pub fn decode(&self) -> Result<Vec<f32>, String> {
+        let count = self.texture_coord_count.max(0) as usize;
+        Ok(interleave(&[
+            decode_component(&self.u_codes, &self.u_quantizer, count)?,
+            decode_component(&self.v_codes, &self.v_quantizer, count)?,
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_interleaved() {
+        let raw = [
+            0.5, 0.25, 0., 0., 1., 1., 2., 3., 0.75, 1., 0., 1., 0., 4., 5., 6.,
+        ];
+        let vertex_data = VertexData::from_interleaved(
+            &raw,
+            Binding::PerVertex,
+            Binding::PerVertex,
+            Binding::None,
+        )
+        .unwrap();
+        assert_eq!(2, vertex_data.vertex_count());
+        assert_eq!(Some([1., 2., 3.]), vertex_data.coordinate(0));
+        assert_eq!(Some([4., 5., 6.]), vertex_data.coordinate(1));
+        assert_eq!(Some([0., 0., 1.]), vertex_data.normal(0));
+        assert_eq!(Some([0., 1., 0.]), vertex_data.normal(1));
+        assert_eq!(Some([0.75, 1.]), vertex_data.texture_coordinate(1));
+        assert!(vertex_data.colors.is_empty());
+        assert_eq!(None, vertex_data.coordinate(2));
+        assert_eq!(Some(Point3d::new(4., 5., 6.)), vertex_data.point(1));
+        assert_eq!(Some(Vector3d::Z), vertex_data.normal_vector(0));
+        assert_eq!(
+            vec![Point3d::new(1., 2., 3.), Point3d::new(4., 5., 6.)],
+            vertex_data.points().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn from_interleaved_invalid_length() {
+        assert!(VertexData::from_interleaved(
+            &[0.; 5],
+            Binding::PerVertex,
+            Binding::None,
+            Binding::None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn interleave_components() {
+        assert_eq!(
+            vec![1., 4., 2., 5., 3., 6.],
+            interleave(&[vec![1., 2., 3.], vec![4., 5., 6.]])
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::{io::Cursor, ops::Range};
+
+use super::{
+    cdp::{Int32Cdp, PredictorType},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    object_type,
+    reader::Reader,
+    segment::{ElementHeader, ElementHeaderZlib, SegmentHeader, SegmentType},
+    vertex::{QuantizedVertexCoordArray, VertexData},
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WireframeRepElement {
+    pub element_header: ElementHeader,
+    pub version: i16,
+    pub coordinates: Vec<f32>,
+    pub polyline_indices: Vec<i32>,
+}
+
+impl WireframeRepElement {
+    pub fn vertex_count(&self) -> usize {
+        self.coordinates.len() / VertexData::COORDINATE_SIZE
+    }
+
+    pub fn polylines(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.polyline_indices
+            .windows(2)
+            .map(|w| (w[0] as usize)..(w[1] as usize))
+    }
+
+    pub fn polyline_points(&self) -> Vec<Vec<[f32; 3]>> {
+        self.polylines()
+            .map(|polyline| {
+                self.coordinates
+                    .chunks_exact(VertexData::COORDINATE_SIZE)
+                    .skip(polyline.start)
+                    .take(polyline.len())
+                    .map(|c| [c[0], c[1], c[2]])
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Deserialize for WireframeRepElement {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let element_header = ElementHeader::deserialize(deserializer)?;
+        if object_type::WIREFRAME_REP != element_header.object_type_id {
+            return Err("invalid wireframe rep element".to_string());
+        }
+        let version = i16::deserialize(deserializer)?;
+        let coordinates = QuantizedVertexCoordArray::deserialize(deserializer)?.decode()?;
+        let polyline_indices =
+            Int32Cdp::deserialize(deserializer)?.decode_with(PredictorType::Stride1)?;
+        let vertex_count = (coordinates.len() / VertexData::COORDINATE_SIZE) as i32;
+        if polyline_indices.windows(2).any(|w| w[0] > w[1])
+            || polyline_indices.iter().any(|i| 0 > *i || vertex_count < *i)
+        {
+            return Err("invalid wireframe polyline indices".to_string());
+        }
+        Ok(Self {
+            element_header,
+            version,
+            coordinates,
+            polyline_indices,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WireframeRepSegment {
+    pub header: SegmentHeader,
+    pub element: WireframeRepElement,
+}
+
+impl Deserialize for WireframeRepSegment {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let header = SegmentHeader::deserialize(deserializer)?;
+        if SegmentType::WireframeRepresentation != header.segment_type {
+            return Err("invalid wireframe segment type".to_string());
+        }
+        let zlib_header = ElementHeaderZlib::deserialize(deserializer)?;
+        let element = if zlib_header.is_compressed() {
+            let mut inflated = Reader::nested(
+                Cursor::new(zlib_header.inflate(deserializer)?),
+                deserializer,
+            );
+            WireframeRepElement::deserialize(&mut inflated)?
+        } else {
+            WireframeRepElement::deserialize(deserializer)?
+        };
+        Ok(Self { header, element })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use crate::jt::{
+        common::GUID,
+        test_utils::{extend_guid, null_cdp_data},
+    };
+
+    use super::*;
+
+    fn wireframe_segment_data(polyline_indices: &[i32]) -> Vec<u8> {
+        let mut body: Vec<u8> = vec![];
+        body.extend(1i16.to_le_bytes());
+        for (min, max) in [(0f32, 3f32), (0f32, 3f32), (2f32, 2f32)] {
+            body.extend(min.to_le_bytes());
+            body.extend(max.to_le_bytes());
+            body.push(2u8);
+        }
+        body.extend(3i32.to_le_bytes());
+        null_cdp_data(&mut body, &[0, 1, 3]);
+        null_cdp_data(&mut body, &[0, 0, 3]);
+        null_cdp_data(&mut body, &[0, 0, 0]);
+        null_cdp_data(&mut body, polyline_indices);
+
+        let mut element: Vec<u8> = vec![];
+        element.extend((17 + body.len() as i32).to_le_bytes());
+        extend_guid(&mut element, &object_type::WIREFRAME_REP);
+        element.push(0u8);
+        element.extend(body);
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&element).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut data: Vec<u8> = vec![];
+        extend_guid(&mut data, &GUID(5, [0; 2], [0; 8]));
+        data.extend(18i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        data.extend(2i32.to_le_bytes());
+        data.extend((compressed.len() as i32 + 1).to_le_bytes());
+        data.push(2u8);
+        data.extend(compressed);
+        data
+    }
+
+    #[test]
+    fn deserialize_wireframe_rep_segment() {
+        let mut deserializer = Reader::new(Cursor::new(wireframe_segment_data(&[0, 2, 3])));
+        let segment = WireframeRepSegment::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            SegmentType::WireframeRepresentation,
+            segment.header.segment_type
+        );
+        assert_eq!(1, segment.element.version);
+        assert_eq!(3, segment.element.vertex_count());
+        assert_eq!(
+            vec![0..2, 2..3],
+            segment.element.polylines().collect::<Vec<Range<usize>>>()
+        );
+        assert_eq!(
+            vec![vec![[0., 0., 2.], [1., 0., 2.]], vec![[3., 3., 2.]]],
+            segment.element.polyline_points()
+        );
+    }
+
+    #[test]
+    fn deserialize_wireframe_rep_with_invalid_indices() {
+        let mut deserializer = Reader::new(Cursor::new(wireframe_segment_data(&[0, 4])));
+        assert!(WireframeRepSegment::deserialize(&mut deserializer).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::Cursor;
+
+use super::{
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    object_type,
+    reader::Reader,
+    segment::{ElementHeader, ElementHeaderZlib, SegmentHeader, SegmentType},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum XtFormat {
+    Text,
+    Binary,
+    Unknown,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct XtBRepElement {
+    pub element_header: ElementHeader,
+    pub version: i16,
+    pub xt_data: Vec<u8>,
+}
+
+impl XtBRepElement {
+    pub fn format(&self) -> XtFormat {
+        if self.xt_data.starts_with(b"**") {
+            XtFormat::Text
+        } else if self.xt_data.starts_with(b"PS") {
+            XtFormat::Binary
+        } else {
+            XtFormat::Unknown
+        }
+    }
+}
+
+impl Deserialize for XtBRepElement {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let element_header = ElementHeader::deserialize(deserializer)?;
+        if object_type::XT_BREP != element_header.object_type_id {
+            return Err("invalid xt brep element".to_string());
+        }
+        Ok(Self {
+            element_header,
+            version: i16::deserialize(deserializer)?,
+            xt_data: Vec::<u8>::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct XtBRepSegment {
+    pub header: SegmentHeader,
+    pub compressed: bool,
+    pub element: XtBRepElement,
+}
+
+impl XtBRepSegment {
+    pub fn parasolid_data(&self) -> &[u8] {
+        &self.element.xt_data
+    }
+}
+
+impl Deserialize for XtBRepSegment {
+    type Error = String;
+
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, Self::Error>
+    where
+        D: Deserializer,
+    {
+        let header = SegmentHeader::deserialize(deserializer)?;
+        if SegmentType::XtBRep != header.segment_type {
+            return Err("invalid xt brep segment type".to_string());
+        }
+        let zlib_header = ElementHeaderZlib::deserialize(deserializer)?;
+        let element = if zlib_header.is_compressed() {
+            let mut inflated = Reader::nested(
+                Cursor::new(zlib_header.inflate(deserializer)?),
+                deserializer,
+            );
+            XtBRepElement::deserialize(&mut inflated)?
+        } else {
+            XtBRepElement::deserialize(deserializer)?
+        };
+        Ok(Self {
+            header,
+            compressed: zlib_header.is_compressed(),
+            element,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use crate::jt::{common::GUID, test_utils::extend_guid};
+
+    use super::*;
+
+    fn xt_segment_data(compress: bool) -> Vec<u8> {
+        let xt = b"**ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz**";
+        let mut element: Vec<u8> = vec![];
+        element.extend((17 + 2 + 4 + xt.len() as i32).to_le_bytes());
+        extend_guid(&mut element, &object_type::XT_BREP);
+        element.push(0u8);
+        element.extend(1i16.to_le_bytes());
+        element.extend((xt.len() as i32).to_le_bytes());
+        element.extend(xt);
+
+        let mut data: Vec<u8> = vec![];
+        extend_guid(&mut data, &GUID(3, [0; 2], [0; 8]));
+        data.extend(17i32.to_le_bytes());
+        data.extend(0i32.to_le_bytes());
+        if compress {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&element).unwrap();
+            let compressed = encoder.finish().unwrap();
+            data.extend(2i32.to_le_bytes());
+            data.extend((compressed.len() as i32 + 1).to_le_bytes());
+            data.push(2u8);
+            data.extend(compressed);
+        } else {
+            data.extend(0i32.to_le_bytes());
+            data.extend((element.len() as i32 + 1).to_le_bytes());
+            data.push(1u8);
+            data.extend(element);
+        }
+        data
+    }
+
+    #[test]
+    fn deserialize_xt_brep_segment() {
+        for compress in [false, true] {
+            let mut deserializer = Reader::new(Cursor::new(xt_segment_data(compress)));
+            let segment = XtBRepSegment::deserialize(&mut deserializer).unwrap();
+            assert_eq!(compress, segment.compressed);
+            assert_eq!(3, segment.header.segment_id.0);
+            assert_eq!(1, segment.element.version);
+            assert_eq!(XtFormat::Text, segment.element.format());
+            assert!(segment.parasolid_data().starts_with(b"**ABC"));
+            assert_eq!(56, segment.parasolid_data().len());
+        }
+    }
+
+    #[test]
+    fn deserialize_invalid_segment_type() {
+        let mut data = xt_segment_data(false);
+        data[16..20].copy_from_slice(&4i32.to_le_bytes());
+        let mut deserializer = Reader::new(Cursor::new(data));
+        assert!(XtBRepSegment::deserialize(&mut deserializer).is_err());
+    }
+}
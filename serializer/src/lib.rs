@@ -1,3 +1,12 @@
 pub mod common;
+pub mod export;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+pub mod geometry;
 pub mod jt;
+pub mod math;
+pub mod model;
 pub mod rhino;
+pub mod step;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,160 @@
+use std::f64::consts::TAU;
+
+use super::{circle::Circle, plane::Plane, point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Arc {
+    pub circle: Circle,
+    pub start_angle: f64,
+    pub end_angle: f64,
+}
+
+impl Arc {
+    pub fn new(circle: Circle, start_angle: f64, end_angle: f64) -> Option<Self> {
+        let sweep = end_angle - start_angle;
+        if 0. < sweep && sweep <= TAU {
+            Some(Self {
+                circle,
+                start_angle,
+                end_angle,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn from_three_points(start: Point3d, interior: Point3d, end: Point3d) -> Option<Self> {
+        let circle = Circle::from_three_points(start, interior, end)?;
+        let interior_angle = circle.closest_angle(&interior)?;
+        let end_angle = circle.closest_angle(&end)?;
+        if interior_angle < end_angle {
+            Self::new(circle, 0., end_angle)
+        } else {
+            let plane = Plane {
+                y_axis: -circle.plane.y_axis,
+                z_axis: -circle.plane.z_axis,
+                ..circle.plane
+            };
+            let circle = Circle::new(plane, circle.radius);
+            Self::new(circle, 0., circle.closest_angle(&end)?)
+        }
+    }
+
+    pub fn angle(&self) -> f64 {
+        self.end_angle - self.start_angle
+    }
+
+    pub fn is_circle(&self) -> bool {
+        (self.angle() - TAU).abs() <= f64::EPSILON * TAU
+    }
+
+    pub fn length(&self) -> f64 {
+        self.angle() * self.circle.radius.abs()
+    }
+
+    pub fn point_at(&self, angle: f64) -> Point3d {
+        self.circle.point_at(angle)
+    }
+
+    pub fn tangent_at(&self, angle: f64) -> Vector3d {
+        self.circle.tangent_at(angle)
+    }
+
+    pub fn closest_angle(&self, point: &Point3d) -> f64 {
+        let angle = match self.circle.closest_angle(point) {
+            Some(angle) => self.start_angle + (angle - self.start_angle).rem_euclid(TAU),
+            None => return self.start_angle,
+        };
+        if angle <= self.end_angle {
+            angle
+        } else if point.distance_to(&self.start_point()) <= point.distance_to(&self.end_point()) {
+            self.start_angle
+        } else {
+            self.end_angle
+        }
+    }
+
+    pub fn closest_point(&self, point: &Point3d) -> Point3d {
+        self.point_at(self.closest_angle(point))
+    }
+
+    pub fn start_point(&self) -> Point3d {
+        self.point_at(self.start_angle)
+    }
+
+    pub fn mid_point(&self) -> Point3d {
+        self.point_at(0.5 * (self.start_angle + self.end_angle))
+    }
+
+    pub fn end_point(&self) -> Point3d {
+        self.point_at(self.end_angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    fn assert_point_close(expected: Point3d, actual: Point3d) {
+        assert!(
+            expected.distance_to(&actual) < 1e-12,
+            "{:?} != {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        let arc = Arc::new(Circle::new(Plane::WORLD_XY, 2.), 0., FRAC_PI_2).unwrap();
+        assert_eq!(PI, arc.length());
+        assert_point_close(Point3d::new(2., 0., 0.), arc.start_point());
+        assert_point_close(Point3d::new(0., 2., 0.), arc.end_point());
+        assert_point_close(Point3d::new(2f64.sqrt(), 2f64.sqrt(), 0.), arc.mid_point());
+        assert!(!arc.is_circle());
+        assert!(Arc::new(Circle::new(Plane::WORLD_XY, 1.), 0., TAU)
+            .unwrap()
+            .is_circle());
+        assert!(Arc::new(Circle::new(Plane::WORLD_XY, 1.), 1., 1.).is_none());
+        assert!(Arc::new(Circle::new(Plane::WORLD_XY, 1.), 0., 7.).is_none());
+    }
+
+    #[test]
+    fn closest_point() {
+        let arc = Arc::new(Circle::new(Plane::WORLD_XY, 2.), FRAC_PI_2, PI).unwrap();
+        assert_point_close(
+            Point3d::new(-2f64.sqrt(), 2f64.sqrt(), 0.),
+            arc.closest_point(&Point3d::new(-3., 3., 1.)),
+        );
+        assert_point_close(
+            Point3d::new(0., 2., 0.),
+            arc.closest_point(&Point3d::new(1., 0.5, 0.)),
+        );
+        assert_point_close(
+            Point3d::new(-2., 0., 0.),
+            arc.closest_point(&Point3d::new(-1., -3., 0.)),
+        );
+        assert_eq!(FRAC_PI_2, arc.closest_angle(&Point3d::ORIGIN));
+        let wrapped = Arc::new(Circle::new(Plane::WORLD_XY, 1.), -FRAC_PI_2, FRAC_PI_2).unwrap();
+        assert!(
+            (-0.5 - wrapped.closest_angle(&Point3d::new(0.5f64.cos(), -0.5f64.sin(), 0.))).abs()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn from_three_points() {
+        let start = Point3d::new(1., 0., 0.);
+        let end = Point3d::new(-1., 0., 0.);
+        let arc = Arc::from_three_points(start, Point3d::new(0., 1., 0.), end).unwrap();
+        assert_point_close(start, arc.start_point());
+        assert_point_close(end, arc.end_point());
+        assert_point_close(Point3d::new(0., 1., 0.), arc.mid_point());
+        let arc = Arc::from_three_points(start, Point3d::new(0., -1., 0.), end).unwrap();
+        assert_point_close(Point3d::new(0., -1., 0.), arc.mid_point());
+        assert!((PI - arc.angle()).abs() < 1e-12);
+    }
+}
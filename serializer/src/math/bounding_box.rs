@@ -0,0 +1,222 @@
+use super::{point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BoundingBox {
+    pub min: Point3d,
+    pub max: Point3d,
+}
+
+impl BoundingBox {
+    pub const EMPTY: Self = Self {
+        min: Point3d::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        max: Point3d::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+    };
+
+    pub fn new(a: Point3d, b: Point3d) -> Self {
+        Self {
+            min: Point3d::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3d::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    pub fn from_points<I: IntoIterator<Item = Point3d>>(points: I) -> Self {
+        let mut bounding_box = Self::EMPTY;
+        bounding_box.extend(points);
+        bounding_box
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z)
+    }
+
+    pub fn include(&mut self, point: &Point3d) {
+        self.min = Point3d::new(
+            self.min.x.min(point.x),
+            self.min.y.min(point.y),
+            self.min.z.min(point.z),
+        );
+        self.max = Point3d::new(
+            self.max.x.max(point.x),
+            self.max.y.max(point.y),
+            self.max.z.max(point.z),
+        );
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Self::new(
+                Point3d::new(
+                    self.min.x.min(other.min.x),
+                    self.min.y.min(other.min.y),
+                    self.min.z.min(other.min.z),
+                ),
+                Point3d::new(
+                    self.max.x.max(other.max.x),
+                    self.max.y.max(other.max.y),
+                    self.max.z.max(other.max.z),
+                ),
+            )
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self {
+            min: Point3d::new(
+                self.min.x.max(other.min.x),
+                self.min.y.max(other.min.y),
+                self.min.z.max(other.min.z),
+            ),
+            max: Point3d::new(
+                self.max.x.min(other.max.x),
+                self.max.y.min(other.max.y),
+                self.max.z.min(other.max.z),
+            ),
+        };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn contains(&self, point: &Point3d) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn contains_box(&self, other: &Self) -> bool {
+        other.is_empty() || (self.contains(&other.min) && self.contains(&other.max))
+    }
+
+    pub fn center(&self) -> Point3d {
+        self.min.midpoint(&self.max)
+    }
+
+    pub fn distance_to(&self, point: &Point3d) -> f64 {
+        if self.is_empty() {
+            return f64::INFINITY;
+        }
+        let closest = Point3d::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        );
+        point.distance_to(&closest)
+    }
+
+    pub fn diagonal(&self) -> Vector3d {
+        self.max - self.min
+    }
+
+    pub fn corners(&self) -> [Point3d; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Point3d::new(min.x, min.y, min.z),
+            Point3d::new(max.x, min.y, min.z),
+            Point3d::new(max.x, max.y, min.z),
+            Point3d::new(min.x, max.y, min.z),
+            Point3d::new(min.x, min.y, max.z),
+            Point3d::new(max.x, min.y, max.z),
+            Point3d::new(max.x, max.y, max.z),
+            Point3d::new(min.x, max.y, max.z),
+        ]
+    }
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Extend<Point3d> for BoundingBox {
+    fn extend<I: IntoIterator<Item = Point3d>>(&mut self, points: I) {
+        points.into_iter().for_each(|point| self.include(&point));
+    }
+}
+
+impl FromIterator<Point3d> for BoundingBox {
+    fn from_iter<I: IntoIterator<Item = Point3d>>(points: I) -> Self {
+        Self::from_points(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_points() {
+        let bounding_box: BoundingBox = [
+            Point3d::new(1., -2., 3.),
+            Point3d::new(-1., 4., 0.),
+            Point3d::new(0., 0., 5.),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(Point3d::new(-1., -2., 0.), bounding_box.min);
+        assert_eq!(Point3d::new(1., 4., 5.), bounding_box.max);
+        assert_eq!(Point3d::new(0., 1., 2.5), bounding_box.center());
+        assert_eq!(Vector3d::new(2., 6., 5.), bounding_box.diagonal());
+        assert!(!bounding_box.is_empty());
+        assert!(BoundingBox::default().is_empty());
+        assert!(BoundingBox::from_points([]).is_empty());
+    }
+
+    #[test]
+    fn union() {
+        let a = BoundingBox::new(Point3d::ORIGIN, Point3d::new(1., 1., 1.));
+        let b = BoundingBox::new(Point3d::new(2., -1., 0.5), Point3d::new(3., 0., 0.75));
+        let union = a.union(&b);
+        assert_eq!(Point3d::new(0., -1., 0.), union.min);
+        assert_eq!(Point3d::new(3., 1., 1.), union.max);
+        assert_eq!(a, a.union(&BoundingBox::EMPTY));
+        assert_eq!(a, BoundingBox::EMPTY.union(&a));
+        assert!(union.contains_box(&a));
+        assert!(union.contains_box(&b));
+        assert!(!a.contains_box(&union));
+    }
+
+    #[test]
+    fn intersection() {
+        let a = BoundingBox::new(Point3d::ORIGIN, Point3d::new(2., 2., 2.));
+        let b = BoundingBox::new(Point3d::new(1., 1., 1.), Point3d::new(3., 3., 3.));
+        let c = BoundingBox::new(Point3d::new(5., 5., 5.), Point3d::new(6., 6., 6.));
+        assert_eq!(
+            Some(BoundingBox::new(
+                Point3d::new(1., 1., 1.),
+                Point3d::new(2., 2., 2.)
+            )),
+            a.intersection(&b)
+        );
+        assert!(a.intersects(&b));
+        assert_eq!(None, a.intersection(&c));
+        assert!(!a.intersects(&BoundingBox::EMPTY));
+    }
+
+    #[test]
+    fn contains() {
+        let bounding_box = BoundingBox::new(Point3d::new(1., 1., 1.), Point3d::ORIGIN);
+        assert!(bounding_box.contains(&Point3d::ORIGIN));
+        assert!(bounding_box.contains(&Point3d::new(0.5, 1., 0.25)));
+        assert!(!bounding_box.contains(&Point3d::new(0.5, 1.5, 0.25)));
+        assert!(!BoundingBox::EMPTY.contains(&Point3d::ORIGIN));
+        assert_eq!(8, bounding_box.corners().len());
+        assert!(bounding_box
+            .corners()
+            .iter()
+            .all(|corner| bounding_box.contains(corner)));
+        assert_eq!(0., bounding_box.distance_to(&Point3d::new(0.5, 0.5, 0.5)));
+        assert_eq!(5., bounding_box.distance_to(&Point3d::new(4., 5., 0.5)));
+        assert_eq!(
+            f64::INFINITY,
+            BoundingBox::EMPTY.distance_to(&Point3d::ORIGIN)
+        );
+    }
+}
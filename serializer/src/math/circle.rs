@@ -0,0 +1,141 @@
+use std::f64::consts::TAU;
+
+use super::{plane::Plane, point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Circle {
+    pub plane: Plane,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(plane: Plane, radius: f64) -> Self {
+        Self { plane, radius }
+    }
+
+    pub fn from_center(center: Point3d, normal: &Vector3d, radius: f64) -> Option<Self> {
+        Some(Self::new(Plane::from_normal(center, normal)?, radius))
+    }
+
+    pub fn from_three_points(a: Point3d, b: Point3d, c: Point3d) -> Option<Self> {
+        let ab = b - a;
+        let ac = c - a;
+        let normal = ab.cross(&ac);
+        let denominator = 2. * normal.length_squared();
+        if denominator.is_nan() || 0. >= denominator {
+            return None;
+        }
+        let offset = (normal.cross(&ab) * ac.length_squared()
+            + ac.cross(&normal) * ab.length_squared())
+            / denominator;
+        let center = a + offset;
+        Some(Self::new(
+            Plane::new(center, &(a - center), &normal.cross(&(a - center)))?,
+            offset.length(),
+        ))
+    }
+
+    pub fn center(&self) -> Point3d {
+        self.plane.origin
+    }
+
+    pub fn normal(&self) -> Vector3d {
+        self.plane.z_axis
+    }
+
+    pub fn diameter(&self) -> f64 {
+        2. * self.radius
+    }
+
+    pub fn circumference(&self) -> f64 {
+        TAU * self.radius.abs()
+    }
+
+    pub fn point_at(&self, angle: f64) -> Point3d {
+        let (sin, cos) = angle.sin_cos();
+        self.plane.point_at(self.radius * cos, self.radius * sin)
+    }
+
+    pub fn tangent_at(&self, angle: f64) -> Vector3d {
+        let (sin, cos) = angle.sin_cos();
+        self.plane.y_axis * cos - self.plane.x_axis * sin
+    }
+
+    pub fn closest_angle(&self, point: &Point3d) -> Option<f64> {
+        let (u, v) = self.plane.closest_parameters(point);
+        if 0. == u && 0. == v {
+            None
+        } else {
+            Some(v.atan2(u).rem_euclid(TAU))
+        }
+    }
+
+    pub fn closest_point(&self, point: &Point3d) -> Point3d {
+        self.point_at(self.closest_angle(point).unwrap_or(0.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    fn assert_point_close(expected: Point3d, actual: Point3d) {
+        assert!(
+            expected.distance_to(&actual) < 1e-12,
+            "{:?} != {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        let circle = Circle::new(
+            Plane {
+                origin: Point3d::new(1., 1., 0.),
+                ..Plane::WORLD_XY
+            },
+            2.,
+        );
+        assert_eq!(4., circle.diameter());
+        assert_eq!(4. * PI, circle.circumference());
+        assert_point_close(Point3d::new(3., 1., 0.), circle.point_at(0.));
+        assert_point_close(Point3d::new(1., 3., 0.), circle.point_at(FRAC_PI_2));
+        assert!((circle.tangent_at(0.) - Vector3d::Y).length() < 1e-12);
+        assert!(
+            (circle.closest_angle(&Point3d::new(1., -5., 0.)).unwrap() - 1.5 * PI).abs() < 1e-12
+        );
+        assert_eq!(None, circle.closest_angle(&circle.center()));
+        assert_point_close(
+            Point3d::new(1., -1., 0.),
+            circle.closest_point(&Point3d::new(1., -5., 3.)),
+        );
+        assert_point_close(
+            Point3d::new(3., 1., 0.),
+            circle.closest_point(&circle.center()),
+        );
+    }
+
+    #[test]
+    fn from_three_points() {
+        let circle = Circle::from_three_points(
+            Point3d::new(1., 0., 2.),
+            Point3d::new(-1., 0., 2.),
+            Point3d::new(0., 1., 2.),
+        )
+        .unwrap();
+        assert_point_close(Point3d::new(0., 0., 2.), circle.center());
+        assert!((1. - circle.radius).abs() < 1e-12);
+        assert_point_close(Point3d::new(1., 0., 2.), circle.point_at(0.));
+        assert!(Circle::from_three_points(
+            Point3d::ORIGIN,
+            Point3d::new(1., 1., 1.),
+            Point3d::new(2., 2., 2.)
+        )
+        .is_none());
+        assert!(Circle::from_center(Point3d::ORIGIN, &Vector3d::ZERO, 1.).is_none());
+    }
+}
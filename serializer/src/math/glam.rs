@@ -0,0 +1,79 @@
+use crate::jt::common::Quaternion;
+
+use super::{point::Point3d, vector::Vector3d, xform::Xform};
+
+impl From<Point3d> for glam::DVec3 {
+    fn from(point: Point3d) -> Self {
+        Self::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<glam::DVec3> for Point3d {
+    fn from(point: glam::DVec3) -> Self {
+        Self::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Vector3d> for glam::DVec3 {
+    fn from(vector: Vector3d) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<glam::DVec3> for Vector3d {
+    fn from(vector: glam::DVec3) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Xform> for glam::DMat4 {
+    fn from(xform: Xform) -> Self {
+        Self::from_cols_array_2d(&xform.transpose().0)
+    }
+}
+
+impl From<glam::DMat4> for Xform {
+    fn from(matrix: glam::DMat4) -> Self {
+        Self(matrix.to_cols_array_2d()).transpose()
+    }
+}
+
+impl From<Quaternion> for glam::Quat {
+    fn from(quaternion: Quaternion) -> Self {
+        let [w, x, y, z] = quaternion.0;
+        Self::from_xyzw(x, y, z, w)
+    }
+}
+
+impl From<glam::Quat> for Quaternion {
+    fn from(quaternion: glam::Quat) -> Self {
+        Self([quaternion.w, quaternion.x, quaternion.y, quaternion.z])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert() {
+        let point = Point3d::new(1., 2., 3.);
+        assert_eq!(glam::DVec3::new(1., 2., 3.), glam::DVec3::from(point));
+        assert_eq!(point, Point3d::from(glam::DVec3::from(point)));
+        assert_eq!(Vector3d::Z, Vector3d::from(glam::DVec3::Z));
+
+        let xform = Xform::translation(&Vector3d::new(1., 2., 3.))
+            * Xform::rotation(1., &Vector3d::new(1., 1., 0.)).unwrap();
+        let matrix = glam::DMat4::from(xform);
+        assert_eq!(
+            glam::DVec3::from(xform.transform_point(&point)),
+            matrix.transform_point3(point.into())
+        );
+        assert_eq!(xform, Xform::from(matrix));
+
+        let quaternion = Quaternion::from_axis_angle([0., 0., 1.], 1.).unwrap();
+        let quat = glam::Quat::from(quaternion);
+        assert!(quat.abs_diff_eq(glam::Quat::from_rotation_z(1.), 1e-6));
+        assert_eq!(quaternion, Quaternion::from(quat));
+    }
+}
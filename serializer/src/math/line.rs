@@ -0,0 +1,83 @@
+use super::{point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Line {
+    pub from: Point3d,
+    pub to: Point3d,
+}
+
+impl Line {
+    pub const fn new(from: Point3d, to: Point3d) -> Self {
+        Self { from, to }
+    }
+
+    pub fn direction(&self) -> Vector3d {
+        self.to - self.from
+    }
+
+    pub fn tangent(&self) -> Option<Vector3d> {
+        self.direction().normalized()
+    }
+
+    pub fn length(&self) -> f64 {
+        self.from.distance_to(&self.to)
+    }
+
+    pub fn point_at(&self, t: f64) -> Point3d {
+        self.from.lerp(&self.to, t)
+    }
+
+    pub fn closest_parameter(&self, point: &Point3d) -> Option<f64> {
+        let direction = self.direction();
+        let length_squared = direction.length_squared();
+        if 0. < length_squared {
+            Some((*point - self.from).dot(&direction) / length_squared)
+        } else {
+            None
+        }
+    }
+
+    pub fn closest_point(&self, point: &Point3d, finite: bool) -> Point3d {
+        match self.closest_parameter(point) {
+            Some(t) if finite => self.point_at(t.clamp(0., 1.)),
+            Some(t) => self.point_at(t),
+            None => self.from,
+        }
+    }
+
+    pub fn reversed(&self) -> Self {
+        Self::new(self.to, self.from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate() {
+        let line = Line::new(Point3d::new(1., 0., 0.), Point3d::new(1., 4., 3.));
+        assert_eq!(5., line.length());
+        assert_eq!(Vector3d::new(0., 4., 3.), line.direction());
+        assert_eq!(Some(Vector3d::new(0., 0.8, 0.6)), line.tangent());
+        assert_eq!(Point3d::new(1., 2., 1.5), line.point_at(0.5));
+        assert_eq!(line.from, line.reversed().to);
+        assert_eq!(None, Line::default().tangent());
+    }
+
+    #[test]
+    fn closest_point() {
+        let line = Line::new(Point3d::ORIGIN, Point3d::new(2., 0., 0.));
+        assert_eq!(Some(1.5), line.closest_parameter(&Point3d::new(3., 1., 0.)));
+        assert_eq!(
+            Point3d::new(2., 0., 0.),
+            line.closest_point(&Point3d::new(3., 1., 0.), true)
+        );
+        assert_eq!(
+            Point3d::new(3., 0., 0.),
+            line.closest_point(&Point3d::new(3., 1., 0.), false)
+        );
+        assert_eq!(None, Line::default().closest_parameter(&Point3d::ORIGIN));
+    }
+}
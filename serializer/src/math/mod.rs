@@ -0,0 +1,25 @@
+pub mod arc;
+pub mod bounding_box;
+pub mod circle;
+#[cfg(feature = "glam")]
+mod glam;
+pub mod line;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+pub mod plane;
+pub mod point;
+pub mod ray;
+pub mod transformable;
+pub mod vector;
+pub mod xform;
+
+pub use arc::Arc;
+pub use bounding_box::BoundingBox;
+pub use circle::Circle;
+pub use line::Line;
+pub use plane::Plane;
+pub use point::Point3d;
+pub use ray::Ray;
+pub use transformable::Transformable;
+pub use vector::Vector3d;
+pub use xform::Xform;
@@ -0,0 +1,86 @@
+use crate::jt::common::Quaternion;
+
+use super::{point::Point3d, vector::Vector3d, xform::Xform};
+
+impl From<Point3d> for nalgebra::Point3<f64> {
+    fn from(point: Point3d) -> Self {
+        Self::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<nalgebra::Point3<f64>> for Point3d {
+    fn from(point: nalgebra::Point3<f64>) -> Self {
+        Self::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Vector3d> for nalgebra::Vector3<f64> {
+    fn from(vector: Vector3d) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<nalgebra::Vector3<f64>> for Vector3d {
+    fn from(vector: nalgebra::Vector3<f64>) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Xform> for nalgebra::Matrix4<f64> {
+    fn from(xform: Xform) -> Self {
+        Self::from_fn(|row, column| xform.0[row][column])
+    }
+}
+
+impl From<nalgebra::Matrix4<f64>> for Xform {
+    fn from(matrix: nalgebra::Matrix4<f64>) -> Self {
+        let mut xform = Self::ZERO;
+        for (row, values) in xform.0.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = matrix[(row, column)];
+            }
+        }
+        xform
+    }
+}
+
+impl From<Quaternion> for nalgebra::Quaternion<f32> {
+    fn from(quaternion: Quaternion) -> Self {
+        let [w, x, y, z] = quaternion.0;
+        Self::new(w, x, y, z)
+    }
+}
+
+impl From<nalgebra::Quaternion<f32>> for Quaternion {
+    fn from(quaternion: nalgebra::Quaternion<f32>) -> Self {
+        Self([quaternion.w, quaternion.i, quaternion.j, quaternion.k])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert() {
+        let point = Point3d::new(1., 2., 3.);
+        assert_eq!(nalgebra::Point3::new(1., 2., 3.), point.into());
+        assert_eq!(point, Point3d::from(nalgebra::Point3::from(point)));
+        assert_eq!(Vector3d::Z, nalgebra::Vector3::z().into());
+
+        let xform = Xform::translation(&Vector3d::new(1., 2., 3.))
+            * Xform::rotation(1., &Vector3d::new(1., 1., 0.)).unwrap();
+        let matrix = nalgebra::Matrix4::from(xform);
+        assert_eq!(
+            nalgebra::Point3::from(xform.transform_point(&point)),
+            matrix.transform_point(&point.into())
+        );
+        assert_eq!(xform, Xform::from(matrix));
+
+        let quaternion = Quaternion::from_axis_angle([0., 0., 1.], 1.).unwrap();
+        let rotation = nalgebra::UnitQuaternion::from_axis_angle(&nalgebra::Vector3::z_axis(), 1.);
+        let converted = nalgebra::Quaternion::from(quaternion);
+        assert!((converted - *rotation.quaternion()).norm() < 1e-6);
+        assert_eq!(quaternion, Quaternion::from(converted));
+    }
+}
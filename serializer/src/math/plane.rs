@@ -0,0 +1,165 @@
+use super::{point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Plane {
+    pub origin: Point3d,
+    pub x_axis: Vector3d,
+    pub y_axis: Vector3d,
+    pub z_axis: Vector3d,
+}
+
+impl Plane {
+    pub const WORLD_XY: Self = Self {
+        origin: Point3d::ORIGIN,
+        x_axis: Vector3d::X,
+        y_axis: Vector3d::Y,
+        z_axis: Vector3d::Z,
+    };
+
+    pub fn new(origin: Point3d, x_axis: &Vector3d, y_axis: &Vector3d) -> Option<Self> {
+        let x_axis = x_axis.normalized()?;
+        let z_axis = x_axis.cross(y_axis).normalized()?;
+        Some(Self {
+            origin,
+            x_axis,
+            y_axis: z_axis.cross(&x_axis),
+            z_axis,
+        })
+    }
+
+    pub fn from_normal(origin: Point3d, normal: &Vector3d) -> Option<Self> {
+        let z_axis = normal.normalized()?;
+        let reference = if z_axis.x.abs() < 0.9 {
+            Vector3d::X
+        } else {
+            Vector3d::Y
+        };
+        let x_axis = reference.cross(&z_axis).cross(&z_axis).normalized()?;
+        Some(Self {
+            origin,
+            x_axis: -x_axis,
+            y_axis: z_axis.cross(&-x_axis),
+            z_axis,
+        })
+    }
+
+    pub fn from_equation(equation: &[f64; 4]) -> Option<Self> {
+        let normal = Vector3d::new(equation[0], equation[1], equation[2]);
+        let length_squared = normal.length_squared();
+        if length_squared.is_nan() || 0. >= length_squared {
+            return None;
+        }
+        let origin = Point3d::ORIGIN + normal * (-equation[3] / length_squared);
+        Self::from_normal(origin, &normal)
+    }
+
+    pub fn from_points(a: Point3d, b: Point3d, c: Point3d) -> Option<Self> {
+        Self::new(a, &(b - a), &(c - a))
+    }
+
+    pub fn normal(&self) -> Vector3d {
+        self.z_axis
+    }
+
+    pub fn equation(&self) -> [f64; 4] {
+        let n = self.z_axis;
+        [n.x, n.y, n.z, -n.dot(&self.origin.to_vector())]
+    }
+
+    pub fn point_at(&self, u: f64, v: f64) -> Point3d {
+        self.origin + self.x_axis * u + self.y_axis * v
+    }
+
+    pub fn signed_distance_to(&self, point: &Point3d) -> f64 {
+        (*point - self.origin).dot(&self.z_axis)
+    }
+
+    pub fn closest_point(&self, point: &Point3d) -> Point3d {
+        *point - self.z_axis * self.signed_distance_to(point)
+    }
+
+    pub fn closest_parameters(&self, point: &Point3d) -> (f64, f64) {
+        let offset = *point - self.origin;
+        (offset.dot(&self.x_axis), offset.dot(&self.y_axis))
+    }
+
+    pub fn flipped(&self) -> Self {
+        Self {
+            origin: self.origin,
+            x_axis: self.y_axis,
+            y_axis: self.x_axis,
+            z_axis: -self.z_axis,
+        }
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Self::WORLD_XY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let plane = Plane::new(
+            Point3d::ORIGIN,
+            &Vector3d::new(2., 0., 0.),
+            &Vector3d::new(1., 1., 0.),
+        )
+        .unwrap();
+        assert_eq!(Plane::WORLD_XY, plane);
+        assert!(Plane::new(Point3d::ORIGIN, &Vector3d::X, &Vector3d::X).is_none());
+        assert!(Plane::new(Point3d::ORIGIN, &Vector3d::ZERO, &Vector3d::Y).is_none());
+        let plane = Plane::from_points(
+            Point3d::new(0., 0., 1.),
+            Point3d::new(0., 1., 1.),
+            Point3d::new(0., 0., 2.),
+        )
+        .unwrap();
+        assert_eq!(Vector3d::X, plane.normal());
+    }
+
+    #[test]
+    fn evaluate() {
+        let plane = Plane {
+            origin: Point3d::new(1., 2., 3.),
+            ..Plane::WORLD_XY
+        };
+        assert_eq!(Point3d::new(3., 5., 3.), plane.point_at(2., 3.));
+        assert_eq!(2., plane.signed_distance_to(&Point3d::new(4., 4., 5.)));
+        assert_eq!(
+            Point3d::new(4., 4., 3.),
+            plane.closest_point(&Point3d::new(4., 4., 5.))
+        );
+        assert_eq!(
+            (3., 2.),
+            plane.closest_parameters(&Point3d::new(4., 4., 5.))
+        );
+        assert_eq!([0., 0., 1., -3.], plane.equation());
+        assert_eq!(
+            -2.,
+            plane
+                .flipped()
+                .signed_distance_to(&Point3d::new(4., 4., 5.))
+        );
+    }
+
+    #[test]
+    fn from_equation() {
+        let plane = Plane::from_equation(&[0., 0., 2., -4.]).unwrap();
+        assert_eq!(Point3d::new(0., 0., 2.), plane.origin);
+        assert_eq!(Vector3d::Z, plane.normal());
+        assert!((plane.x_axis.dot(&plane.y_axis)).abs() < 1e-12);
+        assert!((plane.x_axis.cross(&plane.y_axis) - plane.z_axis).length() < 1e-12);
+        assert!(Plane::from_equation(&[0., 0., 0., 1.]).is_none());
+
+        let plane = Plane::from_normal(Point3d::ORIGIN, &Vector3d::new(1., 1., 1.)).unwrap();
+        assert!((plane.x_axis.cross(&plane.y_axis) - plane.z_axis).length() < 1e-12);
+        assert!((1. - plane.x_axis.length()).abs() < 1e-12);
+    }
+}
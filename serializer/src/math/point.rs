@@ -0,0 +1,128 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use super::vector::Vector3d;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Point3d {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Point3d {
+    pub const ORIGIN: Self = Self::new(0., 0., 0.);
+
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn distance_to(&self, other: &Self) -> f64 {
+        (*other - *self).length()
+    }
+
+    pub fn midpoint(&self, other: &Self) -> Self {
+        self.lerp(other, 0.5)
+    }
+
+    pub fn lerp(&self, other: &Self, t: f64) -> Self {
+        *self + (*other - *self) * t
+    }
+
+    pub fn to_vector(&self) -> Vector3d {
+        Vector3d::new(self.x, self.y, self.z)
+    }
+
+    pub fn to_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl From<[f64; 3]> for Point3d {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<[f32; 3]> for Point3d {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x as f64, y as f64, z as f64)
+    }
+}
+
+impl From<Point3d> for [f64; 3] {
+    fn from(point: Point3d) -> Self {
+        point.to_array()
+    }
+}
+
+impl From<Point3d> for [f32; 3] {
+    fn from(point: Point3d) -> Self {
+        [point.x as f32, point.y as f32, point.z as f32]
+    }
+}
+
+impl Add<Vector3d> for Point3d {
+    type Output = Self;
+
+    fn add(self, vector: Vector3d) -> Self {
+        Self::new(self.x + vector.x, self.y + vector.y, self.z + vector.z)
+    }
+}
+
+impl AddAssign<Vector3d> for Point3d {
+    fn add_assign(&mut self, vector: Vector3d) {
+        *self = *self + vector;
+    }
+}
+
+impl Sub<Vector3d> for Point3d {
+    type Output = Self;
+
+    fn sub(self, vector: Vector3d) -> Self {
+        Self::new(self.x - vector.x, self.y - vector.y, self.z - vector.z)
+    }
+}
+
+impl SubAssign<Vector3d> for Point3d {
+    fn sub_assign(&mut self, vector: Vector3d) {
+        *self = *self - vector;
+    }
+}
+
+impl Sub for Point3d {
+    type Output = Vector3d;
+
+    fn sub(self, other: Self) -> Vector3d {
+        Vector3d::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let a = Point3d::new(1., 2., 3.);
+        let b = Point3d::new(4., 6., 3.);
+        assert_eq!(Vector3d::new(3., 4., 0.), b - a);
+        assert_eq!(b, a + (b - a));
+        assert_eq!(a, b - (b - a));
+        let mut c = a;
+        c += Vector3d::X;
+        c -= Vector3d::Y;
+        assert_eq!(Point3d::new(2., 1., 3.), c);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point3d::new(1., 2., 3.);
+        let b = Point3d::new(4., 6., 3.);
+        assert_eq!(5., a.distance_to(&b));
+        assert_eq!(Point3d::new(2.5, 4., 3.), a.midpoint(&b));
+        assert_eq!(b, a.lerp(&b, 1.));
+        assert_eq!(Vector3d::new(1., 2., 3.), a.to_vector());
+        assert_eq!([1f32, 2., 3.], <[f32; 3]>::from(a));
+    }
+}
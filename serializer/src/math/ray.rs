@@ -0,0 +1,99 @@
+use super::{bounding_box::BoundingBox, point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Ray {
+    pub origin: Point3d,
+    pub direction: Vector3d,
+}
+
+impl Ray {
+    pub const fn new(origin: Point3d, direction: Vector3d) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn point_at(&self, t: f64) -> Point3d {
+        self.origin + self.direction * t
+    }
+
+    pub fn intersect_box(&self, bounding_box: &BoundingBox) -> Option<[f64; 2]> {
+        if bounding_box.is_empty() {
+            return None;
+        }
+        let origin = self.origin.to_array();
+        let direction = self.direction.to_array();
+        let (min, max) = (bounding_box.min.to_array(), bounding_box.max.to_array());
+        let mut range = [0., f64::INFINITY];
+        for axis in 0..3 {
+            if 0. == direction[axis] {
+                if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let a = (min[axis] - origin[axis]) / direction[axis];
+            let b = (max[axis] - origin[axis]) / direction[axis];
+            range = [range[0].max(a.min(b)), range[1].min(a.max(b))];
+        }
+        (range[0] <= range[1]).then_some(range)
+    }
+
+    pub fn intersect_triangle(&self, [a, b, c]: &[Point3d; 3]) -> Option<f64> {
+        let ab = *b - *a;
+        let ac = *c - *a;
+        let p = self.direction.cross(&ac);
+        let determinant = ab.dot(&p);
+        if determinant.abs() <= f64::EPSILON * ab.length() * ac.length() * self.direction.length() {
+            return None;
+        }
+        let offset = self.origin - *a;
+        let u = offset.dot(&p) / determinant;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(&ab);
+        let v = self.direction.dot(&q) / determinant;
+        if 0. > v || 1. < u + v {
+            return None;
+        }
+        let t = ac.dot(&q) / determinant;
+        (0. <= t).then_some(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect_box() {
+        let bounding_box = BoundingBox::new(Point3d::ORIGIN, Point3d::new(1., 1., 1.));
+        let ray = Ray::new(Point3d::new(-1., 0.5, 0.5), Vector3d::X);
+        assert_eq!(Some([1., 2.]), ray.intersect_box(&bounding_box));
+        assert_eq!(Point3d::new(0., 0.5, 0.5), ray.point_at(1.));
+        let inside = Ray::new(Point3d::new(0.5, 0.5, 0.5), Vector3d::Z);
+        assert_eq!(Some([0., 0.5]), inside.intersect_box(&bounding_box));
+        let away = Ray::new(Point3d::new(-1., 0.5, 0.5), -Vector3d::X);
+        assert_eq!(None, away.intersect_box(&bounding_box));
+        let parallel = Ray::new(Point3d::new(-1., 2., 0.5), Vector3d::X);
+        assert_eq!(None, parallel.intersect_box(&bounding_box));
+        assert_eq!(None, ray.intersect_box(&BoundingBox::EMPTY));
+    }
+
+    #[test]
+    fn intersect_triangle() {
+        let triangle = [
+            Point3d::ORIGIN,
+            Point3d::new(1., 0., 0.),
+            Point3d::new(0., 1., 0.),
+        ];
+        let ray = Ray::new(Point3d::new(0.25, 0.25, 2.), -Vector3d::Z);
+        assert_eq!(Some(2.), ray.intersect_triangle(&triangle));
+        let behind = Ray::new(Point3d::new(0.25, 0.25, 2.), Vector3d::Z);
+        assert_eq!(None, behind.intersect_triangle(&triangle));
+        let outside = Ray::new(Point3d::new(0.75, 0.75, 2.), -Vector3d::Z);
+        assert_eq!(None, outside.intersect_triangle(&triangle));
+        let parallel = Ray::new(Point3d::new(0.25, 0.25, 0.), Vector3d::X);
+        assert_eq!(None, parallel.intersect_triangle(&triangle));
+    }
+}
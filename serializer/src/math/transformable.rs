@@ -0,0 +1,111 @@
+use super::{
+    bounding_box::BoundingBox, line::Line, plane::Plane, point::Point3d, vector::Vector3d,
+    xform::Xform,
+};
+
+pub trait Transformable {
+    fn transform(&mut self, xform: &Xform);
+
+    fn transformed(&self, xform: &Xform) -> Self
+    where
+        Self: Clone,
+    {
+        let mut transformed = self.clone();
+        transformed.transform(xform);
+        transformed
+    }
+}
+
+pub(crate) fn transform_homogeneous(xform: &Xform, points: &mut [Point3d], weights: &mut Vec<f64>) {
+    if xform.is_affine() {
+        points
+            .iter_mut()
+            .for_each(|point| *point = xform.transform_point(point));
+        return;
+    }
+    if weights.is_empty() {
+        weights.resize(points.len(), 1.);
+    }
+    let m = &xform.0;
+    for (point, weight) in points.iter_mut().zip(weights.iter_mut()) {
+        let [x, y, z] = point.to_array();
+        let w = m[3][0] * x + m[3][1] * y + m[3][2] * z + m[3][3];
+        *point = xform.transform_point(point);
+        *weight *= w;
+    }
+}
+
+impl Transformable for Point3d {
+    fn transform(&mut self, xform: &Xform) {
+        *self = xform.transform_point(self);
+    }
+}
+
+impl Transformable for Vector3d {
+    fn transform(&mut self, xform: &Xform) {
+        *self = xform.transform_vector(self);
+    }
+}
+
+impl Transformable for Line {
+    fn transform(&mut self, xform: &Xform) {
+        self.from.transform(xform);
+        self.to.transform(xform);
+    }
+}
+
+impl Transformable for Plane {
+    fn transform(&mut self, xform: &Xform) {
+        let origin = xform.transform_point(&self.origin);
+        let x_axis = xform.transform_vector(&self.x_axis);
+        let y_axis = xform.transform_vector(&self.y_axis);
+        match Plane::new(origin, &x_axis, &y_axis) {
+            Some(plane) => *self = plane,
+            None => self.origin = origin,
+        }
+    }
+}
+
+impl Transformable for BoundingBox {
+    fn transform(&mut self, xform: &Xform) {
+        if self.is_empty() {
+            return;
+        }
+        let [min, max] = [self.min.to_array(), self.max.to_array()];
+        *self = BoundingBox::from_points((0..8).map(|i| {
+            let corner = Point3d::new(
+                if 0 == i & 1 { min[0] } else { max[0] },
+                if 0 == i & 2 { min[1] } else { max[1] },
+                if 0 == i & 4 { min[2] } else { max[2] },
+            );
+            xform.transform_point(&corner)
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn math_types() {
+        let xform = Xform::translation(&Vector3d::new(1., 2., 3.));
+        assert_eq!(
+            Point3d::new(1., 3., 3.),
+            Point3d::new(0., 1., 0.).transformed(&xform)
+        );
+        assert_eq!(Vector3d::Y, Vector3d::Y.transformed(&xform));
+
+        let rotation = Xform::rotation(std::f64::consts::FRAC_PI_2, &Vector3d::Z).unwrap();
+        let plane = Plane::WORLD_XY.transformed(&(xform * rotation));
+        assert_eq!(Point3d::new(1., 2., 3.), plane.origin);
+        assert!((plane.x_axis - Vector3d::Y).length() < 1e-12);
+        assert!((plane.z_axis - Vector3d::Z).length() < 1e-12);
+
+        let bounding_box = BoundingBox::new(Point3d::ORIGIN, Point3d::new(2., 1., 1.));
+        let rotated = bounding_box.transformed(&rotation);
+        assert!((rotated.min - Point3d::new(-1., 0., 0.)).length() < 1e-12);
+        assert!((rotated.max - Point3d::new(0., 2., 1.)).length() < 1e-12);
+        assert!(BoundingBox::EMPTY.transformed(&xform).is_empty());
+    }
+}
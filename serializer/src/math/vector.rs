@@ -0,0 +1,178 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Vector3d {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector3d {
+    pub const ZERO: Self = Self::new(0., 0., 0.);
+    pub const X: Self = Self::new(1., 0., 0.);
+    pub const Y: Self = Self::new(0., 1., 0.);
+    pub const Z: Self = Self::new(0., 0., 1.);
+
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn length_squared(&self) -> f64 {
+        self.dot(self)
+    }
+
+    pub fn length(&self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn normalized(&self) -> Option<Self> {
+        let length = self.length();
+        if 0. < length && length.is_finite() {
+            Some(*self / length)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        Self::ZERO == *self
+    }
+
+    pub fn to_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl From<[f64; 3]> for Vector3d {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<[f32; 3]> for Vector3d {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x as f64, y as f64, z as f64)
+    }
+}
+
+impl From<Vector3d> for [f64; 3] {
+    fn from(vector: Vector3d) -> Self {
+        vector.to_array()
+    }
+}
+
+impl From<Vector3d> for [f32; 3] {
+    fn from(vector: Vector3d) -> Self {
+        [vector.x as f32, vector.y as f32, vector.z as f32]
+    }
+}
+
+impl Add for Vector3d {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Vector3d {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector3d {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Vector3d {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vector3d {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Vector3d {
+    type Output = Self;
+
+    fn mul(self, scale: f64) -> Self {
+        Self::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl Mul<Vector3d> for f64 {
+    type Output = Vector3d;
+
+    fn mul(self, vector: Vector3d) -> Vector3d {
+        vector * self
+    }
+}
+
+impl Div<f64> for Vector3d {
+    type Output = Self;
+
+    fn div(self, scale: f64) -> Self {
+        Self::new(self.x / scale, self.y / scale, self.z / scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products() {
+        let a = Vector3d::new(1., 2., 3.);
+        let b = Vector3d::new(4., 5., 6.);
+        assert_eq!(32., a.dot(&b));
+        assert_eq!(Vector3d::new(-3., 6., -3.), a.cross(&b));
+        assert_eq!(Vector3d::Z, Vector3d::X.cross(&Vector3d::Y));
+        assert_eq!(0., a.cross(&b).dot(&a));
+    }
+
+    #[test]
+    fn length() {
+        let vector = Vector3d::new(3., 0., 4.);
+        assert_eq!(25., vector.length_squared());
+        assert_eq!(5., vector.length());
+        assert_eq!(Some(Vector3d::new(0.6, 0., 0.8)), vector.normalized());
+        assert_eq!(None, Vector3d::ZERO.normalized());
+        assert!(Vector3d::default().is_zero());
+    }
+
+    #[test]
+    fn operators() {
+        let mut vector = Vector3d::new(1., 2., 3.) + Vector3d::new(1., 1., 1.);
+        assert_eq!(Vector3d::new(2., 3., 4.), vector);
+        vector -= Vector3d::new(2., 2., 2.);
+        assert_eq!(Vector3d::new(0., 1., 2.), vector);
+        assert_eq!(Vector3d::new(0., -2., -4.), -2. * vector);
+        assert_eq!(Vector3d::new(0., 0.5, 1.), vector / 2.);
+        assert_eq!([0., 1., 2.], <[f64; 3]>::from(vector));
+        assert_eq!(Vector3d::new(1., 2., 3.), Vector3d::from([1f32, 2., 3.]));
+    }
+}
@@ -0,0 +1,348 @@
+use std::ops::Mul;
+
+use super::{point::Point3d, vector::Vector3d};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Xform(pub [[f64; 4]; 4]);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Decomposition {
+    pub translation: Vector3d,
+    pub rotation: Xform,
+    pub scale: Vector3d,
+}
+
+impl Xform {
+    pub const IDENTITY: Self = Self([
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.],
+    ]);
+
+    pub const ZERO: Self = Self([[0.; 4]; 4]);
+
+    pub fn translation(offset: &Vector3d) -> Self {
+        let mut xform = Self::IDENTITY;
+        xform.0[0][3] = offset.x;
+        xform.0[1][3] = offset.y;
+        xform.0[2][3] = offset.z;
+        xform
+    }
+
+    pub fn scale(factors: &Vector3d) -> Self {
+        let mut xform = Self::IDENTITY;
+        xform.0[0][0] = factors.x;
+        xform.0[1][1] = factors.y;
+        xform.0[2][2] = factors.z;
+        xform
+    }
+
+    pub fn uniform_scale(factor: f64) -> Self {
+        Self::scale(&Vector3d::new(factor, factor, factor))
+    }
+
+    pub fn rotation(angle: f64, axis: &Vector3d) -> Option<Self> {
+        let Vector3d { x, y, z } = axis.normalized()?;
+        let (sin, cos) = angle.sin_cos();
+        let t = 1. - cos;
+        Some(Self([
+            [
+                t * x * x + cos,
+                t * x * y - sin * z,
+                t * x * z + sin * y,
+                0.,
+            ],
+            [
+                t * x * y + sin * z,
+                t * y * y + cos,
+                t * y * z - sin * x,
+                0.,
+            ],
+            [
+                t * x * z - sin * y,
+                t * y * z + sin * x,
+                t * z * z + cos,
+                0.,
+            ],
+            [0., 0., 0., 1.],
+        ]))
+    }
+
+    pub fn from_row_major(elements: &[f64; 16]) -> Self {
+        let mut xform = Self::ZERO;
+        for (i, element) in elements.iter().enumerate() {
+            xform.0[i / 4][i % 4] = *element;
+        }
+        xform
+    }
+
+    pub fn to_row_major(&self) -> [f64; 16] {
+        let mut elements = [0.; 16];
+        for (i, element) in elements.iter_mut().enumerate() {
+            *element = self.0[i / 4][i % 4];
+        }
+        elements
+    }
+
+    pub fn is_identity(&self) -> bool {
+        Self::IDENTITY == *self
+    }
+
+    pub fn is_affine(&self) -> bool {
+        [0., 0., 0., 1.] == self.0[3]
+    }
+
+    pub fn multiply(&self, other: &Self) -> Self {
+        let mut xform = Self::ZERO;
+        for row in 0..4 {
+            for column in 0..4 {
+                xform.0[row][column] = (0..4).map(|k| self.0[row][k] * other.0[k][column]).sum();
+            }
+        }
+        xform
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut xform = Self::ZERO;
+        for row in 0..4 {
+            for column in 0..4 {
+                xform.0[column][row] = self.0[row][column];
+            }
+        }
+        xform
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.0;
+        let minor = |r: [usize; 3], c: [usize; 3]| {
+            m[r[0]][c[0]] * (m[r[1]][c[1]] * m[r[2]][c[2]] - m[r[1]][c[2]] * m[r[2]][c[1]])
+                - m[r[0]][c[1]] * (m[r[1]][c[0]] * m[r[2]][c[2]] - m[r[1]][c[2]] * m[r[2]][c[0]])
+                + m[r[0]][c[2]] * (m[r[1]][c[0]] * m[r[2]][c[1]] - m[r[1]][c[1]] * m[r[2]][c[0]])
+        };
+        (0..4)
+            .map(|column| {
+                let columns: Vec<usize> = (0..4).filter(|c| *c != column).collect();
+                let sign = if 0 == column % 2 { 1. } else { -1. };
+                sign * m[0][column] * minor([1, 2, 3], [columns[0], columns[1], columns[2]])
+            })
+            .sum()
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let mut m = self.0;
+        let mut inverse = Self::IDENTITY.0;
+        for column in 0..4 {
+            let pivot =
+                (column..4).max_by(|a, b| m[*a][column].abs().total_cmp(&m[*b][column].abs()))?;
+            if is_negligible(m[pivot][column]) {
+                return None;
+            }
+            m.swap(column, pivot);
+            inverse.swap(column, pivot);
+            let scale = m[column][column];
+            for k in 0..4 {
+                m[column][k] /= scale;
+                inverse[column][k] /= scale;
+            }
+            for row in (0..4).filter(|row| *row != column) {
+                let factor = m[row][column];
+                if 0. != factor {
+                    for k in 0..4 {
+                        m[row][k] -= factor * m[column][k];
+                        inverse[row][k] -= factor * inverse[column][k];
+                    }
+                }
+            }
+        }
+        Some(Self(inverse))
+    }
+
+    pub fn transform_point(&self, point: &Point3d) -> Point3d {
+        let m = &self.0;
+        let [x, y, z] = point.to_array();
+        let w = m[3][0] * x + m[3][1] * y + m[3][2] * z + m[3][3];
+        let w = if 0. == w { 1. } else { w };
+        Point3d::new(
+            (m[0][0] * x + m[0][1] * y + m[0][2] * z + m[0][3]) / w,
+            (m[1][0] * x + m[1][1] * y + m[1][2] * z + m[1][3]) / w,
+            (m[2][0] * x + m[2][1] * y + m[2][2] * z + m[2][3]) / w,
+        )
+    }
+
+    pub fn transform_vector(&self, vector: &Vector3d) -> Vector3d {
+        let m = &self.0;
+        let [x, y, z] = vector.to_array();
+        Vector3d::new(
+            m[0][0] * x + m[0][1] * y + m[0][2] * z,
+            m[1][0] * x + m[1][1] * y + m[1][2] * z,
+            m[2][0] * x + m[2][1] * y + m[2][2] * z,
+        )
+    }
+
+    pub fn decompose(&self) -> Option<Decomposition> {
+        if !self.is_affine() {
+            return None;
+        }
+        let m = &self.0;
+        let translation = Vector3d::new(m[0][3], m[1][3], m[2][3]);
+        let columns = [0, 1, 2].map(|c| Vector3d::new(m[0][c], m[1][c], m[2][c]));
+        let mut scale = Vector3d::new(
+            columns[0].length(),
+            columns[1].length(),
+            columns[2].length(),
+        );
+        if 0. > columns[0].cross(&columns[1]).dot(&columns[2]) {
+            scale.x = -scale.x;
+        }
+        let factors = scale.to_array();
+        if factors.iter().any(|s| is_negligible(*s)) {
+            return None;
+        }
+        let mut rotation = Self::IDENTITY;
+        for (c, column) in columns.iter().enumerate() {
+            let column = *column / factors[c];
+            rotation.0[0][c] = column.x;
+            rotation.0[1][c] = column.y;
+            rotation.0[2][c] = column.z;
+        }
+        Some(Decomposition {
+            translation,
+            rotation,
+            scale,
+        })
+    }
+}
+
+fn is_negligible(value: f64) -> bool {
+    value.is_nan() || value.abs() <= f64::EPSILON
+}
+
+impl Decomposition {
+    pub fn to_xform(&self) -> Xform {
+        Xform::translation(&self.translation)
+            .multiply(&self.rotation)
+            .multiply(&Xform::scale(&self.scale))
+    }
+}
+
+impl Default for Xform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Xform {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.multiply(&other)
+    }
+}
+
+impl Mul<Point3d> for Xform {
+    type Output = Point3d;
+
+    fn mul(self, point: Point3d) -> Point3d {
+        self.transform_point(&point)
+    }
+}
+
+impl Mul<Vector3d> for Xform {
+    type Output = Vector3d;
+
+    fn mul(self, vector: Vector3d) -> Vector3d {
+        self.transform_vector(&vector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn assert_close(expected: &Xform, actual: &Xform) {
+        for (e, a) in expected.to_row_major().iter().zip(actual.to_row_major()) {
+            assert!((e - a).abs() < 1e-12, "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    fn assert_point_close(expected: Point3d, actual: Point3d) {
+        assert!(
+            expected.distance_to(&actual) < 1e-12,
+            "{:?} != {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let translation = Xform::translation(&Vector3d::new(1., 2., 3.));
+        assert_eq!(Point3d::new(1., 2., 3.), translation * Point3d::ORIGIN);
+        assert_eq!(Vector3d::X, translation * Vector3d::X);
+        let rotation = Xform::rotation(FRAC_PI_2, &Vector3d::Z).unwrap();
+        assert_point_close(
+            Point3d::new(0., 1., 0.),
+            rotation * Point3d::new(1., 0., 0.),
+        );
+        assert!(Xform::rotation(1., &Vector3d::ZERO).is_none());
+        let xform = translation * rotation * Xform::uniform_scale(2.);
+        assert_point_close(Point3d::new(1., 4., 3.), xform * Point3d::new(1., 0., 0.));
+    }
+
+    #[test]
+    fn compose() {
+        let a = Xform::translation(&Vector3d::new(1., 0., 0.));
+        let b = Xform::scale(&Vector3d::new(2., 3., 4.));
+        assert_eq!(Xform::IDENTITY, Xform::default());
+        assert_eq!(a, a * Xform::IDENTITY);
+        assert_eq!(a, Xform::IDENTITY * a);
+        assert_eq!(Point3d::new(3., 3., 4.), (a * b) * Point3d::new(1., 1., 1.));
+        assert_eq!(Point3d::new(4., 3., 4.), (b * a) * Point3d::new(1., 1., 1.));
+        assert_eq!(a, a.transpose().transpose());
+        assert_eq!(
+            a.to_row_major(),
+            Xform::from_row_major(&a.to_row_major()).to_row_major()
+        );
+    }
+
+    #[test]
+    fn invert() {
+        let xform = Xform::translation(&Vector3d::new(1., -2., 3.))
+            * Xform::rotation(0.3, &Vector3d::new(1., 1., 0.)).unwrap()
+            * Xform::scale(&Vector3d::new(2., 0.5, 4.));
+        assert_close(&Xform::IDENTITY, &(xform * xform.inverse().unwrap()));
+        assert_close(&Xform::IDENTITY, &(xform.inverse().unwrap() * xform));
+        assert!((4. - xform.determinant()).abs() < 1e-12);
+        assert_eq!(None, Xform::scale(&Vector3d::new(1., 0., 1.)).inverse());
+        assert_eq!(None, Xform::ZERO.inverse());
+    }
+
+    #[test]
+    fn decompose() {
+        let rotation = Xform::rotation(1.2, &Vector3d::new(0., 1., 1.)).unwrap();
+        let xform = Xform::translation(&Vector3d::new(4., 5., 6.))
+            * rotation
+            * Xform::scale(&Vector3d::new(2., 3., 0.5));
+        let decomposition = xform.decompose().unwrap();
+        assert_eq!(Vector3d::new(4., 5., 6.), decomposition.translation);
+        assert!((decomposition.scale - Vector3d::new(2., 3., 0.5)).length() < 1e-12);
+        assert_close(&rotation, &decomposition.rotation);
+        assert_close(&xform, &decomposition.to_xform());
+
+        let mirror = Xform::scale(&Vector3d::new(1., -1., 1.))
+            .decompose()
+            .unwrap();
+        assert_eq!(Vector3d::new(-1., 1., 1.), mirror.scale);
+        assert!(0. < mirror.rotation.determinant());
+
+        let mut projection = Xform::IDENTITY;
+        projection.0[3][2] = 1.;
+        assert_eq!(None, projection.decompose());
+        assert_eq!(None, Xform::ZERO.decompose());
+    }
+}
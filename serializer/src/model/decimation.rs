@@ -0,0 +1,418 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::math::{Point3d, Vector3d};
+
+use super::Mesh;
+
+const BOUNDARY_WEIGHT: f64 = 1e3;
+
+#[derive(Debug, Default, Copy, Clone)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn from_plane(normal: &Vector3d, point: &Point3d, weight: f64) -> Self {
+        let [a, b, c] = [normal.x, normal.y, normal.z];
+        let d = -(a * point.x + b * point.y + c * point.z);
+        Self(
+            [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ]
+            .map(|v| weight * v),
+        )
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.0
+            .iter_mut()
+            .zip(other.0.iter())
+            .for_each(|(a, b)| *a += b);
+    }
+
+    fn error(&self, point: &Point3d) -> f64 {
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+        let (x, y, z) = (point.x, point.y, point.z);
+        x * (aa * x + ab * y + ac * z + ad)
+            + y * (ab * x + bb * y + bc * z + bd)
+            + z * (ac * x + bc * y + cc * z + cd)
+            + (ad * x + bd * y + cd * z + dd)
+    }
+}
+
+struct Collapse {
+    cost: f64,
+    edge: [usize; 2],
+    versions: [usize; 2],
+    position: Point3d,
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+struct Decimator {
+    positions: Vec<Point3d>,
+    quadrics: Vec<Quadric>,
+    versions: Vec<usize>,
+    removed: Vec<bool>,
+    triangles: Vec<[usize; 3]>,
+    alive: Vec<bool>,
+    incident: Vec<Vec<usize>>,
+    heap: BinaryHeap<Collapse>,
+}
+
+impl Decimator {
+    fn new(mesh: &Mesh) -> Self {
+        let positions: Vec<Point3d> = mesh.points().collect();
+        let triangles: Vec<[usize; 3]> = mesh
+            .triangles
+            .iter()
+            .map(|t| t.map(|i| i as usize))
+            .filter(|t| t.iter().all(|i| *i < positions.len()))
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+        let mut decimator = Self {
+            quadrics: vec![Quadric::default(); positions.len()],
+            versions: vec![0; positions.len()],
+            removed: vec![false; positions.len()],
+            alive: vec![true; triangles.len()],
+            incident: vec![vec![]; positions.len()],
+            heap: BinaryHeap::new(),
+            positions,
+            triangles,
+        };
+        let mut edges: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+        for (t, triangle) in decimator.triangles.iter().enumerate() {
+            triangle.iter().for_each(|i| decimator.incident[*i].push(t));
+            for k in 0..3 {
+                let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+                edges.entry([a.min(b), a.max(b)]).or_default().push(t);
+            }
+            let normal = match decimator.normal(triangle) {
+                Some(normal) => normal,
+                None => continue,
+            };
+            let quadric = Quadric::from_plane(&normal, &decimator.positions[triangle[0]], 1.);
+            triangle
+                .iter()
+                .for_each(|i| decimator.quadrics[*i].add(&quadric));
+        }
+        for (edge, faces) in edges.iter() {
+            if 1 != faces.len() {
+                continue;
+            }
+            let [a, b] = edge.map(|i| decimator.positions[i]);
+            let constraint = decimator
+                .normal(&decimator.triangles[faces[0]])
+                .and_then(|normal| (b - a).cross(&normal).normalized());
+            if let Some(constraint) = constraint {
+                let quadric = Quadric::from_plane(&constraint, &a, BOUNDARY_WEIGHT);
+                edge.iter()
+                    .for_each(|i| decimator.quadrics[*i].add(&quadric));
+            }
+        }
+        edges.keys().for_each(|edge| decimator.push(*edge));
+        decimator
+    }
+
+    fn normal(&self, triangle: &[usize; 3]) -> Option<Vector3d> {
+        let [a, b, c] = triangle.map(|i| self.positions[i]);
+        (b - a).cross(&(c - a)).normalized()
+    }
+
+    fn push(&mut self, [a, b]: [usize; 2]) {
+        let mut quadric = self.quadrics[a];
+        quadric.add(&self.quadrics[b]);
+        let (pa, pb) = (self.positions[a], self.positions[b]);
+        let (cost, position) = [pa, pb, pa.midpoint(&pb)]
+            .into_iter()
+            .map(|p| (quadric.error(&p), p))
+            .min_by(|x, y| x.0.total_cmp(&y.0))
+            .unwrap_or((0., pa));
+        self.heap.push(Collapse {
+            cost: cost.max(0.),
+            edge: [a, b],
+            versions: [self.versions[a], self.versions[b]],
+            position,
+        });
+    }
+
+    fn neighbors(&self, vertex: usize) -> HashSet<usize> {
+        self.incident[vertex]
+            .iter()
+            .flat_map(|t| self.triangles[*t])
+            .filter(|i| *i != vertex)
+            .collect()
+    }
+
+    fn can_collapse(&self, [a, b]: [usize; 2], position: &Point3d) -> bool {
+        let shared = self.incident[a]
+            .iter()
+            .filter(|t| self.triangles[**t].contains(&b))
+            .count();
+        if 0 == shared || self.neighbors(a).intersection(&self.neighbors(b)).count() != shared {
+            return false;
+        }
+        let mut faces: HashSet<[usize; 3]> = HashSet::new();
+        for t in self.incident[a].iter().chain(self.incident[b].iter()) {
+            let triangle = self.triangles[*t];
+            if triangle.contains(&a) && triangle.contains(&b) {
+                continue;
+            }
+            let replaced = triangle.map(|i| if i == b { a } else { i });
+            let mut key = replaced;
+            key.sort_unstable();
+            if !faces.insert(key) {
+                return false;
+            }
+            let before = self.normal(&triangle);
+            let after = {
+                let [p, q, r] =
+                    replaced.map(|i| if i == a { *position } else { self.positions[i] });
+                (q - p).cross(&(r - p)).normalized()
+            };
+            match (before, after) {
+                (Some(before), Some(after)) if 0.2 < before.dot(&after) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn collapse(&mut self, [a, b]: [usize; 2], position: Point3d) -> usize {
+        let mut removed: Vec<usize> = vec![];
+        for t in std::mem::take(&mut self.incident[b]) {
+            if self.triangles[t].contains(&a) {
+                self.alive[t] = false;
+                removed.push(t);
+            } else {
+                self.triangles[t]
+                    .iter_mut()
+                    .filter(|i| **i == b)
+                    .for_each(|i| *i = a);
+                self.incident[a].push(t);
+            }
+        }
+        for vertex in removed.iter().flat_map(|t| self.triangles[*t]) {
+            let alive = &self.alive;
+            self.incident[vertex].retain(|t| alive[*t]);
+        }
+        self.positions[a] = position;
+        let quadric = self.quadrics[b];
+        self.quadrics[a].add(&quadric);
+        self.removed[b] = true;
+        self.versions[a] += 1;
+        self.neighbors(a)
+            .into_iter()
+            .for_each(|neighbor| self.push([a, neighbor]));
+        removed.len()
+    }
+
+    fn run(&mut self, target_triangles: usize) {
+        let mut count = self.triangles.len();
+        while target_triangles < count {
+            let Collapse {
+                edge: [a, b],
+                versions,
+                position,
+                ..
+            } = match self.heap.pop() {
+                Some(collapse) => collapse,
+                None => break,
+            };
+            if self.removed[a]
+                || self.removed[b]
+                || versions != [self.versions[a], self.versions[b]]
+                || !self.can_collapse([a, b], &position)
+            {
+                continue;
+            }
+            count -= self.collapse([a, b], position);
+        }
+    }
+
+    fn into_mesh(self, with_normals: bool, colors: &[[f32; 4]]) -> Mesh {
+        let mut remap: Vec<Option<u32>> = vec![None; self.positions.len()];
+        let mut mesh = Mesh::default();
+        for (triangle, _) in self
+            .triangles
+            .iter()
+            .zip(self.alive.iter())
+            .filter(|(_, alive)| **alive)
+        {
+            let indices = triangle.map(|i| {
+                *remap[i].get_or_insert_with(|| {
+                    mesh.positions.push(self.positions[i].into());
+                    if !colors.is_empty() {
+                        mesh.colors.push(colors[i]);
+                    }
+                    mesh.positions.len() as u32 - 1
+                })
+            });
+            mesh.triangles.push(indices);
+        }
+        if with_normals {
+            mesh.compute_vertex_normals();
+        }
+        mesh
+    }
+}
+
+impl Mesh {
+    pub fn decimate(&self, target_triangles: usize) -> Mesh {
+        if self.triangles.len() <= target_triangles {
+            return self.clone();
+        }
+        let mut decimator = Decimator::new(self);
+        decimator.run(target_triangles);
+        let colors = if self.has_colors() {
+            self.colors.as_slice()
+        } else {
+            &[]
+        };
+        decimator.into_mesh(self.has_normals(), colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{PI, TAU};
+
+    use crate::math::BoundingBox;
+
+    use super::*;
+
+    fn grid(size: usize) -> Mesh {
+        let mut mesh = Mesh::default();
+        for i in 0..=size {
+            for j in 0..=size {
+                mesh.positions.push([i as f32, j as f32, 0.]);
+            }
+        }
+        let index = |i: usize, j: usize| (i * (size + 1) + j) as u32;
+        for i in 0..size {
+            for j in 0..size {
+                mesh.triangles
+                    .push([index(i, j), index(i + 1, j), index(i + 1, j + 1)]);
+                mesh.triangles
+                    .push([index(i, j), index(i + 1, j + 1), index(i, j + 1)]);
+            }
+        }
+        mesh
+    }
+
+    fn sphere(rings: usize, segments: usize) -> Mesh {
+        let mut mesh = Mesh::default();
+        mesh.positions.push([0., 0., 1.]);
+        for ring in 1..rings {
+            let polar = PI * ring as f64 / rings as f64;
+            for segment in 0..segments {
+                let azimuth = TAU * segment as f64 / segments as f64;
+                mesh.positions.push(
+                    [
+                        polar.sin() * azimuth.cos(),
+                        polar.sin() * azimuth.sin(),
+                        polar.cos(),
+                    ]
+                    .map(|v| v as f32),
+                );
+            }
+        }
+        mesh.positions.push([0., 0., -1.]);
+        let south = mesh.positions.len() as u32 - 1;
+        let index =
+            |ring: usize, segment: usize| (1 + (ring - 1) * segments + segment % segments) as u32;
+        for segment in 0..segments {
+            mesh.triangles
+                .push([0, index(1, segment), index(1, segment + 1)]);
+            mesh.triangles.push([
+                south,
+                index(rings - 1, segment + 1),
+                index(rings - 1, segment),
+            ]);
+            for ring in 1..rings - 1 {
+                mesh.triangles.push([
+                    index(ring, segment),
+                    index(ring + 1, segment),
+                    index(ring + 1, segment + 1),
+                ]);
+                mesh.triangles.push([
+                    index(ring, segment),
+                    index(ring + 1, segment + 1),
+                    index(ring, segment + 1),
+                ]);
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn decimate_plane() {
+        let mesh = grid(10);
+        let decimated = mesh.decimate(20);
+        assert!(decimated.triangles.len() <= 20);
+        assert!(decimated.is_valid());
+        assert!((100. - decimated.area()).abs() < 1e-6);
+        assert_eq!(
+            BoundingBox::new(Point3d::ORIGIN, Point3d::new(10., 10., 0.)),
+            decimated.bounding_box()
+        );
+    }
+
+    #[test]
+    fn decimate_sphere() {
+        let mut mesh = sphere(16, 32);
+        mesh.compute_vertex_normals();
+        assert!(mesh.is_closed());
+        let volume = mesh.volume().unwrap();
+        let decimated = mesh.decimate(mesh.triangles.len() / 4);
+        assert!(decimated.triangles.len() <= mesh.triangles.len() / 4);
+        assert!(decimated.is_closed());
+        assert!(decimated.is_valid());
+        assert!(decimated.has_normals());
+        assert!((volume - decimated.volume().unwrap()).abs() < 0.1 * volume);
+    }
+
+    #[test]
+    fn decimate_limits() {
+        let tetrahedron = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]],
+        };
+        let decimated = tetrahedron.decimate(0);
+        assert_eq!(4, decimated.triangles.len());
+        assert_eq!(tetrahedron.volume(), decimated.volume());
+        let mesh = grid(2);
+        assert_eq!(mesh, mesh.decimate(8));
+        assert!(Mesh::default().decimate(0).triangles.is_empty());
+    }
+}
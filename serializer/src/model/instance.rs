@@ -0,0 +1,178 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::math::{Transformable, Xform};
+
+use super::{Document, Mesh, Transform};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedMesh {
+    pub node: usize,
+    pub mesh: Arc<Mesh>,
+    pub transform: Transform,
+}
+
+impl PlacedMesh {
+    pub fn world_mesh(&self) -> Mesh {
+        self.mesh.transformed(&Xform::from(&self.transform))
+    }
+}
+
+fn fingerprint(mesh: &Mesh) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for values in [&mesh.positions, &mesh.normals] {
+        values.len().hash(&mut hasher);
+        values
+            .iter()
+            .flatten()
+            .for_each(|v| v.to_bits().hash(&mut hasher));
+    }
+    mesh.triangles.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Document {
+    pub fn unique_meshes(&self) -> Vec<usize> {
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        self.meshes
+            .iter()
+            .enumerate()
+            .map(|(index, mesh)| {
+                let bucket = buckets.entry(fingerprint(mesh)).or_default();
+                match bucket.iter().find(|other| self.meshes[**other] == *mesh) {
+                    Some(other) => *other,
+                    None => {
+                        bucket.push(index);
+                        index
+                    }
+                }
+            })
+            .collect()
+    }
+
+    pub fn deduplicate_meshes(&mut self) -> usize {
+        let unique = self.unique_meshes();
+        let mut remap: Vec<usize> = Vec::with_capacity(unique.len());
+        let mut meshes: Vec<Mesh> = vec![];
+        for (index, mesh) in std::mem::take(&mut self.meshes).into_iter().enumerate() {
+            if unique[index] == index {
+                remap.push(meshes.len());
+                meshes.push(mesh);
+            } else {
+                remap.push(remap[unique[index]]);
+            }
+        }
+        let removed = unique.len() - meshes.len();
+        self.meshes = meshes;
+        for node in self.nodes.iter_mut() {
+            node.meshes
+                .iter_mut()
+                .filter(|index| **index < remap.len())
+                .for_each(|index| *index = remap[*index]);
+        }
+        removed
+    }
+
+    pub fn flatten(&self) -> Vec<PlacedMesh> {
+        let unique = self.unique_meshes();
+        let mut shared: HashMap<usize, Arc<Mesh>> = HashMap::new();
+        let transforms = self.world_transforms();
+        let mut placed: Vec<PlacedMesh> = vec![];
+        for (index, (node, transform)) in self.nodes.iter().zip(transforms).enumerate() {
+            for mesh in node.meshes.iter().filter(|mesh| **mesh < unique.len()) {
+                let canonical = unique[*mesh];
+                let mesh = shared
+                    .entry(canonical)
+                    .or_insert_with(|| Arc::new(self.meshes[canonical].clone()));
+                placed.push(PlacedMesh {
+                    node: index,
+                    mesh: Arc::clone(mesh),
+                    transform,
+                });
+            }
+        }
+        placed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::Node;
+
+    use super::*;
+
+    fn triangle(offset: f32) -> Mesh {
+        Mesh {
+            positions: vec![[offset, 0., 0.], [offset + 1., 0., 0.], [offset, 1., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2]],
+        }
+    }
+
+    fn document() -> Document {
+        let mut transform = Transform::IDENTITY;
+        transform.0[12] = 5.;
+        Document {
+            nodes: vec![
+                Node {
+                    children: vec![1, 2],
+                    meshes: vec![0],
+                    ..Default::default()
+                },
+                Node {
+                    transform,
+                    meshes: vec![1, 2],
+                    ..Default::default()
+                },
+                Node {
+                    meshes: vec![3, 7],
+                    ..Default::default()
+                },
+            ],
+            roots: vec![0],
+            meshes: vec![triangle(0.), triangle(0.), triangle(2.), triangle(0.)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flatten() {
+        let document = document();
+        assert_eq!(vec![0, 0, 2, 0], document.unique_meshes());
+        let placed = document.flatten();
+        assert_eq!(4, placed.len());
+        assert_eq!(
+            vec![0, 1, 1, 2],
+            placed.iter().map(|p| p.node).collect::<Vec<_>>()
+        );
+        assert!(Arc::ptr_eq(&placed[0].mesh, &placed[1].mesh));
+        assert!(Arc::ptr_eq(&placed[0].mesh, &placed[3].mesh));
+        assert!(!Arc::ptr_eq(&placed[0].mesh, &placed[2].mesh));
+        assert_eq!([5., 0., 0.], placed[1].transform.translation());
+        assert_eq!(3, Arc::strong_count(&placed[0].mesh));
+    }
+
+    #[test]
+    fn deduplicate_meshes() {
+        let mut document = document();
+        let bounding_box = document.bounding_box();
+        assert_eq!(2, document.deduplicate_meshes());
+        assert_eq!(vec![triangle(0.), triangle(2.)], document.meshes);
+        assert_eq!(vec![0], document.nodes[0].meshes);
+        assert_eq!(vec![0, 1], document.nodes[1].meshes);
+        assert_eq!(vec![0, 7], document.nodes[2].meshes);
+        assert_eq!(bounding_box, document.bounding_box());
+        assert_eq!(0, document.deduplicate_meshes());
+    }
+
+    #[test]
+    fn world_mesh() {
+        let placed = document().flatten();
+        assert_eq!(triangle(0.), placed[0].world_mesh());
+        assert_eq!(triangle(5.), placed[1].world_mesh());
+    }
+}
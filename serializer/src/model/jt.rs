@@ -0,0 +1,419 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::jt::{
+    archive::Archive,
+    common::{Mx4F32, GUID},
+    deserializer::Deserializer,
+    lsg::{Attribute, Lsg},
+    object_type,
+    property::PropertyValue,
+    shape_lod::{ShapeLodElement, ShapeLodSegment},
+};
+
+use super::{Document, Material, Mesh, Node, Transform, MAX_NODE_COUNT};
+
+const NAME_PROPERTY: &str = "JT_PROP_NAME";
+const SHAPE_PROPERTY: &str = "JT_LLPROP_SHAPEIMPL";
+
+impl From<&Mx4F32> for Transform {
+    fn from(matrix: &Mx4F32) -> Self {
+        Self(matrix.0.map(f64::from))
+    }
+}
+
+struct Converter<'a> {
+    lsg: &'a Lsg,
+    document: Document,
+    materials: HashMap<i32, usize>,
+    shapes: HashMap<i32, usize>,
+    path: HashSet<i32>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(lsg: &'a Lsg) -> Self {
+        Self {
+            lsg,
+            document: Document::default(),
+            materials: HashMap::new(),
+            shapes: HashMap::new(),
+            path: HashSet::new(),
+        }
+    }
+
+    fn load_shapes<F>(&mut self, mut load: F) -> Result<(), String>
+    where
+        F: FnMut(&GUID) -> Result<Option<Mesh>, String>,
+    {
+        let mut shape_ids: Vec<i32> = self
+            .lsg
+            .nodes
+            .values()
+            .filter(|node| node.is_shape())
+            .map(|node| node.object_id())
+            .collect();
+        shape_ids.sort_unstable();
+        let mut segments: HashMap<GUID, Option<usize>> = HashMap::new();
+        for object_id in shape_ids {
+            let segment_id = match self.lsg.metadata.property(object_id, SHAPE_PROPERTY) {
+                Some(PropertyValue::LateLoaded(segment_id)) => *segment_id,
+                _ => continue,
+            };
+            let mesh = match segments.get(&segment_id) {
+                Some(mesh) => *mesh,
+                None => {
+                    let mesh = load(&segment_id)?.map(|mesh| {
+                        self.document.meshes.push(mesh);
+                        self.document.meshes.len() - 1
+                    });
+                    segments.insert(segment_id, mesh);
+                    mesh
+                }
+            };
+            if let Some(mesh) = mesh {
+                self.shapes.insert(object_id, mesh);
+            }
+        }
+        Ok(())
+    }
+
+    fn material(&mut self, object_id: i32) -> usize {
+        let materials = &mut self.document.materials;
+        *self.materials.entry(object_id).or_insert_with(|| {
+            materials.push(Material::default());
+            materials.len() - 1
+        })
+    }
+
+    fn convert(&mut self, object_id: i32) -> Option<usize> {
+        let lsg = self.lsg;
+        let node = lsg.node(object_id)?;
+        if MAX_NODE_COUNT <= self.document.nodes.len() || !self.path.insert(object_id) {
+            return None;
+        }
+        let index = self.document.nodes.len();
+        self.document.nodes.push(Node::default());
+
+        let mut transform = Transform::IDENTITY;
+        let mut material = None;
+        for attribute in node
+            .base_node_data()
+            .attribute_object_ids
+            .iter()
+            .filter_map(|id| lsg.attribute(*id))
+        {
+            match attribute {
+                Attribute::GeometricTransform(t) => {
+                    transform = Transform::from(&t.transformation_matrix).multiply(&transform)
+                }
+                Attribute::Other(object_type_id, _)
+                    if object_type::MATERIAL_ATTRIBUTE == *object_type_id =>
+                {
+                    material = Some(self.material(attribute.object_id()))
+                }
+                Attribute::Other(..) => {}
+            }
+        }
+        let metadata: BTreeMap<String, String> = lsg
+            .metadata
+            .properties(object_id)
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let children = node
+            .children()
+            .iter()
+            .filter_map(|child| self.convert(*child))
+            .collect();
+
+        self.document.nodes[index] = Node {
+            name: metadata.get(NAME_PROPERTY).cloned(),
+            transform,
+            children,
+            meshes: self.shapes.get(&object_id).copied().into_iter().collect(),
+            material,
+            metadata,
+        };
+        self.path.remove(&object_id);
+        Some(index)
+    }
+}
+
+impl Converter<'_> {
+    fn finish(mut self) -> Document {
+        if let Some(root) = self.lsg.root_node_id.and_then(|id| self.convert(id)) {
+            self.document.roots.push(root);
+        }
+        self.document
+    }
+}
+
+fn insert_version(document: &mut Document, archive: &Archive) {
+    if let Ok(version) = archive.header.version() {
+        document
+            .metadata
+            .insert("version".to_string(), version.to_string());
+    }
+}
+
+impl Document {
+    pub fn from_jt_with_geometry<D>(archive: &Archive, deserializer: &mut D) -> Result<Self, String>
+    where
+        D: Deserializer,
+    {
+        let mut document = Self::from_lsg_with_meshes(archive.lsg(), |segment_id| {
+            let segment: ShapeLodSegment = archive.load_segment(deserializer, segment_id)?;
+            match &segment.element {
+                ShapeLodElement::TriStripSet(lod) => Ok(Some(lod.to_mesh()?)),
+                _ => Ok(None),
+            }
+        })?;
+        insert_version(&mut document, archive);
+        Ok(document)
+    }
+
+    fn from_lsg_with_meshes<F>(lsg: &Lsg, load: F) -> Result<Self, String>
+    where
+        F: FnMut(&GUID) -> Result<Option<Mesh>, String>,
+    {
+        let mut converter = Converter::new(lsg);
+        converter.load_shapes(load)?;
+        Ok(converter.finish())
+    }
+}
+
+impl From<&Lsg> for Document {
+    fn from(lsg: &Lsg) -> Self {
+        Converter::new(lsg).finish()
+    }
+}
+
+impl From<&Archive> for Document {
+    fn from(archive: &Archive) -> Self {
+        let mut document = Document::from(archive.lsg());
+        insert_version(&mut document, archive);
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jt::{
+        attribute::{BaseAttributeData, GeometricTransformAttribute},
+        lsg::Node as LsgNode,
+        metadata::Metadata,
+        node::{BaseNodeData, GroupNodeData, TriStripSetShapeNode},
+        property::{BasePropertyAtomData, ElementPropertyTable, PropertyAtom, PropertyTable},
+    };
+
+    use super::*;
+
+    fn group(object_id: i32, attributes: Vec<i32>, children: Vec<i32>) -> LsgNode {
+        LsgNode::Group(GroupNodeData {
+            base_node_data: BaseNodeData {
+                object_id,
+                node_flags: 0,
+                attribute_object_ids: attributes,
+            },
+            child_node_object_ids: children,
+        })
+    }
+
+    fn translation(object_id: i32, offset: [f32; 3]) -> Attribute {
+        let mut transformation_matrix = Mx4F32::IDENTITY;
+        transformation_matrix.0[12..15].copy_from_slice(&offset);
+        Attribute::GeometricTransform(GeometricTransformAttribute {
+            base_attribute_data: BaseAttributeData {
+                object_id,
+                ..Default::default()
+            },
+            stored_values_mask: 0x000E,
+            transformation_matrix,
+        })
+    }
+
+    fn material(object_id: i32) -> Attribute {
+        Attribute::Other(
+            object_type::MATERIAL_ATTRIBUTE,
+            BaseAttributeData {
+                object_id,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn lsg() -> Lsg {
+        let mut lsg = Lsg {
+            root_node_id: Some(1),
+            ..Default::default()
+        };
+        [
+            group(1, vec![10, 20], vec![2, 3]),
+            group(2, vec![11, 20], vec![1, 99]),
+            group(3, vec![21], vec![]),
+        ]
+        .into_iter()
+        .for_each(|node| {
+            lsg.nodes.insert(node.object_id(), node);
+        });
+        [
+            translation(10, [1., 0., 0.]),
+            translation(11, [0., 2., 0.]),
+            material(20),
+            material(21),
+        ]
+        .into_iter()
+        .for_each(|attribute| {
+            lsg.attributes.insert(attribute.object_id(), attribute);
+        });
+        lsg
+    }
+
+    #[test]
+    fn from_lsg() {
+        let document = Document::from(&lsg());
+        assert_eq!(vec![0], document.roots);
+        assert_eq!(3, document.nodes.len());
+        assert_eq!(vec![1, 2], document.nodes[0].children);
+        assert!(document.nodes[1].children.is_empty());
+        assert_eq!([1., 0., 0.], document.nodes[0].transform.translation());
+        assert_eq!([0., 2., 0.], document.nodes[1].transform.translation());
+        assert_eq!(Transform::IDENTITY, document.nodes[2].transform);
+        assert_eq!(2, document.materials.len());
+        assert_eq!(Some(0), document.nodes[0].material);
+        assert_eq!(Some(0), document.nodes[1].material);
+        assert_eq!(Some(1), document.nodes[2].material);
+        assert_eq!([1., 2., 0.], document.world_transforms()[1].translation());
+    }
+
+    #[test]
+    fn from_lsg_with_shared_child() {
+        let mut lsg = lsg();
+        [
+            group(1, vec![], vec![2, 3]),
+            group(2, vec![10], vec![4]),
+            group(3, vec![11], vec![4]),
+            group(4, vec![21], vec![]),
+        ]
+        .into_iter()
+        .for_each(|node| {
+            lsg.nodes.insert(node.object_id(), node);
+        });
+        let document = Document::from(&lsg);
+        assert_eq!(5, document.nodes.len());
+        assert_eq!(vec![1, 3], document.nodes[0].children);
+        assert_eq!(vec![2], document.nodes[1].children);
+        assert_eq!(vec![4], document.nodes[3].children);
+        assert_eq!(Some(0), document.nodes[2].material);
+        assert_eq!(Some(0), document.nodes[4].material);
+        assert_eq!(1, document.materials.len());
+        let transforms = document.world_transforms();
+        assert_eq!([1., 0., 0.], transforms[2].translation());
+        assert_eq!([0., 2., 0.], transforms[4].translation());
+    }
+
+    #[test]
+    fn from_lsg_with_exponential_fan_out() {
+        let mut lsg = Lsg {
+            root_node_id: Some(0),
+            ..Default::default()
+        };
+        (0..32).for_each(|i| {
+            lsg.nodes.insert(i, group(i, vec![], vec![i + 1, i + 1]));
+        });
+        let document = Document::from(&lsg);
+        assert_eq!(MAX_NODE_COUNT, document.nodes.len());
+    }
+
+    fn shape(object_id: i32) -> LsgNode {
+        let mut node = TriStripSetShapeNode::default();
+        node.vertex_shape_node_data
+            .base_shape_node_data
+            .base_node_data
+            .object_id = object_id;
+        LsgNode::TriStripSetShape(node)
+    }
+
+    fn atom(object_id: i32, value: PropertyValue) -> PropertyAtom {
+        PropertyAtom {
+            base_property_atom_data: BasePropertyAtomData {
+                object_id,
+                state_flags: 0,
+            },
+            value,
+        }
+    }
+
+    #[test]
+    fn from_lsg_with_meshes() {
+        let (mesh_id, polyline_id) = (GUID(1, [0; 2], [0; 8]), GUID(2, [0; 2], [0; 8]));
+        let mut lsg = Lsg {
+            root_node_id: Some(1),
+            ..Default::default()
+        };
+        [
+            group(1, vec![], vec![2, 3, 6]),
+            group(2, vec![10], vec![4]),
+            group(3, vec![11], vec![4, 5]),
+            shape(4),
+            shape(5),
+            shape(6),
+        ]
+        .into_iter()
+        .for_each(|node| {
+            lsg.nodes.insert(node.object_id(), node);
+        });
+        lsg.metadata = Metadata::new(
+            vec![
+                atom(30, PropertyValue::String(SHAPE_PROPERTY.to_string())),
+                atom(31, PropertyValue::LateLoaded(mesh_id)),
+                atom(32, PropertyValue::LateLoaded(polyline_id)),
+            ],
+            PropertyTable {
+                version: 1,
+                element_property_tables: vec![
+                    ElementPropertyTable {
+                        element_object_id: 4,
+                        entries: vec![(30, 31)],
+                    },
+                    ElementPropertyTable {
+                        element_object_id: 5,
+                        entries: vec![(30, 32)],
+                    },
+                    ElementPropertyTable {
+                        element_object_id: 6,
+                        entries: vec![(30, 31)],
+                    },
+                ],
+            },
+        );
+        let mesh = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            triangles: vec![[0, 1, 2]],
+            ..Default::default()
+        };
+        let mut loaded: Vec<GUID> = vec![];
+        let document = Document::from_lsg_with_meshes(&lsg, |segment_id| {
+            loaded.push(*segment_id);
+            Ok((mesh_id == *segment_id).then(|| mesh.clone()))
+        })
+        .unwrap();
+        assert_eq!(vec![mesh_id, polyline_id], loaded);
+        assert_eq!(vec![mesh.clone()], document.meshes);
+        assert_eq!(7, document.nodes.len());
+        let meshes: Vec<&[usize]> = document.nodes.iter().map(|n| &n.meshes[..]).collect();
+        assert_eq!(vec![&[][..], &[], &[0], &[], &[0], &[], &[0]], meshes);
+        assert_eq!(3, document.flatten().len());
+
+        assert!(
+            Document::from_lsg_with_meshes(&lsg, |_| Err("missing segment".to_string())).is_err()
+        );
+        assert!(Document::from(&lsg).meshes.is_empty());
+    }
+
+    #[test]
+    fn from_empty_lsg() {
+        let document = Document::from(&Lsg::default());
+        assert!(document.roots.is_empty());
+        assert!(document.nodes.is_empty());
+    }
+}
@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+
+use crate::{
+    geometry::validation::Severity,
+    math::{BoundingBox, Point3d, Transformable, Vector3d, Xform},
+};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub colors: Vec<[f32; 4]>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MeshIssue {
+    IndexOutOfRange { triangle: usize, index: u32 },
+    DegenerateTriangle(usize),
+    NonFinitePosition(usize),
+    NormalCountMismatch { positions: usize, normals: usize },
+    ColorCountMismatch { positions: usize, colors: usize },
+    InconsistentOrientation { triangles: [usize; 2] },
+}
+
+impl MeshIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::DegenerateTriangle(_) | Self::InconsistentOrientation { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MeshMeasurements {
+    pub area: f64,
+    pub volume: Option<f64>,
+    pub bounding_box: BoundingBox,
+}
+
+impl Mesh {
+    pub fn points(&self) -> impl Iterator<Item = Point3d> + '_ {
+        self.positions
+            .iter()
+            .map(|position| Point3d::from(*position))
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.points().collect()
+    }
+
+    pub fn triangle_points(&self, triangle: usize) -> Option<[Point3d; 3]> {
+        let [a, b, c] = self.triangles.get(triangle)?;
+        Some([
+            Point3d::from(*self.positions.get(*a as usize)?),
+            Point3d::from(*self.positions.get(*b as usize)?),
+            Point3d::from(*self.positions.get(*c as usize)?),
+        ])
+    }
+
+    pub fn triangle_normal(&self, triangle: usize) -> Option<Vector3d> {
+        let [a, b, c] = self.triangle_points(triangle)?;
+        (b - a).cross(&(c - a)).normalized()
+    }
+
+    pub fn append(&mut self, other: &Mesh) {
+        let keep_normals = (self.positions.is_empty() || self.has_normals()) && other.has_normals();
+        let keep_colors = (self.positions.is_empty() || self.has_colors()) && other.has_colors();
+        let offset = self.positions.len() as u32;
+        self.positions.extend_from_slice(&other.positions);
+        if keep_normals {
+            self.normals.extend_from_slice(&other.normals);
+        } else {
+            self.normals.clear();
+        }
+        if keep_colors {
+            self.colors.extend_from_slice(&other.colors);
+        } else {
+            self.colors.clear();
+        }
+        self.triangles
+            .extend(other.triangles.iter().map(|t| t.map(|i| i + offset)));
+    }
+
+    pub fn area(&self) -> f64 {
+        (0..self.triangles.len())
+            .filter_map(|t| self.triangle_points(t))
+            .map(|[a, b, c]| 0.5 * (b - a).cross(&(c - a)).length())
+            .sum()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        let mut edges: HashMap<(u32, u32), i32> = HashMap::new();
+        for [a, b, c] in self.triangles.iter() {
+            for (from, to) in [(*a, *b), (*b, *c), (*c, *a)] {
+                let (key, side) = if from < to {
+                    ((from, to), 1)
+                } else {
+                    ((to, from), -1)
+                };
+                *edges.entry(key).or_default() += side;
+            }
+        }
+        !edges.is_empty() && edges.values().all(|balance| 0 == *balance)
+    }
+
+    pub fn volume(&self) -> Option<f64> {
+        if !self.is_closed() {
+            return None;
+        }
+        let volume = (0..self.triangles.len())
+            .map(|t| {
+                let [a, b, c] = self.triangle_points(t)?.map(|p| p - Point3d::ORIGIN);
+                Some(a.dot(&b.cross(&c)))
+            })
+            .sum::<Option<f64>>()?;
+        Some(volume / 6.)
+    }
+
+    pub fn measure(&self) -> MeshMeasurements {
+        MeshMeasurements {
+            area: self.area(),
+            volume: self.volume(),
+            bounding_box: self.bounding_box(),
+        }
+    }
+
+    pub fn compute_vertex_normals(&mut self) {
+        let mut normals = vec![Vector3d::ZERO; self.positions.len()];
+        for triangle in 0..self.triangles.len() {
+            if let Some([a, b, c]) = self.triangle_points(triangle) {
+                let weighted = (b - a).cross(&(c - a));
+                self.triangles[triangle]
+                    .iter()
+                    .for_each(|i| normals[*i as usize] += weighted);
+            }
+        }
+        self.normals = normals
+            .iter()
+            .map(|normal| {
+                let normal = normal.normalized().unwrap_or(Vector3d::ZERO);
+                [normal.x as f32, normal.y as f32, normal.z as f32]
+            })
+            .collect();
+    }
+
+    pub fn has_normals(&self) -> bool {
+        !self.normals.is_empty() && self.normals.len() == self.positions.len()
+    }
+
+    pub fn has_colors(&self) -> bool {
+        !self.colors.is_empty() && self.colors.len() == self.positions.len()
+    }
+
+    pub fn flipped_triangles(&self) -> Vec<usize> {
+        if !self.has_normals() {
+            return vec![];
+        }
+        (0..self.triangles.len())
+            .filter(|t| {
+                let normal = match self.triangle_normal(*t) {
+                    Some(normal) => normal,
+                    None => return false,
+                };
+                let average = self.triangles[*t].iter().fold(Vector3d::ZERO, |sum, i| {
+                    sum + Vector3d::from(self.normals[*i as usize])
+                });
+                0. > normal.dot(&average)
+            })
+            .collect()
+    }
+
+    pub fn flip(&mut self) {
+        self.triangles.iter_mut().for_each(|t| t.swap(1, 2));
+        self.normals
+            .iter_mut()
+            .for_each(|n| *n = [-n[0], -n[1], -n[2]]);
+    }
+
+    pub fn weld(&mut self, tolerance: f64) -> usize {
+        let tolerance = if tolerance.is_finite() {
+            tolerance.max(0.)
+        } else {
+            0.
+        };
+        let cell_size = if 0. < tolerance { tolerance } else { 1. };
+        let cell =
+            |point: &Point3d| [point.x, point.y, point.z].map(|v| (v / cell_size).floor() as i64);
+        let has_normals = self.has_normals();
+        let has_colors = self.has_colors();
+        let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        let mut remap: Vec<u32> = Vec::with_capacity(self.positions.len());
+        let mut positions: Vec<[f32; 3]> = vec![];
+        let mut normals: Vec<Vector3d> = vec![];
+        let mut colors: Vec<[f32; 4]> = vec![];
+        for (i, position) in self.positions.iter().enumerate() {
+            let point = Point3d::from(*position);
+            let [x, y, z] = cell(&point);
+            let found = (-1..=1)
+                .flat_map(|dx| {
+                    (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| [x + dx, y + dy, z + dz]))
+                })
+                .filter_map(|key| cells.get(&key))
+                .flatten()
+                .find(|index| {
+                    point.distance_to(&Point3d::from(positions[**index as usize])) <= tolerance
+                })
+                .copied();
+            let index = match found {
+                Some(index) => index,
+                None => {
+                    positions.push(*position);
+                    normals.push(Vector3d::ZERO);
+                    if has_colors {
+                        colors.push(self.colors[i]);
+                    }
+                    let index = positions.len() as u32 - 1;
+                    cells.entry([x, y, z]).or_default().push(index);
+                    index
+                }
+            };
+            if has_normals {
+                normals[index as usize] += Vector3d::from(self.normals[i]);
+            }
+            remap.push(index);
+        }
+        let merged = self.positions.len() - positions.len();
+        self.triangles = self
+            .triangles
+            .iter()
+            .filter_map(|triangle| {
+                let mut remapped = [0u32; 3];
+                for (target, index) in remapped.iter_mut().zip(triangle) {
+                    *target = *remap.get(*index as usize)?;
+                }
+                let [a, b, c] = remapped;
+                (a != b && b != c && c != a).then_some(remapped)
+            })
+            .collect();
+        self.positions = positions;
+        if has_normals {
+            self.normals = normals
+                .iter()
+                .map(|normal| {
+                    let normal = normal.normalized().unwrap_or(Vector3d::ZERO);
+                    [normal.x as f32, normal.y as f32, normal.z as f32]
+                })
+                .collect();
+        }
+        if has_colors {
+            self.colors = colors;
+        }
+        merged
+    }
+
+    pub fn validate(&self) -> Vec<MeshIssue> {
+        let mut issues: Vec<MeshIssue> = vec![];
+        if !self.normals.is_empty() && self.normals.len() != self.positions.len() {
+            issues.push(MeshIssue::NormalCountMismatch {
+                positions: self.positions.len(),
+                normals: self.normals.len(),
+            });
+        }
+        if !self.colors.is_empty() && self.colors.len() != self.positions.len() {
+            issues.push(MeshIssue::ColorCountMismatch {
+                positions: self.positions.len(),
+                colors: self.colors.len(),
+            });
+        }
+        issues.extend(
+            self.positions
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| !p.iter().all(|v| v.is_finite()))
+                .map(|(i, _)| MeshIssue::NonFinitePosition(i)),
+        );
+        let mut edges: HashMap<(u32, u32), usize> = HashMap::new();
+        for (i, triangle) in self.triangles.iter().enumerate() {
+            if let Some(index) = triangle
+                .iter()
+                .find(|index| **index as usize >= self.positions.len())
+            {
+                issues.push(MeshIssue::IndexOutOfRange {
+                    triangle: i,
+                    index: *index,
+                });
+                continue;
+            }
+            if self.triangle_normal(i).is_none() {
+                issues.push(MeshIssue::DegenerateTriangle(i));
+                continue;
+            }
+            let [a, b, c] = *triangle;
+            for edge in [(a, b), (b, c), (c, a)] {
+                if let Some(other) = edges.insert(edge, i) {
+                    issues.push(MeshIssue::InconsistentOrientation {
+                        triangles: [other, i],
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+}
+
+impl Transformable for Mesh {
+    fn transform(&mut self, xform: &Xform) {
+        let had_normals = self.has_normals();
+        for position in self.positions.iter_mut() {
+            *position = xform.transform_point(&Point3d::from(*position)).into();
+        }
+        if 0. > xform.determinant() {
+            self.triangles.iter_mut().for_each(|t| t.swap(1, 2));
+        }
+        if had_normals {
+            match xform.inverse().filter(|_| xform.is_affine()) {
+                Some(inverse) => {
+                    let normal_xform = inverse.transpose();
+                    for normal in self.normals.iter_mut() {
+                        let transformed = normal_xform
+                            .transform_vector(&Vector3d::from(*normal))
+                            .normalized()
+                            .unwrap_or(Vector3d::ZERO);
+                        *normal = [
+                            transformed.x as f32,
+                            transformed.y as f32,
+                            transformed.z as f32,
+                        ];
+                    }
+                }
+                None => self.compute_vertex_normals(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad() -> Mesh {
+        Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2], [0, 2, 3]],
+        }
+    }
+
+    fn tetrahedron() -> Mesh {
+        Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]],
+        }
+    }
+
+    #[test]
+    fn measure() {
+        let measurements = quad().measure();
+        assert_eq!(1., measurements.area);
+        assert_eq!(None, measurements.volume);
+        assert_eq!(
+            BoundingBox::new(Point3d::ORIGIN, Point3d::new(1., 1., 0.)),
+            measurements.bounding_box
+        );
+
+        let mut mesh = tetrahedron();
+        assert!(mesh.is_closed());
+        assert!((mesh.volume().unwrap() - 1. / 6.).abs() < 1e-12);
+        assert!((mesh.area() - (1.5 + 3f64.sqrt() / 2.)).abs() < 1e-6);
+        mesh.flip();
+        assert!((mesh.volume().unwrap() + 1. / 6.).abs() < 1e-12);
+        mesh.triangles.pop();
+        assert!(!mesh.is_closed());
+        assert_eq!(None, mesh.volume());
+        assert_eq!(0., Mesh::default().area());
+        assert!(!Mesh::default().is_closed());
+    }
+
+    #[test]
+    fn append() {
+        let mut mesh = quad();
+        mesh.compute_vertex_normals();
+        let mut other = mesh.clone();
+        mesh.append(&other);
+        assert_eq!(8, mesh.positions.len());
+        assert!(mesh.has_normals());
+        assert_eq!([4, 6, 7], mesh.triangles[3]);
+        other.normals.clear();
+        mesh.append(&other);
+        assert_eq!(12, mesh.positions.len());
+        assert!(mesh.normals.is_empty());
+    }
+
+    #[test]
+    fn compute_vertex_normals() {
+        let mut mesh = quad();
+        mesh.compute_vertex_normals();
+        assert!(mesh.has_normals());
+        assert!(mesh.normals.iter().all(|n| [0., 0., 1.] == *n));
+
+        let mut tent = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 1.], [1., 1., 1.], [2., 0., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 2], [1, 3, 2]],
+        };
+        tent.compute_vertex_normals();
+        let shared = Vector3d::from(tent.normals[1]);
+        assert!((shared - Vector3d::Z).length() < 1e-6);
+        assert!(Vector3d::from(tent.normals[0]).x < 0.);
+    }
+
+    #[test]
+    fn flipped_triangles() {
+        let mut mesh = quad();
+        mesh.compute_vertex_normals();
+        assert!(mesh.flipped_triangles().is_empty());
+        mesh.triangles[1] = [0, 3, 2];
+        assert_eq!(vec![1], mesh.flipped_triangles());
+        mesh.flip();
+        assert_eq!(vec![1], mesh.flipped_triangles());
+        assert_eq!([0., 0., -1.], mesh.normals[0]);
+        assert!(quad().flipped_triangles().is_empty());
+    }
+
+    #[test]
+    fn weld() {
+        let mut mesh = Mesh {
+            positions: vec![
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 0., 0.],
+                [1.0001, 1., 0.],
+                [0., 1., 0.],
+            ],
+            normals: vec![[0., 0., 1.]; 6],
+            colors: (0..6).map(|i| [i as f32, 0., 0., 1.]).collect(),
+            triangles: vec![[0, 1, 2], [3, 4, 5]],
+        };
+        assert_eq!(2, mesh.weld(1e-3));
+        assert_eq!(4, mesh.positions.len());
+        assert_eq!(vec![[0, 1, 2], [0, 2, 3]], mesh.triangles);
+        assert!(mesh.normals.iter().all(|n| [0., 0., 1.] == *n));
+        assert_eq!(
+            vec![0., 1., 2., 5.],
+            mesh.colors.iter().map(|c| c[0]).collect::<Vec<f32>>()
+        );
+        assert!(mesh.is_valid());
+    }
+
+    #[test]
+    fn weld_exact_and_degenerate() {
+        let mut mesh = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [1e-6, 0., 0.], [0., 1., 0.]],
+            normals: vec![],
+            colors: vec![],
+            triangles: vec![[0, 1, 3], [0, 2, 3], [0, 1, 9]],
+        };
+        assert_eq!(0, mesh.clone().weld(0.));
+        assert_eq!(1, mesh.weld(1e-3));
+        assert_eq!(vec![[0, 1, 2]], mesh.triangles);
+        assert!(mesh.normals.is_empty());
+    }
+
+    #[test]
+    fn validate() {
+        assert!(quad().is_valid());
+        let mut mesh = quad();
+        mesh.triangles.push([0, 1, 7]);
+        mesh.triangles.push([1, 1, 2]);
+        mesh.triangles.push([1, 0, 3]);
+        mesh.normals.push([0., 0., 1.]);
+        mesh.positions.push([f32::NAN, 0., 0.]);
+        assert_eq!(
+            vec![
+                MeshIssue::NormalCountMismatch {
+                    positions: 5,
+                    normals: 1
+                },
+                MeshIssue::NonFinitePosition(4),
+                MeshIssue::IndexOutOfRange {
+                    triangle: 2,
+                    index: 7
+                },
+                MeshIssue::DegenerateTriangle(3),
+            ],
+            mesh.validate()
+        );
+        let mut mesh = quad();
+        mesh.triangles[1] = [0, 3, 2];
+        assert_eq!(
+            vec![MeshIssue::InconsistentOrientation { triangles: [0, 1] }],
+            mesh.validate()
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let mut mesh = tetrahedron();
+        mesh.compute_vertex_normals();
+        let scaled = mesh.transformed(&Xform::scale(&Vector3d::new(2., 1., 1.)));
+        assert!((scaled.volume().unwrap() - 1. / 3.).abs() < 1e-12);
+        assert!(scaled.flipped_triangles().is_empty());
+        assert!(scaled
+            .normals
+            .iter()
+            .all(|n| { (1. - Vector3d::from(*n).length()).abs() < 1e-6 }));
+
+        let mirrored = mesh.transformed(&Xform::scale(&Vector3d::new(-1., 1., 1.)));
+        assert!((mirrored.volume().unwrap() - 1. / 6.).abs() < 1e-12);
+        assert!(mirrored.flipped_triangles().is_empty());
+        assert_eq!([-1., 0., 0.], mirrored.positions[1]);
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    common::units::UnitSystem,
+    math::{BoundingBox, Point3d, Xform},
+};
+
+mod decimation;
+pub mod instance;
+pub mod jt;
+pub mod mesh;
+pub mod rhino;
+pub mod step;
+
+pub use instance::PlacedMesh;
+pub use mesh::Mesh;
+
+pub const MAX_NODE_COUNT: usize = 1 << 20;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Transform(pub [f64; 16]);
+
+impl Transform {
+    pub const IDENTITY: Self = Self([
+        1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
+    ]);
+
+    pub fn multiply(&self, other: &Self) -> Self {
+        let mut elements = [0f64; 16];
+        for (i, element) in elements.iter_mut().enumerate() {
+            let (row, column) = (i / 4, i % 4);
+            *element = (0..4)
+                .map(|k| self.0[row * 4 + k] * other.0[k * 4 + column])
+                .sum();
+        }
+        Self(elements)
+    }
+
+    pub fn translation(&self) -> [f64; 3] {
+        [self.0[12], self.0[13], self.0[14]]
+    }
+
+    pub fn transform_point(&self, point: &Point3d) -> Point3d {
+        let m = &self.0;
+        let [x, y, z] = point.to_array();
+        let w = x * m[3] + y * m[7] + z * m[11] + m[15];
+        let w = if 0. == w { 1. } else { w };
+        Point3d::new(
+            (x * m[0] + y * m[4] + z * m[8] + m[12]) / w,
+            (x * m[1] + y * m[5] + z * m[9] + m[13]) / w,
+            (x * m[2] + y * m[6] + z * m[10] + m[14]) / w,
+        )
+    }
+}
+
+impl From<&Transform> for Xform {
+    fn from(transform: &Transform) -> Self {
+        Xform::from_row_major(&transform.0).transpose()
+    }
+}
+
+impl From<&Xform> for Transform {
+    fn from(xform: &Xform) -> Self {
+        Self(xform.transpose().to_row_major())
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Material {
+    pub name: Option<String>,
+    pub diffuse: Option<[f32; 4]>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Node {
+    pub name: Option<String>,
+    pub transform: Transform,
+    pub children: Vec<usize>,
+    pub meshes: Vec<usize>,
+    pub material: Option<usize>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Document {
+    pub nodes: Vec<Node>,
+    pub roots: Vec<usize>,
+    pub meshes: Vec<Mesh>,
+    pub materials: Vec<Material>,
+    pub metadata: BTreeMap<String, String>,
+    pub units: UnitSystem,
+}
+
+impl Document {
+    pub fn node(&self, index: usize) -> Option<&Node> {
+        self.nodes.get(index)
+    }
+
+    pub fn find_node(&self, name: &str) -> Option<&Node> {
+        self.nodes
+            .iter()
+            .find(|node| Some(name) == node.name.as_deref())
+    }
+
+    pub fn world_transforms(&self) -> Vec<Transform> {
+        let mut transforms = vec![Transform::IDENTITY; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack: Vec<(usize, Transform)> = self
+            .roots
+            .iter()
+            .map(|root| (*root, Transform::IDENTITY))
+            .collect();
+        while let Some((index, parent)) = stack.pop() {
+            let node = match self.nodes.get(index) {
+                Some(node) if !visited[index] => node,
+                _ => continue,
+            };
+            visited[index] = true;
+            transforms[index] = node.transform.multiply(&parent);
+            node.children
+                .iter()
+                .for_each(|child| stack.push((*child, transforms[index])));
+        }
+        transforms
+    }
+
+    pub fn scale(&mut self, factor: f64) {
+        for mesh in self.meshes.iter_mut() {
+            mesh.positions
+                .iter_mut()
+                .flatten()
+                .for_each(|v| *v = (*v as f64 * factor) as f32);
+        }
+        for node in self.nodes.iter_mut() {
+            node.transform.0[12..15]
+                .iter_mut()
+                .for_each(|v| *v *= factor);
+        }
+    }
+
+    pub fn convert_units(&mut self, target: UnitSystem) -> Option<f64> {
+        let factor = self.units.scale_to(&target)?;
+        self.scale(factor);
+        self.units = target;
+        Some(factor)
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut bounding_box = BoundingBox::EMPTY;
+        for (node, transform) in self.nodes.iter().zip(self.world_transforms()) {
+            node.meshes
+                .iter()
+                .filter_map(|index| self.meshes.get(*index))
+                .for_each(|mesh| {
+                    bounding_box.extend(mesh.points().map(|p| transform.transform_point(&p)))
+                });
+        }
+        bounding_box
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation(offset: [f64; 3]) -> Transform {
+        let mut transform = Transform::IDENTITY;
+        transform.0[12..15].copy_from_slice(&offset);
+        transform
+    }
+
+    #[test]
+    fn world_transforms() {
+        let document = Document {
+            nodes: vec![
+                Node {
+                    name: Some("root".to_string()),
+                    transform: translation([1., 0., 0.]),
+                    children: vec![1],
+                    ..Default::default()
+                },
+                Node {
+                    name: Some("child".to_string()),
+                    transform: translation([0., 2., 0.]),
+                    ..Default::default()
+                },
+            ],
+            roots: vec![0],
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![[1., 0., 0.], [1., 2., 0.]],
+            document
+                .world_transforms()
+                .iter()
+                .map(|t| t.translation())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&document.nodes[1]), document.find_node("child"));
+        assert!(document.find_node("missing").is_none());
+    }
+
+    #[test]
+    fn world_transforms_with_cycle() {
+        let document = Document {
+            nodes: vec![
+                Node {
+                    transform: translation([1., 0., 0.]),
+                    children: vec![1],
+                    ..Default::default()
+                },
+                Node {
+                    transform: translation([0., 2., 0.]),
+                    children: vec![0, 1],
+                    meshes: vec![0],
+                    ..Default::default()
+                },
+            ],
+            roots: vec![0],
+            meshes: vec![Mesh {
+                positions: vec![[0., 0., 0.]],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![[1., 0., 0.], [1., 2., 0.]],
+            document
+                .world_transforms()
+                .iter()
+                .map(|t| t.translation())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            BoundingBox::new(Point3d::new(1., 2., 0.), Point3d::new(1., 2., 0.)),
+            document.bounding_box()
+        );
+    }
+
+    #[test]
+    fn transform_to_xform() {
+        let transform = translation([1., 2., 3.]);
+        let xform = Xform::from(&transform);
+        assert_eq!(
+            transform.transform_point(&Point3d::new(1., 1., 1.)),
+            xform.transform_point(&Point3d::new(1., 1., 1.))
+        );
+        assert_eq!(transform, Transform::from(&xform));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let mesh = Mesh {
+            positions: vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 2.]],
+            triangles: vec![[0, 1, 2]],
+            ..Default::default()
+        };
+        assert_eq!(
+            BoundingBox::new(Point3d::ORIGIN, Point3d::new(1., 1., 2.)),
+            mesh.bounding_box()
+        );
+        let document = Document {
+            nodes: vec![
+                Node {
+                    transform: translation([10., 0., 0.]),
+                    children: vec![1],
+                    meshes: vec![0],
+                    ..Default::default()
+                },
+                Node {
+                    transform: translation([0., 0., -5.]),
+                    meshes: vec![0],
+                    ..Default::default()
+                },
+            ],
+            roots: vec![0],
+            meshes: vec![mesh],
+            ..Default::default()
+        };
+        assert_eq!(
+            BoundingBox::new(Point3d::new(10., 0., -5.), Point3d::new(11., 1., 2.)),
+            document.bounding_box()
+        );
+        assert!(Document::default().bounding_box().is_empty());
+    }
+
+    #[test]
+    fn convert_units() {
+        let mut document = Document {
+            nodes: vec![Node {
+                transform: translation([1., 2., 3.]),
+                meshes: vec![0],
+                ..Default::default()
+            }],
+            roots: vec![0],
+            meshes: vec![Mesh {
+                positions: vec![[0., 0., 0.], [1., 1., 1.]],
+                ..Default::default()
+            }],
+            units: UnitSystem::Meters,
+            ..Default::default()
+        };
+        assert_eq!(Some(1000.), document.convert_units(UnitSystem::Millimeters));
+        assert_eq!(UnitSystem::Millimeters, document.units);
+        assert_eq!(
+            BoundingBox::new(
+                Point3d::new(1000., 2000., 3000.),
+                Point3d::new(2000., 3000., 4000.)
+            ),
+            document.bounding_box()
+        );
+        assert_eq!(None, Document::default().convert_units(UnitSystem::Meters));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::rhino::{
+    archive::Archive, document::SharedDocument, object_index::ObjectHandle, properties::Properties,
+    uuid::Uuid, version::Version,
+};
+
+use super::{Document, Node};
+
+fn insert_properties(document: &mut Document, version: Version, properties: &Properties) {
+    let version: u8 = version.into();
+    let history = properties.revision_history();
+    [
+        ("version", version.to_string()),
+        ("created_by", history.created_by().to_string()),
+        ("last_edited_by", history.last_edited_by().to_string()),
+        ("revision_count", history.revision_count().to_string()),
+        ("notes", properties.notes().data().to_string()),
+    ]
+    .into_iter()
+    .chain(match properties {
+        Properties::V1(_) => vec![],
+        Properties::V2(properties) => vec![
+            ("filename", properties.filename().to_string()),
+            ("application", properties.application().name().to_string()),
+        ],
+    })
+    .filter(|(_, value)| !value.is_empty())
+    .for_each(|(key, value)| {
+        document.metadata.insert(key.to_string(), value);
+    });
+}
+
+fn insert_objects(document: &mut Document, mut objects: Vec<(Uuid, ObjectHandle, Option<Uuid>)>) {
+    objects.sort_by_key(|(_, handle, _)| handle.offset);
+    for (id, handle, definition) in objects {
+        let mut node = Node::default();
+        [
+            ("id", Some(id)),
+            ("class", Some(handle.class)),
+            ("definition", definition),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .for_each(|(key, value)| {
+            node.metadata.insert(key.to_string(), value.to_string());
+        });
+        document.roots.push(document.nodes.len());
+        document.nodes.push(node);
+    }
+}
+
+impl From<&Archive> for Document {
+    fn from(archive: &Archive) -> Self {
+        let mut document = Document {
+            units: archive.unit_system(),
+            ..Default::default()
+        };
+        insert_properties(&mut document, archive.version, &archive.properties);
+        if !archive.comment.as_str().is_empty() {
+            document
+                .metadata
+                .insert("comment".to_string(), archive.comment.as_str().to_string());
+        }
+        document
+    }
+}
+
+/// Objects become root nodes carrying their ids; mesh geometry is not decoded.
+impl From<&SharedDocument> for Document {
+    fn from(shared: &SharedDocument) -> Self {
+        let mut document = Document {
+            units: shared
+                .settings
+                .as_ref()
+                .map(|settings| settings.unit_system())
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        if let Some(properties) = &shared.properties {
+            insert_properties(&mut document, shared.version, properties);
+        }
+        let index = &shared.object_index;
+        insert_objects(
+            &mut document,
+            index
+                .objects()
+                .map(|(id, handle)| (*id, *handle, index.definition_id(id).copied()))
+                .collect(),
+        );
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::rhino::{object_index::INSTANCE_REFERENCE_CLASS, typecode};
+
+    use super::*;
+
+    #[test]
+    fn from_shared_document() {
+        let shared = SharedDocument {
+            version: Version::V2,
+            properties: Some(Arc::new(Properties::default())),
+            settings: None,
+            object_index: Default::default(),
+        };
+        let document = Document::from(&shared);
+        assert!(document.nodes.is_empty());
+        assert_eq!(Some(&"2".to_string()), document.metadata.get("version"));
+    }
+
+    #[test]
+    fn insert_object_nodes() {
+        let (mesh, instance, definition) = (
+            Uuid::new(1, 0, 0, [0; 8]),
+            Uuid::new(2, 0, 0, [0; 8]),
+            Uuid::new(3, 0, 0, [0; 8]),
+        );
+        let handle = |offset: u64, class: Uuid| ObjectHandle {
+            typecode: typecode::OBJECT_RECORD,
+            offset,
+            class,
+        };
+        let mut document = Document::default();
+        insert_objects(
+            &mut document,
+            vec![
+                (
+                    instance,
+                    handle(20, INSTANCE_REFERENCE_CLASS),
+                    Some(definition),
+                ),
+                (mesh, handle(10, Uuid::new(4, 0, 0, [0; 8])), None),
+            ],
+        );
+        assert_eq!(vec![0, 1], document.roots);
+        assert_eq!(
+            Some(&mesh.to_string()),
+            document.nodes[0].metadata.get("id")
+        );
+        assert!(!document.nodes[0].metadata.contains_key("definition"));
+        assert_eq!(
+            Some(&definition.to_string()),
+            document.nodes[1].metadata.get("definition")
+        );
+        assert_eq!(
+            Some(&INSTANCE_REFERENCE_CLASS.to_string()),
+            document.nodes[1].metadata.get("class")
+        );
+    }
+}
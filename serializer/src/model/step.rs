@@ -0,0 +1,463 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::units::UnitSystem,
+    math::{Point3d, Vector3d, Xform},
+    step::{
+        entity::{Parameter, Record},
+        file::StepFile,
+    },
+};
+
+use super::{Document, Node, Transform, MAX_NODE_COUNT};
+
+const NEXT_ASSEMBLY_USAGE_OCCURRENCE: &str = "NEXT_ASSEMBLY_USAGE_OCCURRENCE";
+const PRODUCT_DEFINITION: &str = "PRODUCT_DEFINITION";
+const PRODUCT_DEFINITION_SHAPE: &str = "PRODUCT_DEFINITION_SHAPE";
+
+fn coordinates(record: &Record) -> Option<[f64; 3]> {
+    let values: Vec<f64> = record
+        .list(1)?
+        .iter()
+        .map(Parameter::as_f64)
+        .collect::<Option<_>>()?;
+    match values[..] {
+        [x, y] => Some([x, y, 0.]),
+        [x, y, z] => Some([x, y, z]),
+        _ => None,
+    }
+}
+
+fn placement(file: &StepFile, id: u64) -> Option<Xform> {
+    let record = file.record(id, "AXIS2_PLACEMENT_3D")?;
+    let origin = Point3d::from(coordinates(
+        file.record(record.reference(1)?, "CARTESIAN_POINT")?,
+    )?);
+    let direction = |index: usize| {
+        record
+            .reference(index)
+            .and_then(|id| file.record(id, "DIRECTION"))
+            .and_then(coordinates)
+            .map(Vector3d::from)
+    };
+    let z = direction(2).unwrap_or(Vector3d::Z).normalized()?;
+    let reference = direction(3).unwrap_or(Vector3d::X);
+    let x = (reference - z * reference.dot(&z)).normalized()?;
+    let y = z.cross(&x);
+    Some(Xform([
+        [x.x, y.x, z.x, origin.x],
+        [x.y, y.y, z.y, origin.y],
+        [x.z, y.z, z.z, origin.z],
+        [0., 0., 0., 1.],
+    ]))
+}
+
+fn item_transformation(file: &StepFile, id: u64) -> Option<Xform> {
+    let record = file.record(id, "ITEM_DEFINED_TRANSFORMATION")?;
+    let from = placement(file, record.reference(2)?)?;
+    let to = placement(file, record.reference(3)?)?;
+    Some(to * from.inverse()?)
+}
+
+fn length_unit(file: &StepFile) -> UnitSystem {
+    let unit = file
+        .entities()
+        .map(|(_, entity)| entity)
+        .find(|entity| entity.is("LENGTH_UNIT"));
+    if let Some(record) = unit.and_then(|entity| entity.record("SI_UNIT")) {
+        if Some("METRE") != record.parameter(1).and_then(Parameter::as_enumeration) {
+            return UnitSystem::CustomUnits;
+        }
+        return match record.parameter(0).and_then(Parameter::as_enumeration) {
+            None => UnitSystem::Meters,
+            Some("MILLI") => UnitSystem::Millimeters,
+            Some("CENTI") => UnitSystem::Centimeters,
+            Some("DECI") => UnitSystem::Decimeters,
+            Some("MICRO") => UnitSystem::Microns,
+            Some("NANO") => UnitSystem::Nanometers,
+            Some("DECA") => UnitSystem::Dekameters,
+            Some("HECTO") => UnitSystem::Hectometers,
+            Some("KILO") => UnitSystem::Kilometers,
+            Some("MEGA") => UnitSystem::Megameters,
+            Some("GIGA") => UnitSystem::Gigameters,
+            Some(_) => UnitSystem::CustomUnits,
+        };
+    }
+    match unit
+        .and_then(|entity| entity.record("CONVERSION_BASED_UNIT"))
+        .and_then(|record| record.string(0))
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("inch") => UnitSystem::Inches,
+        Some("foot") => UnitSystem::Feet,
+        Some("yard") => UnitSystem::Yards,
+        Some("mile") => UnitSystem::Miles,
+        Some(_) => UnitSystem::CustomUnits,
+        None => UnitSystem::None,
+    }
+}
+
+struct Converter<'a> {
+    file: &'a StepFile,
+    document: Document,
+    occurrences: HashMap<u64, Vec<u64>>,
+    shapes: HashMap<u64, u64>,
+    transforms: HashMap<u64, Xform>,
+    path: Vec<u64>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(file: &'a StepFile) -> Self {
+        let mut converter = Self {
+            file,
+            document: Document::default(),
+            occurrences: HashMap::new(),
+            shapes: HashMap::new(),
+            transforms: HashMap::new(),
+            path: vec![],
+        };
+        for (id, record) in file.instances_of(NEXT_ASSEMBLY_USAGE_OCCURRENCE) {
+            if let Some(parent) = record.reference(3) {
+                converter.occurrences.entry(parent).or_default().push(id);
+            }
+        }
+        for (_, record) in file.instances_of("SHAPE_DEFINITION_REPRESENTATION") {
+            let definition = record
+                .reference(0)
+                .and_then(|id| converter.shape_definition(id));
+            if let (Some(definition), Some(representation)) = (definition, record.reference(1)) {
+                converter.shapes.insert(definition, representation);
+            }
+        }
+        for (_, record) in file.instances_of("CONTEXT_DEPENDENT_SHAPE_REPRESENTATION") {
+            let occurrence = record
+                .reference(1)
+                .and_then(|id| converter.shape_definition(id));
+            let transform = record
+                .reference(0)
+                .and_then(|id| converter.occurrence_transform(id, occurrence?));
+            if let (Some(occurrence), Some(transform)) = (occurrence, transform) {
+                converter.transforms.insert(occurrence, transform);
+            }
+        }
+        converter
+    }
+
+    fn shape_definition(&self, id: u64) -> Option<u64> {
+        self.file.record(id, PRODUCT_DEFINITION_SHAPE)?.reference(2)
+    }
+
+    fn occurrence_transform(&self, relationship: u64, occurrence: u64) -> Option<Xform> {
+        let entity = self.file.entity(relationship)?;
+        let representations = entity.record("REPRESENTATION_RELATIONSHIP")?;
+        let transform = entity
+            .record("REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION")?
+            .reference(0)
+            .and_then(|id| item_transformation(self.file, id))?;
+        let child = self
+            .file
+            .record(occurrence, NEXT_ASSEMBLY_USAGE_OCCURRENCE)?
+            .reference(4)
+            .and_then(|id| self.shapes.get(&id))
+            .copied();
+        if child.is_some() && child == representations.reference(3) {
+            transform.inverse()
+        } else {
+            Some(transform)
+        }
+    }
+
+    fn product(&self, definition: u64) -> Option<&'a Record> {
+        let formation = self
+            .file
+            .record(definition, PRODUCT_DEFINITION)?
+            .reference(2)?;
+        let product = self
+            .file
+            .entity(formation)?
+            .records()
+            .iter()
+            .find(|record| record.name.starts_with("PRODUCT_DEFINITION_FORMATION"))?
+            .reference(2)?;
+        self.file.record(product, "PRODUCT")
+    }
+
+    fn convert(&mut self, definition: u64, occurrence: Option<u64>) -> Option<usize> {
+        if MAX_NODE_COUNT <= self.document.nodes.len() || self.path.contains(&definition) {
+            return None;
+        }
+        self.path.push(definition);
+        let index = self.document.nodes.len();
+        self.document.nodes.push(Node::default());
+
+        let mut node = Node::default();
+        if let Some(product) = self.product(definition) {
+            node.name = product.string(1).map(str::to_string);
+            [
+                ("product_id", product.string(0)),
+                ("description", product.string(2)),
+            ]
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value.filter(|v| !v.is_empty())?)))
+            .for_each(|(key, value)| {
+                node.metadata.insert(key.to_string(), value.to_string());
+            });
+        }
+        if let Some(occurrence) = occurrence {
+            if let Some(transform) = self.transforms.get(&occurrence) {
+                node.transform = Transform::from(transform);
+            }
+            let record = self.file.record(occurrence, NEXT_ASSEMBLY_USAGE_OCCURRENCE);
+            [("occurrence_id", 0), ("occurrence_name", 1)]
+                .into_iter()
+                .filter_map(|(key, i)| Some((key, record?.string(i).filter(|v| !v.is_empty())?)))
+                .for_each(|(key, value)| {
+                    node.metadata.insert(key.to_string(), value.to_string());
+                });
+        }
+        for occurrence in self
+            .occurrences
+            .get(&definition)
+            .cloned()
+            .unwrap_or_default()
+        {
+            let child = self
+                .file
+                .record(occurrence, NEXT_ASSEMBLY_USAGE_OCCURRENCE)
+                .and_then(|record| record.reference(4))
+                .and_then(|child| self.convert(child, Some(occurrence)));
+            node.children.extend(child);
+        }
+
+        self.document.nodes[index] = node;
+        self.path.pop();
+        Some(index)
+    }
+}
+
+impl From<&StepFile> for Document {
+    fn from(file: &StepFile) -> Self {
+        let mut converter = Converter::new(file);
+        let children: HashSet<u64> = file
+            .instances_of(NEXT_ASSEMBLY_USAGE_OCCURRENCE)
+            .filter_map(|(_, record)| record.reference(4))
+            .collect();
+        let roots: Vec<u64> = file
+            .entities()
+            .filter(|(id, entity)| {
+                Some(PRODUCT_DEFINITION) == entity.name() && !children.contains(id)
+            })
+            .map(|(id, _)| id)
+            .collect();
+        for root in roots {
+            if let Some(index) = converter.convert(root, None) {
+                converter.document.roots.push(index);
+            }
+        }
+
+        let mut document = converter.document;
+        document.units = length_unit(file);
+        let header = &file.header;
+        [
+            ("name", header.name.clone()),
+            ("description", header.description.join("\n")),
+            ("time_stamp", header.time_stamp.clone()),
+            ("author", header.author.join(", ")),
+            ("organization", header.organization.join(", ")),
+            ("preprocessor_version", header.preprocessor_version.clone()),
+            ("originating_system", header.originating_system.clone()),
+            ("authorization", header.authorization.clone()),
+            ("schema", header.schemas.join(", ")),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.trim().is_empty())
+        .for_each(|(key, value)| {
+            document.metadata.insert(key.to_string(), value);
+        });
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASSEMBLY: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('assembly'),'2;1');
+FILE_NAME('assembly.stp','2024-01-01T00:00:00',('author'),(''),'','geometria','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+ENDSEC;
+DATA;
+#1=PRODUCT('ASM','assembly','top level',(#100));
+#2=PRODUCT_DEFINITION_FORMATION('','',#1);
+#3=PRODUCT_DEFINITION('design','',#2,#101);
+#4=PRODUCT('PRT','part','',(#100));
+#5=PRODUCT_DEFINITION_FORMATION_WITH_SPECIFIED_SOURCE('','',#4,.NOT_KNOWN.);
+#6=PRODUCT_DEFINITION('design','',#5,#101);
+#10=PRODUCT_DEFINITION_SHAPE('','',#3);
+#11=SHAPE_DEFINITION_REPRESENTATION(#10,#12);
+#12=SHAPE_REPRESENTATION('',(#20,#21,#22),#102);
+#13=PRODUCT_DEFINITION_SHAPE('','',#6);
+#14=SHAPE_DEFINITION_REPRESENTATION(#13,#15);
+#15=SHAPE_REPRESENTATION('',(#20),#102);
+#20=AXIS2_PLACEMENT_3D('',#30,#31,#32);
+#21=AXIS2_PLACEMENT_3D('',#33,#31,#32);
+#22=AXIS2_PLACEMENT_3D('',#34,#31,#35);
+#30=CARTESIAN_POINT('',(0.,0.,0.));
+#31=DIRECTION('',(0.,0.,1.));
+#32=DIRECTION('',(1.,0.,0.));
+#33=CARTESIAN_POINT('',(10.,0.,0.));
+#34=CARTESIAN_POINT('',(0.,5.,0.));
+#35=DIRECTION('',(0.,1.,0.));
+#40=NEXT_ASSEMBLY_USAGE_OCCURRENCE('1','first','',#3,#6,$);
+#41=PRODUCT_DEFINITION_SHAPE('','',#40);
+#42=CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#43,#41);
+#43=(REPRESENTATION_RELATIONSHIP('','',#15,#12)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#44)SHAPE_REPRESENTATION_RELATIONSHIP());
+#44=ITEM_DEFINED_TRANSFORMATION('','',#20,#21);
+#50=NEXT_ASSEMBLY_USAGE_OCCURRENCE('2','second','',#3,#6,$);
+#51=PRODUCT_DEFINITION_SHAPE('','',#50);
+#52=CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#53,#51);
+#53=(REPRESENTATION_RELATIONSHIP('','',#12,#15)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#54)SHAPE_REPRESENTATION_RELATIONSHIP());
+#54=ITEM_DEFINED_TRANSFORMATION('','',#20,#22);
+#102=(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNIT_ASSIGNED_CONTEXT((#103))REPRESENTATION_CONTEXT('',''));
+#103=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    fn assert_point(expected: [f64; 3], actual: Point3d) {
+        assert!(actual.distance_to(&Point3d::from(expected)) < 1e-12);
+    }
+
+    #[test]
+    fn assembly() {
+        let file = StepFile::parse(ASSEMBLY).unwrap();
+        let document = Document::from(&file);
+
+        assert_eq!(UnitSystem::Millimeters, document.units);
+        assert_eq!(
+            Some("assembly.stp"),
+            document.metadata.get("name").map(String::as_str)
+        );
+        assert_eq!(
+            Some("author"),
+            document.metadata.get("author").map(String::as_str)
+        );
+        assert!(!document.metadata.contains_key("organization"));
+
+        assert_eq!(vec![0], document.roots);
+        let root = document.node(0).unwrap();
+        assert_eq!(Some("assembly"), root.name.as_deref());
+        assert_eq!(
+            Some("ASM"),
+            root.metadata.get("product_id").map(String::as_str)
+        );
+        assert_eq!(2, root.children.len());
+
+        let first = document.node(root.children[0]).unwrap();
+        assert_eq!(Some("part"), first.name.as_deref());
+        assert_eq!(
+            Some("first"),
+            first.metadata.get("occurrence_name").map(String::as_str)
+        );
+        let xform = Xform::from(&first.transform);
+        assert_point(
+            [11., 0., 0.],
+            xform.transform_point(&Point3d::new(1., 0., 0.)),
+        );
+
+        let second = document.node(root.children[1]).unwrap();
+        assert_eq!(
+            Some("2"),
+            second.metadata.get("occurrence_id").map(String::as_str)
+        );
+        let xform = Xform::from(&second.transform);
+        assert_point(
+            [-5., -1., 0.],
+            xform.transform_point(&Point3d::new(1., 0., 0.)),
+        );
+    }
+
+    #[test]
+    fn units() {
+        let file = |unit: &str| {
+            StepFile::parse(&format!(
+                "ISO-10303-21;HEADER;ENDSEC;DATA;#1={};ENDSEC;END-ISO-10303-21;",
+                unit
+            ))
+            .unwrap()
+        };
+        for (unit, expected) in [
+            (
+                "(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT($,.METRE.))",
+                UnitSystem::Meters,
+            ),
+            (
+                "(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.CENTI.,.METRE.))",
+                UnitSystem::Centimeters,
+            ),
+            (
+                "(CONVERSION_BASED_UNIT('INCH',#2)LENGTH_UNIT()NAMED_UNIT(#3))",
+                UnitSystem::Inches,
+            ),
+            (
+                "(PLANE_ANGLE_UNIT()NAMED_UNIT(*)SI_UNIT($,.RADIAN.))",
+                UnitSystem::None,
+            ),
+        ] {
+            assert_eq!(expected, Document::from(&file(unit)).units);
+        }
+    }
+
+    #[test]
+    fn cyclic_structure() {
+        let file = StepFile::parse(
+            "ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=PRODUCT('A','a','',());
+#2=PRODUCT_DEFINITION_FORMATION('','',#1);
+#3=PRODUCT_DEFINITION('','',#2,$);
+#4=PRODUCT_DEFINITION('','',#2,$);
+#5=NEXT_ASSEMBLY_USAGE_OCCURRENCE('1','','',#3,#4,$);
+#6=NEXT_ASSEMBLY_USAGE_OCCURRENCE('2','','',#4,#3,$);
+#7=NEXT_ASSEMBLY_USAGE_OCCURRENCE('3','','',#8,#3,$);
+#8=PRODUCT_DEFINITION('','',#2,$);
+ENDSEC;END-ISO-10303-21;",
+        )
+        .unwrap();
+        let document = Document::from(&file);
+        assert_eq!(vec![0], document.roots);
+        assert_eq!(3, document.nodes.len());
+        assert!(document.node(2).unwrap().children.is_empty());
+    }
+
+    #[test]
+    fn exponential_fan_out() {
+        let mut data = "ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=PRODUCT('A','a','',());
+#2=PRODUCT_DEFINITION_FORMATION('','',#1);
+"
+        .to_string();
+        for level in 0..22 {
+            data.push_str(&format!(
+                "#{}=PRODUCT_DEFINITION('','',#2,$);\n",
+                100 + level
+            ));
+            if 21 > level {
+                for i in 0..2 {
+                    data.push_str(&format!(
+                        "#{}=NEXT_ASSEMBLY_USAGE_OCCURRENCE('','','',#{},#{},$);\n",
+                        1000 + 2 * level + i,
+                        100 + level,
+                        101 + level
+                    ));
+                }
+            }
+        }
+        data.push_str("ENDSEC;END-ISO-10303-21;");
+        let document = Document::from(&StepFile::parse(&data).unwrap());
+        assert_eq!(vec![0], document.roots);
+        assert_eq!(MAX_NODE_COUNT, document.nodes.len());
+    }
+}
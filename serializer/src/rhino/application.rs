@@ -1,11 +1,13 @@
 use geometria_derive::RhinoDeserialize;
 
 use super::{
-    chunk, deserialize::Deserialize, deserializer::Deserializer, string::WStringWithLength,
+    chunk, deserialize::Deserialize, deserializer::Deserializer, error::Error,
+    string::WStringWithLength,
 };
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Application {
     #[underlying_type(WStringWithLength)]
     name: String,
@@ -1,11 +1,28 @@
 use geometria_derive::RhinoDeserialize;
+use std::io::{Cursor, Read, Seek};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs::File, path::Path};
+
+use crate::common::units::UnitSystem;
 
 use super::{
-    comment::Comment, deserialize::Deserialize, deserializer::Deserializer, header::Header,
-    properties::Properties, settings::Settings, start_section::StartSection, version::Version,
+    comment::Comment,
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    error::Error,
+    header::Header,
+    options::ParseOptions,
+    properties::Properties,
+    reader::Reader,
+    reader::ReaderBuilder,
+    settings::Settings,
+    start_section::StartSection,
+    validation::{self, ValidationReport},
+    version::Version,
 };
 
 #[derive(RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Archive {
     pub header: Header,
     pub version: Version,
@@ -14,3 +31,82 @@ pub struct Archive {
     pub properties: Properties,
     pub settings: Settings,
 }
+
+impl Archive {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_stream(Cursor::new(bytes))
+    }
+
+    pub fn from_stream<T>(stream: T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        Self::deserialize(&mut Reader::new(stream))
+    }
+
+    pub fn from_stream_with_options<T>(stream: T, options: ParseOptions) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        Self::deserialize(&mut ReaderBuilder::new().options(options).build(stream))
+    }
+
+    pub fn validate<T>(stream: T) -> Result<ValidationReport, Error>
+    where
+        T: Read + Seek,
+    {
+        validation::validate(&mut Reader::new(stream))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        Self::deserialize(&mut ReaderBuilder::new().build_buffered(file))
+    }
+
+    pub fn unit_system(&self) -> UnitSystem {
+        self.settings.unit_system()
+    }
+
+    pub fn convert_units(&mut self, target: UnitSystem) -> Option<f64> {
+        self.settings.convert_units(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::progress::{CancellationToken, ProgressReader};
+
+    use super::*;
+
+    #[test]
+    fn from_bytes_invalid_header() {
+        let error = Archive::from_bytes(b"4D Geometry File Format        1").err();
+        assert!(matches!(
+            error.as_ref().map(Error::root_cause),
+            Some(Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn from_path_not_found() {
+        assert!(matches!(
+            Archive::from_path("tests/resources/serializer/rhino/missing.3dm"),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn from_stream_cancelled() {
+        let cancellation = CancellationToken::new();
+        let stream = ProgressReader::new(Cursor::new(b"3D Geometry File Format        1"))
+            .unwrap()
+            .cancellation(cancellation.clone());
+        cancellation.cancel();
+        let error = Archive::from_stream(stream).err().unwrap();
+        assert_eq!("operation cancelled", error.root_cause().to_string());
+    }
+}
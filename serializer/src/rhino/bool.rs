@@ -1,4 +1,4 @@
-use super::{deserialize::Deserialize, deserializer::Deserializer};
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
 
 pub struct BoolFromI32(bool);
 
@@ -6,7 +6,7 @@ impl<D> Deserialize<'_, D> for BoolFromI32
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         Ok(Self(i32::deserialize(deserializer)? != 0))
@@ -1,10 +1,12 @@
-use once_io::OStream;
+use flate2::Crc;
 use std::io::{Read, Seek, SeekFrom};
 
 use geometria_derive::RhinoDeserialize;
 
 use super::deserialize::Deserialize;
 use super::deserializer::Deserializer;
+use super::error::Error;
+use super::options::ParseOptions;
 use super::typecode::{self, Typecode};
 use super::version::Version as FileVersion;
 
@@ -25,7 +27,7 @@ impl Begin {
     }
 
     fn is_unsigned(self) -> bool {
-        0 == (typecode::SHORT & self.typecode)
+        !self.typecode.is_short()
             || typecode::RGB == self.typecode
             || typecode::RGBDISPLAY == self.typecode
             || typecode::PROPERTIES_OPENNURBS_VERSION == self.typecode
@@ -37,11 +39,11 @@ impl<D> Deserialize<'_, D> for Begin
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let mut chunk_begin = Begin {
-            typecode: u32::deserialize(deserializer)?,
+            typecode: Typecode::deserialize(deserializer)?,
             value: 0i64,
             initial_position: 0u64,
         };
@@ -54,7 +56,7 @@ where
         }
         match deserializer.stream_position() {
             Ok(position) => chunk_begin.initial_position = position,
-            Err(e) => return Err(format!("{}", e)),
+            Err(e) => return Err(Error::Io(e)),
         }
         deserializer.set_chunk_begin(chunk_begin);
         Ok(chunk_begin)
@@ -73,7 +75,7 @@ impl Value {
     }
 
     fn is_unsigned(typecode: Typecode) -> bool {
-        0 == (typecode::SHORT & typecode)
+        !typecode.is_short()
             || typecode::RGB == typecode
             || typecode::RGBDISPLAY == typecode
             || typecode::PROPERTIES_OPENNURBS_VERSION == typecode
@@ -91,7 +93,7 @@ impl<D> Deserialize<'_, D> for Value
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         if 8 == Self::size(deserializer.version()) {
@@ -105,17 +107,15 @@ where
 }
 
 #[derive(RhinoDeserialize)]
-pub struct BigVersion {
-    inner: u8,
-}
+pub struct BigVersion(u8);
 
 impl BigVersion {
     pub fn minor(&self) -> u8 {
-        self.inner & 0x0F
+        self.0 & 0x0F
     }
 
     pub fn major(&self) -> u8 {
-        self.inner >> 4
+        self.0 >> 4
     }
 }
 
@@ -125,11 +125,9 @@ pub struct NormalVersion {
 }
 
 impl NormalVersion {
-    pub fn new(major: i32, minor: i32) -> Result<Self, String> {
-        if 0 > major {
-            Err("invalid major version".to_string())
-        } else if 0 > minor {
-            Err("invalid minor version".to_string())
+    pub fn new(major: i32, minor: i32) -> Result<Self, Error> {
+        if 0 > major || 0 > minor {
+            Err(Error::UnsupportedVersion)
         } else {
             Ok(Self { major, minor })
         }
@@ -148,7 +146,7 @@ impl<D> Deserialize<'_, D> for NormalVersion
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         Self::new(
@@ -160,13 +158,16 @@ where
 
 pub struct Chunk<'a, T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     stream: &'a mut T,
     offset: u64,
     length: u64,
     version: FileVersion,
     begin: Begin,
+    path: Vec<Typecode>,
+    absolute_offset: u64,
+    options: ParseOptions,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -204,7 +205,7 @@ impl PartialEq<std::io::Error> for ChunkError {
 
 impl<'a, T> Chunk<'a, T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     pub fn new(
         stream: &'a mut T,
@@ -222,16 +223,35 @@ where
                 length,
                 version,
                 begin,
+                path: vec![begin.typecode],
+                absolute_offset: offset,
+                options: ParseOptions::default(),
             })
         }
     }
 
+    pub fn absolute_offset(&self) -> u64 {
+        self.absolute_offset
+    }
+
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn version(&self) -> FileVersion {
+        self.version
+    }
+
+    pub fn path(&self) -> &[Typecode] {
+        &self.path
+    }
+
     fn start_position(&self) -> u64 {
         self.offset
     }
 
     fn end_position(&self) -> u64 {
-        self.offset + (self.length - 1)
+        self.offset.saturating_add(self.length - 1)
     }
 
     fn remainder_length(&mut self) -> std::io::Result<u64> {
@@ -244,15 +264,51 @@ where
     }
 
     fn is_long(version: FileVersion, begin: &Begin) -> bool {
-        (0 == begin.typecode & typecode::SHORT)
-            && (0 != begin.typecode || FileVersion::V1 != version)
+        !begin.typecode.is_short()
+            && (0 != begin.typecode.value() || FileVersion::V1 != version)
             && (0 < begin.value)
     }
+
+    fn read_payload(&mut self, length: u64) -> std::io::Result<Vec<u8>> {
+        let position = self.stream.stream_position()?;
+        self.stream
+            .seek(SeekFrom::Start(self.begin.initial_position))?;
+        let mut payload = vec![];
+        (&mut self.stream).take(length).read_to_end(&mut payload)?;
+        self.stream.seek(SeekFrom::Start(position))?;
+        if length != payload.len() as u64 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(payload)
+    }
+
+    pub fn has_crc(&self) -> bool {
+        self.begin.typecode.has_crc() && Self::is_long(self.version, &self.begin)
+    }
+
+    pub fn verify_crc(&mut self) -> Result<(), Error> {
+        if !self.has_crc() {
+            return Ok(());
+        }
+        let payload_length = (self.end_position() + 1).saturating_sub(self.begin.initial_position);
+        if 4 > payload_length {
+            return Err(Error::CrcMismatch);
+        }
+        let payload = self.read_payload(payload_length)?;
+        let (data, crc_bytes) = payload.split_at(payload.len() - 4);
+        let mut crc = Crc::new();
+        crc.update(data);
+        if crc.sum() == u32::from_le_bytes(crc_bytes.try_into().unwrap()) {
+            Ok(())
+        } else {
+            Err(Error::CrcMismatch)
+        }
+    }
 }
 
 impl<'a, T> Read for Chunk<'a, T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let length = std::cmp::min(self.remainder_length()? as usize, buf.len());
@@ -262,37 +318,22 @@ where
 
 impl<'a, T> Seek for Chunk<'a, T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
         let final_position: Option<u64> = match pos {
             SeekFrom::Start(value) => self.start_position().checked_add(value),
-            SeekFrom::End(value) => {
-                // TODO: replace by self.end_position().checked_add_signed(value)
-                if 0 <= value {
-                    self.end_position().checked_add(value as u64)
-                } else {
-                    self.end_position().checked_sub(value.unsigned_abs())
-                }
-            }
-            SeekFrom::Current(value) => {
-                let current_position = self.stream.stream_position()?;
-                // TODO: replace by current_position().checked_add_signed(value)
-                if 0 <= value {
-                    current_position.checked_add(value as u64)
-                } else {
-                    current_position.checked_sub(value.unsigned_abs())
-                }
-            }
+            SeekFrom::End(value) => self.end_position().checked_add_signed(value),
+            SeekFrom::Current(value) => self.stream.stream_position()?.checked_add_signed(value),
         };
 
         match final_position {
             Some(value) => {
-                if value >= self.start_position() {
+                if value < self.start_position() || value - self.start_position() > self.length {
+                    Err(std::io::Error::from(ChunkError::OutOfBounds))
+                } else {
                     self.stream.seek(SeekFrom::Start(value))?;
                     Ok(value - self.start_position())
-                } else {
-                    Err(std::io::Error::from(ChunkError::OutOfBounds))
                 }
             }
             None => Err(std::io::Error::from(ChunkError::InvalidInput)),
@@ -302,7 +343,7 @@ where
 
 impl<'a, T> Deserializer for Chunk<'a, T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     fn deserialize_bytes(&mut self, buf: &mut [u8]) -> Result<(), String> {
         match self.read_exact(buf) {
@@ -326,25 +367,172 @@ where
     fn set_chunk_begin(&mut self, chunk_begin: Begin) {
         self.begin = chunk_begin;
     }
+
+    fn chunk_path(&self) -> Vec<Typecode> {
+        self.path.clone()
+    }
+
+    fn absolute_position(&mut self) -> std::io::Result<u64> {
+        Ok(self.absolute_offset + self.stream_position()?)
+    }
+
+    fn parse_options(&self) -> ParseOptions {
+        self.options.clone()
+    }
+}
+
+impl<'a, T> Chunk<'a, T>
+where
+    T: Deserializer,
+{
+    pub fn with_child<F, R, E>(deserializer: &'a mut T, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Self) -> Result<R, E>,
+        E: From<Error>,
+    {
+        let mut chunk = Self::deserialize(deserializer)?;
+        let value = f(&mut chunk)?;
+        chunk.seek(SeekFrom::End(1)).map_err(Error::from)?;
+        Ok(value)
+    }
 }
 
 impl<'a, T> Deserialize<'a, T> for Chunk<'a, T>
 where
     T: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &'a mut T) -> Result<Self, Self::Error> {
-        let offset = deserializer.stream_position().unwrap();
+        let offset = deserializer.stream_position()?;
+        let absolute_offset = deserializer.absolute_position()?;
+        let mut path = deserializer.chunk_path();
+        let options = deserializer.parse_options();
+        options.limits().check_chunk_depth(path.len() + 1)?;
         let begin = Begin::deserialize(deserializer)?;
-        let current_position = deserializer.stream_position().unwrap();
-        let length = current_position - offset
-            + if Self::is_long(deserializer.version(), &begin) {
-                begin.value as u64
-            } else {
-                0
-            };
-        Ok(Self::new(deserializer, offset, length, deserializer.version(), begin).unwrap())
+        path.push(begin.typecode);
+        let current_position = deserializer.stream_position()?;
+        let length = if Self::is_long(deserializer.version(), &begin) {
+            u64::try_from(begin.value)
+                .ok()
+                .and_then(|value| value.checked_add(current_position - offset))
+                .ok_or(Error::InvalidLength)?
+        } else {
+            current_position - offset
+        };
+        let version = deserializer.version();
+        let mut chunk = Self::new(deserializer, offset, length, version, begin)
+            .map_err(std::io::Error::from)?;
+        chunk.path = path;
+        chunk.absolute_offset = absolute_offset;
+        chunk.options = options;
+        Ok(chunk)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Entry {
+    pub typecode: Typecode,
+    pub offset: u64,
+    pub length: u64,
+    pub depth: usize,
+}
+
+pub struct Chunks<'a, D>
+where
+    D: Deserializer,
+{
+    deserializer: &'a mut D,
+    nested: bool,
+    ends: Vec<u64>,
+    end: Option<u64>,
+    done: bool,
+}
+
+impl<'a, D> Chunks<'a, D>
+where
+    D: Deserializer,
+{
+    pub fn new(deserializer: &'a mut D) -> Self {
+        Self {
+            deserializer,
+            nested: false,
+            ends: vec![],
+            end: None,
+            done: false,
+        }
+    }
+
+    pub fn nested(mut self, nested: bool) -> Self {
+        self.nested = nested;
+        self
+    }
+
+    fn stream_end(&mut self) -> std::io::Result<u64> {
+        if let Some(end) = self.end {
+            return Ok(end);
+        }
+        let position = self.deserializer.stream_position()?;
+        let end = self.deserializer.seek(SeekFrom::End(0))?;
+        self.deserializer.seek(SeekFrom::Start(position))?;
+        self.end = Some(end);
+        Ok(end)
+    }
+
+    fn next_entry(&mut self) -> Result<Option<Entry>, Error> {
+        let offset = self.deserializer.stream_position()?;
+        while self.ends.last().is_some_and(|end| offset >= *end) {
+            self.ends.pop();
+        }
+        if offset >= self.stream_end()? {
+            return Ok(None);
+        }
+        self.deserializer
+            .limits()
+            .check_chunk_depth(self.ends.len() + 1)?;
+        let version = self.deserializer.version();
+        let begin = Begin::deserialize(self.deserializer)?;
+        let length = if Chunk::<D>::is_long(version, &begin) {
+            begin.value as u64
+        } else {
+            0
+        };
+        let entry = Entry {
+            typecode: begin.typecode,
+            offset,
+            length,
+            depth: self.ends.len(),
+        };
+        if self.nested && begin.typecode.is_table() && 0 < length {
+            self.ends.push(begin.initial_position + length);
+        } else {
+            self.deserializer.seek(SeekFrom::Current(length as i64))?;
+        }
+        Ok(Some(entry))
+    }
+}
+
+impl<D> Iterator for Chunks<'_, D>
+where
+    D: Deserializer,
+{
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_entry() {
+            Ok(Some(entry)) => Some(Ok(entry)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
     }
 }
 
@@ -353,12 +541,83 @@ mod tests {
     use std::io::{Cursor, Read, Seek, SeekFrom};
 
     use crate::rhino::chunk::ChunkError;
+    use crate::rhino::options::Limits;
     use crate::rhino::typecode::{self};
     use crate::rhino::version::Version as FileVersion;
     use crate::rhino::{deserialize::Deserialize, reader::Reader};
 
     use super::*;
 
+    fn table_data() -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_TABLE.to_le_bytes());
+        data.extend(18u32.to_le_bytes());
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend(2u32.to_le_bytes());
+        data.extend([1u8, 2u8]);
+        data.extend(typecode::ENDOFTABLE.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        data.extend("abc".as_bytes());
+        data
+    }
+
+    #[test]
+    fn iterate_top_level_chunks() {
+        let mut deserializer = Reader::new(Cursor::new(table_data()));
+        let entries = Chunks::new(&mut deserializer)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                Entry {
+                    typecode: typecode::PROPERTIES_TABLE,
+                    offset: 0,
+                    length: 18,
+                    depth: 0
+                },
+                Entry {
+                    typecode: typecode::COMMENTBLOCK,
+                    offset: 26,
+                    length: 3,
+                    depth: 0
+                },
+            ],
+            entries
+        );
+    }
+
+    #[test]
+    fn iterate_nested_chunks() {
+        let mut deserializer = Reader::new(Cursor::new(table_data()));
+        let entries = Chunks::new(&mut deserializer)
+            .nested(true)
+            .map(|entry| entry.map(|entry| (entry.typecode, entry.offset, entry.depth)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                (typecode::PROPERTIES_TABLE, 0, 0),
+                (typecode::PROPERTIES_NOTES, 8, 1),
+                (typecode::ENDOFTABLE, 18, 1),
+                (typecode::COMMENTBLOCK, 26, 0),
+            ],
+            entries
+        );
+    }
+
+    #[test]
+    fn iterate_truncated_chunk() {
+        let mut data = table_data();
+        data.truncate(28);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        let mut chunks = Chunks::new(&mut deserializer);
+        assert!(chunks.next().unwrap().is_ok());
+        assert!(chunks.next().unwrap().is_err());
+        assert!(chunks.next().is_none());
+    }
+
     #[test]
     fn deserialize_version() {
         let major_version = 1u8;
@@ -369,6 +628,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
 
         let version = BigVersion::deserialize(&mut deserializer).unwrap();
@@ -393,9 +653,9 @@ mod tests {
         assert!(Value::is_unsigned(typecode::RGBDISPLAY));
         assert!(Value::is_unsigned(typecode::PROPERTIES_OPENNURBS_VERSION));
         assert!(Value::is_unsigned(typecode::OBJECT_RECORD_TYPE));
-        assert!(Value::is_unsigned(!typecode::SHORT));
-        assert!(Value::is_unsigned(0));
-        assert!(!Value::is_unsigned(typecode::SHORT));
+        assert!(Value::is_unsigned(Typecode::from(!typecode::SHORT)));
+        assert!(Value::is_unsigned(Typecode::default()));
+        assert!(!Value::is_unsigned(Typecode::from(typecode::SHORT)));
     }
 
     #[test]
@@ -405,10 +665,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V50,
             chunk_begin: Begin {
-                typecode: 0,
+                typecode: Typecode::default(),
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -423,10 +684,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V50,
             chunk_begin: Begin {
-                typecode: 0,
+                typecode: Typecode::default(),
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -441,10 +703,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V50,
             chunk_begin: Begin {
-                typecode: 0,
+                typecode: Typecode::default(),
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -463,6 +726,7 @@ mod tests {
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -481,6 +745,7 @@ mod tests {
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -499,6 +764,7 @@ mod tests {
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -513,10 +779,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin {
-                typecode: typecode::SHORT,
+                typecode: Typecode::from(typecode::SHORT),
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -531,10 +798,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin {
-                typecode: typecode::SHORT,
+                typecode: Typecode::from(typecode::SHORT),
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -549,10 +817,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin {
-                typecode: typecode::SHORT,
+                typecode: Typecode::from(typecode::SHORT),
                 value: 0,
                 initial_position: 0,
             },
+            options: Default::default(),
         };
         assert_eq!(
             Value::deserialize(&mut deserializer).ok(),
@@ -579,6 +848,29 @@ mod tests {
         assert_eq!(chunk.err(), Some(ChunkError::EmptyChunk));
     }
 
+    #[test]
+    fn chunk_accessors() {
+        let mut data: Vec<u8> = vec![0u8; 2];
+        data.extend(typecode::PROPERTIES_TABLE.to_le_bytes());
+        data.extend(12u32.to_le_bytes());
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend(4u32.to_le_bytes());
+        data.extend([0u8; 4]);
+        let mut deserializer = Reader::new(Cursor::new(data));
+        deserializer.seek(SeekFrom::Start(2)).unwrap();
+        let mut table = Chunk::deserialize(&mut deserializer).unwrap();
+        assert_eq!(2, table.absolute_offset());
+        assert_eq!(20, table.length());
+        assert_eq!(FileVersion::V1, table.version());
+        let notes = Chunk::deserialize(&mut table).unwrap();
+        assert_eq!(10, notes.absolute_offset());
+        assert_eq!(12, notes.length());
+        assert_eq!(
+            &[typecode::PROPERTIES_TABLE, typecode::PROPERTIES_NOTES],
+            notes.path()
+        );
+    }
+
     #[test]
     fn chunk_start_position() {
         let data = [0; 10];
@@ -895,6 +1187,82 @@ mod tests {
         assert_eq!(0, stream.position());
     }
 
+    #[test]
+    fn seek_chunk_past_the_end() {
+        let mut stream = Cursor::new([0; 11]);
+        let mut chunk = Chunk::new(&mut stream, 1, 9, FileVersion::V1, Begin::default()).unwrap();
+        for pos in [SeekFrom::Start(10), SeekFrom::End(2), SeekFrom::Current(11)] {
+            assert_eq!(ChunkError::OutOfBounds, chunk.seek(pos).err().unwrap());
+        }
+        assert_eq!(0, stream.position());
+    }
+
+    fn check_seek(offset: u64, length: u64, current: u64, pos: SeekFrom) {
+        let position = match pos {
+            SeekFrom::Start(value) => offset as i128 + value as i128,
+            SeekFrom::End(value) => (offset + (length - 1)) as i128 + value as i128,
+            SeekFrom::Current(value) => current as i128 + value as i128,
+        };
+        let expected = if 0 > position || (u64::MAX as i128) < position {
+            Err(ChunkError::InvalidInput)
+        } else if (offset as i128) > position || (offset as i128 + length as i128) < position {
+            Err(ChunkError::OutOfBounds)
+        } else {
+            Ok((position - offset as i128) as u64)
+        };
+        let mut stream = Cursor::new([0u8; 0]);
+        stream.set_position(current);
+        let result = {
+            let mut chunk = Chunk::new(
+                &mut stream,
+                offset,
+                length,
+                FileVersion::V1,
+                Begin::default(),
+            )
+            .unwrap();
+            chunk.seek(pos)
+        };
+        match expected {
+            Ok(position) => {
+                assert_eq!(Some(position), result.ok());
+                assert_eq!(offset + position, stream.position());
+            }
+            Err(error) => {
+                assert_eq!(error, result.err().unwrap());
+                assert_eq!(current, stream.position());
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn seek_chunk_from_end_any_offset(
+            offset in 0..u64::MAX / 2,
+            length in 1..u64::MAX / 2,
+            value: i64,
+        ) {
+            check_seek(offset, length, offset, SeekFrom::End(value));
+        }
+
+        #[test]
+        fn seek_chunk_from_current_any_offset(
+            offset: u64,
+            length in 1..=u64::MAX,
+            current: u64,
+            value: i64,
+        ) {
+            let length = length.min((u64::MAX - offset).saturating_add(1));
+            check_seek(offset, length, current, SeekFrom::Current(value));
+        }
+
+        #[test]
+        fn seek_chunk_from_start_any_offset(offset: u64, length in 1..=u64::MAX, value: u64) {
+            let length = length.min((u64::MAX - offset).saturating_add(1));
+            check_seek(offset, length, offset, SeekFrom::Start(value));
+        }
+    }
+
     #[test]
     fn seek_chunk_from_current_to_start() {
         let data = [0; 11];
@@ -1086,6 +1454,58 @@ mod tests {
         assert_eq!(offset + length, stream.position());
     }
 
+    #[test]
+    fn with_child() {
+        let mut deserializer = Reader::new(Cursor::new(table_data()));
+        let notes = Chunk::with_child(&mut deserializer, |table| {
+            Chunk::with_child(table, |notes| -> Result<_, Error> {
+                Ok((notes.chunk_begin().typecode, u8::deserialize(notes)?))
+            })
+        })
+        .unwrap();
+        assert_eq!((typecode::PROPERTIES_NOTES, 1), notes);
+        assert_eq!(26, deserializer.stream_position().unwrap());
+        assert_eq!(
+            typecode::COMMENTBLOCK,
+            Chunk::with_child(&mut deserializer, |chunk| -> Result<_, Error> {
+                Ok(chunk.chunk_begin().typecode)
+            })
+            .unwrap()
+        );
+        assert!(matches!(
+            Chunk::with_child(&mut deserializer, |_| -> Result<(), Error> { Ok(()) }),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn chunk_depth_limit() {
+        let options = ParseOptions::default().with_limits(Limits {
+            max_chunk_depth: 1,
+            ..Limits::default()
+        });
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(table_data()),
+            version: FileVersion::V1,
+            chunk_begin: Begin::default(),
+            options: options.clone(),
+        };
+        let result = Chunk::with_child(&mut deserializer, |table| {
+            Chunk::with_child(table, |_| -> Result<(), Error> { Ok(()) })
+        });
+        assert!(matches!(result, Err(Error::LimitExceeded("chunk depth"))));
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(table_data()),
+            version: FileVersion::V1,
+            chunk_begin: Begin::default(),
+            options,
+        };
+        let result: Result<Vec<Entry>, Error> =
+            Chunks::new(&mut deserializer).nested(true).collect();
+        assert!(matches!(result, Err(Error::LimitExceeded("chunk depth"))));
+    }
+
     #[test]
     fn read_string_from_chunk() {
         let data = "hello world!".as_bytes();
@@ -1095,4 +1515,48 @@ mod tests {
         chunk.read_to_string(&mut result).unwrap();
         assert_eq!(result, "hello".to_string());
     }
+
+    fn crc_chunk_data(payload: &[u8], crc: u32) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend((payload.len() as u32 + 4).to_le_bytes());
+        data.extend(payload);
+        data.extend(crc.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn verify_chunk_crc() {
+        let payload = [1u8, 2, 3, 4, 5];
+        let mut crc = Crc::new();
+        crc.update(&payload);
+        for (checksum, valid) in [(crc.sum(), true), (crc.sum() ^ 1, false)] {
+            let mut deserializer = Reader {
+                stream: &mut Cursor::new(crc_chunk_data(&payload, checksum)),
+                version: FileVersion::V1,
+                chunk_begin: Begin::default(),
+                options: Default::default(),
+            };
+            let mut chunk = Chunk::deserialize(&mut deserializer).unwrap();
+            assert!(chunk.has_crc());
+            assert_eq!(valid, chunk.verify_crc().is_ok());
+            assert_eq!(1, u8::deserialize(&mut chunk).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_truncated_chunk_crc() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend(0x7FFFFFFFFFFFFFF0i64.to_le_bytes());
+        data.extend([1u8, 2, 3, 4, 5]);
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: FileVersion::V50,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let mut chunk = Chunk::deserialize(&mut deserializer).unwrap();
+        assert!(matches!(chunk.verify_crc(), Err(Error::Io(_))));
+    }
 }
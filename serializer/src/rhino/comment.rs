@@ -2,27 +2,36 @@ use super::{
     chunk::Chunk,
     deserialize::Deserialize,
     deserializer::Deserializer,
+    error::Error,
     typecode::{self},
 };
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Comment(String);
 
 impl<D> Deserialize<'_, D> for Comment
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let mut chunk = Chunk::deserialize(deserializer)?;
-        if typecode::COMMENTBLOCK == chunk.chunk_begin().typecode {
+        let typecode = chunk.chunk_begin().typecode;
+        if typecode::COMMENTBLOCK == typecode {
             Ok(Comment(String::deserialize(&mut chunk)?))
         } else {
-            Err("invalid typecode".to_string())
+            Err(Error::InvalidTypecode(typecode))
         }
     }
 }
 
+impl Comment {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl From<Comment> for String {
     fn from(comment: Comment) -> Self {
         comment.0
@@ -34,11 +43,14 @@ mod tests {
     use std::io::Cursor;
 
     use crate::rhino::{
-        chunk::Begin, deserialize::Deserialize, reader::Reader, typecode,
+        chunk::Begin,
+        deserialize::Deserialize,
+        reader::Reader,
+        typecode::{self, Typecode},
         version::Version as FileVersion,
     };
 
-    use super::Comment;
+    use super::{Comment, Error};
 
     #[test]
     fn deserialize_comment() {
@@ -54,9 +66,11 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
 
         let comment = Comment::deserialize(&mut deserializer).unwrap();
+        assert_eq!(string, comment.as_str());
         assert_eq!(string, String::from(comment));
     }
 
@@ -74,8 +88,12 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
 
-        assert!(Comment::deserialize(&mut deserializer).is_err());
+        assert!(matches!(
+            Comment::deserialize(&mut deserializer),
+            Err(Error::InvalidTypecode(Typecode::Unknown(0)))
+        ));
     }
 }
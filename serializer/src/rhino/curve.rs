@@ -0,0 +1,108 @@
+use geometria_derive::RhinoDeserialize;
+
+use crate::math::{Arc, Circle, Line, Plane, Point3d};
+
+use super::{chunk, deserialize::Deserialize, deserializer::Deserializer, error::Error};
+
+#[derive(Debug, Clone, PartialEq, RhinoDeserialize)]
+#[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LineCurve {
+    pub line: Line,
+    pub domain: [f64; 2],
+    pub dimension: i32,
+}
+
+impl Default for LineCurve {
+    fn default() -> Self {
+        Self {
+            line: Line::default(),
+            domain: [0., 1.],
+            dimension: 3,
+        }
+    }
+}
+
+impl LineCurve {
+    pub fn point_at(&self, t: f64) -> Point3d {
+        let [start, end] = self.domain;
+        self.line.point_at((t - start) / (end - start))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, RhinoDeserialize)]
+#[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ArcCurve {
+    pub arc: Arc,
+    pub domain: [f64; 2],
+    pub dimension: i32,
+}
+
+impl Default for ArcCurve {
+    fn default() -> Self {
+        Self {
+            arc: Arc {
+                circle: Circle::new(Plane::WORLD_XY, 1.),
+                start_angle: 0.,
+                end_angle: std::f64::consts::TAU,
+            },
+            domain: [0., std::f64::consts::TAU],
+            dimension: 3,
+        }
+    }
+}
+
+impl ArcCurve {
+    pub fn point_at(&self, t: f64) -> Point3d {
+        let [start, end] = self.domain;
+        let s = (t - start) / (end - start);
+        self.arc
+            .point_at(self.arc.start_angle + s * self.arc.angle())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use std::io::Cursor;
+
+    use crate::rhino::{
+        geometry::tests::{circle_data, extend_f64},
+        reader::Reader,
+    };
+
+    use super::*;
+
+    #[test]
+    fn deserialize_line_curve() {
+        let mut data: Vec<u8> = vec![0x10];
+        extend_f64(&mut data, &[0., 0., 0., 2., 0., 0., 0., 4.]);
+        data.extend(3i32.to_le_bytes());
+        let curve = LineCurve::deserialize(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!([0., 4.], curve.domain);
+        assert_eq!(3, curve.dimension);
+        assert_eq!(Point3d::new(1., 0., 0.), curve.point_at(2.));
+    }
+
+    #[test]
+    fn deserialize_arc_curve() {
+        let mut data: Vec<u8> = vec![0x10];
+        data.extend(circle_data([0., 0., 1.], 2.));
+        extend_f64(&mut data, &[0., PI, 0., 1.]);
+        data.extend(2i32.to_le_bytes());
+        let curve = ArcCurve::deserialize(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!(2, curve.dimension);
+        assert_eq!(2. * PI, curve.arc.length());
+        assert!(curve.point_at(1.).distance_to(&Point3d::new(-2., 0., 1.)) < 1e-12);
+    }
+
+    #[test]
+    fn deserialize_unsupported_version() {
+        let data: Vec<u8> = vec![0x20];
+        assert_eq!(
+            LineCurve::default(),
+            LineCurve::deserialize(&mut Reader::new(Cursor::new(data))).unwrap()
+        );
+    }
+}
@@ -4,8 +4,10 @@ pub type Year = u16;
 pub type Month = u8;
 pub type DayOfMonth = u8;
 pub type DayOfYear = u16;
+pub type DayOfWeek = u8;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GregorianDate {
     year: Year,
     month: Month,
@@ -105,6 +107,69 @@ impl GregorianDate {
         date.day_of_month = self.month_days();
         date
     }
+
+    pub const fn days_since_epoch(&self) -> i64 {
+        let month = self.month as i64;
+        let year = self.year as i64 - if 2 >= month { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + self.day_of_month as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    pub const fn from_days_since_epoch(days: i64) -> Result<GregorianDate, Error> {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if 10 > month { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + if 2 >= month { 1 } else { 0 };
+        if (Self::FIRST_YEAR as i64) > year || (Year::MAX as i64) < year {
+            return Err(Error::InvalidYear);
+        }
+        Ok(GregorianDate {
+            year: year as Year,
+            month: month as Month,
+            day_of_month: day as DayOfMonth,
+        })
+    }
+
+    pub const fn day_of_week(&self) -> DayOfWeek {
+        (self.days_since_epoch() + 4).rem_euclid(7) as DayOfWeek
+    }
+
+    pub const fn add_days(&self, days: i64) -> Result<GregorianDate, Error> {
+        match self.days_since_epoch().checked_add(days) {
+            Some(days) => Self::from_days_since_epoch(days),
+            None => Err(Error::InvalidYear),
+        }
+    }
+
+    pub const fn sub_days(&self, days: i64) -> Result<GregorianDate, Error> {
+        match self.days_since_epoch().checked_sub(days) {
+            Some(days) => Self::from_days_since_epoch(days),
+            None => Err(Error::InvalidYear),
+        }
+    }
+
+    pub const fn days_until(&self, other: &GregorianDate) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+}
+
+impl Display for GregorianDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}",
+            self.year, self.month, self.day_of_month
+        )
+    }
 }
 
 enum InternalDate {
@@ -117,6 +182,12 @@ pub struct GregorianDateBuilder {
     date: InternalDate,
 }
 
+impl Default for GregorianDateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GregorianDateBuilder {
     pub const fn new() -> Self {
         GregorianDateBuilder {
@@ -505,6 +576,64 @@ mod tests {
             .is_leap_year());
     }
 
+    fn date(year: Year, month: Month, day: DayOfMonth) -> GregorianDate {
+        GregorianDateBuilder::new()
+            .year(year)
+            .month_and_day(month, day)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("2021-03-04", date(2021, 3, 4).to_string());
+        assert_eq!("1582-12-31", date(1582, 12, 31).to_string());
+    }
+
+    #[test]
+    fn days_since_epoch() {
+        assert_eq!(0, date(1970, 1, 1).days_since_epoch());
+        assert_eq!(18690, date(2021, 3, 4).days_since_epoch());
+        assert_eq!(-141427, date(1582, 10, 15).days_since_epoch());
+        for days in [-141427, -1, 0, 59, 11016, 18690, 2932896] {
+            assert_eq!(
+                days,
+                GregorianDate::from_days_since_epoch(days)
+                    .unwrap()
+                    .days_since_epoch()
+            );
+        }
+        assert_eq!(
+            Some(date(1969, 12, 31)),
+            GregorianDate::from_days_since_epoch(-1).ok()
+        );
+        assert_eq!(
+            Some(Error::InvalidYear),
+            GregorianDate::from_days_since_epoch(-200000).err()
+        );
+    }
+
+    #[test]
+    fn day_of_week() {
+        assert_eq!(4, date(1970, 1, 1).day_of_week());
+        assert_eq!(4, date(2021, 3, 4).day_of_week());
+        assert_eq!(0, date(2000, 12, 31).day_of_week());
+        assert_eq!(5, date(1582, 10, 15).day_of_week());
+    }
+
+    #[test]
+    fn add_and_sub_days() {
+        assert_eq!(Ok(date(2000, 3, 1)), date(2000, 2, 28).add_days(2));
+        assert_eq!(Ok(date(2001, 3, 1)), date(2001, 2, 28).add_days(1));
+        assert_eq!(Ok(date(1999, 12, 31)), date(2000, 1, 1).sub_days(1));
+        assert_eq!(Ok(date(2000, 1, 1)), date(2000, 1, 1).add_days(0));
+        assert_eq!(Ok(date(2001, 1, 1)), date(2000, 12, 31).sub_days(-1));
+        assert_eq!(Err(Error::InvalidYear), date(1582, 1, 1).sub_days(1));
+        assert_eq!(Err(Error::InvalidYear), date(2000, 1, 1).add_days(i64::MAX));
+        assert_eq!(366, date(2000, 1, 1).days_until(&date(2001, 1, 1)));
+        assert_eq!(-365, date(2002, 1, 1).days_until(&date(2001, 1, 1)));
+    }
+
     #[test]
     fn cmp_impl() {
         assert_eq!(
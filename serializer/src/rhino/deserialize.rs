@@ -3,6 +3,7 @@ use std::fmt::Display;
 use std::mem;
 
 use super::deserializer::Deserializer;
+use super::error::Error;
 
 pub trait Deserialize<'de, D>
 where
@@ -20,13 +21,13 @@ macro_rules! impl_deserialize_num {
         where
             D: Deserializer,
         {
-            type Error = String;
+            type Error = Error;
 
             fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
                 let mut bytes = [0; mem::size_of::<Self>()];
                 match deserializer.read_exact(&mut bytes) {
                     Ok(()) => Ok(Self::from_le_bytes(bytes)),
-                    Err(e) => Err(format!("{}", e)),
+                    Err(e) => Err(Error::Io(e)),
                 }
             }
         }
@@ -54,16 +55,674 @@ impl_deserialize_num! {f64}
 impl<D, T, const N: usize> Deserialize<'_, D> for [T; N]
 where
     D: Deserializer,
-    T: for<'a> Deserialize<'a, D> + Default + Copy,
-    String: for<'a> From<<T as Deserialize<'a, D>>::Error>,
+    T: for<'a> Deserialize<'a, D>,
+    Error: for<'a> From<<T as Deserialize<'a, D>>::Error>,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
-        let mut rv: Self = [T::default(); N];
-        for i in 0..N {
-            rv[i] = T::deserialize(deserializer)?;
+        let mut values: Vec<T> = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(T::deserialize(deserializer)?);
+        }
+        match values.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => Err(Error::InvalidLength),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Seek, SeekFrom};
+
+    use geometria_derive::RhinoDeserialize;
+
+    use flate2::Crc;
+
+    use crate::rhino::{
+        chunk::{self, Begin, Chunk},
+        options::ParseOptions,
+        reader::Reader,
+        sequence::Sequence,
+        typecode::{self, Typecode},
+        version::Version,
+    };
+
+    use super::*;
+
+    #[derive(RhinoDeserialize)]
+    struct Pair(u16, #[padding(u8)] u32);
+
+    #[derive(RhinoDeserialize)]
+    struct GenericPair<T>(T, T);
+
+    #[derive(RhinoDeserialize)]
+    struct Cached {
+        value: u16,
+        #[skip]
+        cache: Vec<u16>,
+        next: u16,
+    }
+
+    #[derive(RhinoDeserialize)]
+    struct Table<K, V> {
+        keys: Sequence<K>,
+        values: Sequence<V>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct PairError(String);
+
+    impl From<String> for PairError {
+        fn from(message: String) -> Self {
+            Self(message)
+        }
+    }
+
+    impl From<Error> for PairError {
+        fn from(e: Error) -> Self {
+            Self(e.to_string())
+        }
+    }
+
+    impl std::fmt::Display for PairError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    #[derive(RhinoDeserialize)]
+    #[error_type(PairError)]
+    struct TypedPair<T>(T, #[expected(0)] u8);
+
+    #[derive(RhinoDeserialize)]
+    struct Flagged(#[deserialize_with = "deserialize_flag"] bool, u16);
+
+    #[derive(Default, RhinoDeserialize)]
+    struct Versioned {
+        value: u16,
+        #[file_version(Version::V2..)]
+        extension: u16,
+        #[file_version(..Version::V50)]
+        #[padding(u8)]
+        legacy: u8,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[file_version(Version::V50..)]
+    struct Modern(u32);
+
+    #[derive(RhinoDeserialize)]
+    struct ShortSequences {
+        #[length(u8)]
+        bytes: Vec<u8>,
+        #[length(u32)]
+        values: Vec<f32>,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[normal_chunk]
+    #[crc]
+    struct Checked {
+        value: u16,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[big_chunk_version(major == 1)]
+    struct Scaled {
+        value: u16,
+        #[big_chunk_version(minor > 0)]
+        #[default_value(1.5)]
+        scale: f64,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[big_chunk_version(major in 1..=2)]
+    struct Ranged {
+        #[big_chunk_version(minor >= 1 && minor <= 3)]
+        middle: u8,
+        #[big_chunk_version(minor in 2..)]
+        late: u8,
+        #[big_chunk_version(!(minor == 2) || minor > 4)]
+        other: u8,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[table]
+    struct Previews {
+        #[table_field(PROPERTIES_PREVIEWIMAGE | PROPERTIES_COMPRESSED_PREVIEWIMAGE)]
+        #[deserialize_with = "deserialize_preview"]
+        preview: u16,
+    }
+
+    fn deserialize_preview<D>(deserializer: &mut D) -> Result<u16, Error>
+    where
+        D: Deserializer,
+    {
+        let value = u16::deserialize(deserializer)?;
+        if typecode::PROPERTIES_COMPRESSED_PREVIEWIMAGE == deserializer.chunk_begin().typecode {
+            Ok(100 + value)
+        } else {
+            Ok(value)
+        }
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[normal_chunk]
+    struct Tail {
+        value: u8,
+        #[raw_bytes]
+        rest: Vec<u8>,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[big_chunk_version]
+    struct Windowed {
+        #[since(1)]
+        added: u8,
+        #[until(1)]
+        removed: u8,
+        #[since(1)]
+        #[until(2)]
+        transient: u8,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[table(end = COMMENTBLOCK)]
+    struct Terminated {
+        #[table_field(PROPERTIES_PREVIEWIMAGE)]
+        preview: u16,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    #[big_chunk_version]
+    struct OptionalWindowed {
+        value: u8,
+        #[since(1)]
+        added: Option<u16>,
+        #[file_version(..Version::V2)]
+        legacy: Option<u8>,
+    }
+
+    #[derive(Default, RhinoDeserialize)]
+    struct Nested {
+        value: u8,
+        #[in_chunk(PROPERTIES_NOTES)]
+        notes: u16,
+        next: u8,
+    }
+
+    fn deserialize_flag<D>(deserializer: &mut D) -> Result<bool, Error>
+    where
+        D: Deserializer,
+    {
+        Ok(0 != u8::deserialize(deserializer)?)
+    }
+
+    #[test]
+    fn deserialize_tuple_struct() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(7u16.to_le_bytes());
+        data.push(0xFF);
+        data.extend(11u32.to_le_bytes());
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let pair = Pair::deserialize(&mut deserializer).unwrap();
+        assert_eq!(7, pair.0);
+        assert_eq!(11, pair.1);
+    }
+
+    #[test]
+    fn deserialize_generic_struct() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(3i16.to_le_bytes());
+        data.extend((-4i16).to_le_bytes());
+        data.extend(2i32.to_le_bytes());
+        data.extend([1u8, 2u8]);
+        data.extend(2i32.to_le_bytes());
+        data.extend(0.5f32.to_le_bytes());
+        data.extend(1.5f32.to_le_bytes());
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let pair = GenericPair::<i16>::deserialize(&mut deserializer).unwrap();
+        assert_eq!((3, -4), (pair.0, pair.1));
+        let table = Table::<u8, f32>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(vec![1, 2], Vec::from(table.keys));
+        assert_eq!(vec![0.5, 1.5], Vec::from(table.values));
+    }
+
+    #[test]
+    fn deserialize_skipped_field() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1u16.to_le_bytes());
+        data.extend(2u16.to_le_bytes());
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let cached = Cached::deserialize(&mut deserializer).unwrap();
+        assert_eq!(1, cached.value);
+        assert!(cached.cache.is_empty());
+        assert_eq!(2, cached.next);
+    }
+
+    #[test]
+    fn deserialize_custom_error_type() {
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new([3u8, 0u8, 0u8, 4u8, 0u8, 1u8]),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let pair = TypedPair::<u16>::deserialize(&mut deserializer).unwrap();
+        assert_eq!((3, 0), (pair.0, pair.1));
+        assert_eq!(
+            Some(PairError("invalid 1".to_string())),
+            TypedPair::<u16>::deserialize(&mut deserializer).err()
+        );
+    }
+
+    #[test]
+    fn deserialize_with_function() {
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new([2u8, 9u8, 0u8]),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let flagged = Flagged::deserialize(&mut deserializer).unwrap();
+        assert!(flagged.0);
+        assert_eq!(9, flagged.1);
+    }
+
+    #[test]
+    fn deserialize_file_version_gated_fields() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1u16.to_le_bytes());
+        data.extend([0xFFu8, 2u8]);
+        data.extend(3u16.to_le_bytes());
+        data.extend(4u16.to_le_bytes());
+        data.extend([0xFFu8, 5u8]);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let versioned = Versioned::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            (1, 0, 2),
+            (versioned.value, versioned.extension, versioned.legacy)
+        );
+        deserializer.version = Version::V4;
+        let versioned = Versioned::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            (3, 4, 5),
+            (versioned.value, versioned.extension, versioned.legacy)
+        );
+    }
+
+    #[test]
+    fn deserialize_file_version_gated_struct() {
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(7u32.to_le_bytes()),
+            version: Version::V4,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        assert_eq!(0, Modern::deserialize(&mut deserializer).unwrap().0);
+        deserializer.version = Version::V60;
+        assert_eq!(7, Modern::deserialize(&mut deserializer).unwrap().0);
+    }
+
+    #[test]
+    fn deserialize_custom_length_sequences() {
+        let mut data: Vec<u8> = vec![2u8, 7u8, 8u8];
+        data.extend(1u32.to_le_bytes());
+        data.extend(0.5f32.to_le_bytes());
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let sequences = ShortSequences::deserialize(&mut deserializer).unwrap();
+        assert_eq!(vec![7, 8], sequences.bytes);
+        assert_eq!(vec![0.5], sequences.values);
+    }
+
+    #[test]
+    fn deserialize_crc_checked_chunk() {
+        let payload = 9u16.to_le_bytes();
+        let mut crc = Crc::new();
+        crc.update(&payload);
+        for (checksum, valid) in [(crc.sum(), true), (!crc.sum(), false)] {
+            let mut data: Vec<u8> = vec![];
+            data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+            data.extend(6u32.to_le_bytes());
+            data.extend(payload);
+            data.extend(checksum.to_le_bytes());
+
+            let mut deserializer = Reader {
+                stream: &mut Cursor::new(data),
+                version: Version::V1,
+                chunk_begin: Begin::default(),
+                options: ParseOptions::strict(),
+            };
+            match Checked::deserialize(&mut deserializer) {
+                Ok(checked) => {
+                    assert!(valid);
+                    assert_eq!(9, checked.value);
+                }
+                Err(e) => {
+                    assert!(!valid);
+                    assert!(matches!(e.root_cause(), Error::CrcMismatch));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deserialize_default_expression() {
+        let mut data: Vec<u8> = vec![0x10];
+        data.extend(3u16.to_le_bytes());
+        data.push(0x11);
+        data.extend(4u16.to_le_bytes());
+        data.extend(0.5f64.to_le_bytes());
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let scaled = Scaled::deserialize(&mut deserializer).unwrap();
+        assert_eq!((3, 1.5), (scaled.value, scaled.scale));
+        let scaled = Scaled::deserialize(&mut deserializer).unwrap();
+        assert_eq!((4, 0.5), (scaled.value, scaled.scale));
+    }
+
+    #[test]
+    fn deserialize_compound_big_chunk_version() {
+        let data: Vec<u8> = vec![0x10, 1, 0x12, 2, 3, 0x24, 4, 5, 0x30];
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let mut values = vec![];
+        for _ in 0..4 {
+            let ranged = Ranged::deserialize(&mut deserializer).unwrap();
+            values.push((ranged.middle, ranged.late, ranged.other));
+        }
+        assert_eq!(vec![(0, 0, 1), (2, 3, 0), (0, 4, 5), (0, 0, 0)], values);
+    }
+
+    #[test]
+    fn deserialize_multiple_typecodes_field() {
+        for (typecode, expected) in [
+            (typecode::PROPERTIES_PREVIEWIMAGE, 7),
+            (typecode::PROPERTIES_COMPRESSED_PREVIEWIMAGE, 107),
+        ] {
+            let mut data: Vec<u8> = vec![];
+            data.extend(typecode.to_le_bytes());
+            data.extend(2u32.to_le_bytes());
+            data.extend(7u16.to_le_bytes());
+            data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+            data.extend(1u32.to_le_bytes());
+            data.push(0);
+
+            let mut deserializer = Reader {
+                stream: &mut Cursor::new(data),
+                version: Version::V1,
+                chunk_begin: Begin::default(),
+                options: Default::default(),
+            };
+            let previews = Previews::deserialize(&mut deserializer).unwrap();
+            assert_eq!(expected, previews.preview);
+        }
+    }
+
+    #[test]
+    fn deserialize_raw_bytes() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend(4u32.to_le_bytes());
+        data.extend([1u8, 2, 3, 4]);
+        data.extend([5u8, 6]);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let tail = Tail::deserialize(&mut deserializer).unwrap();
+        assert_eq!(1, tail.value);
+        assert_eq!(vec![2, 3, 4], tail.rest);
+    }
+
+    #[test]
+    fn deserialize_since_until() {
+        let data: Vec<u8> = vec![0x00, 1, 0x11, 2, 3, 4, 0x13, 5];
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let mut values = vec![];
+        for _ in 0..3 {
+            let windowed = Windowed::deserialize(&mut deserializer).unwrap();
+            values.push((windowed.added, windowed.removed, windowed.transient));
+        }
+        assert_eq!(vec![(0, 1, 0), (2, 3, 4), (5, 0, 0)], values);
+    }
+
+    #[test]
+    fn deserialize_table_with_custom_end() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(0);
+        data.extend(typecode::PROPERTIES_PREVIEWIMAGE.to_le_bytes());
+        data.extend(2u32.to_le_bytes());
+        data.extend(7u16.to_le_bytes());
+        data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(0);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let terminated = Terminated::deserialize(&mut deserializer).unwrap();
+        assert_eq!(7, terminated.preview);
+    }
+
+    #[test]
+    fn deserialize_table_with_unknown_typecode() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(0x00012345u32.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(0);
+        data.extend(typecode::PROPERTIES_PREVIEWIMAGE.to_le_bytes());
+        data.extend(2u32.to_le_bytes());
+        data.extend(7u16.to_le_bytes());
+        data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(0);
+
+        let options = ParseOptions::lenient();
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data.clone()),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: options.clone(),
+        };
+        let terminated = Terminated::deserialize(&mut deserializer).unwrap();
+        assert_eq!(7, terminated.preview);
+        let warnings = options.take_warnings();
+        assert_eq!(1, warnings.len());
+        assert!(matches!(
+            warnings[0].root_cause(),
+            Error::InvalidTypecode(Typecode::Unknown(0x00012345))
+        ));
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: ParseOptions::strict(),
+        };
+        assert!(matches!(
+            Terminated::deserialize(&mut deserializer)
+                .err()
+                .as_ref()
+                .map(Error::root_cause),
+            Some(Error::InvalidTypecode(Typecode::Unknown(0x00012345)))
+        ));
+    }
+
+    #[test]
+    fn deserialize_lenient_crc_mismatch() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        data.extend(6u32.to_le_bytes());
+        data.extend(9u16.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+
+        let options = ParseOptions::lenient();
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: options.clone(),
+        };
+        assert_eq!(9, Checked::deserialize(&mut deserializer).unwrap().value);
+        let warnings = options.take_warnings();
+        assert_eq!(1, warnings.len());
+        assert_eq!(
+            "PROPERTIES_NOTES @ 0x8: invalid chunk crc",
+            warnings[0].to_string()
+        );
+    }
+
+    #[test]
+    fn deserialize_non_copy_array() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1i32.to_le_bytes());
+        data.push(5);
+        data.extend(2i32.to_le_bytes());
+        data.extend([6u8, 7u8]);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let [first, second] = <[Sequence<u8>; 2]>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(vec![5], Vec::from(first));
+        assert_eq!(vec![6, 7], Vec::from(second));
+    }
+
+    #[test]
+    fn deserialize_version_gated_options() {
+        let mut data: Vec<u8> = vec![0x00, 1, 2, 0x01, 3];
+        data.extend(4u16.to_le_bytes());
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        let windowed = OptionalWindowed::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            (1, None, Some(2)),
+            (windowed.value, windowed.added, windowed.legacy)
+        );
+        deserializer.version = Version::V2;
+        let windowed = OptionalWindowed::deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            (3, Some(4), None),
+            (windowed.value, windowed.added, windowed.legacy)
+        );
+    }
+
+    #[test]
+    fn deserialize_error_context() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::PROPERTIES_PREVIEWIMAGE.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(7);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: Begin::default(),
+            options: Default::default(),
+        };
+        match Terminated::deserialize(&mut deserializer) {
+            Err(Error::Context {
+                error,
+                offset,
+                chunks,
+            }) => {
+                assert!(matches!(*error, Error::Io(_)));
+                assert_eq!(8, offset);
+                assert_eq!(vec![typecode::PROPERTIES_PREVIEWIMAGE], chunks);
+            }
+            _ => panic!("expected error with context"),
+        }
+    }
+
+    #[test]
+    fn deserialize_field_in_chunk() {
+        for (typecode, expected) in [
+            (typecode::PROPERTIES_NOTES, Ok((1, 2, 3))),
+            (
+                typecode::PROPERTIES_APPLICATION,
+                Err("invalid notes chunk".to_string()),
+            ),
+        ] {
+            let mut data: Vec<u8> = vec![1];
+            data.extend(typecode.to_le_bytes());
+            data.extend(3u32.to_le_bytes());
+            data.extend(2u16.to_le_bytes());
+            data.extend([0xFF, 3]);
+
+            let mut deserializer = Reader {
+                stream: &mut Cursor::new(data),
+                version: Version::V1,
+                chunk_begin: Begin::default(),
+                options: Default::default(),
+            };
+            assert_eq!(
+                expected,
+                Nested::deserialize(&mut deserializer)
+                    .map(|nested| (nested.value, nested.notes, nested.next))
+                    .map_err(|e| e.root_cause().to_string())
+            );
         }
-        Ok(rv)
     }
 }
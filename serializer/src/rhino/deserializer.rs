@@ -1,11 +1,14 @@
-use once_io::OStream;
-
 use super::chunk;
+use super::error::Error;
+use super::options::{Limits, ParseOptions};
+use super::typecode::Typecode;
 use super::version::Version;
 
+use std::io::{Read, Seek};
+
 pub trait Deserializer
 where
-    Self: Sized + OStream,
+    Self: Sized + Read + Seek,
 {
     fn deserialize_bytes(&mut self, buf: &mut [u8]) -> Result<(), String>;
 
@@ -14,4 +17,25 @@ where
 
     fn chunk_begin(&self) -> chunk::Begin;
     fn set_chunk_begin(&mut self, chunk_begin: chunk::Begin);
+
+    fn chunk_path(&self) -> Vec<Typecode> {
+        vec![]
+    }
+
+    fn absolute_position(&mut self) -> std::io::Result<u64> {
+        self.stream_position()
+    }
+
+    fn parse_options(&self) -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    fn limits(&self) -> Limits {
+        self.parse_options().limits()
+    }
+
+    fn report(&mut self, error: Error) -> Result<(), Error> {
+        let error = error.with_context(self);
+        self.parse_options().report(error)
+    }
 }
@@ -0,0 +1,473 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    sync::Arc,
+};
+
+use super::{
+    chunk::{Begin, Chunks, Entry},
+    comment::Comment,
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    error::Error,
+    header::Header,
+    object_index::ObjectIndex,
+    properties::Properties,
+    reader::Reader,
+    settings::Settings,
+    start_section::StartSection,
+    typecode::{self, Typecode},
+    version::Version,
+};
+
+#[cfg(feature = "rayon")]
+use super::reader::ReaderBuilder;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Checkpoint {
+    pub offset: u64,
+    pub tables: Vec<(Typecode, u64)>,
+    pub complete: bool,
+}
+
+pub struct DocumentReader<T>
+where
+    T: Read + Seek,
+{
+    reader: Reader<T>,
+    version: Version,
+    tables_offset: u64,
+    scan_offset: u64,
+    tables: Vec<(Typecode, u64)>,
+    complete: bool,
+    properties: Option<Arc<Properties>>,
+    settings: Option<Arc<Settings>>,
+    object_index: Option<Arc<ObjectIndex>>,
+}
+
+impl<T> DocumentReader<T>
+where
+    T: Read + Seek,
+{
+    pub fn open(stream: T) -> Result<Self, Error> {
+        let mut document = Self::open_lazy(stream)?;
+        document.scan_all()?;
+        Ok(document)
+    }
+
+    pub fn open_lazy(stream: T) -> Result<Self, Error> {
+        let mut reader = Reader::new(stream);
+        Header::deserialize(&mut reader)?;
+        let version = Version::deserialize(&mut reader)?;
+        Comment::deserialize(&mut reader)?;
+        StartSection::deserialize(&mut reader)?;
+        let tables_offset = reader.stream_position()?;
+        Ok(Self {
+            reader,
+            version,
+            tables_offset,
+            scan_offset: tables_offset,
+            tables: vec![],
+            complete: false,
+            properties: None,
+            settings: None,
+            object_index: None,
+        })
+    }
+
+    pub fn resume(stream: T, checkpoint: Checkpoint) -> Result<Self, Error> {
+        let mut document = Self::open_lazy(stream)?;
+        if checkpoint.offset < document.tables_offset {
+            return Err(Error::Other("invalid checkpoint".to_string()));
+        }
+        document.scan_offset = checkpoint.offset;
+        document.tables = checkpoint.tables;
+        document.complete = checkpoint.complete;
+        Ok(document)
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            offset: self.scan_offset,
+            tables: self.tables.clone(),
+            complete: self.complete,
+        }
+    }
+
+    pub fn step(&mut self, max_chunks: usize) -> Result<bool, Error> {
+        if self.complete {
+            return Ok(true);
+        }
+        self.reader.seek(SeekFrom::Start(self.scan_offset))?;
+        let mut count = 0;
+        for entry in Chunks::new(&mut self.reader).take(max_chunks) {
+            let entry = entry?;
+            if entry.typecode.is_table() {
+                self.tables.push((entry.typecode, entry.offset));
+            }
+            count += 1;
+        }
+        self.scan_offset = self.reader.stream_position()?;
+        self.complete = count < max_chunks;
+        Ok(self.complete)
+    }
+
+    pub fn scan_all(&mut self) -> Result<(), Error> {
+        while !self.step(usize::MAX)? {}
+        Ok(())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn tables(&self) -> &[(Typecode, u64)] {
+        &self.tables
+    }
+
+    pub fn table_offset(&self, typecode: Typecode) -> Option<u64> {
+        self.tables
+            .iter()
+            .find(|(t, _)| typecode == *t)
+            .map(|(_, offset)| *offset)
+    }
+
+    pub fn materialized_tables(&self) -> Vec<Typecode> {
+        [
+            (typecode::PROPERTIES_TABLE, self.properties.is_some()),
+            (typecode::SETTINGS_TABLE, self.settings.is_some()),
+            (typecode::OBJECT_TABLE, self.object_index.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, materialized)| *materialized)
+        .map(|(typecode, _)| typecode)
+        .collect()
+    }
+
+    pub fn is_materialized(&self, typecode: Typecode) -> bool {
+        self.materialized_tables().contains(&typecode)
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn chunks(&mut self) -> Result<Vec<Entry>, Error> {
+        self.reader.seek(SeekFrom::Start(self.tables_offset))?;
+        Chunks::new(&mut self.reader).nested(true).collect()
+    }
+
+    pub fn chunk_data(&mut self, entry: &Entry) -> Result<Vec<u8>, Error> {
+        let header_length = 4 + Begin::size_of_length(self.version) as u64;
+        self.reader
+            .seek(SeekFrom::Start(entry.offset + header_length))?;
+        let mut data = vec![];
+        (&mut self.reader)
+            .take(entry.length)
+            .read_to_end(&mut data)?;
+        if entry.length > data.len() as u64 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(data)
+    }
+
+    pub fn object_index(&mut self) -> Result<&ObjectIndex, Error> {
+        if self.object_index.is_none() {
+            let entries = self.chunks()?;
+            let version = self.version;
+            let index = ObjectIndex::build(version, &entries, |entry| self.chunk_data(entry))?;
+            self.object_index = Some(Arc::new(index));
+        }
+        Ok(self.object_index.get_or_insert_with(Default::default))
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn materialize_parallel<F>(&mut self, open: F) -> Result<(), Error>
+    where
+        F: Fn() -> std::io::Result<T> + Sync,
+    {
+        self.scan_all()?;
+        let version = self.reader.version();
+        let checkpoint = self.checkpoint();
+        let properties_offset = match version {
+            Version::V1 => Some(self.tables_offset),
+            _ => self.table_offset(typecode::PROPERTIES_TABLE),
+        };
+        let settings_offset = self.table_offset(typecode::SETTINGS_TABLE);
+        let ((properties, settings), object_index) = rayon::join(
+            || {
+                rayon::join(
+                    || Self::load_table(&open, version, properties_offset),
+                    || Self::load_table(&open, version, settings_offset),
+                )
+            },
+            || -> Result<Arc<ObjectIndex>, Error> {
+                let mut document = Self::resume(open()?, checkpoint)?;
+                document.object_index()?;
+                Ok(document.object_index.unwrap_or_default())
+            },
+        );
+        self.properties = properties?.map(Arc::new);
+        self.settings = settings?.map(Arc::new);
+        self.object_index = Some(object_index?);
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    fn load_table<F, R>(open: &F, version: Version, offset: Option<u64>) -> Result<Option<R>, Error>
+    where
+        F: Fn() -> std::io::Result<T>,
+        R: for<'a> Deserialize<'a, Reader<T>, Error = Error>,
+    {
+        match offset {
+            Some(offset) => {
+                let mut reader = ReaderBuilder::new().version(version).build(open()?);
+                reader.seek(SeekFrom::Start(offset))?;
+                Ok(Some(R::deserialize(&mut reader)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn find_table(&mut self, typecode: Typecode) -> Result<Option<u64>, Error> {
+        while self.table_offset(typecode).is_none() && !self.step(1)? {}
+        Ok(self.table_offset(typecode))
+    }
+
+    pub fn properties(&mut self) -> Result<Option<&Properties>, Error> {
+        if self.properties.is_none() {
+            if Version::V1 == self.reader.version() {
+                self.reader.seek(SeekFrom::Start(self.tables_offset))?;
+                self.properties = Some(Arc::new(Properties::deserialize(&mut self.reader)?));
+            } else if let Some(offset) = self.find_table(typecode::PROPERTIES_TABLE)? {
+                self.reader.seek(SeekFrom::Start(offset))?;
+                self.properties = Some(Arc::new(Properties::deserialize(&mut self.reader)?));
+            }
+        }
+        Ok(self.properties.as_deref())
+    }
+
+    pub fn settings(&mut self) -> Result<Option<&Settings>, Error> {
+        if self.settings.is_none() {
+            if let Some(offset) = self.find_table(typecode::SETTINGS_TABLE)? {
+                self.reader.seek(SeekFrom::Start(offset))?;
+                self.settings = Some(Arc::new(Settings::deserialize(&mut self.reader)?));
+            }
+        }
+        Ok(self.settings.as_deref())
+    }
+
+    pub fn shared(&mut self) -> Result<SharedDocument, Error> {
+        self.properties()?;
+        self.settings()?;
+        self.object_index()?;
+        Ok(SharedDocument {
+            version: self.version,
+            properties: self.properties.clone(),
+            settings: self.settings.clone(),
+            object_index: self.object_index.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct SharedDocument {
+    pub version: Version,
+    pub properties: Option<Arc<Properties>>,
+    pub settings: Option<Arc<Settings>>,
+    pub object_index: Arc<ObjectIndex>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn document_data() -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend("3D Geometry File Format        2".as_bytes());
+        data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        data.extend("abc".as_bytes());
+        data.extend(typecode::SETTINGS_TABLE.to_le_bytes());
+        data.extend(24u32.to_le_bytes());
+        data.extend(typecode::SETTINGS_CURRENT_COLOR.to_le_bytes());
+        data.extend(8u32.to_le_bytes());
+        data.extend(7i32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend(typecode::ENDOFTABLE.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(typecode::PROPERTIES_TABLE.to_le_bytes());
+        data.extend(8u32.to_le_bytes());
+        data.extend(typecode::ENDOFTABLE.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn open() {
+        let document = DocumentReader::open(Cursor::new(document_data())).unwrap();
+        assert_eq!(Version::V2, document.version());
+        assert!(document.is_complete());
+        assert_eq!(Some(43), document.table_offset(typecode::SETTINGS_TABLE));
+        assert_eq!(Some(75), document.table_offset(typecode::PROPERTIES_TABLE));
+    }
+
+    #[test]
+    fn step_and_resume() {
+        let mut document = DocumentReader::open_lazy(Cursor::new(document_data())).unwrap();
+        assert!(document.tables().is_empty());
+        assert!(!document.step(1).unwrap());
+        assert_eq!(&[(typecode::SETTINGS_TABLE, 43)], document.tables());
+        let checkpoint = document.checkpoint();
+        assert_eq!(75, checkpoint.offset);
+
+        let mut document =
+            DocumentReader::resume(Cursor::new(document_data()), checkpoint).unwrap();
+        assert!(document.materialized_tables().is_empty());
+        assert!(document.settings().unwrap().is_some());
+        assert!(document.is_materialized(typecode::SETTINGS_TABLE));
+        assert!(!document.step(1).unwrap());
+        assert!(document.step(1).unwrap());
+        assert!(document.is_complete());
+        assert_eq!(2, document.tables().len());
+
+        let checkpoint = Checkpoint {
+            offset: 0,
+            tables: vec![],
+            complete: false,
+        };
+        assert!(DocumentReader::resume(Cursor::new(document_data()), checkpoint).is_err());
+    }
+
+    #[test]
+    fn find_tables_while_scanning() {
+        let mut document = DocumentReader::open_lazy(Cursor::new(document_data())).unwrap();
+        assert!(document.properties().unwrap().is_some());
+        assert!(!document.is_complete());
+        assert_eq!(
+            vec![typecode::PROPERTIES_TABLE],
+            document.materialized_tables()
+        );
+        assert!(document.settings().unwrap().is_some());
+        assert_eq!(91, document.checkpoint().offset);
+    }
+
+    #[test]
+    fn read_tables_on_demand() {
+        let mut document = DocumentReader::open(Cursor::new(document_data())).unwrap();
+        assert!(matches!(
+            document.properties().unwrap(),
+            Some(Properties::V2(_))
+        ));
+        let settings = document.settings().unwrap().unwrap();
+        assert_eq!(7, settings.current_color.color);
+        assert_eq!(1, settings.current_color.source);
+        assert!(document.properties().unwrap().is_some());
+    }
+
+    #[test]
+    fn read_v1_properties_after_scanning() {
+        let mut data: Vec<u8> = vec![];
+        data.extend("3D Geometry File Format        1".as_bytes());
+        data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        data.extend("abc".as_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        let mut document = DocumentReader::open(Cursor::new(data)).unwrap();
+        assert_eq!(Version::V1, document.version());
+        assert!(document.settings().unwrap().is_none());
+        match document.properties().unwrap() {
+            Some(Properties::V1(properties)) => assert_eq!("abc", properties.comment()),
+            _ => panic!("expected v1 properties"),
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let mut document = DocumentReader::open(Cursor::new(document_data())).unwrap();
+        let chunks = document.chunks().unwrap();
+        assert_eq!(
+            vec![
+                (typecode::SETTINGS_TABLE, 43, 0),
+                (typecode::SETTINGS_CURRENT_COLOR, 51, 1),
+                (typecode::ENDOFTABLE, 67, 1),
+                (typecode::PROPERTIES_TABLE, 75, 0),
+                (typecode::ENDOFTABLE, 83, 1),
+            ],
+            chunks
+                .iter()
+                .map(|entry| (entry.typecode, entry.offset, entry.depth))
+                .collect::<Vec<_>>()
+        );
+        let data = document.chunk_data(&chunks[1]).unwrap();
+        assert_eq!(7i32.to_le_bytes(), data[..4]);
+        assert_eq!(1i32.to_le_bytes(), data[4..]);
+        assert!(document.object_index().unwrap().is_empty());
+
+        let entry = Entry {
+            length: u64::MAX,
+            ..chunks[1]
+        };
+        assert!(matches!(document.chunk_data(&entry), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn missing_table() {
+        let mut data = document_data();
+        data.truncate(75);
+        let mut document = DocumentReader::open(Cursor::new(data)).unwrap();
+        assert!(document.properties().unwrap().is_none());
+        assert!(document.settings().unwrap().is_some());
+    }
+
+    #[test]
+    fn shared() {
+        let mut document = DocumentReader::open(Cursor::new(document_data())).unwrap();
+        let shared = document.shared().unwrap();
+        let colors: Vec<i32> = (0..2)
+            .map(|_| {
+                let shared = shared.clone();
+                std::thread::spawn(move || shared.settings.unwrap().current_color.color)
+            })
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert_eq!(vec![7, 7], colors);
+        assert!(shared.properties.is_some());
+        assert!(shared.object_index.is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn materialize_parallel() {
+        let data = document_data();
+        let mut document = DocumentReader::open_lazy(Cursor::new(data.clone())).unwrap();
+        document
+            .materialize_parallel(|| Ok(Cursor::new(data.clone())))
+            .unwrap();
+        assert!(document.is_complete());
+        assert_eq!(
+            vec![
+                typecode::PROPERTIES_TABLE,
+                typecode::SETTINGS_TABLE,
+                typecode::OBJECT_TABLE
+            ],
+            document.materialized_tables()
+        );
+        assert_eq!(7, document.settings().unwrap().unwrap().current_color.color);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_settings() {
+        let mut document = DocumentReader::open(Cursor::new(document_data())).unwrap();
+        let settings = document.settings().unwrap().unwrap();
+        let json = serde_json::to_value(settings).unwrap();
+        assert_eq!(
+            serde_json::json!({"color": 7, "source": 1}),
+            json["current_color"]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use super::deserializer::Deserializer;
+use super::typecode::Typecode;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    InvalidHeader,
+    InvalidTypecode(Typecode),
+    InvalidLength,
+    CrcMismatch,
+    UnsupportedVersion,
+    OutOfRange(&'static str),
+    LimitExceeded(&'static str),
+    Other(String),
+    Context {
+        error: Box<Error>,
+        offset: u64,
+        chunks: Vec<Typecode>,
+    },
+}
+
+impl Error {
+    pub fn with_context<D>(self, deserializer: &mut D) -> Self
+    where
+        D: Deserializer,
+    {
+        match self {
+            Self::Context { .. } => self,
+            error => Self::Context {
+                offset: deserializer.absolute_position().unwrap_or_default(),
+                chunks: deserializer.chunk_path(),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Self::Context { error, .. } => error.root_cause(),
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::InvalidHeader => write!(f, "invalid header"),
+            Self::InvalidTypecode(typecode) => write!(f, "invalid typecode {}", typecode),
+            Self::InvalidLength => write!(f, "invalid length"),
+            Self::CrcMismatch => write!(f, "invalid chunk crc"),
+            Self::UnsupportedVersion => write!(f, "unsupported version"),
+            Self::OutOfRange(name) => write!(f, "{} out of range", name),
+            Self::LimitExceeded(name) => write!(f, "{} limit exceeded", name),
+            Self::Other(message) => write!(f, "{}", message),
+            Self::Context {
+                error,
+                offset,
+                chunks,
+            } => {
+                for (index, chunk) in chunks.iter().enumerate() {
+                    if 0 < index {
+                        write!(f, " > ")?;
+                    }
+                    write!(f, "{}", chunk)?;
+                }
+                if !chunks.is_empty() {
+                    write!(f, " ")?;
+                }
+                write!(f, "@ 0x{:X}: {}", offset, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Context { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use crate::rhino::typecode;
+
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("invalid header", Error::InvalidHeader.to_string());
+        assert_eq!(
+            "invalid typecode 0x00012345",
+            Error::InvalidTypecode(Typecode::from(0x00012345)).to_string()
+        );
+        assert_eq!(
+            "invalid typecode COMMENTBLOCK",
+            Error::InvalidTypecode(Typecode::from(1)).to_string()
+        );
+        assert_eq!("invalid chunk crc", Error::CrcMismatch.to_string());
+        assert_eq!("unknown", Error::from("unknown".to_string()).to_string());
+    }
+
+    #[test]
+    fn display_context() {
+        let error = Error::Context {
+            error: Box::new(Error::CrcMismatch),
+            offset: 0x1A2B,
+            chunks: vec![typecode::PROPERTIES_TABLE, typecode::PROPERTIES_NOTES],
+        };
+        assert_eq!(
+            "PROPERTIES_TABLE > PROPERTIES_NOTES @ 0x1A2B: invalid chunk crc",
+            error.to_string()
+        );
+        assert!(matches!(error.root_cause(), Error::CrcMismatch));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn io_source() {
+        let error = Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        assert!(matches!(error, Error::Io(_)));
+        assert!(error.source().is_some());
+        assert!(Error::InvalidHeader.source().is_none());
+    }
+}
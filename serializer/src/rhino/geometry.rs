@@ -0,0 +1,147 @@
+use crate::math::{Arc, Circle, Line, Plane, Point3d, Vector3d};
+
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
+
+impl<D> Deserialize<'_, D> for Point3d
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        Ok(Self::from(<[f64; 3]>::deserialize(deserializer)?))
+    }
+}
+
+impl<D> Deserialize<'_, D> for Vector3d
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        Ok(Self::from(<[f64; 3]>::deserialize(deserializer)?))
+    }
+}
+
+impl<D> Deserialize<'_, D> for Plane
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        let plane = Self {
+            origin: Point3d::deserialize(deserializer)?,
+            x_axis: Vector3d::deserialize(deserializer)?,
+            y_axis: Vector3d::deserialize(deserializer)?,
+            z_axis: Vector3d::deserialize(deserializer)?,
+        };
+        <[f64; 4]>::deserialize(deserializer)?;
+        Ok(plane)
+    }
+}
+
+impl<D> Deserialize<'_, D> for Line
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            Point3d::deserialize(deserializer)?,
+            Point3d::deserialize(deserializer)?,
+        ))
+    }
+}
+
+impl<D> Deserialize<'_, D> for Circle
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        let plane = Plane::deserialize(deserializer)?;
+        let radius = f64::deserialize(deserializer)?;
+        <[Point3d; 3]>::deserialize(deserializer)?;
+        Ok(Self::new(plane, radius))
+    }
+}
+
+impl<D> Deserialize<'_, D> for Arc
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        let circle = Circle::deserialize(deserializer)?;
+        let [start_angle, end_angle] = <[f64; 2]>::deserialize(deserializer)?;
+        Ok(Self {
+            circle,
+            start_angle,
+            end_angle,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::f64::consts::PI;
+
+    use std::io::Cursor;
+
+    use crate::rhino::reader::Reader;
+
+    use super::*;
+
+    pub fn extend_f64(data: &mut Vec<u8>, values: &[f64]) {
+        values.iter().for_each(|v| data.extend(v.to_le_bytes()));
+    }
+
+    pub fn circle_data(center: [f64; 3], radius: f64) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        extend_f64(&mut data, &center);
+        extend_f64(&mut data, &[1., 0., 0., 0., 1., 0., 0., 0., 1.]);
+        extend_f64(&mut data, &[0., 0., 1., -center[2]]);
+        extend_f64(&mut data, &[radius]);
+        extend_f64(&mut data, &[0.; 9]);
+        data
+    }
+
+    #[test]
+    fn deserialize_line() {
+        let mut data: Vec<u8> = vec![];
+        extend_f64(&mut data, &[1., 2., 3., 4., 5., 6.]);
+        let line = Line::deserialize(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!(
+            Line::new(Point3d::new(1., 2., 3.), Point3d::new(4., 5., 6.)),
+            line
+        );
+    }
+
+    #[test]
+    fn deserialize_arc() {
+        let mut data = circle_data([1., 1., 2.], 3.);
+        extend_f64(&mut data, &[0., PI]);
+        let arc = Arc::deserialize(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!(
+            Plane {
+                origin: Point3d::new(1., 1., 2.),
+                ..Plane::WORLD_XY
+            },
+            arc.circle.plane
+        );
+        assert_eq!(3., arc.circle.radius);
+        assert_eq!(3. * PI, arc.length());
+    }
+
+    #[test]
+    fn deserialize_truncated_circle() {
+        let mut data = circle_data([0.; 3], 1.);
+        data.truncate(data.len() - 1);
+        assert!(Circle::deserialize(&mut Reader::new(Cursor::new(data))).is_err());
+    }
+}
@@ -1,26 +1,16 @@
+use geometria_derive::RhinoDeserialize;
+
 use super::deserialize::Deserialize;
 use super::deserializer::Deserializer;
+use super::error::Error;
 
-pub struct Header;
-
-const FILE_BEGIN: &[u8] = "3D Geometry File Format ".as_bytes();
+const FILE_BEGIN: [u8; 24] = *b"3D Geometry File Format ";
 
-impl<D> Deserialize<'_, D> for Header
-where
-    D: Deserializer,
-{
-    type Error = String;
-
-    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
-        let mut buffer = [0; FILE_BEGIN.len()];
-        match deserializer.read_exact(&mut buffer) {
-            Ok(()) => match FILE_BEGIN == buffer {
-                true => Ok(Header {}),
-                false => Err("invalid header".to_string()),
-            },
-            Err(e) => Err(e.to_string()),
-        }
-    }
+#[derive(RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Header {
+    #[expected(FILE_BEGIN, Error::InvalidHeader)]
+    _file_begin: [u8; 24],
 }
 
 #[cfg(test)]
@@ -38,6 +28,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
 
         assert!(Header::deserialize(&mut deserializer).is_ok());
@@ -51,8 +42,10 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
-        assert!(Header::deserialize(&mut deserializer).is_err());
+        let error = Header::deserialize(&mut deserializer).err().unwrap();
+        assert!(matches!(error.root_cause(), Error::InvalidHeader));
     }
 
     #[test]
@@ -63,7 +56,9 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
-        assert!(Header::deserialize(&mut deserializer).is_err());
+        let error = Header::deserialize(&mut deserializer).err().unwrap();
+        assert!(matches!(error.root_cause(), Error::Io(_)));
     }
 }
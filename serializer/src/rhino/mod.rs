@@ -3,54 +3,48 @@ pub mod archive;
 mod bool;
 pub mod chunk;
 mod comment;
-mod date;
-mod deserialize;
-mod deserializer;
+pub mod curve;
+pub mod date;
+pub mod deserialize;
+pub mod deserializer;
+pub mod document;
+pub mod error;
+mod geometry;
 mod header;
 pub mod notes;
-mod on_version;
-mod preview_image;
-mod properties;
-mod reader;
+pub mod object_index;
+pub mod on_version;
+pub mod options;
+pub mod preview_image;
+pub mod properties;
+pub mod reader;
 pub mod revision_history;
 mod sequence;
 pub mod settings;
 mod start_section;
-mod string;
-mod time;
-mod typecode;
+pub mod string;
+pub mod time;
+pub mod typecode;
 pub mod uuid;
-mod version;
+pub mod validation;
+pub mod version;
+mod xform;
+
+pub use error::Error;
 
 #[cfg(test)]
 mod tests {
-    use super::{archive::Archive, deserialize::Deserialize, version::Version, *};
-    use reader::Reader;
-    use std::fs::File;
+    use super::archive::Archive;
 
     #[test]
     fn serialize_3dm_v1() {
-        let mut deserializer = Reader {
-            stream: File::open("tests/resources/serializer/rhino/v1/v1_three_points.3dm").unwrap(),
-            version: Version::V1,
-            chunk_begin: chunk::Begin::default(),
-        };
-        match Archive::deserialize(&mut deserializer) {
-            Ok(_) => assert!(true),
-            Err(_) => assert!(false),
-        }
+        assert!(
+            Archive::from_path("tests/resources/serializer/rhino/v1/v1_three_points.3dm").is_ok()
+        );
     }
 
     #[test]
     fn serialize_3dm_v2() {
-        let mut deserializer = Reader {
-            stream: File::open("tests/resources/serializer/rhino/v2/v2_my_brep.3dm").unwrap(),
-            version: Version::V1,
-            chunk_begin: chunk::Begin::default(),
-        };
-        match Archive::deserialize(&mut deserializer) {
-            Ok(_) => assert!(true),
-            Err(_) => assert!(false),
-        }
+        assert!(Archive::from_path("tests/resources/serializer/rhino/v2/v2_my_brep.3dm").is_ok());
     }
 }
@@ -5,11 +5,13 @@ use super::{
     chunk,
     deserialize::Deserialize,
     deserializer::Deserializer,
+    error::Error,
     string::{StringWithLength, WStringWithLength},
     version::Version,
 };
 
 #[derive(Default, RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NotesV1 {
     pub visible: i32,
     pub window_left: i32,
@@ -22,6 +24,7 @@ pub struct NotesV1 {
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NotesV2 {
     #[underlying_type(BoolFromI32)]
     pub html_encoded: bool,
@@ -35,6 +38,7 @@ pub struct NotesV2 {
     pub window_bottom: i32,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Notes {
     V1(NotesV1),
     V2(NotesV2),
@@ -46,11 +50,51 @@ impl Default for Notes {
     }
 }
 
+impl Notes {
+    pub fn data(&self) -> &str {
+        match self {
+            Self::V1(notes) => &notes.data,
+            Self::V2(notes) => &notes.data,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        match self {
+            Self::V1(notes) => 0 != notes.visible,
+            Self::V2(notes) => notes.visible,
+        }
+    }
+
+    pub fn is_html_encoded(&self) -> bool {
+        match self {
+            Self::V1(_) => false,
+            Self::V2(notes) => notes.html_encoded,
+        }
+    }
+
+    pub fn window(&self) -> (i32, i32, i32, i32) {
+        match self {
+            Self::V1(notes) => (
+                notes.window_left,
+                notes.window_top,
+                notes.window_right,
+                notes.window_bottom,
+            ),
+            Self::V2(notes) => (
+                notes.window_left,
+                notes.window_top,
+                notes.window_right,
+                notes.window_bottom,
+            ),
+        }
+    }
+}
+
 impl<D> Deserialize<'_, D> for Notes
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let notes;
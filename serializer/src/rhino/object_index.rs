@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use super::{
+    chunk::{Begin, Chunks, Entry},
+    deserialize::Deserialize,
+    error::Error,
+    reader::ReaderBuilder,
+    typecode::{self, Typecode},
+    uuid::Uuid,
+    version::Version,
+};
+
+pub const INSTANCE_REFERENCE_CLASS: Uuid = Uuid::new(
+    0xF9CFB638,
+    0xB9D4,
+    0x4340,
+    [0x87, 0xE3, 0xC5, 0x6E, 0x78, 0x65, 0xD9, 0x6A],
+);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ObjectHandle {
+    pub typecode: Typecode,
+    pub offset: u64,
+    pub class: Uuid,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ObjectIndex {
+    objects: HashMap<Uuid, ObjectHandle>,
+    definitions: HashMap<Uuid, ObjectHandle>,
+    instance_references: HashMap<Uuid, Uuid>,
+}
+
+impl ObjectIndex {
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn object(&self, id: &Uuid) -> Option<&ObjectHandle> {
+        self.objects.get(id)
+    }
+
+    pub fn objects(&self) -> impl Iterator<Item = (&Uuid, &ObjectHandle)> {
+        self.objects.iter()
+    }
+
+    pub fn definition(&self, id: &Uuid) -> Option<&ObjectHandle> {
+        self.definitions.get(id)
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = (&Uuid, &ObjectHandle)> {
+        self.definitions.iter()
+    }
+
+    pub fn definition_id(&self, instance_reference: &Uuid) -> Option<&Uuid> {
+        self.instance_references.get(instance_reference)
+    }
+
+    pub fn resolve_instance(&self, instance_reference: &Uuid) -> Option<&ObjectHandle> {
+        self.definition_id(instance_reference)
+            .and_then(|id| self.definition(id))
+    }
+
+    pub fn build<F>(version: Version, entries: &[Entry], mut payload: F) -> Result<Self, Error>
+    where
+        F: FnMut(&Entry) -> Result<Vec<u8>, Error>,
+    {
+        let mut index = Self::default();
+        for entry in entries.iter().filter(|e| 1 == e.depth) {
+            match entry.typecode {
+                typecode::OBJECT_RECORD => {
+                    let data = payload(entry)?;
+                    let record = Record::parse(version, without_crc(entry.typecode, &data))?;
+                    let handle = record.handle(entry);
+                    if let Some(id) = record.id {
+                        if INSTANCE_REFERENCE_CLASS == handle.class {
+                            if let Some(definition) = record.data_id {
+                                index.instance_references.insert(id, definition);
+                            }
+                        }
+                        index.objects.insert(id, handle);
+                    }
+                }
+                typecode::INSTANCE_DEFINITION_RECORD => {
+                    let data = payload(entry)?;
+                    let record = Record::parse(version, without_crc(entry.typecode, &data))?;
+                    if let Some(id) = record.data_id {
+                        index.definitions.insert(id, record.handle(entry));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(index)
+    }
+}
+
+#[derive(Default)]
+struct Record {
+    class: Uuid,
+    id: Option<Uuid>,
+    data_id: Option<Uuid>,
+}
+
+impl Record {
+    fn parse(version: Version, data: &[u8]) -> Result<Self, Error> {
+        let mut record = Self::default();
+        for (typecode, data) in children(version, data)? {
+            match typecode {
+                typecode::OPENNURBS_CLASS => {
+                    for (typecode, data) in children(version, data)? {
+                        match typecode {
+                            typecode::OPENNURBS_CLASS_UUID => {
+                                record.class = read_uuid(version, data, 0)?
+                            }
+                            typecode::OPENNURBS_CLASS_DATA => {
+                                record.data_id = read_uuid(version, data, 1).ok()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                typecode::OBJECT_RECORD_ATTRIBUTES => {
+                    record.id = Some(read_uuid(version, data, 1)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(record)
+    }
+
+    fn handle(&self, entry: &Entry) -> ObjectHandle {
+        ObjectHandle {
+            typecode: entry.typecode,
+            offset: entry.offset,
+            class: self.class,
+        }
+    }
+}
+
+fn children(version: Version, data: &[u8]) -> Result<Vec<(Typecode, &[u8])>, Error> {
+    let header_length = 4 + Begin::size_of_length(version) as usize;
+    let mut reader = ReaderBuilder::new()
+        .version(version)
+        .build(Cursor::new(data));
+    Chunks::new(&mut reader)
+        .map(|entry| {
+            let entry = entry?;
+            let begin = entry.offset as usize + header_length;
+            let end = begin
+                .checked_add(entry.length as usize)
+                .filter(|end| *end <= data.len())
+                .ok_or(Error::InvalidLength)?;
+            Ok((
+                entry.typecode,
+                without_crc(entry.typecode, &data[begin..end]),
+            ))
+        })
+        .collect()
+}
+
+fn without_crc(typecode: Typecode, data: &[u8]) -> &[u8] {
+    if typecode.has_crc() {
+        &data[..data.len().saturating_sub(4)]
+    } else {
+        data
+    }
+}
+
+fn read_uuid(version: Version, data: &[u8], skip: usize) -> Result<Uuid, Error> {
+    let mut reader = ReaderBuilder::new()
+        .version(version)
+        .build(Cursor::new(data.get(skip..).unwrap_or_default()));
+    Uuid::deserialize(&mut reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(typecode: Typecode, payload: &[u8]) -> Vec<u8> {
+        let crc = if typecode.has_crc() { 4 } else { 0 };
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode.to_le_bytes());
+        data.extend((payload.len() as u32 + crc).to_le_bytes());
+        data.extend(payload);
+        data.extend(vec![0u8; crc as usize]);
+        data
+    }
+
+    fn uuid_bytes(uuid: &Uuid) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(uuid.data1.to_le_bytes());
+        data.extend(uuid.data2.to_le_bytes());
+        data.extend(uuid.data3.to_le_bytes());
+        data.extend(uuid.data4);
+        data
+    }
+
+    fn versioned(uuid: &Uuid) -> Vec<u8> {
+        [vec![0x10u8], uuid_bytes(uuid)].concat()
+    }
+
+    fn class(class: &Uuid, data: &[u8]) -> Vec<u8> {
+        let payload = [
+            chunk(typecode::OPENNURBS_CLASS_UUID, &uuid_bytes(class)),
+            chunk(typecode::OPENNURBS_CLASS_DATA, data),
+            chunk(typecode::OPENNURBS_CLASS_END, &[]),
+        ]
+        .concat();
+        chunk(typecode::OPENNURBS_CLASS, &payload)
+    }
+
+    fn object_record(id: &Uuid, class_id: &Uuid, data: &[u8]) -> Vec<u8> {
+        let payload = [
+            class(class_id, data),
+            chunk(typecode::OBJECT_RECORD_ATTRIBUTES, &versioned(id)),
+            chunk(typecode::OBJECT_RECORD_END, &[]),
+        ]
+        .concat();
+        chunk(typecode::OBJECT_RECORD, &payload)
+    }
+
+    fn definition_record(id: &Uuid) -> Vec<u8> {
+        chunk(
+            typecode::INSTANCE_DEFINITION_RECORD,
+            &class(&Uuid::new(1, 0, 0, [0; 8]), &versioned(id)),
+        )
+    }
+
+    #[test]
+    fn build() {
+        let point = Uuid::new(1, 1, 1, [1; 8]);
+        let instance = Uuid::new(2, 2, 2, [2; 8]);
+        let definition = Uuid::new(3, 3, 3, [3; 8]);
+        let records = [
+            object_record(&point, &Uuid::new(4, 0, 0, [0; 8]), &[0x10]),
+            object_record(
+                &instance,
+                &INSTANCE_REFERENCE_CLASS,
+                &versioned(&definition),
+            ),
+            definition_record(&definition),
+        ];
+        let mut offset = 0;
+        let entries: Vec<Entry> = records
+            .iter()
+            .map(|record| {
+                let entry = Entry {
+                    typecode: Typecode::from(u32::from_le_bytes(record[..4].try_into().unwrap())),
+                    offset,
+                    length: record.len() as u64 - 8,
+                    depth: 1,
+                };
+                offset += record.len() as u64;
+                entry
+            })
+            .collect();
+        let index = ObjectIndex::build(Version::V2, &entries, |entry| {
+            let record = &records[entries
+                .iter()
+                .position(|e| e.offset == entry.offset)
+                .unwrap()];
+            Ok(record[8..].to_vec())
+        })
+        .unwrap();
+
+        assert_eq!(2, index.len());
+        assert_eq!(Some(0), index.object(&point).map(|h| h.offset));
+        assert_eq!(
+            Some(INSTANCE_REFERENCE_CLASS),
+            index.object(&instance).map(|h| h.class)
+        );
+        assert_eq!(Some(&definition), index.definition_id(&instance));
+        assert_eq!(
+            Some(typecode::INSTANCE_DEFINITION_RECORD),
+            index.resolve_instance(&instance).map(|h| h.typecode)
+        );
+        assert!(index.resolve_instance(&point).is_none());
+    }
+}
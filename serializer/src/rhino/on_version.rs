@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use super::{
     date::{DayOfMonth, GregorianDate, GregorianDateBuilder, Month, Year},
     deserialize::Deserialize,
@@ -68,7 +70,8 @@ type MajorVersion = u8;
 type MinorVersion = u8;
 type Platform = u8;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Version {
     major_version: MajorVersion,
     minor_version: MinorVersion,
@@ -143,6 +146,36 @@ impl Version {
     pub fn platform(&self) -> u8 {
         self.platform
     }
+
+    pub fn platform_name(&self) -> &'static str {
+        match self.platform {
+            0 => "developer",
+            1 => "windows",
+            2 => "apple",
+            _ => "unknown",
+        }
+    }
+
+    pub fn is_debug(&self) -> bool {
+        MAJOR_VERSION_DEBUG == self.major_version
+    }
+
+    pub fn is_at_least(&self, major_version: MajorVersion, minor_version: MinorVersion) -> bool {
+        (self.major_version, self.minor_version) >= (major_version, minor_version)
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{} ({}, {})",
+            self.major_version,
+            self.minor_version,
+            self.date,
+            self.platform_name()
+        )
+    }
 }
 
 impl Default for Version {
@@ -202,11 +235,14 @@ impl TryFrom<DateFormatVersion> for Version {
         let major_version: MajorVersion = if 200612060 == value {
             5
         } else {
-            (value % 10).try_into().unwrap()
+            (value % 10) as MajorVersion
+        };
+        let day = ((value / 10) % 100) as DayOfMonth;
+        let month = ((value / (10 * 100)) % 100) as Month;
+        let year: Year = match (value / (10 * 100 * 100)).try_into() {
+            Ok(year) => year,
+            Err(_) => return Err(Error::InvalidDate),
         };
-        let day: DayOfMonth = ((value / 10) % 100).try_into().unwrap();
-        let month: Month = ((value / (10 * 100)) % 100).try_into().unwrap();
-        let year: Year = (value / (10 * 100 * 100)).try_into().unwrap();
         let date = match GregorianDateBuilder::new()
             .year(year)
             .month_and_day(month, day)
@@ -236,7 +272,7 @@ impl<D> Deserialize<'_, D> for Version
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = super::error::Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let raw_version = deserializer.chunk_begin().value as u64;
@@ -244,7 +280,7 @@ where
             Ok(version) => Ok(version),
             Err(_) => match Version::try_from(NormalFormatVersion(raw_version)) {
                 Ok(version) => Ok(version),
-                Err(_) => Err("3dm file error: invalid opennurbs version".to_string()),
+                Err(_) => Err(super::error::Error::UnsupportedVersion),
             },
         }
     }
@@ -432,6 +468,38 @@ mod tests {
         assert_eq!(2, version.platform());
     }
 
+    #[test]
+    fn display() {
+        let date = GregorianDateBuilder::new()
+            .year(2021)
+            .month_and_day(3, 4)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "7.3 (2021-03-04, windows)",
+            Version::new(7, 3, date, 1).unwrap().to_string()
+        );
+        assert_eq!(
+            "0.0 (2000-12-21, developer)",
+            Version::default().to_string()
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let date = GregorianDateBuilder::new().year(2021).build().unwrap();
+        let version = Version::new(7, 3, date, 1).unwrap();
+        assert!(Version::new(7, 4, date, 0).unwrap() > version);
+        assert!(Version::new(6, 9, date, 1).unwrap() < version);
+        assert!(Version::new(7, 3, date.add_days(1).unwrap(), 1).unwrap() > version);
+        assert!(version.is_at_least(7, 3));
+        assert!(version.is_at_least(6, 9));
+        assert!(!version.is_at_least(7, 4));
+        assert!(!version.is_at_least(8, 0));
+        assert!(!version.is_debug());
+        assert!(Version::new(9, 0, date, 0).unwrap().is_debug());
+    }
+
     #[test]
     fn conversions() {
         let mut initial_version = Version::new(0, 0, MIN_DATE, 0).unwrap();
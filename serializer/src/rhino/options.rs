@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+
+use super::error::Error;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Strictness {
+    Strict,
+    #[default]
+    Lenient,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limits {
+    pub max_string_length: u64,
+    pub max_sequence_count: u64,
+    pub max_chunk_depth: usize,
+    pub max_decompressed_size: u64,
+}
+
+impl Limits {
+    pub const fn unlimited() -> Self {
+        Self {
+            max_string_length: u64::MAX,
+            max_sequence_count: u64::MAX,
+            max_chunk_depth: usize::MAX,
+            max_decompressed_size: u64::MAX,
+        }
+    }
+
+    pub fn check_string_length(&self, length: u64) -> Result<(), Error> {
+        Self::check("string length", length, self.max_string_length)
+    }
+
+    pub fn check_sequence_count(&self, count: u64) -> Result<(), Error> {
+        Self::check("sequence count", count, self.max_sequence_count)
+    }
+
+    pub fn check_chunk_depth(&self, depth: usize) -> Result<(), Error> {
+        Self::check("chunk depth", depth as u64, self.max_chunk_depth as u64)
+    }
+
+    pub fn check_decompressed_size(&self, size: u64) -> Result<(), Error> {
+        Self::check("decompressed size", size, self.max_decompressed_size)
+    }
+
+    fn check(name: &'static str, value: u64, limit: u64) -> Result<(), Error> {
+        if value > limit {
+            Err(Error::LimitExceeded(name))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_string_length: 1 << 24,
+            max_sequence_count: 1 << 24,
+            max_chunk_depth: 64,
+            max_decompressed_size: 1 << 28,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    strictness: Strictness,
+    limits: Limits,
+    warnings: Arc<Mutex<Vec<Error>>>,
+}
+
+impl ParseOptions {
+    pub fn new(strictness: Strictness) -> Self {
+        Self {
+            strictness,
+            ..Default::default()
+        }
+    }
+
+    pub fn strict() -> Self {
+        Self::new(Strictness::Strict)
+    }
+
+    pub fn lenient() -> Self {
+        Self::new(Strictness::Lenient)
+    }
+
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn report(&self, error: Error) -> Result<(), Error> {
+        match self.strictness {
+            Strictness::Strict => Err(error),
+            Strictness::Lenient => {
+                self.warnings.lock().unwrap().push(error);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.warnings.lock().unwrap().len()
+    }
+
+    pub fn take_warnings(&self) -> Vec<Error> {
+        std::mem::take(&mut *self.warnings.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_fails() {
+        let options = ParseOptions::strict();
+        assert!(matches!(
+            options.report(Error::CrcMismatch),
+            Err(Error::CrcMismatch)
+        ));
+        assert_eq!(0, options.warning_count());
+    }
+
+    #[test]
+    fn limits() {
+        let options = ParseOptions::strict().with_limits(Limits {
+            max_string_length: 4,
+            ..Limits::default()
+        });
+        assert_eq!(Strictness::Strict, options.strictness());
+        assert!(options.limits().check_string_length(4).is_ok());
+        assert!(matches!(
+            options.limits().check_string_length(5),
+            Err(Error::LimitExceeded("string length"))
+        ));
+        assert!(Limits::unlimited().check_chunk_depth(usize::MAX).is_ok());
+        assert_eq!(
+            "chunk depth limit exceeded",
+            Limits::default()
+                .check_chunk_depth(65)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn lenient_records_warnings() {
+        let options = ParseOptions::default();
+        assert_eq!(Strictness::Lenient, options.strictness());
+        assert!(options.report(Error::CrcMismatch).is_ok());
+        assert!(options.clone().report(Error::InvalidLength).is_ok());
+        assert_eq!(2, options.warning_count());
+        let warnings = options.take_warnings();
+        assert!(matches!(
+            warnings[..],
+            [Error::CrcMismatch, Error::InvalidLength]
+        ));
+        assert_eq!(0, options.warning_count());
+    }
+}
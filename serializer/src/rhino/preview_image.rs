@@ -1,13 +1,303 @@
-use geometria_derive::RhinoDeserialize;
+use std::io::Read;
 
-use super::{deserialize::Deserialize, deserializer::Deserializer};
+use flate2::{read::ZlibDecoder, Crc};
 
-#[derive(Default, RhinoDeserialize)]
+use super::{
+    chunk::{Begin, Chunk},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    error::Error,
+    typecode,
+};
+
+const BITMAP_INFO_HEADER_SIZE: usize = 40;
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PreviewImage {
-    // TODO
+    dib: Vec<u8>,
 }
 
-#[derive(Default, RhinoDeserialize)]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CompressedPreviewImage {
-    // TODO
+    dib: Vec<u8>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Preview {
+    Image(PreviewImage),
+    Compressed(CompressedPreviewImage),
+}
+
+impl Default for Preview {
+    fn default() -> Self {
+        Self::Image(PreviewImage::default())
+    }
+}
+
+impl PreviewImage {
+    pub fn dib(&self) -> &[u8] {
+        &self.dib
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dib.is_empty()
+    }
+
+    pub fn to_bmp(&self) -> Result<Vec<u8>, Error> {
+        bitmap_file(&self.dib)
+    }
+}
+
+impl CompressedPreviewImage {
+    pub fn dib(&self) -> &[u8] {
+        &self.dib
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dib.is_empty()
+    }
+
+    pub fn to_bmp(&self) -> Result<Vec<u8>, Error> {
+        bitmap_file(&self.dib)
+    }
+}
+
+impl Preview {
+    pub fn dib(&self) -> &[u8] {
+        match self {
+            Self::Image(image) => image.dib(),
+            Self::Compressed(image) => image.dib(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dib().is_empty()
+    }
+
+    pub fn to_bmp(&self) -> Result<Vec<u8>, Error> {
+        bitmap_file(self.dib())
+    }
+}
+
+pub fn bitmap_file(dib: &[u8]) -> Result<Vec<u8>, Error> {
+    if BITMAP_INFO_HEADER_SIZE > dib.len() {
+        return Err(Error::Other("invalid preview image".to_string()));
+    }
+    let header_size = u32::from_le_bytes(dib[0..4].try_into().unwrap());
+    let bit_count = u16::from_le_bytes(dib[14..16].try_into().unwrap());
+    let colors_used = u32::from_le_bytes(dib[32..36].try_into().unwrap());
+    let colors = match (colors_used, bit_count) {
+        (0, 1..=8) => 1u32 << bit_count,
+        (colors, _) => colors,
+    };
+    let pixels_offset = colors
+        .checked_mul(4)
+        .and_then(|size| size.checked_add(header_size))
+        .and_then(|size| size.checked_add(14))
+        .ok_or(Error::InvalidLength)?;
+    let file_size = u32::try_from(14 + dib.len()).map_err(|_| Error::InvalidLength)?;
+    let mut data: Vec<u8> = Vec::with_capacity(14 + dib.len());
+    data.extend(b"BM");
+    data.extend(file_size.to_le_bytes());
+    data.extend(0u32.to_le_bytes());
+    data.extend(pixels_offset.to_le_bytes());
+    data.extend(dib);
+    Ok(data)
+}
+
+#[cfg(feature = "image")]
+impl TryFrom<&Preview> for image::DynamicImage {
+    type Error = Error;
+
+    fn try_from(preview: &Preview) -> Result<Self, Self::Error> {
+        image::load_from_memory_with_format(&preview.to_bmp()?, image::ImageFormat::Bmp)
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+}
+
+fn read_payload<D>(deserializer: &mut D) -> Result<Vec<u8>, Error>
+where
+    D: Deserializer,
+{
+    let mut data = vec![];
+    deserializer.read_to_end(&mut data)?;
+    if deserializer.chunk_begin().typecode.has_crc() {
+        data.truncate(data.len().saturating_sub(4));
+    }
+    Ok(data)
+}
+
+impl<D> Deserialize<'_, D> for PreviewImage
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        Ok(Self {
+            dib: read_payload(deserializer)?,
+        })
+    }
+}
+
+impl<D> Deserialize<'_, D> for CompressedPreviewImage
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        let size = if 8 == Begin::size_of_length(deserializer.version()) {
+            u64::deserialize(deserializer)?
+        } else {
+            u32::deserialize(deserializer)? as u64
+        };
+        if 0 == size {
+            return Ok(Self::default());
+        }
+        deserializer.limits().check_decompressed_size(size)?;
+        let crc = u32::deserialize(deserializer)?;
+        let dib = match u8::deserialize(deserializer)? {
+            0 => {
+                let mut dib = vec![];
+                deserializer.take(size).read_to_end(&mut dib)?;
+                dib
+            }
+            1 => Chunk::with_child(deserializer, |chunk| -> Result<_, Error> {
+                if typecode::ANONYMOUS_CHUNK != chunk.chunk_begin().typecode {
+                    return Err(Error::InvalidTypecode(chunk.chunk_begin().typecode));
+                }
+                let mut dib = vec![];
+                ZlibDecoder::new(chunk).take(size).read_to_end(&mut dib)?;
+                Ok(dib)
+            })?,
+            _ => return Err(Error::Other("invalid compression method".to_string())),
+        };
+        if size != dib.len() as u64 {
+            return Err(Error::InvalidLength);
+        }
+        let mut dib_crc = Crc::new();
+        dib_crc.update(&dib);
+        if crc != dib_crc.sum() {
+            deserializer.report(Error::CrcMismatch)?;
+        }
+        Ok(Self { dib })
+    }
+}
+
+impl<D> Deserialize<'_, D> for Preview
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        if typecode::PROPERTIES_COMPRESSED_PREVIEWIMAGE == deserializer.chunk_begin().typecode {
+            Ok(Self::Compressed(CompressedPreviewImage::deserialize(
+                deserializer,
+            )?))
+        } else {
+            Ok(Self::Image(PreviewImage::deserialize(deserializer)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use crate::rhino::{reader::ReaderBuilder, typecode::Typecode, version::Version};
+
+    use super::*;
+
+    fn dib() -> Vec<u8> {
+        let mut dib = vec![0u8; BITMAP_INFO_HEADER_SIZE];
+        dib[0..4].copy_from_slice(&40u32.to_le_bytes());
+        dib[4..8].copy_from_slice(&2i32.to_le_bytes());
+        dib[8..12].copy_from_slice(&1i32.to_le_bytes());
+        dib[12..14].copy_from_slice(&1u16.to_le_bytes());
+        dib[14..16].copy_from_slice(&24u16.to_le_bytes());
+        dib.extend([0, 0, 255, 0, 255, 0, 0, 0]);
+        dib
+    }
+
+    fn chunk(typecode: Typecode, payload: &[u8]) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode.to_le_bytes());
+        data.extend((payload.len() as u32 + 4).to_le_bytes());
+        data.extend(payload);
+        let mut crc = Crc::new();
+        crc.update(payload);
+        data.extend(crc.sum().to_le_bytes());
+        data
+    }
+
+    fn compressed(method: u8) -> Vec<u8> {
+        let dib = dib();
+        let mut crc = Crc::new();
+        crc.update(&dib);
+        let mut payload: Vec<u8> = vec![];
+        payload.extend((dib.len() as u32).to_le_bytes());
+        payload.extend(crc.sum().to_le_bytes());
+        payload.push(method);
+        if 0 == method {
+            payload.extend(&dib);
+        } else {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&dib).unwrap();
+            payload.extend(chunk(typecode::ANONYMOUS_CHUNK, &encoder.finish().unwrap()));
+        }
+        chunk(typecode::PROPERTIES_COMPRESSED_PREVIEWIMAGE, &payload)
+    }
+
+    fn preview(data: Vec<u8>) -> Preview {
+        let mut reader = ReaderBuilder::new()
+            .version(Version::V2)
+            .build(Cursor::new(data));
+        Chunk::with_child(&mut reader, |chunk| Preview::deserialize(chunk)).unwrap()
+    }
+
+    #[test]
+    fn deserialize_preview_image() {
+        let preview = preview(chunk(typecode::PROPERTIES_PREVIEWIMAGE, &dib()));
+        assert!(matches!(preview, Preview::Image(_)));
+        assert_eq!(dib(), preview.dib());
+    }
+
+    #[test]
+    fn deserialize_compressed_preview_image() {
+        for method in [0, 1] {
+            let preview = preview(compressed(method));
+            assert!(matches!(preview, Preview::Compressed(_)));
+            assert_eq!(dib(), preview.dib());
+        }
+    }
+
+    #[test]
+    fn bitmap_file_header() {
+        let mut dib = vec![0u8; 40];
+        dib[0..4].copy_from_slice(&40u32.to_le_bytes());
+        dib[14..16].copy_from_slice(&8u16.to_le_bytes());
+        dib.extend([0u8; 4 * 256 + 4]);
+        let data = bitmap_file(&dib).unwrap();
+        assert_eq!(b"BM", &data[..2]);
+        assert_eq!((data.len() as u32).to_le_bytes(), data[2..6]);
+        assert_eq!((14u32 + 40 + 1024).to_le_bytes(), data[10..14]);
+        assert!(bitmap_file(&[0u8; 8]).is_err());
+        assert!(Preview::default().to_bmp().is_err());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn dynamic_image() {
+        let image = image::DynamicImage::try_from(&preview(compressed(1))).unwrap();
+        assert_eq!((2, 1), (image.width(), image.height()));
+        let image = image.to_rgb8();
+        assert_eq!([255, 0, 0], image.get_pixel(0, 0).0);
+        assert_eq!([0, 255, 0], image.get_pixel(1, 0).0);
+    }
 }
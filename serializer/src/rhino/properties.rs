@@ -3,13 +3,14 @@ use std::io::{Seek, SeekFrom};
 
 use super::{
     application::Application, chunk::Chunk, deserialize::Deserialize, deserializer::Deserializer,
-    notes::Notes, on_version::Version as OnVersion, preview_image::CompressedPreviewImage,
+    error::Error, notes::Notes, on_version::Version as OnVersion, preview_image::Preview,
     preview_image::PreviewImage, revision_history::RevisionHistory, string::WStringWithLength,
     typecode, version::Version,
 };
 
 #[derive(Default, RhinoDeserialize)]
 #[table]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PropertiesV1 {
     #[table_field(COMMENTBLOCK)]
     comment: String,
@@ -23,6 +24,7 @@ pub struct PropertiesV1 {
 
 #[derive(Default, RhinoDeserialize)]
 #[table(PROPERTIES_TABLE)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PropertiesV2 {
     #[table_field(PROPERTIES_AS_FILE_NAME)]
     #[underlying_type(WStringWithLength)]
@@ -35,12 +37,11 @@ pub struct PropertiesV2 {
     notes: Notes,
     #[table_field(PROPERTIES_APPLICATION)]
     application: Application,
-    #[table_field(PROPERTIES_PREVIEWIMAGE)]
-    preview_image: PreviewImage,
-    #[table_field(PROPERTIES_COMPRESSED_PREVIEWIMAGE)]
-    compressed_preview_image: CompressedPreviewImage,
+    #[table_field(PROPERTIES_PREVIEWIMAGE | PROPERTIES_COMPRESSED_PREVIEWIMAGE)]
+    preview: Preview,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Properties {
     V1(PropertiesV1),
     V2(PropertiesV2),
@@ -52,16 +53,83 @@ impl Default for Properties {
     }
 }
 
+impl PropertiesV1 {
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    pub fn revision_history(&self) -> &RevisionHistory {
+        &self.revision_history
+    }
+
+    pub fn notes(&self) -> &Notes {
+        &self.notes
+    }
+
+    pub fn preview_image(&self) -> &PreviewImage {
+        &self.preview_image
+    }
+}
+
+impl PropertiesV2 {
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn version(&self) -> &OnVersion {
+        &self.version
+    }
+
+    pub fn revision_history(&self) -> &RevisionHistory {
+        &self.revision_history
+    }
+
+    pub fn notes(&self) -> &Notes {
+        &self.notes
+    }
+
+    pub fn application(&self) -> &Application {
+        &self.application
+    }
+
+    pub fn preview(&self) -> &Preview {
+        &self.preview
+    }
+}
+
+impl Properties {
+    pub fn revision_history(&self) -> &RevisionHistory {
+        match self {
+            Self::V1(properties) => properties.revision_history(),
+            Self::V2(properties) => properties.revision_history(),
+        }
+    }
+
+    pub fn notes(&self) -> &Notes {
+        match self {
+            Self::V1(properties) => properties.notes(),
+            Self::V2(properties) => properties.notes(),
+        }
+    }
+
+    pub fn preview_dib(&self) -> &[u8] {
+        match self {
+            Self::V1(properties) => properties.preview_image().dib(),
+            Self::V2(properties) => properties.preview().dib(),
+        }
+    }
+}
+
 impl<D> Deserialize<'_, D> for Properties
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let properties: Properties;
         if Version::V1 == deserializer.version() {
-            deserializer.seek(SeekFrom::Start(32u64)).unwrap();
+            deserializer.seek(SeekFrom::Start(32u64))?;
             properties = Properties::V1(PropertiesV1::deserialize(deserializer)?);
         } else {
             properties = Properties::V2(PropertiesV2::deserialize(deserializer)?);
@@ -1,22 +1,89 @@
 use super::chunk;
 use super::deserializer::Deserializer;
+use super::options::ParseOptions;
 use super::version::Version;
 
-use once_io::OStream;
-use std::{io::Read, io::Seek, io::SeekFrom};
+use std::{io::BufReader, io::Read, io::Seek, io::SeekFrom};
 
 pub struct Reader<T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     pub stream: T,
     pub version: Version,
     pub chunk_begin: chunk::Begin,
+    pub options: ParseOptions,
+}
+
+impl<T> Reader<T>
+where
+    T: Read + Seek,
+{
+    pub fn new(stream: T) -> Self {
+        ReaderBuilder::new().build(stream)
+    }
+}
+
+pub struct ReaderBuilder {
+    version: Version,
+    buffer_capacity: usize,
+    options: Option<ParseOptions>,
+}
+
+impl ReaderBuilder {
+    pub const fn new() -> Self {
+        ReaderBuilder {
+            version: Version::V1,
+            buffer_capacity: 8 * 1024,
+            options: None,
+        }
+    }
+
+    pub const fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub const fn buffer_capacity(mut self, buffer_capacity: usize) -> Self {
+        self.buffer_capacity = buffer_capacity;
+        self
+    }
+
+    pub fn options(mut self, options: ParseOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    pub fn build<T>(self, stream: T) -> Reader<T>
+    where
+        T: Read + Seek,
+    {
+        Reader {
+            stream,
+            version: self.version,
+            chunk_begin: chunk::Begin::default(),
+            options: self.options.unwrap_or_default(),
+        }
+    }
+
+    pub fn build_buffered<T>(self, stream: T) -> Reader<BufReader<T>>
+    where
+        T: Read + Seek,
+    {
+        let capacity = self.buffer_capacity;
+        self.build(BufReader::with_capacity(capacity, stream))
+    }
+}
+
+impl Default for ReaderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> Read for Reader<T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         self.stream.read(buf)
@@ -25,7 +92,7 @@ where
 
 impl<T> Seek for Reader<T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
         self.stream.seek(pos)
@@ -34,7 +101,7 @@ where
 
 impl<T> Deserializer for Reader<T>
 where
-    T: OStream,
+    T: Read + Seek,
 {
     fn deserialize_bytes(&mut self, buf: &mut [u8]) -> Result<(), String> {
         match self.read_exact(buf) {
@@ -58,4 +125,48 @@ where
     fn set_chunk_begin(&mut self, chunk_begin: chunk::Begin) {
         self.chunk_begin = chunk_begin;
     }
+
+    fn parse_options(&self) -> ParseOptions {
+        self.options.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::rhino::{deserialize::Deserialize, typecode::Typecode};
+
+    use super::*;
+
+    #[test]
+    fn new() {
+        let mut reader = Reader::new(Cursor::new(7u32.to_le_bytes()));
+        assert_eq!(Version::V1, reader.version());
+        assert_eq!(Typecode::default(), reader.chunk_begin().typecode);
+        assert_eq!(7, u32::deserialize(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn build_with_version() {
+        let reader = ReaderBuilder::new()
+            .version(Version::V50)
+            .build(Cursor::new([0u8; 4]));
+        assert_eq!(Version::V50, reader.version());
+    }
+
+    #[test]
+    fn build_buffered() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(1u16.to_le_bytes());
+        data.extend(2u16.to_le_bytes());
+
+        let mut reader = ReaderBuilder::new()
+            .buffer_capacity(1)
+            .build_buffered(Cursor::new(data));
+        assert_eq!(1, u16::deserialize(&mut reader).unwrap());
+        reader.seek(SeekFrom::Start(0)).unwrap();
+        assert_eq!(1, u16::deserialize(&mut reader).unwrap());
+        assert_eq!(2, u16::deserialize(&mut reader).unwrap());
+    }
 }
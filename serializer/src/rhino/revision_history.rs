@@ -4,12 +4,14 @@ use super::{
     chunk,
     deserialize::Deserialize,
     deserializer::Deserializer,
+    error::Error,
     string::{StringWithLength, WStringWithLength},
     time::Time,
     version::Version,
 };
 
 #[derive(Default, RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RevisionHistoryV1 {
     #[underlying_type(StringWithLength)]
     pub created_by: String,
@@ -24,6 +26,7 @@ pub struct RevisionHistoryV1 {
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RevisionHistoryV2 {
     #[underlying_type(WStringWithLength)]
     pub created_by: String,
@@ -34,6 +37,7 @@ pub struct RevisionHistoryV2 {
     pub revision_count: i32,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum RevisionHistory {
     V1(RevisionHistoryV1),
     V2(RevisionHistoryV2),
@@ -45,11 +49,48 @@ impl Default for RevisionHistory {
     }
 }
 
+impl RevisionHistory {
+    pub fn created_by(&self) -> &str {
+        match self {
+            Self::V1(history) => &history.created_by,
+            Self::V2(history) => &history.created_by,
+        }
+    }
+
+    pub fn create_time(&self) -> &Time {
+        match self {
+            Self::V1(history) => &history.create_time,
+            Self::V2(history) => &history.create_time,
+        }
+    }
+
+    pub fn last_edited_by(&self) -> &str {
+        match self {
+            Self::V1(history) => &history.last_edited_by,
+            Self::V2(history) => &history.last_edited_by,
+        }
+    }
+
+    pub fn last_edit_time(&self) -> &Time {
+        match self {
+            Self::V1(history) => &history.last_edit_time,
+            Self::V2(history) => &history.last_edit_time,
+        }
+    }
+
+    pub fn revision_count(&self) -> i32 {
+        match self {
+            Self::V1(history) => history.revision_count,
+            Self::V2(history) => history.revision_count,
+        }
+    }
+}
+
 impl<D> Deserialize<'_, D> for RevisionHistory
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let revision_history;
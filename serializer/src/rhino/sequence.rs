@@ -1,34 +1,46 @@
-use super::{deserialize::Deserialize, deserializer::Deserializer};
+use std::marker::PhantomData;
+
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
 
 #[derive(Default)]
-pub struct Sequence<T> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sequence<T, L = i32> {
     pub data: Vec<T>,
+    length: PhantomData<L>,
 }
 
-impl<T> From<Sequence<T>> for Vec<T> {
-    fn from(array: Sequence<T>) -> Self {
+impl<T, L> From<Sequence<T, L>> for Vec<T> {
+    fn from(array: Sequence<T, L>) -> Self {
         array.data
     }
 }
 
-impl<D, T> Deserialize<'_, D> for Sequence<T>
+impl<D, T, L> Deserialize<'_, D> for Sequence<T, L>
 where
     D: Deserializer,
     T: for<'a> Deserialize<'a, D>,
-    String: for<'a> From<<T as Deserialize<'a, D>>::Error>,
+    Error: for<'a> From<<T as Deserialize<'a, D>>::Error>,
+    L: for<'a> Deserialize<'a, D>,
+    Error: for<'a> From<<L as Deserialize<'a, D>>::Error>,
+    usize: TryFrom<L>,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
-        let length = i32::deserialize(deserializer)?;
-        if 0 <= length {
-            let mut data: Vec<T> = vec![];
-            for _ in 0..length {
-                data.push(T::deserialize(deserializer)?);
+        let length = L::deserialize(deserializer)?;
+        match usize::try_from(length) {
+            Ok(length) => {
+                deserializer.limits().check_sequence_count(length as u64)?;
+                let mut data: Vec<T> = vec![];
+                for _ in 0..length {
+                    data.push(T::deserialize(deserializer)?);
+                }
+                Ok(Self {
+                    data,
+                    length: PhantomData,
+                })
             }
-            Ok(Self { data })
-        } else {
-            Err("invalid array length".to_string())
+            Err(_) => Err(Error::InvalidLength),
         }
     }
 }
@@ -37,7 +49,12 @@ where
 mod tests {
     use std::io::Cursor;
 
-    use crate::rhino::{chunk, reader::Reader, version::Version};
+    use crate::rhino::{
+        chunk,
+        options::{Limits, ParseOptions},
+        reader::Reader,
+        version::Version,
+    };
 
     use super::*;
 
@@ -50,10 +67,32 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
         assert!(Sequence::<u8>::deserialize(&mut deserializer).is_err());
     }
 
+    #[test]
+    fn count_limit() {
+        let mut data: Vec<u8> = vec![];
+        data.extend((3i32).to_le_bytes());
+        data.extend([1u8, 2, 3]);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: chunk::Begin::default(),
+            options: ParseOptions::default().with_limits(Limits {
+                max_sequence_count: 2,
+                ..Limits::default()
+            }),
+        };
+        assert!(matches!(
+            Sequence::<u8>::deserialize(&mut deserializer),
+            Err(Error::LimitExceeded("sequence count"))
+        ));
+    }
+
     #[test]
     fn short_length() {
         let mut data: Vec<u8> = vec![];
@@ -64,6 +103,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
         assert!(Sequence::<u8>::deserialize(&mut deserializer).is_err());
     }
@@ -79,10 +119,36 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
         assert_eq!(
             Vec::<u8>::from(Sequence::<u8>::deserialize(&mut deserializer).ok().unwrap()),
             vec![0, 1]
         );
     }
+
+    #[test]
+    fn custom_length() {
+        let mut data: Vec<u8> = vec![];
+        data.push(2u8);
+        data.extend(3u16.to_le_bytes());
+        data.extend(4u16.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(5u8);
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
+        };
+        assert_eq!(
+            vec![3, 4],
+            Vec::from(Sequence::<u16, u8>::deserialize(&mut deserializer).unwrap())
+        );
+        assert_eq!(
+            vec![5],
+            Vec::from(Sequence::<u8, u32>::deserialize(&mut deserializer).unwrap())
+        );
+    }
 }
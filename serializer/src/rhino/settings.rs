@@ -2,23 +2,97 @@ use geometria_derive::RhinoDeserialize;
 
 use std::io::{Seek, SeekFrom};
 
+use crate::common::units::UnitSystem;
+
 use super::{
     bool::BoolFromI32, chunk, chunk::Chunk, deserialize::Deserialize, deserializer::Deserializer,
-    sequence::Sequence, string::WStringWithLength, typecode,
+    error::Error, sequence::Sequence, string::WStringWithLength, typecode,
 };
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version(major > 1)]
 #[normal_chunk]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PlugIn {}
 
 type PlugInList = Sequence<PlugIn>;
 
-#[derive(Default, RhinoDeserialize)]
-pub struct UnitsAndTolerances {}
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UnitsAndTolerances {
+    pub unit_system: UnitSystem,
+    pub absolute_tolerance: f64,
+    pub angle_tolerance: f64,
+    pub relative_tolerance: f64,
+    pub distance_display_mode: i32,
+    pub distance_display_precision: i32,
+    pub custom_meters_per_unit: f64,
+    pub custom_unit_name: String,
+}
+
+impl Default for UnitsAndTolerances {
+    fn default() -> Self {
+        Self {
+            unit_system: UnitSystem::Millimeters,
+            absolute_tolerance: 0.001,
+            angle_tolerance: std::f64::consts::PI / 180.,
+            relative_tolerance: 0.01,
+            distance_display_mode: 0,
+            distance_display_precision: 3,
+            custom_meters_per_unit: 1.,
+            custom_unit_name: String::new(),
+        }
+    }
+}
+
+impl UnitsAndTolerances {
+    pub fn meters_per_unit(&self) -> Option<f64> {
+        match self.unit_system {
+            UnitSystem::CustomUnits => Some(self.custom_meters_per_unit)
+                .filter(|meters_per_unit| meters_per_unit.is_normal()),
+            unit_system => unit_system.meters_per_unit(),
+        }
+    }
+
+    pub fn convert_units(&mut self, target: UnitSystem) -> Option<f64> {
+        let scale = self.meters_per_unit()? / target.meters_per_unit()?;
+        self.unit_system = target;
+        self.absolute_tolerance *= scale;
+        Some(scale)
+    }
+}
+
+impl<D> Deserialize<'_, D> for UnitsAndTolerances
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        let mut units = Self::default();
+        let version = i32::deserialize(deserializer)?;
+        if !(100..200).contains(&version) {
+            return Ok(units);
+        }
+        units.unit_system = UnitSystem::from(i32::deserialize(deserializer)?);
+        units.absolute_tolerance = f64::deserialize(deserializer)?;
+        units.angle_tolerance = f64::deserialize(deserializer)?;
+        units.relative_tolerance = f64::deserialize(deserializer)?;
+        if 101 <= version {
+            units.distance_display_mode = i32::deserialize(deserializer)?;
+            units.distance_display_precision = i32::deserialize(deserializer)?;
+            if 102 <= version {
+                units.custom_meters_per_unit = f64::deserialize(deserializer)?;
+                units.custom_unit_name = WStringWithLength::deserialize(deserializer)?.into();
+            }
+        }
+        Ok(units)
+    }
+}
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MeshParameters {
     #[underlying_type(BoolFromI32)]
     pub compute_curvature: bool,
@@ -26,6 +100,7 @@ pub struct MeshParameters {
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Annotation {
     pub dim_scale: f64,
     pub text_height: f64,
@@ -43,27 +118,33 @@ pub struct Annotation {
     pub resolution: i32,
     #[underlying_type(WStringWithLength)]
     pub face_name: String,
-    #[big_chunk_version(minor > 0)]
+    #[since(1)]
+    #[default_value(1.0)]
     pub world_view_text_scale: f64,
-    #[big_chunk_version(minor > 0)]
+    #[since(1)]
+    #[default_value(1)]
     pub enable_annotation_scaling: u8,
-    #[big_chunk_version(minor > 1)]
+    #[since(2)]
+    #[default_value(1.0)]
     pub world_view_hatch_scale: f64,
-    #[big_chunk_version(minor > 1)]
+    #[since(2)]
+    #[default_value(1)]
     pub enable_hatch_scaling: u8,
-    #[big_chunk_version(minor > 2)]
+    #[since(3)]
     pub enable_model_space_annotation_scaling: u8,
-    #[big_chunk_version(minor > 2)]
+    #[since(3)]
     pub enable_layout_space_annotation_scaling: u8,
 }
 
 #[derive(Default, RhinoDeserialize)]
 #[big_chunk_version(major == 1)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Attributes {
     pub line_type_display_scale: f64,
 }
 
 #[derive(Default, RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CurrentColor {
     pub color: i32,
     pub source: i32,
@@ -71,6 +152,7 @@ pub struct CurrentColor {
 
 #[derive(Default, RhinoDeserialize)]
 #[table(SETTINGS_TABLE)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Settings {
     #[table_field(SETTINGS_PLUGINLIST)]
     pub plugin_list: PlugInList,
@@ -90,3 +172,91 @@ pub struct Settings {
     #[table_field(SETTINGS_CURRENT_COLOR)]
     pub current_color: CurrentColor,
 }
+
+impl Settings {
+    pub fn unit_system(&self) -> UnitSystem {
+        self.units_and_tolerances.unit_system
+    }
+
+    pub fn convert_units(&mut self, target: UnitSystem) -> Option<f64> {
+        let scale = self.units_and_tolerances.convert_units(target)?;
+        let annotation = &mut self.anotation;
+        for length in [
+            &mut annotation.text_height,
+            &mut annotation.dim_exe,
+            &mut annotation.dim_exo,
+            &mut annotation.arrow_length,
+            &mut annotation.arrow_width,
+            &mut annotation.center_mark,
+        ] {
+            *length *= scale;
+        }
+        Some(scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::rhino::reader::Reader;
+
+    use super::*;
+
+    fn units_data(version: i32) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(version.to_le_bytes());
+        data.extend(8i32.to_le_bytes());
+        [0.01f64, 0.5, 0.02]
+            .iter()
+            .for_each(|v| data.extend(v.to_le_bytes()));
+        data.extend(1i32.to_le_bytes());
+        data.extend(4i32.to_le_bytes());
+        data.extend(0.5f64.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        "ab\0"
+            .encode_utf16()
+            .for_each(|u| data.extend(u.to_le_bytes()));
+        data
+    }
+
+    #[test]
+    fn deserialize_units_and_tolerances() {
+        let mut reader = Reader::new(Cursor::new(units_data(102)));
+        let units = UnitsAndTolerances::deserialize(&mut reader).unwrap();
+        assert_eq!(UnitSystem::Inches, units.unit_system);
+        assert_eq!(0.01, units.absolute_tolerance);
+        assert_eq!(0.5, units.angle_tolerance);
+        assert_eq!(4, units.distance_display_precision);
+        assert_eq!(0.5, units.custom_meters_per_unit);
+        assert_eq!("ab", units.custom_unit_name);
+        assert_eq!(Some(0.0254), units.meters_per_unit());
+
+        let mut reader = Reader::new(Cursor::new(units_data(100)));
+        let units = UnitsAndTolerances::deserialize(&mut reader).unwrap();
+        assert_eq!(3, units.distance_display_precision);
+        assert_eq!(1., units.custom_meters_per_unit);
+
+        let mut reader = Reader::new(Cursor::new(units_data(200)));
+        assert_eq!(
+            UnitsAndTolerances::default(),
+            UnitsAndTolerances::deserialize(&mut reader).unwrap()
+        );
+    }
+
+    #[test]
+    fn convert_units() {
+        let mut settings = Settings::default();
+        settings.anotation.text_height = 2.;
+        assert_eq!(UnitSystem::Millimeters, settings.unit_system());
+        assert_eq!(Some(0.1), settings.convert_units(UnitSystem::Centimeters));
+        assert_eq!(UnitSystem::Centimeters, settings.unit_system());
+        assert!((0.0001 - settings.units_and_tolerances.absolute_tolerance).abs() < 1e-15);
+        assert!((0.2 - settings.anotation.text_height).abs() < 1e-15);
+        assert_eq!(None, settings.convert_units(UnitSystem::None));
+
+        settings.units_and_tolerances.unit_system = UnitSystem::CustomUnits;
+        settings.units_and_tolerances.custom_meters_per_unit = 2.;
+        assert_eq!(Some(2.), settings.convert_units(UnitSystem::Meters));
+    }
+}
@@ -1,21 +1,22 @@
 use std::io::SeekFrom;
 
 use super::{
-    chunk::Value, deserialize::Deserialize, deserializer::Deserializer, typecode,
+    chunk::Value, deserialize::Deserialize, deserializer::Deserializer, error::Error, typecode,
     typecode::Typecode, version::Version,
 };
 
 // TODO: add version::Version as member of StartSection.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StartSection;
 
 impl<D> Deserialize<'_, D> for StartSection
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
-        let backup_position = SeekFrom::Start(deserializer.stream_position().unwrap());
+        let backup_position = SeekFrom::Start(deserializer.stream_position()?);
         if Version::V1 == deserializer.version() {
             loop {
                 let typecode = Typecode::deserialize(deserializer)?;
@@ -32,10 +33,10 @@ where
                     | typecode::NAMED_CPLANE
                     | typecode::NAMED_VIEW => {
                         let value: i64 = Value::deserialize(deserializer)?.into();
-                        deserializer.seek(SeekFrom::Current(value)).unwrap();
+                        deserializer.seek(SeekFrom::Current(value))?;
                     }
                     _ => {
-                        if typecode::TABLE == typecode & 0xFFFF0000 {
+                        if typecode.is_table() {
                             deserializer.set_version(Version::V2);
                         }
                         break;
@@ -45,7 +46,7 @@ where
         }
 
         if Version::V1 == deserializer.version() {
-            deserializer.seek(backup_position).unwrap();
+            deserializer.seek(backup_position)?;
         }
         Ok(StartSection {})
     }
@@ -78,6 +79,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
 
         assert!(StartSection::deserialize(&mut deserializer).is_ok());
@@ -100,6 +102,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
 
         assert!(StartSection::deserialize(&mut deserializer).is_ok());
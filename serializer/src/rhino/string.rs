@@ -1,21 +1,18 @@
 use std::io::Read;
 
-use super::{deserialize::Deserialize, deserializer::Deserializer};
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
 
 impl<D> Deserialize<'_, D> for String
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let mut string = String::new();
         match deserializer.read_to_string(&mut string) {
             Ok(_) => Ok(string),
-            Err(e) => {
-                println!("{}", e);
-                Err(format!("{}", e))
-            }
+            Err(e) => Err(Error::Io(e)),
         }
     }
 }
@@ -26,20 +23,21 @@ impl<D> Deserialize<'_, D> for StringWithLength
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let length = u32::deserialize(deserializer)?;
+        deserializer.limits().check_string_length(length as u64)?;
         let mut string = String::new();
         match deserializer.take(length as u64).read_to_string(&mut string) {
             Ok(size) => {
                 if size as u64 == length as u64 {
                     Ok(StringWithLength(string))
                 } else {
-                    Err("Invalid length".to_string())
+                    Err(Error::InvalidLength)
                 }
             }
-            Err(e) => Err(format!("{}", e)),
+            Err(e) => Err(Error::Io(e)),
         }
     }
 }
@@ -57,10 +55,11 @@ impl<D> Deserialize<'_, D> for WStringWithLength
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let length = u32::deserialize(deserializer)?;
+        deserializer.limits().check_string_length(length as u64)?;
         if 0 < length {
             let mut buf: Vec<u16> = vec![];
             for _ in 0..(length - 1) {
@@ -69,7 +68,7 @@ where
             u16::deserialize(deserializer)?;
             match String::from_utf16(&buf) {
                 Ok(string) => Ok(Self(string)),
-                Err(e) => Err(e.to_string()),
+                Err(e) => Err(Error::Other(e.to_string())),
             }
         } else {
             Ok(Self(String::new()))
@@ -89,6 +88,8 @@ mod tests {
 
     use crate::rhino::chunk::Begin;
     use crate::rhino::deserialize::Deserialize;
+    use crate::rhino::error::Error;
+    use crate::rhino::options::{Limits, ParseOptions};
     use crate::rhino::reader::Reader;
     use crate::rhino::string::WStringWithLength;
     use crate::rhino::version::Version as FileVersion;
@@ -107,12 +108,43 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
 
         let string_with_length = StringWithLength::deserialize(&mut deserializer).unwrap();
         assert_eq!(string, String::from(string_with_length));
     }
 
+    #[test]
+    fn deserialize_string_over_limit() {
+        let options = ParseOptions::default().with_limits(Limits {
+            max_string_length: 16,
+            ..Limits::default()
+        });
+        for data in [0xFFFFFFFFu32.to_le_bytes(), 17u32.to_le_bytes()] {
+            let mut deserializer = Reader {
+                stream: &mut Cursor::new(data),
+                version: FileVersion::V1,
+                chunk_begin: Begin::default(),
+                options: options.clone(),
+            };
+            assert!(matches!(
+                StringWithLength::deserialize(&mut deserializer),
+                Err(Error::LimitExceeded("string length"))
+            ));
+            let mut deserializer = Reader {
+                stream: &mut Cursor::new(data),
+                version: FileVersion::V1,
+                chunk_begin: Begin::default(),
+                options: options.clone(),
+            };
+            assert!(matches!(
+                WStringWithLength::deserialize(&mut deserializer),
+                Err(Error::LimitExceeded("string length"))
+            ));
+        }
+    }
+
     #[test]
     fn deserialize_string_with_invalid_length() {
         let string = "The string".to_string();
@@ -125,6 +157,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
         assert!(StringWithLength::deserialize(&mut deserializer).is_err());
     }
@@ -142,6 +175,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
         let wstring_with_length = WStringWithLength::deserialize(&mut deserializer).unwrap();
         string.pop();
@@ -161,6 +195,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: FileVersion::V1,
             chunk_begin: Begin::default(),
+            options: Default::default(),
         };
         assert!(WStringWithLength::deserialize(&mut deserializer).is_err());
     }
@@ -1,18 +1,145 @@
-use super::{deserialize::Deserialize, deserializer::Deserializer};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{
+    date::{self, DayOfMonth, GregorianDate, GregorianDateBuilder, Month, Year},
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    error::Error,
+};
 use geometria_derive::RhinoDeserialize;
 
 #[derive(Default, RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Time {
+    #[range(0..=60)]
     pub second: u32,
+    #[range(0..=59)]
     pub minute: u32,
+    #[range(0..=23)]
     pub hour: u32,
+    #[range(0..=31)]
     pub month_day: u32,
+    #[range(0..=11)]
     pub month: u32,
     pub year: u32,
+    #[range(0..=6)]
     pub week_day: u32,
+    #[range(0..=365)]
     pub year_day: u32,
 }
 
+const SECONDS_PER_DAY: i64 = 86400;
+
+impl Time {
+    const BASE_YEAR: u32 = 1900;
+
+    pub fn date(&self) -> Result<GregorianDate, date::Error> {
+        let year =
+            Year::try_from(Self::BASE_YEAR + self.year).map_err(|_| date::Error::InvalidYear)?;
+        let month = Month::try_from(self.month + 1).map_err(|_| date::Error::InvalidMonth)?;
+        let day =
+            DayOfMonth::try_from(self.month_day).map_err(|_| date::Error::InvalidDayOfMonth)?;
+        GregorianDateBuilder::new()
+            .year(year)
+            .month_and_day(month, day)
+            .build()
+    }
+
+    fn seconds_since_epoch(&self) -> Result<i64, date::Error> {
+        Ok(self.date()?.days_since_epoch() * SECONDS_PER_DAY
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64)
+    }
+
+    fn from_seconds_since_epoch(seconds: i64) -> Result<Self, date::Error> {
+        let date = GregorianDate::from_days_since_epoch(seconds.div_euclid(SECONDS_PER_DAY))?;
+        let seconds = seconds.rem_euclid(SECONDS_PER_DAY) as u32;
+        Ok(Self {
+            second: seconds % 60,
+            minute: seconds / 60 % 60,
+            hour: seconds / 3600,
+            ..Self::try_from(date)?
+        })
+    }
+}
+
+impl TryFrom<&Time> for GregorianDate {
+    type Error = date::Error;
+
+    fn try_from(time: &Time) -> Result<Self, Self::Error> {
+        time.date()
+    }
+}
+
+impl TryFrom<GregorianDate> for Time {
+    type Error = date::Error;
+
+    fn try_from(date: GregorianDate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            month_day: date.day_of_month() as u32,
+            month: date.month() as u32 - 1,
+            year: (date.year() as u32)
+                .checked_sub(Self::BASE_YEAR)
+                .ok_or(date::Error::InvalidYear)?,
+            week_day: date.day_of_week() as u32,
+            year_day: date.day_of_year() as u32 - 1,
+            ..Default::default()
+        })
+    }
+}
+
+impl TryFrom<&Time> for SystemTime {
+    type Error = date::Error;
+
+    fn try_from(time: &Time) -> Result<Self, Self::Error> {
+        let seconds = time.seconds_since_epoch()?;
+        let duration = Duration::from_secs(seconds.unsigned_abs());
+        Ok(if 0 > seconds {
+            UNIX_EPOCH - duration
+        } else {
+            UNIX_EPOCH + duration
+        })
+    }
+}
+
+impl TryFrom<SystemTime> for Time {
+    type Error = date::Error;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs_f64().ceil() as i64),
+        };
+        Self::from_seconds_since_epoch(seconds)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&Time> for chrono::NaiveDateTime {
+    type Error = date::Error;
+
+    fn try_from(time: &Time) -> Result<Self, Self::Error> {
+        let date = time.date()?;
+        chrono::NaiveDate::from_ymd_opt(
+            date.year() as i32,
+            date.month() as u32,
+            date.day_of_month() as u32,
+        )
+        .and_then(|date| date.and_hms_opt(time.hour, time.minute, time.second))
+        .ok_or(date::Error::InvalidDayOfMonth)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDateTime> for Time {
+    type Error = date::Error;
+
+    fn try_from(time: chrono::NaiveDateTime) -> Result<Self, Self::Error> {
+        Self::from_seconds_since_epoch(time.and_utc().timestamp())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -21,7 +148,7 @@ mod tests {
         mem,
     };
 
-    use crate::rhino::{chunk, reader::Reader, version::Version};
+    use crate::rhino::{chunk, options::ParseOptions, reader::Reader, version::Version};
 
     use super::*;
 
@@ -51,6 +178,7 @@ mod tests {
             stream: &mut cursor,
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
 
         let time = Time::deserialize(&mut deserializer).unwrap();
@@ -63,4 +191,131 @@ mod tests {
         assert_eq!(time.week_day, week_day);
         assert_eq!(time.year_day, year_day);
     }
+
+    fn time(year: u32, month: u32, month_day: u32, hour: u32, minute: u32) -> Time {
+        Time {
+            second: 5,
+            minute,
+            hour,
+            month_day,
+            month,
+            year,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn date() {
+        let date = GregorianDate::try_from(&time(121, 2, 4, 10, 30)).unwrap();
+        assert_eq!(
+            (2021, 3, 4),
+            (date.year(), date.month(), date.day_of_month())
+        );
+        assert_eq!(
+            Some(date::Error::InvalidDayOfMonth),
+            Time::default().date().err()
+        );
+
+        let time = Time::try_from(date).unwrap();
+        assert_eq!((121, 2, 4), (time.year, time.month, time.month_day));
+        assert_eq!((0, 0, 0), (time.hour, time.minute, time.second));
+        assert_eq!(4, time.week_day);
+        assert_eq!(62, time.year_day);
+    }
+
+    #[test]
+    fn system_time() {
+        let epoch = SystemTime::try_from(&time(70, 0, 1, 0, 0)).unwrap();
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(5), epoch);
+
+        let value = SystemTime::try_from(&time(121, 2, 4, 10, 30)).unwrap();
+        assert_eq!(
+            Duration::from_secs(1614853805),
+            value.duration_since(UNIX_EPOCH).unwrap()
+        );
+        let roundtrip = Time::try_from(value).unwrap();
+        assert_eq!(
+            (121, 2, 4, 10, 30, 5, 4, 62),
+            (
+                roundtrip.year,
+                roundtrip.month,
+                roundtrip.month_day,
+                roundtrip.hour,
+                roundtrip.minute,
+                roundtrip.second,
+                roundtrip.week_day,
+                roundtrip.year_day
+            )
+        );
+
+        let before_epoch = Time::try_from(UNIX_EPOCH - Duration::from_secs(1)).unwrap();
+        assert_eq!(
+            (69, 11, 31, 23, 59, 59),
+            (
+                before_epoch.year,
+                before_epoch.month,
+                before_epoch.month_day,
+                before_epoch.hour,
+                before_epoch.minute,
+                before_epoch.second
+            )
+        );
+        assert_eq!(
+            UNIX_EPOCH - Duration::from_secs(1),
+            SystemTime::try_from(&before_epoch).unwrap()
+        );
+        assert_eq!(
+            Some(date::Error::InvalidYear),
+            Time::try_from(UNIX_EPOCH - Duration::from_secs(71 * 365 * 86400)).err()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn naive_date_time() {
+        let value = chrono::NaiveDateTime::try_from(&time(121, 2, 4, 10, 30)).unwrap();
+        assert_eq!("2021-03-04 10:30:05", value.to_string());
+        let roundtrip = Time::try_from(value).unwrap();
+        assert_eq!(
+            (121, 2, 4, 4),
+            (
+                roundtrip.year,
+                roundtrip.month,
+                roundtrip.month_day,
+                roundtrip.week_day
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_out_of_range() {
+        let mut data = [0u8; mem::size_of::<Time>()];
+        data[16..20].copy_from_slice(&12u32.to_le_bytes());
+
+        let options = ParseOptions::lenient();
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: chunk::Begin::default(),
+            options: options.clone(),
+        };
+        assert_eq!(12, Time::deserialize(&mut deserializer).unwrap().month);
+        let warnings = options.take_warnings();
+        assert_eq!(1, warnings.len());
+        assert_eq!("@ 0x14: month out of range", warnings[0].to_string());
+
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: chunk::Begin::default(),
+            options: ParseOptions::strict(),
+        };
+        assert!(matches!(
+            Time::deserialize(&mut deserializer)
+                .err()
+                .as_ref()
+                .map(Error::root_cause),
+            Some(Error::OutOfRange("month"))
+        ));
+    }
 }
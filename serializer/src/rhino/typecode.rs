@@ -1,212 +1,363 @@
-pub type Typecode = u32;
-
-pub const COMMENTBLOCK: Typecode = 0x00000001;
-//const ENDOFFILE: Typecode = 0x00007FFF;
-//const ENDOFFILE_GOO: Typecode = 0x00007FFE;
-//const LEGACY_GEOMETRY: Typecode = 0x00010000;
-//const OPENNURBS_OBJECT: Typecode = 0x00020000;
-//const GEOMETRY: Typecode = 0x00100000;
-const ANNOTATION: Typecode = 0x00200000;
-const DISPLAY: Typecode = 0x00400000;
-//const RENDER: Typecode = 0x00800000;
-const INTERFACE: Typecode = 0x02000000;
-//const TOLERANCE: Typecode = 0x08000000;
-pub const TABLE: Typecode = 0x10000000;
-const TABLEREC: Typecode = 0x20000000;
-//const USER: Typecode = 0x40000000;
-pub const SHORT: Typecode = 0x80000000;
-const CRC: Typecode = 0x8000;
-//const ANONYMOUS_CHUNK: Typecode = (USER | CRC | 0x0000);
-//const UTF8_STRING_CHUNK: Typecode = (USER | CRC | 0x0001);
-//const MODEL_ATTRIBUTES_CHUNK: Typecode = (USER | CRC | 0x0002);
-//const DICTIONARY: Typecode = (USER | CRC | 0x0010);
-//const DICTIONARY_ID: Typecode = (USER | CRC | 0x0011);
-//const DICTIONARY_ENTRY: Typecode = (USER | CRC | 0x0012);
-//const DICTIONARY_END: Typecode = (USER | SHORT | 0x0013);
-//const XDATA: Typecode = (USER | 0x0001);
-//const MATERIAL_TABLE: Typecode = (TABLE | 0x0010);
-//const LAYER_TABLE: Typecode = (TABLE | 0x0011);
-//const LIGHT_TABLE: Typecode = (TABLE | 0x0012);
-//const OBJECT_TABLE: Typecode = (TABLE | 0x0013);
-pub const PROPERTIES_TABLE: Typecode = TABLE | 0x0014;
-pub const SETTINGS_TABLE: Typecode = TABLE | 0x0015;
-//const BITMAP_TABLE: Typecode = (TABLE | 0x0016);
-//const USER_TABLE: Typecode = (TABLE | 0x0017);
-//const GROUP_TABLE: Typecode = (TABLE | 0x0018);
-//const FONT_TABLE: Typecode = (TABLE | 0x0019);
-//const DIMSTYLE_TABLE: Typecode = (TABLE | 0x0020);
-//const INSTANCE_DEFINITION_TABLE: Typecode = (TABLE | 0x0021);
-//const HATCHPATTERN_TABLE: Typecode = (TABLE | 0x0022);
-//const LINETYPE_TABLE: Typecode = (TABLE | 0x0023);
-//const OBSOLETE_LAYERSET_TABLE: Typecode = (TABLE | 0x0024);
-//const TEXTURE_MAPPING_TABLE: Typecode = (TABLE | 0x0025);
-//const HISTORYRECORD_TABLE: Typecode = (TABLE | 0x0026);
-pub const ENDOFTABLE: Typecode = 0xFFFFFFFF;
-pub const PROPERTIES_REVISIONHISTORY: Typecode = TABLEREC | CRC | 0x0021;
-pub const PROPERTIES_NOTES: Typecode = TABLEREC | CRC | 0x0022;
-pub const PROPERTIES_PREVIEWIMAGE: Typecode = TABLEREC | CRC | 0x0023;
-pub const PROPERTIES_APPLICATION: Typecode = TABLEREC | CRC | 0x0024;
-pub const PROPERTIES_COMPRESSED_PREVIEWIMAGE: Typecode = TABLEREC | CRC | 0x0025;
-pub const PROPERTIES_OPENNURBS_VERSION: Typecode = TABLEREC | SHORT | 0x0026;
-pub const PROPERTIES_AS_FILE_NAME: Typecode = TABLEREC | CRC | 0x0027;
-pub const SETTINGS_PLUGINLIST: Typecode = TABLEREC | CRC | 0x0135;
-pub const SETTINGS_UNITSANDTOLS: Typecode = TABLEREC | CRC | 0x0031;
-pub const SETTINGS_RENDERMESH: Typecode = TABLEREC | CRC | 0x0032;
-pub const SETTINGS_ANALYSISMESH: Typecode = TABLEREC | CRC | 0x0033;
-pub const SETTINGS_ANNOTATION: Typecode = TABLEREC | CRC | 0x0034;
-//const SETTINGS_NAMED_CPLANE_LIST: Typecode = (TABLEREC | CRC | 0x0035);
-//const SETTINGS_NAMED_VIEW_LIST: Typecode = (TABLEREC | CRC | 0x0036);
-//const SETTINGS_VIEW_LIST: Typecode = (TABLEREC | CRC | 0x0037);
-//const SETTINGS_CURRENT_LAYER_INDEX: Typecode = (TABLEREC | SHORT | 0x0038);
-//const SETTINGS_CURRENT_MATERIAL_INDEX: Typecode = (TABLEREC | CRC | 0x0039);
-pub const SETTINGS_CURRENT_COLOR: Typecode = TABLEREC | CRC | 0x003A;
-//const SETTINGS__NEVER__USE__THIS: Typecode = (TABLEREC | CRC | 0x003E);
-//const SETTINGS_CURRENT_WIRE_DENSITY: Typecode = (TABLEREC | SHORT | 0x003C);
-//const SETTINGS_RENDER: Typecode = (TABLEREC | CRC | 0x003D);
-//const SETTINGS_GRID_DEFAULTS: Typecode = (TABLEREC | CRC | 0x003F);
-pub const SETTINGS_MODEL_URL: Typecode = TABLEREC | CRC | 0x0131;
-//const SETTINGS_CURRENT_FONT_INDEX: Typecode = (TABLEREC | SHORT | 0x0132);
-//const SETTINGS_CURRENT_DIMSTYLE_INDEX: Typecode = (TABLEREC | SHORT | 0x0133);
-pub const SETTINGS_ATTRIBUTES: Typecode = TABLEREC | CRC | 0x0134;
-//const SETTINGS_RENDER_USERDATA: Typecode = (TABLEREC | CRC | 0x0136);
-//const VIEW_RECORD: Typecode = (TABLEREC | CRC | 0x003B);
-//const VIEW_CPLANE: Typecode = (TABLEREC | CRC | 0x013B);
-//const VIEW_VIEWPORT: Typecode = (TABLEREC | CRC | 0x023B);
-//const VIEW_SHOWCONGRID: Typecode = (TABLEREC | SHORT | 0x033B);
-//const VIEW_SHOWCONAXES: Typecode = (TABLEREC | SHORT | 0x043B);
-//const VIEW_SHOWWORLDAXES: Typecode = (TABLEREC | SHORT | 0x053B);
-//const VIEW_TRACEIMAGE: Typecode = (TABLEREC | CRC | 0x063B);
-//const VIEW_WALLPAPER: Typecode = (TABLEREC | CRC | 0x073B);
-//const VIEW_WALLPAPER_V3: Typecode = (TABLEREC | CRC | 0x074B);
-//const VIEW_TARGET: Typecode = (TABLEREC | CRC | 0x083B);
-//const VIEW_V3_DISPLAYMODE: Typecode = (TABLEREC | SHORT | 0x093B);
-//const VIEW_NAME: Typecode = (TABLEREC | CRC | 0x0A3B);
-//const VIEW_POSITION: Typecode = (TABLEREC | CRC | 0x0B3B);
-//const VIEW_ATTRIBUTES: Typecode = (TABLEREC | CRC | 0x0C3B);
-//const VIEW_VIEWPORT_USERDATA: Typecode = (TABLEREC | CRC | 0x0D3B);
-//const BITMAP_RECORD: Typecode = (TABLEREC | CRC | 0x0090);
-//const MATERIAL_RECORD: Typecode = (TABLEREC | CRC | 0x0040);
-//const LAYER_RECORD: Typecode = (TABLEREC | CRC | 0x0050);
-//const LIGHT_RECORD: Typecode = (TABLEREC | CRC | 0x0060);
-//const LIGHT_RECORD_ATTRIBUTES: Typecode = (INTERFACE | CRC | 0x0061);
-//const LIGHT_RECORD_ATTRIBUTES_USERDATA: Typecode = (INTERFACE | 0x0062);
-//const LIGHT_RECORD_END: Typecode = (INTERFACE | SHORT | 0x006F);
-//const USER_TABLE_UUID: Typecode = (TABLEREC | CRC | 0x0080);
-//const USER_TABLE_RECORD_HEADER: Typecode = (TABLEREC | CRC | 0x0082);
-//const USER_RECORD: Typecode = (TABLEREC | 0x0081);
-//const GROUP_RECORD: Typecode = (TABLEREC | CRC | 0x0073);
-//const FONT_RECORD: Typecode = (TABLEREC | CRC | 0x0074);
-//const DIMSTYLE_RECORD: Typecode = (TABLEREC | CRC | 0x0075);
-//const INSTANCE_DEFINITION_RECORD: Typecode = (TABLEREC | CRC | 0x0076);
-//const HATCHPATTERN_RECORD: Typecode = (TABLEREC | CRC | 0x0077);
-//const LINETYPE_RECORD: Typecode = (TABLEREC | CRC | 0x0078);
-//const OBSOLETE_LAYERSET_RECORD: Typecode = (TABLEREC | CRC | 0x0079);
-//const TEXTURE_MAPPING_RECORD: Typecode = (TABLEREC | CRC | 0x007A);
-//const HISTORYRECORD_RECORD: Typecode = (TABLEREC | CRC | 0x007B);
-//const OBJECT_RECORD: Typecode = (TABLEREC | CRC | 0x0070);
-pub const OBJECT_RECORD_TYPE: Typecode = INTERFACE | SHORT | 0x0071;
-//const OBJECT_RECORD_ATTRIBUTES: Typecode = (INTERFACE | CRC | 0x0072);
-//const OBJECT_RECORD_ATTRIBUTES_USERDATA: Typecode = (INTERFACE | 0x0073);
-//const OBJECT_RECORD_HISTORY: Typecode = (INTERFACE | CRC | 0x0074);
-//const OBJECT_RECORD_HISTORY_HEADER: Typecode = (INTERFACE | CRC | 0x0075);
-//const OBJECT_RECORD_HISTORY_DATA: Typecode = (INTERFACE | CRC | 0x0076);
-//const OBJECT_RECORD_END: Typecode = (INTERFACE | SHORT | 0x007F);
-//const OPENNURBS_CLASS: Typecode = (OPENNURBS_OBJECT | 0x7FFA);
-//const OPENNURBS_CLASS_UUID: Typecode = (OPENNURBS_OBJECT | CRC | 0x7FFB);
-//const OPENNURBS_CLASS_DATA: Typecode = (OPENNURBS_OBJECT | CRC | 0x7FFC);
-//const OPENNURBS_CLASS_USERDATA: Typecode = (OPENNURBS_OBJECT | 0x7FFD);
-//const OPENNURBS_CLASS_USERDATA_HEADER: Typecode = (OPENNURBS_OBJECT | CRC | 0x7FF9);
-//const OPENNURBS_CLASS_END: Typecode = (OPENNURBS_OBJECT | SHORT | 0x7FFF);
-pub const ANNOTATION_SETTINGS: Typecode = ANNOTATION | 0x0001;
-//const TEXT_BLOCK: Typecode = (ANNOTATION | 0x0004);
-//const ANNOTATION_LEADER: Typecode = (ANNOTATION | 0x0005);
-//const LINEAR_DIMENSION: Typecode = (ANNOTATION | 0x0006);
-//const ANGULAR_DIMENSION: Typecode = (ANNOTATION | 0x0007);
-//const RADIAL_DIMENSION: Typecode = (ANNOTATION | 0x0008);
-//const RHINOIO_OBJECT_NURBS_CURVE: Typecode = (OPENNURBS_OBJECT | 0x0008);
-//const RHINOIO_OBJECT_NURBS_SURFACE: Typecode = (OPENNURBS_OBJECT | 0x0009);
-//const RHINOIO_OBJECT_BREP: Typecode = (OPENNURBS_OBJECT | 0x000B);
-//const RHINOIO_OBJECT_DATA: Typecode = (OPENNURBS_OBJECT | 0xFFFE);
-//const RHINOIO_OBJECT_END: Typecode = (OPENNURBS_OBJECT | 0xFFFF);
-//const OPENNURBS_BUFFER: Typecode = (OPENNURBS_OBJECT | CRC | 0x0100);
-//const LEGACY_ASM: Typecode = (LEGACY_GEOMETRY | 0x0001);
-//const LEGACY_PRT: Typecode = (LEGACY_GEOMETRY | 0x0002);
-//const LEGACY_SHL: Typecode = (LEGACY_GEOMETRY | 0x0003);
-//const LEGACY_FAC: Typecode = (LEGACY_GEOMETRY | 0x0004);
-//const LEGACY_BND: Typecode = (LEGACY_GEOMETRY | 0x0005);
-//const LEGACY_TRM: Typecode = (LEGACY_GEOMETRY | 0x0006);
-//const LEGACY_SRF: Typecode = (LEGACY_GEOMETRY | 0x0007);
-//const LEGACY_CRV: Typecode = (LEGACY_GEOMETRY | 0x0008);
-//const LEGACY_SPL: Typecode = (LEGACY_GEOMETRY | 0x0009);
-//const LEGACY_PNT: Typecode = (LEGACY_GEOMETRY | 0x000A);
-//const STUFF: Typecode = 0x0100;
-//const LEGACY_ASMSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_ASM);
-//const LEGACY_PRTSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_PRT);
-//const LEGACY_SHLSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_SHL);
-//const LEGACY_FACSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_FAC);
-//const LEGACY_BNDSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_BND);
-//const LEGACY_TRMSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_TRM);
-//const LEGACY_SRFSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_SRF);
-//const LEGACY_CRVSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_CRV);
-//const LEGACY_SPLSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_SPL);
-//const LEGACY_PNTSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_PNT);
-//const RH_POINT: Typecode = (GEOMETRY | 0x0001);
-//const RH_SPOTLIGHT: Typecode = (RENDER | 0x0001);
-//const OLD_RH_TRIMESH: Typecode = (GEOMETRY | 0x0011);
-//const OLD_MESH_VERTEX_NORMALS: Typecode = (GEOMETRY | 0x0012);
-//const OLD_MESH_UV: Typecode = (GEOMETRY | 0x0013);
-//const OLD_FULLMESH: Typecode = (GEOMETRY | 0x0014);
-//const MESH_OBJECT: Typecode = (GEOMETRY | 0x0015);
-//const COMPRESSED_MESH_GEOMETRY: Typecode = (GEOMETRY | 0x0017);
-//const ANALYSIS_MESH: Typecode = (GEOMETRY | 0x0018);
-//const NAME: Typecode = (INTERFACE | 0x0001);
-//const VIEW: Typecode = (INTERFACE | 0x0002);
-//const CPLANE: Typecode = (INTERFACE | 0x0003);
-pub const NAMED_CPLANE: Typecode = INTERFACE | 0x0004;
-pub const NAMED_VIEW: Typecode = INTERFACE | 0x0005;
-pub const VIEWPORT: Typecode = INTERFACE | 0x0006;
-//const SHOWGRID: Typecode = (SHORT | INTERFACE | 0x0007);
-//const SHOWGRIDAXES: Typecode = (SHORT | INTERFACE | 0x0008);
-//const SHOWWORLDAXES: Typecode = (SHORT | INTERFACE | 0x0009);
-//const VIEWPORT_POSITION: Typecode = (INTERFACE | 0x000A);
-//const VIEWPORT_TRACEINFO: Typecode = (INTERFACE | 0x000B);
-//const SNAPSIZE: Typecode = (INTERFACE | 0x000C);
-//const NEAR_CLIP_PLANE: Typecode = (INTERFACE | 0x000D);
-//const HIDE_TRACE: Typecode = (INTERFACE | 0x000E);
-pub const NOTES: Typecode = INTERFACE | 0x000F;
-pub const UNIT_AND_TOLERANCES: Typecode = INTERFACE | 0x0010;
-//const MAXIMIZED_VIEWPORT: Typecode = (SHORT | INTERFACE | 0x0011);
-//const VIEWPORT_WALLPAPER: Typecode = (INTERFACE | 0x0012);
-pub const SUMMARY: Typecode = INTERFACE | 0x0013;
-pub const BITMAPPREVIEW: Typecode = INTERFACE | 0x0014;
-//const VIEWPORT_V1_DISPLAYMODE: Typecode = (SHORT | INTERFACE | 0x0015);
-//const LAYERTABLE: Typecode = (SHORT | TABLE | 0x0001);
-//const LAYERREF: Typecode = (SHORT | TABLEREC | 0x0001);
-pub const RGB: Typecode = SHORT | DISPLAY | 0x0001;
-//const TEXTUREMAP: Typecode = (DISPLAY | 0x0002);
-//const BUMPMAP: Typecode = (DISPLAY | 0x0003);
-//const TRANSPARENCY: Typecode = (SHORT | DISPLAY | 0x0004);
-//const DISP_AM_RESOLUTION: Typecode = (SHORT | DISPLAY | 0x0005);
-pub const RGBDISPLAY: Typecode = SHORT | DISPLAY | 0x0006;
-//const RENDER_MATERIAL_ID: Typecode = (DISPLAY | 0x0007);
-pub const LAYER: Typecode = DISPLAY | 0x0010;
-//const LAYER_OBSELETE_1: Typecode = (SHORT | DISPLAY | 0x0013);
-//const LAYER_OBSELETE_2: Typecode = (SHORT | DISPLAY | 0x0014);
-//const LAYER_OBSELETE_3: Typecode = (SHORT | DISPLAY | 0x0015);
-//const LAYERON: Typecode = (SHORT | DISPLAY | 0x0016);
-//const LAYERTHAWED: Typecode = (SHORT | DISPLAY | 0x0017);
-//const LAYERLOCKED: Typecode = (SHORT | DISPLAY | 0x0018);
-//const LAYERVISIBLE: Typecode = (SHORT | DISPLAY | 0x0012);
-//const LAYERPICKABLE: Typecode = (SHORT | DISPLAY | 0x0030);
-//const LAYERSNAPABLE: Typecode = (SHORT | DISPLAY | 0x0031);
-//const LAYERRENDERABLE: Typecode = (SHORT | DISPLAY | 0x0032);
-//const LAYERSTATE: Typecode = (SHORT | DISPLAY | 0x0033);
-//const LAYERINDEX: Typecode = (SHORT | DISPLAY | 0x0034);
-//const LAYERMATERIALINDEX: Typecode = (SHORT | DISPLAY | 0x0035);
-pub const RENDERMESHPARAMS: Typecode = DISPLAY | 0x0020;
-//const DISP_CPLINES: Typecode = (SHORT | DISPLAY | 0x0022);
-//const DISP_MAXLENGTH: Typecode = (DISPLAY | 0x0023);
-pub const CURRENTLAYER: Typecode = SHORT | DISPLAY | 0x0025;
-//const LAYERNAME: Typecode = (DISPLAY | 0x0011);
-//const LEGACY_TOL_FIT: Typecode = (TOLERANCE | 0x0001);
-//const LEGACY_TOL_ANGLE: Typecode = (TOLERANCE | 0x0002);
+use std::fmt::{Debug, Display};
+
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
+
+//const ENDOFFILE_GOO: u32 = 0x00007FFE;
+//const LEGACY_GEOMETRY: u32 = 0x00010000;
+const OPENNURBS_OBJECT: u32 = 0x00020000;
+//const GEOMETRY: u32 = 0x00100000;
+const ANNOTATION: u32 = 0x00200000;
+const DISPLAY: u32 = 0x00400000;
+//const RENDER: u32 = 0x00800000;
+const INTERFACE: u32 = 0x02000000;
+//const TOLERANCE: u32 = 0x08000000;
+pub const TABLE: u32 = 0x10000000;
+const TABLEREC: u32 = 0x20000000;
+const USER: u32 = 0x40000000;
+pub const SHORT: u32 = 0x80000000;
+pub const CRC: u32 = 0x8000;
+
+macro_rules! typecodes {
+    ($($variant:ident = $constant:ident = $value:expr,)*) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+        pub enum Typecode {
+            $($variant,)*
+            Unknown(u32),
+        }
+
+        $(pub const $constant: Typecode = Typecode::$variant;)*
+
+        impl Typecode {
+            pub const fn value(self) -> u32 {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(value) => value,
+                }
+            }
+
+            pub const fn name(self) -> Option<&'static str> {
+                match self {
+                    $(Self::$variant => Some(stringify!($constant)),)*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl From<u32> for Typecode {
+            fn from(value: u32) -> Self {
+                $(if $value == value {
+                    return Self::$variant;
+                })*
+                Self::Unknown(value)
+            }
+        }
+    };
+}
+
+typecodes! {
+    CommentBlock = COMMENTBLOCK = 0x00000001,
+    EndOfFile = ENDOFFILE = 0x00007FFF,
+    AnonymousChunk = ANONYMOUS_CHUNK = USER | CRC,
+    //const UTF8_STRING_CHUNK: Typecode = (USER | CRC | 0x0001);
+    //const MODEL_ATTRIBUTES_CHUNK: Typecode = (USER | CRC | 0x0002);
+    //const DICTIONARY: Typecode = (USER | CRC | 0x0010);
+    //const DICTIONARY_ID: Typecode = (USER | CRC | 0x0011);
+    //const DICTIONARY_ENTRY: Typecode = (USER | CRC | 0x0012);
+    //const DICTIONARY_END: Typecode = (USER | SHORT | 0x0013);
+    //const XDATA: Typecode = (USER | 0x0001);
+    MaterialTable = MATERIAL_TABLE = TABLE | 0x0010,
+    LayerTable = LAYER_TABLE = TABLE | 0x0011,
+    LightTable = LIGHT_TABLE = TABLE | 0x0012,
+    ObjectTable = OBJECT_TABLE = TABLE | 0x0013,
+    PropertiesTable = PROPERTIES_TABLE = TABLE | 0x0014,
+    SettingsTable = SETTINGS_TABLE = TABLE | 0x0015,
+    BitmapTable = BITMAP_TABLE = TABLE | 0x0016,
+    UserTable = USER_TABLE = TABLE | 0x0017,
+    GroupTable = GROUP_TABLE = TABLE | 0x0018,
+    FontTable = FONT_TABLE = TABLE | 0x0019,
+    DimstyleTable = DIMSTYLE_TABLE = TABLE | 0x0020,
+    InstanceDefinitionTable = INSTANCE_DEFINITION_TABLE = TABLE | 0x0021,
+    HatchPatternTable = HATCHPATTERN_TABLE = TABLE | 0x0022,
+    LinetypeTable = LINETYPE_TABLE = TABLE | 0x0023,
+    //const OBSOLETE_LAYERSET_TABLE: Typecode = (TABLE | 0x0024);
+    TextureMappingTable = TEXTURE_MAPPING_TABLE = TABLE | 0x0025,
+    HistoryRecordTable = HISTORYRECORD_TABLE = TABLE | 0x0026,
+    EndOfTable = ENDOFTABLE = 0xFFFFFFFF,
+    PropertiesRevisionHistory = PROPERTIES_REVISIONHISTORY = TABLEREC | CRC | 0x0021,
+    PropertiesNotes = PROPERTIES_NOTES = TABLEREC | CRC | 0x0022,
+    PropertiesPreviewImage = PROPERTIES_PREVIEWIMAGE = TABLEREC | CRC | 0x0023,
+    PropertiesApplication = PROPERTIES_APPLICATION = TABLEREC | CRC | 0x0024,
+    PropertiesCompressedPreviewImage = PROPERTIES_COMPRESSED_PREVIEWIMAGE = TABLEREC | CRC | 0x0025,
+    PropertiesOpennurbsVersion = PROPERTIES_OPENNURBS_VERSION = TABLEREC | SHORT | 0x0026,
+    PropertiesAsFileName = PROPERTIES_AS_FILE_NAME = TABLEREC | CRC | 0x0027,
+    SettingsPluginList = SETTINGS_PLUGINLIST = TABLEREC | CRC | 0x0135,
+    SettingsUnitsAndTols = SETTINGS_UNITSANDTOLS = TABLEREC | CRC | 0x0031,
+    SettingsRenderMesh = SETTINGS_RENDERMESH = TABLEREC | CRC | 0x0032,
+    SettingsAnalysisMesh = SETTINGS_ANALYSISMESH = TABLEREC | CRC | 0x0033,
+    SettingsAnnotation = SETTINGS_ANNOTATION = TABLEREC | CRC | 0x0034,
+    //const SETTINGS_NAMED_CPLANE_LIST: Typecode = (TABLEREC | CRC | 0x0035);
+    //const SETTINGS_NAMED_VIEW_LIST: Typecode = (TABLEREC | CRC | 0x0036);
+    //const SETTINGS_VIEW_LIST: Typecode = (TABLEREC | CRC | 0x0037);
+    //const SETTINGS_CURRENT_LAYER_INDEX: Typecode = (TABLEREC | SHORT | 0x0038);
+    //const SETTINGS_CURRENT_MATERIAL_INDEX: Typecode = (TABLEREC | CRC | 0x0039);
+    SettingsCurrentColor = SETTINGS_CURRENT_COLOR = TABLEREC | CRC | 0x003A,
+    //const SETTINGS__NEVER__USE__THIS: Typecode = (TABLEREC | CRC | 0x003E);
+    //const SETTINGS_CURRENT_WIRE_DENSITY: Typecode = (TABLEREC | SHORT | 0x003C);
+    //const SETTINGS_RENDER: Typecode = (TABLEREC | CRC | 0x003D);
+    //const SETTINGS_GRID_DEFAULTS: Typecode = (TABLEREC | CRC | 0x003F);
+    SettingsModelUrl = SETTINGS_MODEL_URL = TABLEREC | CRC | 0x0131,
+    //const SETTINGS_CURRENT_FONT_INDEX: Typecode = (TABLEREC | SHORT | 0x0132);
+    //const SETTINGS_CURRENT_DIMSTYLE_INDEX: Typecode = (TABLEREC | SHORT | 0x0133);
+    SettingsAttributes = SETTINGS_ATTRIBUTES = TABLEREC | CRC | 0x0134,
+    //const SETTINGS_RENDER_USERDATA: Typecode = (TABLEREC | CRC | 0x0136);
+    //const VIEW_RECORD: Typecode = (TABLEREC | CRC | 0x003B);
+    //const VIEW_CPLANE: Typecode = (TABLEREC | CRC | 0x013B);
+    //const VIEW_VIEWPORT: Typecode = (TABLEREC | CRC | 0x023B);
+    //const VIEW_SHOWCONGRID: Typecode = (TABLEREC | SHORT | 0x033B);
+    //const VIEW_SHOWCONAXES: Typecode = (TABLEREC | SHORT | 0x043B);
+    //const VIEW_SHOWWORLDAXES: Typecode = (TABLEREC | SHORT | 0x053B);
+    //const VIEW_TRACEIMAGE: Typecode = (TABLEREC | CRC | 0x063B);
+    //const VIEW_WALLPAPER: Typecode = (TABLEREC | CRC | 0x073B);
+    //const VIEW_WALLPAPER_V3: Typecode = (TABLEREC | CRC | 0x074B);
+    //const VIEW_TARGET: Typecode = (TABLEREC | CRC | 0x083B);
+    //const VIEW_V3_DISPLAYMODE: Typecode = (TABLEREC | SHORT | 0x093B);
+    //const VIEW_NAME: Typecode = (TABLEREC | CRC | 0x0A3B);
+    //const VIEW_POSITION: Typecode = (TABLEREC | CRC | 0x0B3B);
+    //const VIEW_ATTRIBUTES: Typecode = (TABLEREC | CRC | 0x0C3B);
+    //const VIEW_VIEWPORT_USERDATA: Typecode = (TABLEREC | CRC | 0x0D3B);
+    //const BITMAP_RECORD: Typecode = (TABLEREC | CRC | 0x0090);
+    //const MATERIAL_RECORD: Typecode = (TABLEREC | CRC | 0x0040);
+    //const LAYER_RECORD: Typecode = (TABLEREC | CRC | 0x0050);
+    //const LIGHT_RECORD: Typecode = (TABLEREC | CRC | 0x0060);
+    //const LIGHT_RECORD_ATTRIBUTES: Typecode = (INTERFACE | CRC | 0x0061);
+    //const LIGHT_RECORD_ATTRIBUTES_USERDATA: Typecode = (INTERFACE | 0x0062);
+    //const LIGHT_RECORD_END: Typecode = (INTERFACE | SHORT | 0x006F);
+    //const USER_TABLE_UUID: Typecode = (TABLEREC | CRC | 0x0080);
+    //const USER_TABLE_RECORD_HEADER: Typecode = (TABLEREC | CRC | 0x0082);
+    //const USER_RECORD: Typecode = (TABLEREC | 0x0081);
+    //const GROUP_RECORD: Typecode = (TABLEREC | CRC | 0x0073);
+    //const FONT_RECORD: Typecode = (TABLEREC | CRC | 0x0074);
+    //const DIMSTYLE_RECORD: Typecode = (TABLEREC | CRC | 0x0075);
+    InstanceDefinitionRecord = INSTANCE_DEFINITION_RECORD = TABLEREC | CRC | 0x0076,
+    //const HATCHPATTERN_RECORD: Typecode = (TABLEREC | CRC | 0x0077);
+    //const LINETYPE_RECORD: Typecode = (TABLEREC | CRC | 0x0078);
+    //const OBSOLETE_LAYERSET_RECORD: Typecode = (TABLEREC | CRC | 0x0079);
+    //const TEXTURE_MAPPING_RECORD: Typecode = (TABLEREC | CRC | 0x007A);
+    //const HISTORYRECORD_RECORD: Typecode = (TABLEREC | CRC | 0x007B);
+    ObjectRecord = OBJECT_RECORD = TABLEREC | CRC | 0x0070,
+    ObjectRecordType = OBJECT_RECORD_TYPE = INTERFACE | SHORT | 0x0071,
+    ObjectRecordAttributes = OBJECT_RECORD_ATTRIBUTES = INTERFACE | CRC | 0x0072,
+    //const OBJECT_RECORD_ATTRIBUTES_USERDATA: Typecode = (INTERFACE | 0x0073);
+    //const OBJECT_RECORD_HISTORY: Typecode = (INTERFACE | CRC | 0x0074);
+    //const OBJECT_RECORD_HISTORY_HEADER: Typecode = (INTERFACE | CRC | 0x0075);
+    //const OBJECT_RECORD_HISTORY_DATA: Typecode = (INTERFACE | CRC | 0x0076);
+    ObjectRecordEnd = OBJECT_RECORD_END = INTERFACE | SHORT | 0x007F,
+    OpennurbsClass = OPENNURBS_CLASS = OPENNURBS_OBJECT | 0x7FFA,
+    OpennurbsClassUuid = OPENNURBS_CLASS_UUID = OPENNURBS_OBJECT | CRC | 0x7FFB,
+    OpennurbsClassData = OPENNURBS_CLASS_DATA = OPENNURBS_OBJECT | CRC | 0x7FFC,
+    //const OPENNURBS_CLASS_USERDATA: Typecode = (OPENNURBS_OBJECT | 0x7FFD);
+    //const OPENNURBS_CLASS_USERDATA_HEADER: Typecode = (OPENNURBS_OBJECT | CRC | 0x7FF9);
+    OpennurbsClassEnd = OPENNURBS_CLASS_END = OPENNURBS_OBJECT | SHORT | 0x7FFF,
+    AnnotationSettings = ANNOTATION_SETTINGS = ANNOTATION | 0x0001,
+    //const TEXT_BLOCK: Typecode = (ANNOTATION | 0x0004);
+    //const ANNOTATION_LEADER: Typecode = (ANNOTATION | 0x0005);
+    //const LINEAR_DIMENSION: Typecode = (ANNOTATION | 0x0006);
+    //const ANGULAR_DIMENSION: Typecode = (ANNOTATION | 0x0007);
+    //const RADIAL_DIMENSION: Typecode = (ANNOTATION | 0x0008);
+    //const RHINOIO_OBJECT_NURBS_CURVE: Typecode = (OPENNURBS_OBJECT | 0x0008);
+    //const RHINOIO_OBJECT_NURBS_SURFACE: Typecode = (OPENNURBS_OBJECT | 0x0009);
+    //const RHINOIO_OBJECT_BREP: Typecode = (OPENNURBS_OBJECT | 0x000B);
+    //const RHINOIO_OBJECT_DATA: Typecode = (OPENNURBS_OBJECT | 0xFFFE);
+    //const RHINOIO_OBJECT_END: Typecode = (OPENNURBS_OBJECT | 0xFFFF);
+    //const OPENNURBS_BUFFER: Typecode = (OPENNURBS_OBJECT | CRC | 0x0100);
+    //const LEGACY_ASM: Typecode = (LEGACY_GEOMETRY | 0x0001);
+    //const LEGACY_PRT: Typecode = (LEGACY_GEOMETRY | 0x0002);
+    //const LEGACY_SHL: Typecode = (LEGACY_GEOMETRY | 0x0003);
+    //const LEGACY_FAC: Typecode = (LEGACY_GEOMETRY | 0x0004);
+    //const LEGACY_BND: Typecode = (LEGACY_GEOMETRY | 0x0005);
+    //const LEGACY_TRM: Typecode = (LEGACY_GEOMETRY | 0x0006);
+    //const LEGACY_SRF: Typecode = (LEGACY_GEOMETRY | 0x0007);
+    //const LEGACY_CRV: Typecode = (LEGACY_GEOMETRY | 0x0008);
+    //const LEGACY_SPL: Typecode = (LEGACY_GEOMETRY | 0x0009);
+    //const LEGACY_PNT: Typecode = (LEGACY_GEOMETRY | 0x000A);
+    //const STUFF: Typecode = 0x0100;
+    //const LEGACY_ASMSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_ASM);
+    //const LEGACY_PRTSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_PRT);
+    //const LEGACY_SHLSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_SHL);
+    //const LEGACY_FACSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_FAC);
+    //const LEGACY_BNDSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_BND);
+    //const LEGACY_TRMSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_TRM);
+    //const LEGACY_SRFSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_SRF);
+    //const LEGACY_CRVSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_CRV);
+    //const LEGACY_SPLSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_SPL);
+    //const LEGACY_PNTSTUFF: Typecode = (LEGACY_GEOMETRY | STUFF | LEGACY_PNT);
+    //const RH_POINT: Typecode = (GEOMETRY | 0x0001);
+    //const RH_SPOTLIGHT: Typecode = (RENDER | 0x0001);
+    //const OLD_RH_TRIMESH: Typecode = (GEOMETRY | 0x0011);
+    //const OLD_MESH_VERTEX_NORMALS: Typecode = (GEOMETRY | 0x0012);
+    //const OLD_MESH_UV: Typecode = (GEOMETRY | 0x0013);
+    //const OLD_FULLMESH: Typecode = (GEOMETRY | 0x0014);
+    //const MESH_OBJECT: Typecode = (GEOMETRY | 0x0015);
+    //const COMPRESSED_MESH_GEOMETRY: Typecode = (GEOMETRY | 0x0017);
+    //const ANALYSIS_MESH: Typecode = (GEOMETRY | 0x0018);
+    //const NAME: Typecode = (INTERFACE | 0x0001);
+    //const VIEW: Typecode = (INTERFACE | 0x0002);
+    //const CPLANE: Typecode = (INTERFACE | 0x0003);
+    NamedCplane = NAMED_CPLANE = INTERFACE | 0x0004,
+    NamedView = NAMED_VIEW = INTERFACE | 0x0005,
+    Viewport = VIEWPORT = INTERFACE | 0x0006,
+    //const SHOWGRID: Typecode = (SHORT | INTERFACE | 0x0007);
+    //const SHOWGRIDAXES: Typecode = (SHORT | INTERFACE | 0x0008);
+    //const SHOWWORLDAXES: Typecode = (SHORT | INTERFACE | 0x0009);
+    //const VIEWPORT_POSITION: Typecode = (INTERFACE | 0x000A);
+    //const VIEWPORT_TRACEINFO: Typecode = (INTERFACE | 0x000B);
+    //const SNAPSIZE: Typecode = (INTERFACE | 0x000C);
+    //const NEAR_CLIP_PLANE: Typecode = (INTERFACE | 0x000D);
+    //const HIDE_TRACE: Typecode = (INTERFACE | 0x000E);
+    Notes = NOTES = INTERFACE | 0x000F,
+    UnitAndTolerances = UNIT_AND_TOLERANCES = INTERFACE | 0x0010,
+    //const MAXIMIZED_VIEWPORT: Typecode = (SHORT | INTERFACE | 0x0011);
+    //const VIEWPORT_WALLPAPER: Typecode = (INTERFACE | 0x0012);
+    Summary = SUMMARY = INTERFACE | 0x0013,
+    BitmapPreview = BITMAPPREVIEW = INTERFACE | 0x0014,
+    //const VIEWPORT_V1_DISPLAYMODE: Typecode = (SHORT | INTERFACE | 0x0015);
+    //const LAYERTABLE: Typecode = (SHORT | TABLE | 0x0001);
+    //const LAYERREF: Typecode = (SHORT | TABLEREC | 0x0001);
+    Rgb = RGB = SHORT | DISPLAY | 0x0001,
+    //const TEXTUREMAP: Typecode = (DISPLAY | 0x0002);
+    //const BUMPMAP: Typecode = (DISPLAY | 0x0003);
+    //const TRANSPARENCY: Typecode = (SHORT | DISPLAY | 0x0004);
+    //const DISP_AM_RESOLUTION: Typecode = (SHORT | DISPLAY | 0x0005);
+    RgbDisplay = RGBDISPLAY = SHORT | DISPLAY | 0x0006,
+    //const RENDER_MATERIAL_ID: Typecode = (DISPLAY | 0x0007);
+    Layer = LAYER = DISPLAY | 0x0010,
+    //const LAYER_OBSELETE_1: Typecode = (SHORT | DISPLAY | 0x0013);
+    //const LAYER_OBSELETE_2: Typecode = (SHORT | DISPLAY | 0x0014);
+    //const LAYER_OBSELETE_3: Typecode = (SHORT | DISPLAY | 0x0015);
+    //const LAYERON: Typecode = (SHORT | DISPLAY | 0x0016);
+    //const LAYERTHAWED: Typecode = (SHORT | DISPLAY | 0x0017);
+    //const LAYERLOCKED: Typecode = (SHORT | DISPLAY | 0x0018);
+    //const LAYERVISIBLE: Typecode = (SHORT | DISPLAY | 0x0012);
+    //const LAYERPICKABLE: Typecode = (SHORT | DISPLAY | 0x0030);
+    //const LAYERSNAPABLE: Typecode = (SHORT | DISPLAY | 0x0031);
+    //const LAYERRENDERABLE: Typecode = (SHORT | DISPLAY | 0x0032);
+    //const LAYERSTATE: Typecode = (SHORT | DISPLAY | 0x0033);
+    //const LAYERINDEX: Typecode = (SHORT | DISPLAY | 0x0034);
+    //const LAYERMATERIALINDEX: Typecode = (SHORT | DISPLAY | 0x0035);
+    RenderMeshParams = RENDERMESHPARAMS = DISPLAY | 0x0020,
+    //const DISP_CPLINES: Typecode = (SHORT | DISPLAY | 0x0022);
+    //const DISP_MAXLENGTH: Typecode = (DISPLAY | 0x0023);
+    CurrentLayer = CURRENTLAYER = SHORT | DISPLAY | 0x0025,
+    //const LAYERNAME: Typecode = (DISPLAY | 0x0011);
+    //const LEGACY_TOL_FIT: Typecode = (TOLERANCE | 0x0001);
+    //const LEGACY_TOL_ANGLE: Typecode = (TOLERANCE | 0x0002);
+}
+
+impl Typecode {
+    pub const fn is_known(self) -> bool {
+        !matches!(self, Self::Unknown(_))
+    }
+
+    pub const fn is_short(self) -> bool {
+        0 != self.value() & SHORT
+    }
+
+    pub const fn has_crc(self) -> bool {
+        0 != self.value() & CRC
+    }
+
+    pub const fn is_table(self) -> bool {
+        TABLE == self.value() & 0xFFFF0000
+    }
+
+    pub const fn to_le_bytes(self) -> [u8; 4] {
+        self.value().to_le_bytes()
+    }
+}
+
+impl Default for Typecode {
+    fn default() -> Self {
+        Self::from(0)
+    }
+}
+
+impl From<Typecode> for u32 {
+    fn from(typecode: Typecode) -> Self {
+        typecode.value()
+    }
+}
+
+impl Display for Typecode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "{:#010x}", self.value()),
+        }
+    }
+}
+
+impl Debug for Typecode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "Unknown({:#010x})", self.value()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Typecode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.name() {
+            Some(name) => serializer.serialize_str(name),
+            None => serializer.serialize_u32(self.value()),
+        }
+    }
+}
+
+impl<D> Deserialize<'_, D> for Typecode
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        Ok(Self::from(u32::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(PROPERTIES_NOTES, Typecode::from(0x20008022));
+        assert_eq!(0x20008022, u32::from(Typecode::PropertiesNotes));
+        assert_eq!(Typecode::Unknown(0x00012345), Typecode::from(0x00012345));
+        assert_eq!(Typecode::Unknown(0), Typecode::default());
+    }
+
+    #[test]
+    fn flags() {
+        assert!(PROPERTIES_TABLE.is_table());
+        assert!(!PROPERTIES_NOTES.is_table());
+        assert!(PROPERTIES_NOTES.has_crc());
+        assert!(PROPERTIES_OPENNURBS_VERSION.is_short());
+        assert!(!Typecode::Unknown(0x00012345).is_known());
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!("PROPERTIES_NOTES", PROPERTIES_NOTES.to_string());
+        assert_eq!("0x00012345", Typecode::from(0x00012345).to_string());
+        assert_eq!("SETTINGS_TABLE", format!("{:?}", SETTINGS_TABLE));
+        assert_eq!(
+            "Unknown(0x00012345)",
+            format!("{:?}", Typecode::from(0x00012345))
+        );
+    }
+}
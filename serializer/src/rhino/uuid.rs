@@ -1,11 +1,64 @@
+use std::fmt::Display;
+
 use geometria_derive::RhinoDeserialize;
 
-use super::{deserialize::Deserialize, deserializer::Deserializer};
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
 
-#[derive(RhinoDeserialize)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, RhinoDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Uuid {
     pub data1: u32,
     pub data2: u16,
     pub data3: u16,
-    pub data4: [u8; 4],
+    pub data4: [u8; 8],
+}
+
+impl Uuid {
+    pub const fn new(data1: u32, data2: u16, data3: u16, data4: [u8; 8]) -> Self {
+        Self {
+            data1,
+            data2,
+            data3,
+            data4,
+        }
+    }
+
+    pub fn is_nil(&self) -> bool {
+        Self::default() == *self
+    }
+}
+
+impl Display for Uuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-",
+            self.data1, self.data2, self.data3, self.data4[0], self.data4[1]
+        )?;
+        self.data4[2..]
+            .iter()
+            .try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::rhino::reader::Reader;
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(0xF9CFB638u32.to_le_bytes());
+        data.extend(0xB9D4u16.to_le_bytes());
+        data.extend(0x4340u16.to_le_bytes());
+        data.extend([0x87, 0xE3, 0xC5, 0x6E, 0x78, 0x65, 0xD9, 0x6A]);
+        let uuid = Uuid::deserialize(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!("F9CFB638-B9D4-4340-87E3-C56E7865D96A", uuid.to_string());
+        assert!(!uuid.is_nil());
+        assert!(Uuid::default().is_nil());
+    }
 }
@@ -0,0 +1,233 @@
+use std::io::SeekFrom;
+
+use super::{
+    chunk::{Begin, Chunk, Chunks},
+    comment::Comment,
+    deserialize::Deserialize,
+    deserializer::Deserializer,
+    error::Error,
+    header::Header,
+    start_section::StartSection,
+    typecode::{self, Typecode},
+    version::Version,
+};
+
+const TABLE_ORDER: [Typecode; 16] = [
+    typecode::PROPERTIES_TABLE,
+    typecode::SETTINGS_TABLE,
+    typecode::BITMAP_TABLE,
+    typecode::TEXTURE_MAPPING_TABLE,
+    typecode::MATERIAL_TABLE,
+    typecode::LINETYPE_TABLE,
+    typecode::LAYER_TABLE,
+    typecode::GROUP_TABLE,
+    typecode::FONT_TABLE,
+    typecode::DIMSTYLE_TABLE,
+    typecode::LIGHT_TABLE,
+    typecode::HATCHPATTERN_TABLE,
+    typecode::INSTANCE_DEFINITION_TABLE,
+    typecode::OBJECT_TABLE,
+    typecode::HISTORYRECORD_TABLE,
+    typecode::USER_TABLE,
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum IssueKind {
+    UnknownTypecode,
+    LengthMismatch,
+    CrcMismatch,
+    TableOrder,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub typecode: Typecode,
+    pub offset: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn count(&self, kind: IssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| kind == issue.kind)
+            .count()
+    }
+
+    fn push(&mut self, kind: IssueKind, typecode: Typecode, offset: u64) {
+        self.issues.push(Issue {
+            kind,
+            typecode,
+            offset,
+        });
+    }
+}
+
+pub fn validate<D>(deserializer: &mut D) -> Result<ValidationReport, Error>
+where
+    D: Deserializer,
+{
+    Header::deserialize(deserializer)?;
+    Version::deserialize(deserializer)?;
+    Comment::deserialize(deserializer)?;
+    StartSection::deserialize(deserializer)?;
+    let start = deserializer.stream_position()?;
+    let stream_end = deserializer.seek(SeekFrom::End(0))?;
+    deserializer.seek(SeekFrom::Start(start))?;
+
+    let mut report = ValidationReport::default();
+    let header_length = 4 + Begin::size_of_length(deserializer.version()) as u64;
+    let mut ends: Vec<u64> = vec![];
+    let mut crc_offsets = vec![];
+    let mut table_rank = 0;
+    let mut end_of_file = false;
+    for entry in Chunks::new(deserializer).nested(true) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => {
+                report.push(IssueKind::LengthMismatch, Typecode::default(), stream_end);
+                break;
+            }
+        };
+        ends.truncate(entry.depth);
+        let end = entry.offset + header_length + entry.length;
+        if end > ends.last().copied().unwrap_or(stream_end) {
+            report.push(IssueKind::LengthMismatch, entry.typecode, entry.offset);
+        }
+        if entry.typecode.is_table() && 0 < entry.length {
+            ends.push(end);
+        }
+        if !entry.typecode.is_known() {
+            report.push(IssueKind::UnknownTypecode, entry.typecode, entry.offset);
+        }
+        if entry.typecode.has_crc() && 0 < entry.length && end <= stream_end {
+            crc_offsets.push((entry.typecode, entry.offset));
+        }
+        if 0 == entry.depth {
+            if end_of_file {
+                report.push(IssueKind::TableOrder, entry.typecode, entry.offset);
+            }
+            end_of_file |= typecode::ENDOFFILE == entry.typecode;
+            if let Some(rank) = TABLE_ORDER.iter().position(|t| *t == entry.typecode) {
+                if rank < table_rank {
+                    report.push(IssueKind::TableOrder, entry.typecode, entry.offset);
+                }
+                table_rank = table_rank.max(rank);
+            }
+        }
+    }
+
+    for (typecode, offset) in crc_offsets {
+        deserializer.seek(SeekFrom::Start(offset))?;
+        let mut chunk = Chunk::deserialize(deserializer)?;
+        if chunk.has_crc() && chunk.verify_crc().is_err() {
+            report.push(IssueKind::CrcMismatch, typecode, offset);
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::rhino::reader::Reader;
+
+    use super::*;
+
+    fn archive_data(tables: &[(Typecode, &[u8])]) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend("3D Geometry File Format        2".as_bytes());
+        data.extend(typecode::COMMENTBLOCK.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        data.extend("abc".as_bytes());
+        for (typecode, payload) in tables {
+            data.extend(typecode.to_le_bytes());
+            data.extend((payload.len() as u32).to_le_bytes());
+            data.extend(*payload);
+        }
+        data
+    }
+
+    fn end_of_table() -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(typecode::ENDOFTABLE.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn validate_healthy_archive() {
+        let end = end_of_table();
+        let data = archive_data(&[
+            (typecode::PROPERTIES_TABLE, &end),
+            (typecode::SETTINGS_TABLE, &end),
+            (typecode::ENDOFFILE, &[0u8; 4]),
+        ]);
+        let report = validate(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn validate_table_order_and_unknown_typecodes() {
+        let end = end_of_table();
+        let data = archive_data(&[
+            (typecode::SETTINGS_TABLE, &end),
+            (typecode::PROPERTIES_TABLE, &end),
+            (Typecode::Unknown(0x00012345), &[0u8; 2]),
+        ]);
+        let report = validate(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!(
+            vec![
+                Issue {
+                    kind: IssueKind::TableOrder,
+                    typecode: typecode::PROPERTIES_TABLE,
+                    offset: 59,
+                },
+                Issue {
+                    kind: IssueKind::UnknownTypecode,
+                    typecode: Typecode::Unknown(0x00012345),
+                    offset: 75,
+                },
+            ],
+            report.issues
+        );
+    }
+
+    #[test]
+    fn validate_crc_and_length() {
+        let mut notes: Vec<u8> = vec![];
+        notes.extend(typecode::PROPERTIES_NOTES.to_le_bytes());
+        notes.extend(6u32.to_le_bytes());
+        notes.extend(9u16.to_le_bytes());
+        notes.extend(0u32.to_le_bytes());
+        notes.extend(end_of_table());
+        let mut data = archive_data(&[(typecode::PROPERTIES_TABLE, &notes)]);
+        data.extend(typecode::SETTINGS_TABLE.to_le_bytes());
+        data.extend(64u32.to_le_bytes());
+        let report = validate(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!(1, report.count(IssueKind::CrcMismatch));
+        assert_eq!(1, report.count(IssueKind::LengthMismatch));
+        assert_eq!(0, report.count(IssueKind::UnknownTypecode));
+        assert_eq!(
+            Some(typecode::PROPERTIES_NOTES),
+            report
+                .issues
+                .iter()
+                .find(|issue| IssueKind::CrcMismatch == issue.kind)
+                .map(|issue| issue.typecode)
+        );
+    }
+}
@@ -2,8 +2,10 @@ use std::fmt::Display;
 
 use super::deserialize::Deserialize;
 use super::deserializer::Deserializer;
+use super::error::Error;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Version {
     V1,
     V2,
@@ -63,7 +65,7 @@ impl<D> Deserialize<'_, D> for Version
 where
     D: Deserializer,
 {
-    type Error = String;
+    type Error = Error;
 
     fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
         let mut buffer = [0; 8];
@@ -73,20 +75,23 @@ where
                     .iter()
                     .skip_while(|x| **x == ' ' as u8)
                     .try_fold(0u8, |acc, x| match (*x as char).to_digit(10) {
-                        Some(d) => Ok(acc * 10u8 + (d as u8)),
-                        None => Err("invalid version".to_string()),
+                        Some(d) => acc
+                            .checked_mul(10u8)
+                            .and_then(|acc| acc.checked_add(d as u8))
+                            .ok_or(Error::UnsupportedVersion),
+                        None => Err(Error::UnsupportedVersion),
                     }) {
                     Ok(v) => match Version::try_from(v) {
                         Ok(version) => {
                             deserializer.set_version(version);
                             Ok(version)
                         }
-                        Err(e) => Err(e.to_string()),
+                        Err(_) => Err(Error::UnsupportedVersion),
                     },
-                    Err(e) => Err(e.to_string()),
+                    Err(e) => Err(e),
                 }
             }
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(Error::Io(e)),
         }
     }
 }
@@ -102,19 +107,19 @@ mod tests {
     #[test]
     fn conversions() {
         let mut version = Version::V1;
-        assert_eq!(1u8, version.into());
+        assert_eq!(1u8, Into::<u8>::into(version));
         version = Version::V2;
-        assert_eq!(2u8, version.into());
+        assert_eq!(2u8, Into::<u8>::into(version));
         version = Version::V3;
-        assert_eq!(3u8, version.into());
+        assert_eq!(3u8, Into::<u8>::into(version));
         version = Version::V4;
-        assert_eq!(4u8, version.into());
+        assert_eq!(4u8, Into::<u8>::into(version));
         version = Version::V50;
-        assert_eq!(50u8, version.into());
+        assert_eq!(50u8, Into::<u8>::into(version));
         version = Version::V60;
-        assert_eq!(60u8, version.into());
+        assert_eq!(60u8, Into::<u8>::into(version));
         version = Version::V70;
-        assert_eq!(70u8, version.into());
+        assert_eq!(70u8, Into::<u8>::into(version));
 
         assert_eq!(Version::try_from(1u8).ok(), Some(Version::V1));
         assert_eq!(Version::try_from(2u8).ok(), Some(Version::V2));
@@ -129,6 +134,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ordering() {
+        assert!(Version::V1 < Version::V2);
+        assert!(Version::V4 < Version::V50);
+        assert!(Version::V70 > Version::V60);
+        assert!((Version::V50..).contains(&Version::V70));
+        assert!(!(Version::V50..).contains(&Version::V4));
+    }
+
     #[test]
     fn deserialize_ok() {
         let data = "       1".as_bytes();
@@ -136,6 +150,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
 
         assert_eq!(
@@ -151,10 +166,26 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
         assert!(Version::deserialize(&mut deserializer).is_err());
     }
 
+    #[test]
+    fn deserialize_overflowing_version() {
+        let data = "     999".as_bytes();
+        let mut deserializer = Reader {
+            stream: &mut Cursor::new(data),
+            version: Version::V1,
+            chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
+        };
+        assert!(matches!(
+            Version::deserialize(&mut deserializer),
+            Err(Error::UnsupportedVersion)
+        ));
+    }
+
     #[test]
     fn deserialize_io_error() {
         let data = "    1".as_bytes();
@@ -162,6 +193,7 @@ mod tests {
             stream: &mut Cursor::new(data),
             version: Version::V1,
             chunk_begin: chunk::Begin::default(),
+            options: Default::default(),
         };
         assert!(Version::deserialize(&mut deserializer).is_err());
     }
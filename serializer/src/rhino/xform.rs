@@ -0,0 +1,47 @@
+use crate::math::Xform;
+
+use super::{deserialize::Deserialize, deserializer::Deserializer, error::Error};
+
+impl<D> Deserialize<'_, D> for Xform
+where
+    D: Deserializer,
+{
+    type Error = Error;
+
+    fn deserialize(deserializer: &mut D) -> Result<Self, Self::Error> {
+        Ok(Self::from_row_major(&<[f64; 16]>::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        math::{Point3d, Vector3d},
+        rhino::reader::Reader,
+    };
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let mut data: Vec<u8> = vec![];
+        [
+            1f64, 0., 0., 5., 0., 1., 0., 6., 0., 0., 1., 7., 0., 0., 0., 1.,
+        ]
+        .iter()
+        .for_each(|v| data.extend(v.to_le_bytes()));
+        let xform = Xform::deserialize(&mut Reader::new(Cursor::new(data))).unwrap();
+        assert_eq!(Xform::translation(&Vector3d::new(5., 6., 7.)), xform);
+        assert_eq!(Point3d::new(5., 6., 7.), xform * Point3d::ORIGIN);
+    }
+
+    #[test]
+    fn deserialize_truncated() {
+        let data = vec![0u8; 15 * 8];
+        assert!(Xform::deserialize(&mut Reader::new(Cursor::new(data))).is_err());
+    }
+}
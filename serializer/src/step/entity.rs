@@ -0,0 +1,183 @@
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Parameter {
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Enumeration(String),
+    Binary(String),
+    Reference(u64),
+    List(Vec<Parameter>),
+    Typed(String, Box<Parameter>),
+    Omitted,
+    Derived,
+}
+
+impl Parameter {
+    pub fn as_reference(&self) -> Option<u64> {
+        match self {
+            Self::Reference(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_enumeration(&self) -> Option<&str> {
+        match self {
+            Self::Enumeration(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Real(value) => Some(*value),
+            Self::Integer(value) => Some(*value as f64),
+            Self::Typed(_, value) => value.as_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Parameter]> {
+        match self {
+            Self::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn is_omitted(&self) -> bool {
+        matches!(self, Self::Omitted | Self::Derived)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Record {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+}
+
+impl Record {
+    pub fn parameter(&self, index: usize) -> Option<&Parameter> {
+        self.parameters.get(index)
+    }
+
+    pub fn reference(&self, index: usize) -> Option<u64> {
+        self.parameter(index)?.as_reference()
+    }
+
+    pub fn string(&self, index: usize) -> Option<&str> {
+        self.parameter(index)?.as_str()
+    }
+
+    pub fn real(&self, index: usize) -> Option<f64> {
+        self.parameter(index)?.as_f64()
+    }
+
+    pub fn list(&self, index: usize) -> Option<&[Parameter]> {
+        self.parameter(index)?.as_list()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Entity {
+    Simple(Record),
+    Complex(Vec<Record>),
+}
+
+impl Entity {
+    pub fn records(&self) -> &[Record] {
+        match self {
+            Self::Simple(record) => std::slice::from_ref(record),
+            Self::Complex(records) => records,
+        }
+    }
+
+    pub fn record(&self, name: &str) -> Option<&Record> {
+        self.records().iter().find(|record| name == record.name)
+    }
+
+    pub fn is(&self, name: &str) -> bool {
+        self.record(name).is_some()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Simple(record) => Some(&record.name),
+            Self::Complex(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, parameters: Vec<Parameter>) -> Record {
+        Record {
+            name: name.to_string(),
+            parameters,
+        }
+    }
+
+    #[test]
+    fn accessors() {
+        let point = record(
+            "CARTESIAN_POINT",
+            vec![
+                Parameter::String("origin".to_string()),
+                Parameter::List(vec![Parameter::Real(1.), Parameter::Integer(2)]),
+                Parameter::Typed("LENGTH_MEASURE".to_string(), Box::new(Parameter::Real(3.))),
+                Parameter::Reference(4),
+                Parameter::Omitted,
+            ],
+        );
+        assert_eq!(Some("origin"), point.string(0));
+        assert_eq!(
+            vec![Some(1.), Some(2.)],
+            point
+                .list(1)
+                .unwrap()
+                .iter()
+                .map(Parameter::as_f64)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(3.), point.real(2));
+        assert_eq!(Some(4), point.reference(3));
+        assert!(point.parameter(4).unwrap().is_omitted());
+        assert_eq!(None, point.reference(0));
+        assert_eq!(None, point.string(9));
+
+        let simple = Entity::Simple(point);
+        assert_eq!(Some("CARTESIAN_POINT"), simple.name());
+        assert!(simple.is("CARTESIAN_POINT"));
+
+        let complex = Entity::Complex(vec![
+            record("LENGTH_UNIT", vec![]),
+            record(
+                "SI_UNIT",
+                vec![
+                    Parameter::Enumeration("MILLI".to_string()),
+                    Parameter::Enumeration("METRE".to_string()),
+                ],
+            ),
+        ]);
+        assert_eq!(None, complex.name());
+        assert!(complex.is("LENGTH_UNIT"));
+        assert_eq!(
+            Some("METRE"),
+            complex
+                .record("SI_UNIT")
+                .and_then(|r| r.parameter(1))
+                .and_then(Parameter::as_enumeration)
+        );
+        assert!(!complex.is("PLANE_ANGLE_UNIT"));
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    UnexpectedEnd,
+    UnexpectedCharacter { line: usize, character: char },
+    UnexpectedToken { line: usize, expected: &'static str },
+    InvalidString { line: usize },
+    NestingTooDeep { line: usize },
+    DuplicateInstance(u64),
+    Other(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::UnexpectedEnd => write!(f, "unexpected end of file"),
+            Self::UnexpectedCharacter { line, character } => {
+                write!(f, "unexpected character {:?} at line {}", character, line)
+            }
+            Self::UnexpectedToken { line, expected } => {
+                write!(f, "expected {} at line {}", expected, line)
+            }
+            Self::InvalidString { line } => write!(f, "invalid string at line {}", line),
+            Self::NestingTooDeep { line } => write!(f, "nesting too deep at line {}", line),
+            Self::DuplicateInstance(id) => write!(f, "duplicate instance #{}", id),
+            Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "unexpected character '?' at line 3",
+            Error::UnexpectedCharacter {
+                line: 3,
+                character: '?'
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "expected ';' at line 7",
+            String::from(Error::UnexpectedToken {
+                line: 7,
+                expected: "';'"
+            })
+        );
+        assert_eq!(
+            "duplicate instance #12",
+            Error::DuplicateInstance(12).to_string()
+        );
+    }
+}
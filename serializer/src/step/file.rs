@@ -0,0 +1,419 @@
+use std::{collections::BTreeMap, io::Read};
+
+use super::{
+    entity::{Entity, Parameter, Record},
+    error::Error,
+    lexer::{Lexer, Token},
+};
+
+const MAGIC: &str = "ISO-10303-21";
+const END_MAGIC: &str = "END-ISO-10303-21";
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Header {
+    pub description: Vec<String>,
+    pub implementation_level: String,
+    pub name: String,
+    pub time_stamp: String,
+    pub author: Vec<String>,
+    pub organization: Vec<String>,
+    pub preprocessor_version: String,
+    pub originating_system: String,
+    pub authorization: String,
+    pub schemas: Vec<String>,
+}
+
+fn strings(parameter: Option<&Parameter>) -> Vec<String> {
+    parameter
+        .and_then(Parameter::as_list)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Parameter::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+fn string(parameter: Option<&Parameter>) -> String {
+    parameter
+        .and_then(Parameter::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+impl Header {
+    fn apply(&mut self, record: &Record) {
+        let p = |index| record.parameter(index);
+        match record.name.as_str() {
+            "FILE_DESCRIPTION" => {
+                self.description = strings(p(0));
+                self.implementation_level = string(p(1));
+            }
+            "FILE_NAME" => {
+                self.name = string(p(0));
+                self.time_stamp = string(p(1));
+                self.author = strings(p(2));
+                self.organization = strings(p(3));
+                self.preprocessor_version = string(p(4));
+                self.originating_system = string(p(5));
+                self.authorization = string(p(6));
+            }
+            "FILE_SCHEMA" => self.schemas = strings(p(0)),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StepFile {
+    pub header: Header,
+    entities: BTreeMap<u64, Entity>,
+}
+
+impl StepFile {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        Parser::new(input).parse()
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, Error> {
+        let mut data: Vec<u8> = vec![];
+        reader.read_to_end(&mut data)?;
+        Self::parse(&String::from_utf8_lossy(&data))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn entity(&self, id: u64) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = (u64, &Entity)> {
+        self.entities.iter().map(|(id, entity)| (*id, entity))
+    }
+
+    pub fn record(&self, id: u64, name: &str) -> Option<&Record> {
+        self.entity(id)?.record(name)
+    }
+
+    pub fn instances_of<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = (u64, &'a Record)> + 'a {
+        self.entities
+            .iter()
+            .filter_map(move |(id, entity)| entity.record(name).map(|record| (*id, record)))
+    }
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<Token>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    const MAX_DEPTH: usize = 256;
+
+    fn new(input: &'a str) -> Self {
+        Self {
+            lexer: Lexer::new(input),
+            peeked: None,
+            depth: 0,
+        }
+    }
+
+    fn peek(&mut self) -> Result<&Token, Error> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        self.peeked.as_ref().ok_or(Error::UnexpectedEnd)
+    }
+
+    fn next(&mut self) -> Result<Token, Error> {
+        self.peek()?;
+        self.peeked.take().ok_or(Error::UnexpectedEnd)
+    }
+
+    fn unexpected(&self, expected: &'static str) -> Error {
+        Error::UnexpectedToken {
+            line: self.lexer.line(),
+            expected,
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), Error> {
+        if token == self.next()? {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn keyword(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            Token::Keyword(keyword) => Ok(keyword),
+            _ => Err(self.unexpected("keyword")),
+        }
+    }
+
+    fn parameters(&mut self) -> Result<Vec<Parameter>, Error> {
+        self.expect(Token::LeftParenthesis, "'('")?;
+        self.list()
+    }
+
+    fn list(&mut self) -> Result<Vec<Parameter>, Error> {
+        let mut parameters: Vec<Parameter> = vec![];
+        if Token::RightParenthesis == *self.peek()? {
+            self.next()?;
+            return Ok(parameters);
+        }
+        loop {
+            parameters.push(self.parameter()?);
+            match self.next()? {
+                Token::Comma => {}
+                Token::RightParenthesis => return Ok(parameters),
+                _ => return Err(self.unexpected("',' or ')'")),
+            }
+        }
+    }
+
+    fn parameter(&mut self) -> Result<Parameter, Error> {
+        if Self::MAX_DEPTH <= self.depth {
+            return Err(Error::NestingTooDeep {
+                line: self.lexer.line(),
+            });
+        }
+        self.depth += 1;
+        let parameter = self.value();
+        self.depth -= 1;
+        parameter
+    }
+
+    fn value(&mut self) -> Result<Parameter, Error> {
+        Ok(match self.next()? {
+            Token::Integer(value) => Parameter::Integer(value),
+            Token::Real(value) => Parameter::Real(value),
+            Token::String(value) => Parameter::String(value),
+            Token::Enumeration(value) => Parameter::Enumeration(value),
+            Token::Binary(value) => Parameter::Binary(value),
+            Token::InstanceName(id) => Parameter::Reference(id),
+            Token::Omitted => Parameter::Omitted,
+            Token::Derived => Parameter::Derived,
+            Token::LeftParenthesis => Parameter::List(self.list()?),
+            Token::Keyword(name) => {
+                self.expect(Token::LeftParenthesis, "'('")?;
+                let value = self.parameter()?;
+                self.expect(Token::RightParenthesis, "')'")?;
+                Parameter::Typed(name, Box::new(value))
+            }
+            _ => return Err(self.unexpected("parameter")),
+        })
+    }
+
+    fn record(&mut self) -> Result<Record, Error> {
+        Ok(Record {
+            name: self.keyword()?,
+            parameters: self.parameters()?,
+        })
+    }
+
+    fn entity(&mut self) -> Result<Entity, Error> {
+        if Token::LeftParenthesis != *self.peek()? {
+            return Ok(Entity::Simple(self.record()?));
+        }
+        self.next()?;
+        let mut records: Vec<Record> = vec![];
+        while Token::RightParenthesis != *self.peek()? {
+            records.push(self.record()?);
+        }
+        self.next()?;
+        Ok(Entity::Complex(records))
+    }
+
+    fn header(&mut self, header: &mut Header) -> Result<(), Error> {
+        loop {
+            if Token::Keyword("ENDSEC".to_string()) == *self.peek()? {
+                self.next()?;
+                return self.expect(Token::Semicolon, "';'");
+            }
+            header.apply(&self.record()?);
+            self.expect(Token::Semicolon, "';'")?;
+        }
+    }
+
+    fn data(&mut self, entities: &mut BTreeMap<u64, Entity>) -> Result<(), Error> {
+        if Token::LeftParenthesis == *self.peek()? {
+            self.parameters()?;
+        }
+        self.expect(Token::Semicolon, "';'")?;
+        loop {
+            let id = match self.next()? {
+                Token::InstanceName(id) => id,
+                Token::Keyword(keyword) if "ENDSEC" == keyword => {
+                    return self.expect(Token::Semicolon, "';'");
+                }
+                _ => return Err(self.unexpected("instance")),
+            };
+            self.expect(Token::Equals, "'='")?;
+            let entity = self.entity()?;
+            self.expect(Token::Semicolon, "';'")?;
+            if entities.insert(id, entity).is_some() {
+                return Err(Error::DuplicateInstance(id));
+            }
+        }
+    }
+
+    fn parse(mut self) -> Result<StepFile, Error> {
+        if MAGIC != self.keyword()? {
+            return Err(self.unexpected(MAGIC));
+        }
+        self.expect(Token::Semicolon, "';'")?;
+        let mut file = StepFile::default();
+        loop {
+            match self.keyword()?.as_str() {
+                "HEADER" => {
+                    self.expect(Token::Semicolon, "';'")?;
+                    self.header(&mut file.header)?;
+                }
+                "DATA" => self.data(&mut file.entities)?,
+                "ANCHOR" | "REFERENCE" | "SIGNATURE" => self.skip_section()?,
+                END_MAGIC => {
+                    self.expect(Token::Semicolon, "';'")?;
+                    return Ok(file);
+                }
+                _ => return Err(self.unexpected("section")),
+            }
+        }
+    }
+
+    fn skip_section(&mut self) -> Result<(), Error> {
+        loop {
+            if let Token::Keyword(keyword) = self.next()? {
+                if "ENDSEC" == keyword {
+                    return self.expect(Token::Semicolon, "';'");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('a part'),'2;1');
+FILE_NAME('part.stp','2024-01-02T03:04:05',('author'),('company'),
+  'preprocessor','system','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));
+ENDSEC;
+DATA;
+#1 = CARTESIAN_POINT('', (0., 1.5, -2.));
+#2 = ( LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.) );
+#3 = LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4), #2);
+#4 = PRODUCT('p', 'part', '', (#5));
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    #[test]
+    fn parse() {
+        let file = StepFile::parse(FILE).unwrap();
+        assert_eq!(vec!["a part"], file.header.description);
+        assert_eq!("2;1", file.header.implementation_level);
+        assert_eq!("part.stp", file.header.name);
+        assert_eq!(vec!["author"], file.header.author);
+        assert_eq!(vec!["company"], file.header.organization);
+        assert_eq!("system", file.header.originating_system);
+        assert_eq!(
+            vec!["AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }"],
+            file.header.schemas
+        );
+
+        assert_eq!(4, file.len());
+        let point = file.record(1, "CARTESIAN_POINT").unwrap();
+        assert_eq!(
+            vec![Some(0.), Some(1.5), Some(-2.)],
+            point
+                .list(1)
+                .unwrap()
+                .iter()
+                .map(Parameter::as_f64)
+                .collect::<Vec<_>>()
+        );
+        let unit = file.entity(2).unwrap();
+        assert_eq!(3, unit.records().len());
+        assert_eq!(
+            Some(&Parameter::Derived),
+            unit.record("NAMED_UNIT").unwrap().parameter(0)
+        );
+        assert_eq!(
+            Some(25.4),
+            file.record(3, "LENGTH_MEASURE_WITH_UNIT").unwrap().real(0)
+        );
+        assert_eq!(
+            vec![2],
+            file.instances_of("SI_UNIT")
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(file, StepFile::read(FILE.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            StepFile::parse("ISO-10303-22;"),
+            Err(Error::UnexpectedToken { line: 1, .. })
+        ));
+        assert!(matches!(
+            StepFile::parse("ISO-10303-21;\nDATA;\n#1=A();\n#1=B();\nENDSEC;"),
+            Err(Error::DuplicateInstance(1))
+        ));
+        assert!(matches!(
+            StepFile::parse("ISO-10303-21;\nDATA;\n#1=A(1 2);"),
+            Err(Error::UnexpectedToken { line: 3, .. })
+        ));
+        assert!(matches!(
+            StepFile::parse("ISO-10303-21;\nDATA;\n#1=A(1);"),
+            Err(Error::UnexpectedEnd)
+        ));
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth: usize| {
+            format!(
+                "ISO-10303-21;\nDATA;\n#1=A({}1{});\nENDSEC;END-ISO-10303-21;",
+                "(".repeat(depth),
+                ")".repeat(depth)
+            )
+        };
+        assert!(StepFile::parse(&nested(Parser::MAX_DEPTH - 1)).is_ok());
+        assert!(matches!(
+            StepFile::parse(&nested(Parser::MAX_DEPTH)),
+            Err(Error::NestingTooDeep { line: 3 })
+        ));
+        assert!(matches!(
+            StepFile::parse(&nested(400_000)),
+            Err(Error::NestingTooDeep { .. })
+        ));
+        let typed = format!(
+            "ISO-10303-21;\nDATA;\n#1=A({}1{});",
+            "B(".repeat(400_000),
+            ")".repeat(400_000)
+        );
+        assert!(matches!(
+            StepFile::parse(&typed),
+            Err(Error::NestingTooDeep { .. })
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+use std::{iter::Peekable, str::Chars};
+
+use super::error::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Keyword(String),
+    InstanceName(u64),
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Enumeration(String),
+    Binary(String),
+    Omitted,
+    Derived,
+    LeftParenthesis,
+    RightParenthesis,
+    Comma,
+    Semicolon,
+    Equals,
+}
+
+pub struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+fn is_keyword_character(c: char) -> bool {
+    c.is_ascii_alphanumeric() || '_' == c || '-' == c
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if '\n' == c {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn take_while<F>(&mut self, mut predicate: F) -> String
+    where
+        F: FnMut(char) -> bool,
+    {
+        let mut text = String::new();
+        while let Some(c) = self.chars.peek().copied().filter(|c| predicate(*c)) {
+            text.push(c);
+            self.bump();
+        }
+        text
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), Error> {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') => {
+                    self.bump();
+                    if Some('*') != self.bump() {
+                        return Err(Error::UnexpectedCharacter {
+                            line: self.line,
+                            character: '/',
+                        });
+                    }
+                    let mut previous = ' ';
+                    loop {
+                        let c = self.bump().ok_or(Error::UnexpectedEnd)?;
+                        if '*' == previous && '/' == c {
+                            break;
+                        }
+                        previous = c;
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Token, Error> {
+        let mut text = String::new();
+        if let Some(sign) = self.chars.next_if(|c| '+' == *c || '-' == *c) {
+            text.push(sign);
+        }
+        text.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        let mut real = false;
+        if let Some(point) = self.chars.next_if_eq(&'.') {
+            real = true;
+            text.push(point);
+            text.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+        if let Some(exponent) = self.chars.next_if(|c| 'E' == *c || 'e' == *c) {
+            real = true;
+            text.push(exponent);
+            if let Some(sign) = self.chars.next_if(|c| '+' == *c || '-' == *c) {
+                text.push(sign);
+            }
+            text.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+        let invalid = || Error::UnexpectedToken {
+            line: self.line,
+            expected: "number",
+        };
+        if real {
+            text.parse().map(Token::Real).map_err(|_| invalid())
+        } else {
+            text.parse().map(Token::Integer).map_err(|_| invalid())
+        }
+    }
+
+    fn string(&mut self) -> Result<Token, Error> {
+        let line = self.line;
+        let mut raw = String::new();
+        loop {
+            match self.bump().ok_or(Error::UnexpectedEnd)? {
+                '\'' if self.chars.next_if_eq(&'\'').is_some() => raw.push('\''),
+                '\'' => break,
+                '\n' | '\r' => {}
+                c => raw.push(c),
+            }
+        }
+        decode(&raw)
+            .map(Token::String)
+            .ok_or(Error::InvalidString { line })
+    }
+
+    fn delimited(&mut self, delimiter: char, expected: &'static str) -> Result<String, Error> {
+        let text = self.take_while(|c| c != delimiter && !c.is_whitespace());
+        match self.bump() {
+            Some(c) if c == delimiter => Ok(text),
+            _ => Err(Error::UnexpectedToken {
+                line: self.line,
+                expected,
+            }),
+        }
+    }
+
+    pub fn next_token(&mut self) -> Result<Option<Token>, Error> {
+        self.skip_whitespace_and_comments()?;
+        let c = match self.chars.peek() {
+            Some(c) => *c,
+            None => return Ok(None),
+        };
+        let token = match c {
+            '(' | ')' | ',' | ';' | '=' | '$' | '*' => {
+                self.bump();
+                match c {
+                    '(' => Token::LeftParenthesis,
+                    ')' => Token::RightParenthesis,
+                    ',' => Token::Comma,
+                    ';' => Token::Semicolon,
+                    '=' => Token::Equals,
+                    '$' => Token::Omitted,
+                    _ => Token::Derived,
+                }
+            }
+            '#' => {
+                self.bump();
+                let digits = self.take_while(|c| c.is_ascii_digit());
+                Token::InstanceName(digits.parse().map_err(|_| Error::UnexpectedToken {
+                    line: self.line,
+                    expected: "instance name",
+                })?)
+            }
+            '\'' => {
+                self.bump();
+                self.string()?
+            }
+            '"' => {
+                self.bump();
+                Token::Binary(self.delimited('"', "binary")?)
+            }
+            '.' => {
+                self.bump();
+                Token::Enumeration(self.delimited('.', "enumeration")?)
+            }
+            '!' => {
+                self.bump();
+                Token::Keyword(format!("!{}", self.take_while(is_keyword_character)))
+            }
+            c if c.is_ascii_digit() || '+' == c || '-' == c => self.number()?,
+            c if c.is_ascii_alphabetic() || '_' == c => {
+                Token::Keyword(self.take_while(is_keyword_character).to_ascii_uppercase())
+            }
+            character => {
+                return Err(Error::UnexpectedCharacter {
+                    line: self.line,
+                    character,
+                })
+            }
+        };
+        Ok(Some(token))
+    }
+}
+
+fn hex(text: &str) -> Option<u32> {
+    u32::from_str_radix(text, 16).ok()
+}
+
+fn decode(raw: &str) -> Option<String> {
+    let mut decoded = String::new();
+    let mut rest = raw;
+    while let Some(position) = rest.find('\\') {
+        decoded.push_str(&rest[..position]);
+        rest = &rest[position..];
+        if let Some(tail) = rest.strip_prefix("\\\\") {
+            decoded.push('\\');
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("\\X2\\") {
+            let end = tail.find("\\X0\\")?;
+            if !end.is_multiple_of(4) {
+                return None;
+            }
+            let units: Vec<u16> = tail.as_bytes()[..end]
+                .chunks(4)
+                .map(|chunk| hex(std::str::from_utf8(chunk).ok()?).map(|v| v as u16))
+                .collect::<Option<_>>()?;
+            decoded.push_str(&String::from_utf16(&units).ok()?);
+            rest = &tail[end + 4..];
+        } else if let Some(tail) = rest.strip_prefix("\\X4\\") {
+            let end = tail.find("\\X0\\")?;
+            if !end.is_multiple_of(8) {
+                return None;
+            }
+            for chunk in tail.as_bytes()[..end].chunks(8) {
+                decoded.push(char::from_u32(hex(std::str::from_utf8(chunk).ok()?)?)?);
+            }
+            rest = &tail[end + 4..];
+        } else if let Some(tail) = rest.strip_prefix("\\X\\") {
+            decoded.push(char::from_u32(hex(tail.get(..2)?)?)?);
+            rest = &tail[2..];
+        } else if let Some(tail) = rest.strip_prefix("\\S\\") {
+            let c = tail.chars().next()?;
+            decoded.push(char::from_u32(c as u32 + 128)?);
+            rest = &tail[c.len_utf8()..];
+        } else if rest.starts_with("\\P") && Some(&b'\\') == rest.as_bytes().get(3) {
+            rest = &rest[4..];
+        } else {
+            decoded.push('\\');
+            rest = &rest[1..];
+        }
+    }
+    decoded.push_str(rest);
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Result<Vec<Token>, Error> {
+        let mut lexer = Lexer::new(input);
+        let mut tokens: Vec<Token> = vec![];
+        while let Some(token) = lexer.next_token()? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    #[test]
+    fn tokenize() {
+        assert_eq!(
+            vec![
+                Token::InstanceName(12),
+                Token::Equals,
+                Token::Keyword("CARTESIAN_POINT".to_string()),
+                Token::LeftParenthesis,
+                Token::String("it's".to_string()),
+                Token::Comma,
+                Token::LeftParenthesis,
+                Token::Real(1.),
+                Token::Comma,
+                Token::Real(-2.5e-3),
+                Token::Comma,
+                Token::Integer(3),
+                Token::RightParenthesis,
+                Token::Comma,
+                Token::Enumeration("MILLI".to_string()),
+                Token::Comma,
+                Token::Omitted,
+                Token::Comma,
+                Token::Derived,
+                Token::Comma,
+                Token::Binary("0F".to_string()),
+                Token::RightParenthesis,
+                Token::Semicolon,
+            ],
+            tokens("#12 = cartesian_point('it''s', (1., -2.5E-3, 3), .MILLI., $, *, \"0F\");")
+                .unwrap()
+        );
+        assert_eq!(
+            vec![
+                Token::Keyword("ISO-10303-21".to_string()),
+                Token::Semicolon,
+                Token::Keyword("HEADER".to_string()),
+            ],
+            tokens("ISO-10303-21;\n/* a\n comment */\nHEADER").unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_strings() {
+        assert_eq!(
+            Token::String("Stra\u{df}e \u{3b1}\u{1f600} a\\b".to_string()),
+            tokens(r"'Stra\S\_e \X2\03B1\X0\\X4\0001F600\X0\ a\\b'").unwrap()[0]
+        );
+        assert_eq!(
+            Token::String("caf\u{e9}".to_string()),
+            tokens(r"'caf\X\E9'").unwrap()[0]
+        );
+        assert_eq!(
+            Token::String("ab".to_string()),
+            tokens(r"'a\PA\b'").unwrap()[0]
+        );
+        assert_eq!(
+            Token::String("\\P\u{20ac}\\".to_string()),
+            tokens("'\\P\u{20ac}\\'").unwrap()[0]
+        );
+        assert!(matches!(
+            tokens(r"'\X2\12\X0\'"),
+            Err(Error::InvalidString { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(tokens("'open"), Err(Error::UnexpectedEnd)));
+        assert!(matches!(
+            tokens("\n\n@"),
+            Err(Error::UnexpectedCharacter {
+                line: 3,
+                character: '@'
+            })
+        ));
+        assert!(matches!(tokens("/* open"), Err(Error::UnexpectedEnd)));
+        assert!(matches!(
+            tokens(".MILLI"),
+            Err(Error::UnexpectedToken { .. })
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    jt::archive::Archive as JtArchive,
+    rhino::{archive::Archive as RhinoArchive, properties::Properties},
+};
+
+fn to_js_error<E>(error: E) -> JsValue
+where
+    E: ToString,
+{
+    JsValue::from_str(&error.to_string())
+}
+
+#[wasm_bindgen]
+pub struct RhinoInfo {
+    archive: RhinoArchive,
+}
+
+#[wasm_bindgen]
+impl RhinoInfo {
+    pub fn version(&self) -> u8 {
+        self.archive.version.into()
+    }
+
+    pub fn comment(&self) -> String {
+        self.archive.comment.as_str().to_string()
+    }
+
+    #[wasm_bindgen(js_name = createdBy)]
+    pub fn created_by(&self) -> String {
+        self.archive
+            .properties
+            .revision_history()
+            .created_by()
+            .to_string()
+    }
+
+    #[wasm_bindgen(js_name = lastEditedBy)]
+    pub fn last_edited_by(&self) -> String {
+        self.archive
+            .properties
+            .revision_history()
+            .last_edited_by()
+            .to_string()
+    }
+
+    #[wasm_bindgen(js_name = revisionCount)]
+    pub fn revision_count(&self) -> i32 {
+        self.archive.properties.revision_history().revision_count()
+    }
+
+    pub fn notes(&self) -> String {
+        self.archive.properties.notes().data().to_string()
+    }
+
+    #[wasm_bindgen(js_name = applicationName)]
+    pub fn application_name(&self) -> Option<String> {
+        match &self.archive.properties {
+            Properties::V1(_) => None,
+            Properties::V2(properties) => Some(properties.application().name().to_string()),
+        }
+    }
+}
+
+#[wasm_bindgen(js_name = inspect3dm)]
+pub fn inspect_3dm(bytes: &[u8]) -> Result<RhinoInfo, JsValue> {
+    let archive = RhinoArchive::from_bytes(bytes).map_err(to_js_error)?;
+    Ok(RhinoInfo { archive })
+}
+
+#[wasm_bindgen]
+pub struct JtInfo {
+    archive: JtArchive,
+}
+
+#[wasm_bindgen]
+impl JtInfo {
+    pub fn version(&self) -> Option<String> {
+        self.archive.header.version().ok().map(|v| v.to_string())
+    }
+
+    #[wasm_bindgen(js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.archive.statistics().node_count
+    }
+
+    #[wasm_bindgen(js_name = partCount)]
+    pub fn part_count(&self) -> usize {
+        self.archive.statistics().part_count
+    }
+
+    #[wasm_bindgen(js_name = instanceCount)]
+    pub fn instance_count(&self) -> usize {
+        self.archive.statistics().instance_count
+    }
+
+    #[wasm_bindgen(js_name = shapeCount)]
+    pub fn shape_count(&self) -> usize {
+        self.archive.statistics().shape_count
+    }
+
+    #[wasm_bindgen(js_name = segmentCount)]
+    pub fn segment_count(&self) -> usize {
+        self.archive.segments().len()
+    }
+}
+
+#[wasm_bindgen(js_name = inspectJt)]
+pub fn inspect_jt(bytes: &[u8]) -> Result<JtInfo, JsValue> {
+    let archive = JtArchive::from_bytes(bytes).map_err(to_js_error)?;
+    Ok(JtInfo { archive })
+}